@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use shared::solution::Solution;
+use shared::timing::{Stats, measure};
+
+use crate::table::{align, format_duration};
+
+/// Timings of one stage of a day
+#[derive(Debug, PartialEq)]
+pub struct BenchRow {
+    pub day: u8,
+    /// `parse`, `part1` or `part2`
+    pub stage: &'static str,
+    /// timings, or why the stage couldn't be timed
+    pub stats: Result<Stats, String>,
+}
+
+/// Times reading the input and solving each part of a day
+///
+/// Each stage is run once untimed to check it succeeds, which also
+/// counts as a warmup run, before being timed.
+///
+/// # Arguments
+///
+/// * `solution` - day to time
+/// * `input` - path of the puzzle input
+/// * `warmup` - untimed runs of each stage
+/// * `samples` - timed runs of each stage
+///
+/// # Returns
+///
+/// * timings of each stage, or none if the input is missing
+///
+pub fn bench_day(
+    solution: &dyn Solution,
+    input: &Path,
+    warmup: usize,
+    samples: usize,
+) -> Vec<BenchRow> {
+    if !input.is_file() {
+        return Vec::new();
+    }
+    let filename = input.to_string_lossy();
+    let mut rows = Vec::new();
+    let mut time = |stage, run: &dyn Fn() -> Result<(), String>| {
+        let stats = run().map(|()| measure(warmup.saturating_sub(1), samples, run));
+        rows.push(BenchRow {
+            day: solution.day(),
+            stage,
+            stats,
+        });
+    };
+    time("parse", &|| {
+        solution.parse(&filename).map_err(|err| err.to_string())
+    });
+    time("part1", &|| {
+        solution
+            .part1(&filename)
+            .map(drop)
+            .map_err(|err| err.to_string())
+    });
+    if solution.parts() >= 2 {
+        time("part2", &|| {
+            solution
+                .part2(&filename)
+                .map(drop)
+                .map_err(|err| err.to_string())
+        });
+    }
+    rows
+}
+
+/// Table of timings, one line per stage, with any errors listed after it
+pub fn render(rows: &[BenchRow]) -> String {
+    let header = ["Day", "Stage", "Samples", "Mean", "Min", "Stddev"];
+    let mut table = vec![header.map(String::from).to_vec()];
+    for row in rows {
+        let mut line = vec![row.day.to_string(), String::from(row.stage)];
+        match &row.stats {
+            Ok(stats) => line.extend([
+                stats.samples.to_string(),
+                format_duration(stats.mean),
+                format_duration(stats.min),
+                format_duration(stats.stddev),
+            ]),
+            Err(_) => line.extend([
+                String::from("error"),
+                String::new(),
+                String::new(),
+                String::new(),
+            ]),
+        }
+        table.push(line);
+    }
+    let mut out = align(&table, &[true, false, true, true, true, true]);
+    for row in rows {
+        if let Err(err) = &row.stats {
+            out.push_str(&format!("Day{} {}: {}\n", row.day, row.stage, err));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::workspace_root;
+
+    #[test]
+    fn bench_day_times_each_stage() {
+        let input = workspace_root().join("day14/src/test.txt");
+        let rows = bench_day(&day14::Day14, &input, 1, 3);
+        let stages: Vec<&str> = rows.iter().map(|r| r.stage).collect();
+        assert_eq!(stages, ["parse", "part1", "part2"]);
+        assert_eq!(rows[1].stats.as_ref().unwrap().samples, 3);
+        assert!(rows.iter().all(|r| r.stats.is_ok()));
+
+        let rows = bench_day(&day25::Day25, &input, 0, 1);
+        assert_eq!(rows.len(), 2);
+        assert!(rows[1].stats.is_err());
+        assert!(bench_day(&day14::Day14, Path::new("no/such/input"), 1, 1).is_empty());
+    }
+
+    #[test]
+    fn render_aligns_rows() {
+        let stats = Stats {
+            samples: 10,
+            mean: Duration::from_millis(3),
+            min: Duration::from_millis(2),
+            max: Duration::from_millis(5),
+            stddev: Duration::from_micros(500),
+        };
+        let rows = [
+            BenchRow {
+                day: 4,
+                stage: "part1",
+                stats: Ok(stats),
+            },
+            BenchRow {
+                day: 25,
+                stage: "part1",
+                stats: Err(String::from("game over")),
+            },
+        ];
+        assert_eq!(
+            render(&rows).lines().collect::<Vec<_>>(),
+            [
+                "Day  Stage  Samples    Mean     Min  Stddev",
+                "  4  part1       10  3.00ms  2.00ms   500µs",
+                " 25  part1    error",
+                "Day25 part1: game over",
+            ]
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::{YEAR, cache_dir, workspace_root};
+
+/// Name of the config file looked for at the workspace root
+pub const CONFIG_FILE: &str = "aoc.toml";
+/// Environment variable overriding the session token
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable overriding the input directory
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+/// Environment variable overriding the year
+pub const YEAR_VAR: &str = "AOC_YEAR";
+/// Environment variable overriding the color setting
+pub const COLOR_VAR: &str = "AOC_COLOR";
+/// Conventional variable turning color off, when `AOC_COLOR` isn't set
+pub const NO_COLOR_VAR: &str = "NO_COLOR";
+
+/// When output is colored
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// only when printing to a terminal
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Reads `auto`, `always` or `never`
+    pub fn parse(s: &str) -> Option<ColorChoice> {
+        match s.trim() {
+            "auto" => Some(ColorChoice::Auto),
+            "always" => Some(ColorChoice::Always),
+            "never" => Some(ColorChoice::Never),
+            _ => None,
+        }
+    }
+
+    /// Whether to color output going to a terminal or not
+    pub fn enabled(self, terminal: bool) -> bool {
+        match self {
+            ColorChoice::Auto => terminal,
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+/// Reasons the settings couldn't be worked out
+#[derive(Debug)]
+pub enum ConfigError {
+    /// the config file couldn't be read
+    Io { path: PathBuf, err: io::Error },
+    /// the config file isn't valid
+    Parse { path: PathBuf, message: String },
+    /// an environment variable has a value that makes no sense
+    BadVar { name: &'static str, value: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, err } => write!(f, "{}: {}", path.display(), err),
+            ConfigError::Parse { path, message } => {
+                write!(f, "{}: {}", path.display(), message.trim())
+            }
+            ConfigError::BadVar { name, value } => write!(f, "{}: bad value {:?}", name, value),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Settings shared by the runner, fetcher and submitter
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// value of the site's session cookie
+    pub session: Option<String>,
+    /// where inputs are downloaded to and read from first
+    pub input_dir: PathBuf,
+    /// year of the calendar
+    pub year: u16,
+    pub color: ColorChoice,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            input_dir: cache_dir(),
+            year: YEAR,
+            color: ColorChoice::Auto,
+        }
+    }
+}
+
+impl Config {
+    /// Reads settings from the text of a config file
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - TOML, any setting left out taking its default
+    /// * `base` - directory a relative `input_dir` is taken from
+    ///
+    pub fn parse(text: &str, base: &Path) -> Result<Config, String> {
+        let mut config: Config = toml::from_str(text).map_err(|err| err.to_string())?;
+        config.input_dir = base.join(&config.input_dir);
+        Ok(config)
+    }
+
+    /// Reads settings from a config file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - config file, or none for `aoc.toml` at the workspace
+    ///   root, which may be missing
+    ///
+    /// # Returns
+    ///
+    /// * the settings, defaults if there's no file, or why they couldn't
+    ///   be read
+    ///
+    pub fn load(path: Option<&Path>) -> Result<Config, ConfigError> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => (workspace_root().join(CONFIG_FILE), false),
+        };
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound && !required => {
+                return Ok(Config::default());
+            }
+            Err(err) => return Err(ConfigError::Io { path, err }),
+        };
+        let base = path.parent().unwrap_or(Path::new("."));
+        Config::parse(&text, base).map_err(|message| ConfigError::Parse { path, message })
+    }
+
+    /// Overrides settings from environment variables
+    ///
+    /// # Arguments
+    ///
+    /// * `var` - looks up a variable, returning none when it isn't set
+    ///
+    pub fn apply_env(
+        mut self,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<Config, ConfigError> {
+        let var = |name| var(name).filter(|v| !v.is_empty());
+        if let Some(session) = var(SESSION_VAR) {
+            self.session = Some(session);
+        }
+        if let Some(dir) = var(INPUT_DIR_VAR) {
+            self.input_dir = PathBuf::from(dir);
+        }
+        if let Some(year) = var(YEAR_VAR) {
+            self.year = year.trim().parse().map_err(|_| ConfigError::BadVar {
+                name: YEAR_VAR,
+                value: year,
+            })?;
+        }
+        match (var(COLOR_VAR), var(NO_COLOR_VAR)) {
+            (Some(color), _) => {
+                self.color = ColorChoice::parse(&color).ok_or(ConfigError::BadVar {
+                    name: COLOR_VAR,
+                    value: color,
+                })?;
+            }
+            (None, Some(_)) => self.color = ColorChoice::Never,
+            (None, None) => {}
+        }
+        Ok(self)
+    }
+
+    /// File recording every answer `aoc submit` has sent
+    pub fn submissions_path(&self) -> PathBuf {
+        self.input_dir.join("submissions.tsv")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn parse_fills_defaults() {
+        let base = Path::new("/work");
+        let config = Config::parse("session = \"abc\"\ninput_dir = \"cache\"\n", base).unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc"));
+        assert_eq!(config.input_dir, PathBuf::from("/work/cache"));
+        assert_eq!(config.year, YEAR);
+        assert_eq!(config.color, ColorChoice::Auto);
+
+        let config = Config::parse(
+            "input_dir = \"/abs\"\nyear = 2020\ncolor = \"never\"\n",
+            base,
+        )
+        .unwrap();
+        assert_eq!(config.input_dir, PathBuf::from("/abs"));
+        assert_eq!(config.year, 2020);
+        assert_eq!(config.color, ColorChoice::Never);
+
+        assert!(Config::parse("colour = \"never\"\n", base).is_err());
+        assert!(Config::parse("color = \"sometimes\"\n", base).is_err());
+    }
+
+    #[test]
+    fn load_allows_missing_default_file_only() {
+        let missing = Path::new("no/such/aoc.toml");
+        assert!(matches!(
+            Config::load(Some(missing)),
+            Err(ConfigError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn env_overrides_file() {
+        let env = |pairs: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = pairs
+                .iter()
+                .map(|(k, v)| (String::from(*k), String::from(*v)))
+                .collect();
+            move |name: &str| vars.get(name).cloned()
+        };
+        let config = Config::default()
+            .apply_env(env(&[
+                (SESSION_VAR, "tok"),
+                (INPUT_DIR_VAR, "/tmp/in"),
+                (YEAR_VAR, "2019"),
+                (NO_COLOR_VAR, "1"),
+            ]))
+            .unwrap();
+        assert_eq!(config.session.as_deref(), Some("tok"));
+        assert_eq!(config.input_dir, PathBuf::from("/tmp/in"));
+        assert_eq!(config.color, ColorChoice::Never);
+        assert_eq!(
+            config.submissions_path(),
+            PathBuf::from("/tmp/in/submissions.tsv")
+        );
+
+        let config = Config::default()
+            .apply_env(env(&[
+                (COLOR_VAR, "always"),
+                (NO_COLOR_VAR, "1"),
+                (SESSION_VAR, ""),
+            ]))
+            .unwrap();
+        assert_eq!(config.color, ColorChoice::Always);
+        assert_eq!(config.session, None);
+
+        assert!(matches!(
+            Config::default().apply_env(env(&[(YEAR_VAR, "twenty")])),
+            Err(ConfigError::BadVar { name: YEAR_VAR, .. })
+        ));
+    }
+}
@@ -0,0 +1,147 @@
+use serde::Serialize;
+
+use crate::{DayResult, PartResult};
+
+/// One part as scripts see it
+#[derive(Serialize)]
+struct Part<'a> {
+    part: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+    seconds: f64,
+}
+
+impl<'a> From<&'a PartResult> for Part<'a> {
+    fn from(result: &'a PartResult) -> Self {
+        Part {
+            part: result.part,
+            answer: result.answer.as_deref().ok(),
+            error: result.answer.as_ref().err().map(String::as_str),
+            seconds: result.elapsed.as_secs_f64(),
+        }
+    }
+}
+
+/// One day as scripts see it
+#[derive(Serialize)]
+struct Day<'a> {
+    day: u8,
+    title: &'a str,
+    input: String,
+    /// why nothing was run, when the input is missing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    parts: Vec<Part<'a>>,
+    seconds: f64,
+}
+
+/// Everything a run produced
+#[derive(Serialize)]
+struct Report<'a> {
+    days: Vec<Day<'a>>,
+    solved: usize,
+    failed: usize,
+    seconds: f64,
+}
+
+/// Renders results as pretty printed JSON
+///
+/// Each part has either an `answer` or an `error`, and a day whose input
+/// is missing has an `error` and no parts. Timings are in seconds.
+///
+/// # Arguments
+///
+/// * `results` - days in the order they should be listed
+///
+pub fn render(results: &[DayResult]) -> String {
+    let parts = || results.iter().flat_map(|r| &r.parts);
+    let report = Report {
+        days: results
+            .iter()
+            .map(|r| Day {
+                day: r.day,
+                title: r.title,
+                input: r.input.display().to_string(),
+                error: (!r.has_input()).then(|| format!("{}: no such file", r.input.display())),
+                parts: r.parts.iter().map(Part::from).collect(),
+                seconds: r.elapsed().as_secs_f64(),
+            })
+            .collect(),
+        solved: parts().filter(|p| p.answer.is_ok()).count(),
+        failed: parts().filter(|p| p.answer.is_err()).count(),
+        seconds: results.iter().map(|r| r.elapsed().as_secs_f64()).sum(),
+    };
+    serde_json::to_string_pretty(&report).expect("report serializes")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    #[test]
+    fn render_reports_answers_and_errors() {
+        let results = [
+            DayResult {
+                day: 2,
+                title: "1202 Program Alarm",
+                input: PathBuf::from("day2/src/input.txt"),
+                parts: vec![
+                    PartResult {
+                        day: 2,
+                        part: 1,
+                        answer: Ok(String::from("3931283")),
+                        elapsed: Duration::from_millis(250),
+                    },
+                    PartResult {
+                        day: 2,
+                        part: 2,
+                        answer: Err(String::from("no noun and verb give 19690720")),
+                        elapsed: Duration::from_millis(500),
+                    },
+                ],
+            },
+            DayResult {
+                day: 9,
+                title: "Sensor Boost",
+                input: PathBuf::from("day9/src/input.txt"),
+                parts: Vec::new(),
+            },
+        ];
+        let rendered: Value = serde_json::from_str(&render(&results)).unwrap();
+        assert_eq!(
+            rendered,
+            json!({
+                "days": [
+                    {
+                        "day": 2,
+                        "title": "1202 Program Alarm",
+                        "input": "day2/src/input.txt",
+                        "parts": [
+                            {"part": 1, "answer": "3931283", "seconds": 0.25},
+                            {"part": 2, "error": "no noun and verb give 19690720", "seconds": 0.5},
+                        ],
+                        "seconds": 0.75,
+                    },
+                    {
+                        "day": 9,
+                        "title": "Sensor Boost",
+                        "input": "day9/src/input.txt",
+                        "error": "day9/src/input.txt: no such file",
+                        "parts": [],
+                        "seconds": 0.0,
+                    },
+                ],
+                "solved": 1,
+                "failed": 1,
+                "seconds": 0.75,
+            })
+        );
+    }
+}
@@ -0,0 +1,342 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use shared::client::cached_input;
+use shared::solution::Solution;
+use shared::{progress_visible, set_progress_visible};
+
+pub mod bench;
+pub mod config;
+pub mod json;
+pub mod scaffold;
+pub mod submissions;
+pub mod table;
+pub mod verify;
+pub mod watch;
+
+/// Every day in the workspace, in calendar order
+pub static SOLUTIONS: [&dyn Solution; 25] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
+
+/// Looks up the solution for a day
+///
+/// # Arguments
+///
+/// * `day` - day of the advent calendar, from 1
+///
+/// # Returns
+///
+/// * the day's solution, or none if there isn't one
+///
+pub fn solution(day: u8) -> Option<&'static dyn Solution> {
+    SOLUTIONS.iter().copied().find(|s| s.day() == day)
+}
+
+/// Directory holding the workspace, with one crate per day beneath it
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("runner crate sits inside the workspace")
+        .to_path_buf()
+}
+
+/// Year of the calendar this workspace solves
+pub const YEAR: u16 = 2019;
+
+/// Directory `aoc fetch` downloads inputs into unless configured otherwise
+pub fn cache_dir() -> PathBuf {
+    workspace_root().join("inputs")
+}
+
+/// File of right answers `aoc verify` checks against
+pub fn golden_path() -> PathBuf {
+    workspace_root().join("answers.txt")
+}
+
+/// Where a day's own crate keeps its puzzle input
+pub fn crate_input(day: u8) -> PathBuf {
+    workspace_root()
+        .join(format!("day{}", day))
+        .join("src")
+        .join("input.txt")
+}
+
+/// Input a day runs against, a downloaded one first and otherwise the
+/// day crate's own
+///
+/// # Arguments
+///
+/// * `input_dir` - directory inputs are downloaded into
+/// * `day` - day of the calendar
+///
+pub fn input_path(input_dir: &Path, day: u8) -> PathBuf {
+    let cached = cached_input(input_dir, day);
+    if cached.is_file() {
+        cached
+    } else {
+        crate_input(day)
+    }
+}
+
+/// Outcome of running one part
+#[derive(Debug, PartialEq)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: u8,
+    /// formatted answer, or the error message
+    pub answer: Result<String, String>,
+    /// time the solver took, zero if it never ran
+    pub elapsed: Duration,
+}
+
+/// Runs one part of a day against an input file
+///
+/// Progress is only drawn, if the caller turned it on, for parts the day
+/// flags as long running. A solver that panics is reported as an error
+/// for this part alone, so the rest of a table still gets run.
+///
+/// # Arguments
+///
+/// * `solution` - day to run
+/// * `part` - 1 or 2
+/// * `input` - path of the puzzle input
+///
+/// # Returns
+///
+/// * answer or error, along with how long the solver took
+///
+pub fn run_part(solution: &dyn Solution, part: u8, input: &Path) -> PartResult {
+    let (day, elapsed) = (solution.day(), Duration::ZERO);
+    if !input.is_file() {
+        return PartResult {
+            day,
+            part,
+            answer: Err(format!("{}: no such file", input.display())),
+            elapsed,
+        };
+    }
+    let visible = progress_visible();
+    set_progress_visible(visible && solution.long_running(part));
+    let start = Instant::now();
+    let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+        solution
+            .solve(part, &input.to_string_lossy())
+            .map_err(|err| err.to_string())
+    }));
+    let elapsed = start.elapsed();
+    let answer = solved.unwrap_or_else(|payload| Err(panic_message(payload.as_ref())));
+    set_progress_visible(visible);
+    PartResult {
+        day,
+        part,
+        answer,
+        elapsed,
+    }
+}
+
+/// Describes what a solver panicked with
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause");
+    format!("panicked: {}", message)
+}
+
+/// Outcome of running the parts of one day
+#[derive(Debug, PartialEq)]
+pub struct DayResult {
+    pub day: u8,
+    pub title: &'static str,
+    /// input the parts were run against
+    pub input: PathBuf,
+    /// parts in the order run, empty when the input is missing
+    pub parts: Vec<PartResult>,
+}
+
+impl DayResult {
+    /// Whether the day's input was there to run against
+    pub fn has_input(&self) -> bool {
+        !self.parts.is_empty()
+    }
+
+    /// Result for one part, if it was run
+    pub fn part(&self, part: u8) -> Option<&PartResult> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+
+    /// Time taken over every part
+    pub fn elapsed(&self) -> Duration {
+        self.parts.iter().map(|p| p.elapsed).sum()
+    }
+}
+
+/// Runs some parts of a day against an input file
+///
+/// # Arguments
+///
+/// * `solution` - day to run
+/// * `parts` - parts to run, in order
+/// * `input` - path of the puzzle input
+///
+/// # Returns
+///
+/// * result of each part, or none at all if the input is missing
+///
+pub fn run_day(solution: &dyn Solution, parts: &[u8], input: &Path) -> DayResult {
+    let parts = if input.is_file() {
+        parts
+            .iter()
+            .map(|&part| run_part(solution, part, input))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    DayResult {
+        day: solution.day(),
+        title: solution.title(),
+        input: input.to_path_buf(),
+        parts,
+    }
+}
+
+/// Runs every part of every day against its own input
+///
+/// # Arguments
+///
+/// * `input_dir` - directory inputs are downloaded into
+///
+pub fn run_all(input_dir: &Path) -> Vec<DayResult> {
+    SOLUTIONS
+        .iter()
+        .map(|s| {
+            let parts: Vec<u8> = (1..=s.parts()).collect();
+            run_day(*s, &parts, &input_path(input_dir, s.day()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solutions_are_in_calendar_order() {
+        for (i, s) in SOLUTIONS.iter().enumerate() {
+            assert_eq!(s.day() as usize, i + 1);
+        }
+        assert_eq!(solution(25).unwrap().parts(), 1);
+        assert!(solution(SOLUTIONS.len() as u8 + 1).is_none());
+        assert!(solution(0).is_none());
+    }
+
+    #[test]
+    fn crate_input_points_into_day_crate() {
+        let path = crate_input(7);
+        assert!(path.ends_with("day7/src/input.txt"));
+        assert!(path.parent().unwrap().join("lib.rs").is_file());
+    }
+
+    #[test]
+    fn run_part_reports_missing_input() {
+        let result = run_part(&day5::Day5, 1, Path::new("no/such/input.txt"));
+        assert_eq!(
+            result.answer,
+            Err(String::from("no/such/input.txt: no such file"))
+        );
+        assert_eq!(result.elapsed, Duration::ZERO);
+    }
+
+    #[test]
+    fn run_part_solves() {
+        let input = workspace_root().join("day14/src/test.txt");
+        let result = run_part(&day14::Day14, 1, &input);
+        assert_eq!(result.answer, Ok(String::from("31")));
+        let result = run_part(&day25::Day25, 2, &input);
+        assert_eq!(result.answer, Err(String::from("day 25 has no part2")));
+    }
+
+    #[test]
+    fn run_part_survives_panics() {
+        struct Panics;
+
+        impl Solution for Panics {
+            fn day(&self) -> u8 {
+                1
+            }
+
+            fn title(&self) -> &'static str {
+                "Panics"
+            }
+
+            fn part1(&self, _filename: &str) -> shared::solution::Answer {
+                panic!("no {}", "luck")
+            }
+
+            fn part2(&self, _filename: &str) -> shared::solution::Answer {
+                panic!("no luck")
+            }
+        }
+
+        let input = workspace_root().join("day1/src/input.txt");
+        let result = run_day(&Panics, &[1, 2], &input);
+        for part in [1, 2] {
+            assert_eq!(
+                result.part(part).unwrap().answer,
+                Err(String::from("panicked: no luck"))
+            );
+        }
+    }
+
+    #[test]
+    fn long_running_parts_are_flagged() {
+        let long: Vec<(u8, u8)> = SOLUTIONS
+            .iter()
+            .flat_map(|s| (1..=s.parts()).map(move |p| (s.day(), p)))
+            .filter(|&(day, part)| solution(day).unwrap().long_running(part))
+            .collect();
+        assert_eq!(long, [(2, 2), (16, 2), (18, 1), (18, 2)]);
+    }
+
+    #[test]
+    fn run_day_skips_missing_input() {
+        let result = run_day(&day5::Day5, &[1, 2], Path::new("no/such/input.txt"));
+        assert!(!result.has_input());
+        assert_eq!(result.elapsed(), Duration::ZERO);
+
+        let input = workspace_root().join("day14/src/test3.txt");
+        let result = run_day(&day14::Day14, &[2, 1], &input);
+        assert_eq!(result.title, "Space Stoichiometry");
+        assert_eq!(result.parts[0].part, 2);
+        assert_eq!(result.part(1).unwrap().answer, Ok(String::from("13312")));
+        assert_eq!(result.part(2).unwrap().answer, Ok(String::from("82892753")));
+    }
+}
@@ -0,0 +1,527 @@
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use aoc::bench::{self, BenchRow, bench_day};
+use aoc::config::{CONFIG_FILE, Config, SESSION_VAR};
+use aoc::scaffold::scaffold;
+use aoc::submissions::{Submission, Submissions};
+use aoc::verify::{self, Check, Golden};
+use aoc::watch::{Answers, diff, parse_answers, snapshot, watched_paths};
+use aoc::{
+    SOLUTIONS, golden_path, input_path, json, run_all, run_day, run_part, solution, table,
+    workspace_root,
+};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use shared::client::{Client, Verdict, cached_input, download};
+use shared::{logging, set_progress_visible};
+
+/// Runs the Advent of Code 2019 solutions in this workspace
+#[derive(Parser)]
+struct Cli {
+    /// settings file, instead of `aoc.toml` at the workspace root
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// log solver diagnostics to stderr, twice to trace every step
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+    #[command(subcommand)]
+    command: Cmd,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    /// Solve a day's puzzle
+    Run(RunArgs),
+    /// Download puzzle inputs into the cache the runner reads first
+    Fetch {
+        /// day to fetch
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25), required_unless_present = "all")]
+        day: Option<u8>,
+        /// fetch every day
+        #[arg(long, conflicts_with = "day")]
+        all: bool,
+        /// download again even if the input is already cached
+        #[arg(long)]
+        force: bool,
+    },
+    /// Solve a part and send the answer to the puzzle site
+    Submit {
+        /// day to submit
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// part to submit
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+    },
+    /// Run every day and check the answers against the known right ones
+    Verify {
+        /// answers file, instead of `answers.txt` at the workspace root
+        #[arg(long)]
+        answers: Option<PathBuf>,
+    },
+    /// Create the crate for a new day and add it to the workspace and runner
+    New {
+        /// day to create, past the calendar's end for an extra puzzle
+        #[arg(value_parser = clap::value_parser!(u8).range(1..))]
+        day: u8,
+    },
+    /// Time reading the input and solving each part over many runs
+    Bench {
+        /// only time this day, may be repeated
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..))]
+        day: Vec<u8>,
+        /// untimed runs of each stage first
+        #[arg(long, default_value_t = 3)]
+        warmup: usize,
+        /// timed runs of each stage, at least one
+        #[arg(long, default_value_t = 10)]
+        samples: usize,
+    },
+    /// Rerun a day whenever its source or input changes, showing how the
+    /// answers moved
+    Watch {
+        /// day to watch
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..))]
+        day: u8,
+        /// milliseconds between checks for changes
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+    },
+    /// Run every day and render the results as a markdown table
+    Report {
+        /// write the table to this file instead of printing it
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// day to run
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..), required_unless_present = "all")]
+    day: Option<u8>,
+    /// run every day and finish with a table of answers and timings
+    #[arg(long, conflicts_with_all = ["day", "part", "input"])]
+    all: bool,
+    /// part to run
+    #[arg(long, value_enum, default_value_t = Parts::Both)]
+    part: Parts,
+    /// input file, instead of the day's own `src/input.txt`
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// how to print the results
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+/// How results are printed
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// answers, or a table of every day with `--all`
+    Text,
+    /// answers, timings and errors as JSON, errors included on stdout
+    Json,
+}
+
+/// Which parts of a day to run
+#[derive(Clone, Copy, ValueEnum)]
+enum Parts {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    Both,
+}
+
+impl Parts {
+    fn numbers(self, parts: u8) -> Vec<u8> {
+        match self {
+            Parts::One => vec![1],
+            Parts::Two => vec![2],
+            Parts::Both => (1..=parts).collect(),
+        }
+    }
+}
+
+/// Wraps text in a terminal color when color is on
+///
+/// # Arguments
+///
+/// * `text` - text to color
+/// * `code` - ANSI color code, such as 31 for red
+/// * `on` - whether to color at all
+///
+fn paint(text: &str, code: u8, on: bool) -> String {
+    if on {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        String::from(text)
+    }
+}
+
+const RED: u8 = 31;
+const GREEN: u8 = 32;
+const YELLOW: u8 = 33;
+
+/// Runs one day or all of them, printing the results
+fn run(args: RunArgs, config: &Config) -> ExitCode {
+    let RunArgs {
+        day,
+        all,
+        part,
+        input,
+        format,
+    } = args;
+    set_progress_visible(matches!(format, Format::Text) && io::stderr().is_terminal());
+    let results = match day.filter(|_| !all) {
+        None => run_all(&config.input_dir),
+        Some(day) => {
+            let Some(solution) = solution(day) else {
+                eprintln!("day {} isn't solved yet", day);
+                return ExitCode::FAILURE;
+            };
+            let input = input.unwrap_or_else(|| input_path(&config.input_dir, day));
+            vec![run_day(solution, &part.numbers(solution.parts()), &input)]
+        }
+    };
+
+    let failed = results
+        .iter()
+        .flat_map(|r| &r.parts)
+        .any(|p| p.answer.is_err())
+        || (!all && !results[0].has_input());
+    let color = config.color.enabled(io::stderr().is_terminal());
+    match format {
+        Format::Json => println!("{}", json::render(&results)),
+        Format::Text if all => {
+            print!("{}", table::summary(&results));
+            for part in results.iter().flat_map(|r| &r.parts) {
+                if let Err(err) = &part.answer {
+                    eprintln!(
+                        "Day{} Part{}: {}",
+                        part.day,
+                        part.part,
+                        paint(err, RED, color)
+                    );
+                }
+            }
+        }
+        Format::Text => {
+            let result = &results[0];
+            if !result.has_input() {
+                let missing = format!("{}: no such file", result.input.display());
+                eprintln!("{}", paint(&missing, RED, color));
+            }
+            for part in &result.parts {
+                match &part.answer {
+                    Ok(answer) => println!("Day{} Part{}: {}", part.day, part.part, answer),
+                    Err(err) => {
+                        eprintln!(
+                            "Day{} Part{}: {}",
+                            part.day,
+                            part.part,
+                            paint(err, RED, color)
+                        )
+                    }
+                }
+            }
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Runs every day and prints or writes the markdown table
+fn report(output: Option<PathBuf>, config: &Config) -> ExitCode {
+    let markdown = table::markdown(&run_all(&config.input_dir));
+    let Some(output) = output else {
+        print!("{}", markdown);
+        return ExitCode::SUCCESS;
+    };
+    match fs::write(&output, markdown) {
+        Ok(()) => {
+            println!("wrote {}", output.display());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{}: {}", output.display(), err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Downloads inputs for some days, skipping those already cached
+fn fetch(days: Vec<u8>, force: bool, config: &Config) -> ExitCode {
+    let client = match client(config) {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let dir = &config.input_dir;
+    let mut code = ExitCode::SUCCESS;
+    for day in days {
+        let cached = cached_input(dir, day);
+        if cached.is_file() && !force {
+            println!("Day{}: already at {}", day, cached.display());
+            continue;
+        }
+        match download(&client, day, dir) {
+            Ok(path) => println!("Day{}: saved to {}", day, path.display()),
+            Err(err) => {
+                eprintln!("Day{}: {}", day, err);
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+    code
+}
+
+/// Logged in client, or a message on how to log in
+fn client(config: &Config) -> Result<Client, String> {
+    let session = config.session.as_deref().unwrap_or_default();
+    Client::new(session, config.year).map_err(|err| {
+        format!(
+            "{}: put the site's session cookie in {} or as `session` in {}",
+            err, SESSION_VAR, CONFIG_FILE
+        )
+    })
+}
+
+/// Solves a part and submits its answer unless it was sent before
+fn submit(day: u8, part: u8, config: &Config) -> Result<(), String> {
+    let solution = solution(day).ok_or_else(|| format!("day {} isn't solved yet", day))?;
+    set_progress_visible(io::stderr().is_terminal());
+    let answer = run_part(solution, part, &input_path(&config.input_dir, day))
+        .answer
+        .map_err(|err| format!("Day{} Part{}: {}", day, part, err))?;
+    let path = config.submissions_path();
+    let mut log = Submissions::load(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    if let Some(previous) = log.previous(day, part, &answer) {
+        println!(
+            "Day{} Part{}: not sending {}, {} was already {}",
+            day, part, answer, previous.answer, previous.verdict
+        );
+        return if previous.solved() {
+            Ok(())
+        } else {
+            Err(String::from("answer already rejected"))
+        };
+    }
+
+    let verdict = client(config)?
+        .submit(day, part, &answer)
+        .map_err(|err| err.to_string())?;
+    println!("Day{} Part{}: {} is {}", day, part, answer, verdict);
+    let settled = matches!(
+        verdict,
+        Verdict::Correct | Verdict::Incorrect { .. } | Verdict::AlreadySolved
+    );
+    if settled {
+        log.record(Submission {
+            day,
+            part,
+            answer,
+            verdict: verdict.to_string(),
+        })
+        .map_err(|err| format!("{}: {}", path.display(), err))?;
+    }
+    match verdict {
+        Verdict::Correct | Verdict::AlreadySolved => Ok(()),
+        _ => Err(String::from("answer not accepted")),
+    }
+}
+
+/// Runs every day and reports how each part compares with its right
+/// answer, failing on any mismatch
+fn verify(answers: Option<PathBuf>, config: &Config) -> ExitCode {
+    let path = answers.unwrap_or_else(golden_path);
+    let golden = match Golden::load(&path) {
+        Ok(golden) => golden,
+        Err(err) => {
+            eprintln!("{}: {}", path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+    set_progress_visible(io::stderr().is_terminal());
+    let checks = verify::verify(&run_all(&config.input_dir), &golden);
+    let count = |f: fn(&Check) -> bool| checks.iter().filter(|(_, _, c)| f(c)).count();
+    let color = config.color.enabled(io::stdout().is_terminal());
+    for (day, part, check) in &checks {
+        let code = match check {
+            Check::Pass => GREEN,
+            Check::Fail { .. } => RED,
+            Check::Missing(_) => YELLOW,
+        };
+        println!(
+            "Day{} Part{}: {}",
+            day,
+            part,
+            paint(&check.to_string(), code, color)
+        );
+    }
+    let failed = count(|c| matches!(c, Check::Fail { .. }));
+    println!(
+        "{} passed, {} failed, {} missing",
+        count(|c| *c == Check::Pass),
+        failed,
+        count(|c| matches!(c, Check::Missing(_)))
+    );
+    if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Creates a day crate in the workspace, listing what was made
+fn new_day(day: u8) -> ExitCode {
+    match scaffold(&workspace_root(), day) {
+        Ok(created) => {
+            for path in created {
+                println!("created {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Times every day with an input, or only some days
+fn bench(days: Vec<u8>, warmup: usize, samples: usize, config: &Config) -> ExitCode {
+    let rows: Vec<BenchRow> = SOLUTIONS
+        .iter()
+        .filter(|s| days.is_empty() || days.contains(&s.day()))
+        .flat_map(|s| bench_day(*s, &input_path(&config.input_dir, s.day()), warmup, samples))
+        .collect();
+    if rows.is_empty() {
+        eprintln!("no inputs to time against");
+        return ExitCode::FAILURE;
+    }
+    print!("{}", bench::render(&rows));
+    if rows.iter().any(|r| r.stats.is_err()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Rebuilds the runner and solves a day with it, so source changes are
+/// picked up
+///
+/// # Arguments
+///
+/// * `day` - day to solve
+/// * `config` - settings file to hand on, if one was given
+/// * `verbose` - times `-v` was given, handed on too
+///
+/// # Returns
+///
+/// * answers, or why the build or run produced none
+///
+fn rerun(day: u8, config: Option<&Path>, verbose: u8) -> Result<Answers, String> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    let mut command = Command::new(cargo);
+    command
+        .current_dir(workspace_root())
+        .args(["run", "-q", "-p", "aoc", "--", "run", "--format", "json"])
+        .args(["--day", &day.to_string()]);
+    if let Some(path) = config {
+        // the child runs from the workspace root, not where we were started
+        let path = std::path::absolute(path).map_err(|err| err.to_string())?;
+        command.arg("--config").arg(path);
+    }
+    if verbose > 0 {
+        command.arg(format!("-{}", "v".repeat(verbose as usize)));
+    }
+    let output = command
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|err| err.to_string())?;
+    parse_answers(&String::from_utf8_lossy(&output.stdout))
+        .map_err(|err| format!("no answers: {}", err))
+}
+
+/// Reruns a day on every change until interrupted
+fn watch(
+    day: u8,
+    interval: u64,
+    config: &Config,
+    config_path: Option<&Path>,
+    verbose: u8,
+) -> ExitCode {
+    let mut paths = watched_paths(&config.input_dir, day);
+    paths.push(config_path.map_or_else(|| workspace_root().join(CONFIG_FILE), Path::to_path_buf));
+    let mut previous: Option<Answers> = None;
+    loop {
+        let seen = snapshot(&paths);
+        println!("Day{}: running", day);
+        match rerun(day, config_path, verbose) {
+            Ok(answers) => {
+                for line in diff(previous.as_ref(), &answers) {
+                    println!("{}", line);
+                }
+                previous = Some(answers);
+            }
+            Err(err) => eprintln!("Day{}: {}", day, err),
+        }
+        while snapshot(&paths) == seen {
+            thread::sleep(Duration::from_millis(interval));
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    logging::init(cli.verbose).expect("logger is only installed here");
+    let config = match Config::load(cli.config.as_deref())
+        .and_then(|c| c.apply_env(|name| env::var(name).ok()))
+    {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    match cli.command {
+        Cmd::Run(args) => run(args, &config),
+        Cmd::Fetch { day, all, force } => {
+            let days = match day.filter(|_| !all) {
+                Some(day) => vec![day],
+                None => (1..=25).collect(),
+            };
+            fetch(days, force, &config)
+        }
+        Cmd::Submit { day, part } => match submit(day, part, &config) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("{}", err);
+                ExitCode::FAILURE
+            }
+        },
+        Cmd::Verify { answers } => verify(answers, &config),
+        Cmd::New { day } => new_day(day),
+        Cmd::Bench {
+            day,
+            warmup,
+            samples,
+        } => bench(day, warmup, samples, &config),
+        Cmd::Watch { day, interval } => {
+            watch(day, interval, &config, cli.config.as_deref(), cli.verbose)
+        }
+        Cmd::Report { output } => report(output, &config),
+    }
+}
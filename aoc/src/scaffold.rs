@@ -0,0 +1,281 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Files of a new day crate, relative to its directory, with `__DAY__`
+/// standing for the day number
+const TEMPLATES: [(&str, &str); 5] = [
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.tmpl")),
+    ("src/lib.rs", include_str!("../templates/lib.rs.tmpl")),
+    ("src/main.rs", include_str!("../templates/main.rs.tmpl")),
+    ("src/test.txt", "0\n"),
+    ("src/input.txt", ""),
+];
+
+/// Reasons a day crate couldn't be created
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// the crate's directory is already there
+    Exists(PathBuf),
+    /// the workspace manifest has no `members` list to add to
+    NoMembers(PathBuf),
+    /// the runner's manifest or `SOLUTIONS` has no list of days to add to
+    NoDays(PathBuf),
+    /// a file couldn't be read or written
+    Io(io::Error),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(dir) => write!(f, "{} already exists", dir.display()),
+            ScaffoldError::NoMembers(manifest) => {
+                write!(f, "{} has no workspace members list", manifest.display())
+            }
+            ScaffoldError::NoDays(file) => write!(f, "{} has no list of days", file.display()),
+            ScaffoldError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+impl From<io::Error> for ScaffoldError {
+    fn from(err: io::Error) -> Self {
+        ScaffoldError::Io(err)
+    }
+}
+
+/// Adds a member to the end of a workspace manifest's `members` list
+///
+/// # Arguments
+///
+/// * `manifest` - contents of the workspace `Cargo.toml`
+/// * `member` - path of the new member
+///
+/// # Returns
+///
+/// * the updated manifest, unchanged if the member is already listed, or
+///   none if there is no one-entry-per-line `members` list
+///
+pub fn add_member(manifest: &str, member: &str) -> Option<String> {
+    let quoted = format!("\"{}\"", member);
+    let start = manifest.find("members = [\n")?;
+    let end = start + manifest[start..].find("\n]")? + 1;
+    if manifest[start..end]
+        .lines()
+        .any(|l| l.trim().trim_end_matches(',') == quoted)
+    {
+        return Some(String::from(manifest));
+    }
+    Some(format!(
+        "{}    {},\n{}",
+        &manifest[..end],
+        quoted,
+        &manifest[end..]
+    ))
+}
+
+/// Adds a day crate to the end of the days in the runner's dependencies
+///
+/// # Arguments
+///
+/// * `manifest` - contents of the runner's `Cargo.toml`
+/// * `day` - day of the calendar
+///
+/// # Returns
+///
+/// * the updated manifest, unchanged if the day is already a dependency,
+///   or none if no day is
+///
+pub fn add_dependency(manifest: &str, day: u8) -> Option<String> {
+    let line = format!("day{0} = {{ path = \"../day{0}\" }}", day);
+    if manifest.lines().any(|l| l == line) {
+        return Some(String::from(manifest));
+    }
+    let last = manifest.rfind("\nday")? + 1;
+    let end = last + manifest[last..].find('\n')? + 1;
+    Some(format!(
+        "{}{}\n{}",
+        &manifest[..end],
+        line,
+        &manifest[end..]
+    ))
+}
+
+/// Adds a day to the end of the runner's `SOLUTIONS`, growing its length
+///
+/// # Arguments
+///
+/// * `lib` - contents of the runner's `src/lib.rs`
+/// * `day` - day of the calendar
+///
+/// # Returns
+///
+/// * the updated source, unchanged if the day is already listed, or none
+///   if there is no one-entry-per-line `SOLUTIONS` array
+///
+pub fn add_solution(lib: &str, day: u8) -> Option<String> {
+    const DECL: &str = "pub static SOLUTIONS: [&dyn Solution; ";
+    let entry = format!("    &day{0}::Day{0},", day);
+    let start = lib.find(DECL)? + DECL.len();
+    let len_end = start + lib[start..].find(']')?;
+    let len: usize = lib[start..len_end].parse().ok()?;
+    let end = len_end + lib[len_end..].find("\n];")? + 1;
+    if lib[len_end..end].lines().any(|l| l == entry) {
+        return Some(String::from(lib));
+    }
+    Some(format!(
+        "{}{}{}{}\n{}",
+        &lib[..start],
+        len + 1,
+        &lib[len_end..end],
+        entry,
+        &lib[end..]
+    ))
+}
+
+/// Creates a crate for a new day in a workspace
+///
+/// The crate gets part stubs, a Solution impl, tests, a main, an empty
+/// `src/input.txt` to paste the input into, a place in the workspace and
+/// an entry in the runner's dependencies and `SOLUTIONS` so `aoc` runs it.
+/// Every file is patched in memory first, so nothing is written unless
+/// all of them can be.
+///
+/// # Arguments
+///
+/// * `root` - workspace directory
+/// * `day` - day of the calendar
+///
+/// # Returns
+///
+/// * files created, or why the crate couldn't be
+///
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let name = format!("day{}", day);
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(ScaffoldError::Exists(dir));
+    }
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = add_member(&fs::read_to_string(&manifest_path)?, &name)
+        .ok_or_else(|| ScaffoldError::NoMembers(manifest_path.clone()))?;
+    let runner_manifest_path = root.join("aoc").join("Cargo.toml");
+    let runner_manifest = add_dependency(&fs::read_to_string(&runner_manifest_path)?, day)
+        .ok_or_else(|| ScaffoldError::NoDays(runner_manifest_path.clone()))?;
+    let runner_lib_path = root.join("aoc").join("src").join("lib.rs");
+    let runner_lib = add_solution(&fs::read_to_string(&runner_lib_path)?, day)
+        .ok_or_else(|| ScaffoldError::NoDays(runner_lib_path.clone()))?;
+
+    let mut created = Vec::new();
+    for (file, template) in TEMPLATES {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().expect("templates sit in the crate"))?;
+        fs::write(&path, template.replace("__DAY__", &day.to_string()))?;
+        created.push(path);
+    }
+    fs::write(&manifest_path, manifest)?;
+    fs::write(&runner_manifest_path, runner_manifest)?;
+    fs::write(&runner_lib_path, runner_lib)?;
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str =
+        "[workspace]\nresolver = \"3\"\nmembers = [\n    \"aoc\",\n    \"day1\",\n]\n";
+
+    const RUNNER_MANIFEST: &str = "[package]\nname = \"aoc\"\n\n[dependencies]\n\
+        clap = \"4\"\nday1 = { path = \"../day1\" }\nshared = { path = \"../shared\" }\n";
+
+    const RUNNER_LIB: &str = "use shared::solution::Solution;\n\n\
+        pub static SOLUTIONS: [&dyn Solution; 1] = [\n    &day1::Day1,\n];\n";
+
+    /// Directory removed when the test ends, even if an assertion failed
+    struct TempDir(PathBuf);
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn add_member_appends() {
+        assert_eq!(
+            add_member(MANIFEST, "day2").unwrap(),
+            "[workspace]\nresolver = \"3\"\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day2\",\n]\n"
+        );
+        assert_eq!(add_member(MANIFEST, "day1").unwrap(), MANIFEST);
+        assert_eq!(add_member("[workspace]\n", "day2"), None);
+    }
+
+    #[test]
+    fn add_dependency_appends_after_last_day() {
+        assert_eq!(
+            add_dependency(RUNNER_MANIFEST, 2).unwrap(),
+            RUNNER_MANIFEST.replace("day1\" }\n", "day1\" }\nday2 = { path = \"../day2\" }\n")
+        );
+        assert_eq!(add_dependency(RUNNER_MANIFEST, 1).unwrap(), RUNNER_MANIFEST);
+        assert_eq!(add_dependency("[dependencies]\n", 2), None);
+    }
+
+    #[test]
+    fn add_solution_grows_array() {
+        assert_eq!(
+            add_solution(RUNNER_LIB, 2).unwrap(),
+            "use shared::solution::Solution;\n\n\
+             pub static SOLUTIONS: [&dyn Solution; 2] = [\n    &day1::Day1,\n    &day2::Day2,\n];\n"
+        );
+        assert_eq!(add_solution(RUNNER_LIB, 1).unwrap(), RUNNER_LIB);
+        assert_eq!(add_solution("use shared::solution::Solution;\n", 2), None);
+    }
+
+    #[test]
+    fn scaffold_creates_crate() {
+        let dir =
+            TempDir(std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id())));
+        let root = &dir.0;
+        fs::create_dir_all(root.join("day1")).unwrap();
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join("Cargo.toml"), MANIFEST).unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), RUNNER_MANIFEST).unwrap();
+        fs::write(root.join("aoc/src/lib.rs"), RUNNER_LIB).unwrap();
+
+        let created = scaffold(root, 7).unwrap();
+        assert_eq!(created.len(), TEMPLATES.len());
+        let lib = fs::read_to_string(root.join("day7/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub enum Day7Error"));
+        assert!(lib.contains("impl Solution for Day7 {"));
+        assert!(lib.contains("shared::try_ingest_file(filename)"));
+        assert!(!lib.contains("__DAY__"));
+        let main = fs::read_to_string(root.join("day7/src/main.rs")).unwrap();
+        assert!(main.contains("use day7::{Day7Error, part1, part2};"));
+        assert!(main.contains("    #[command(flatten)]\n    args: DayArgs,\n"));
+        assert_eq!(
+            fs::read_to_string(root.join("day7/src/input.txt")).unwrap(),
+            ""
+        );
+        assert!(
+            fs::read_to_string(root.join("Cargo.toml"))
+                .unwrap()
+                .contains("    \"day7\",\n]")
+        );
+        assert!(
+            fs::read_to_string(root.join("aoc/Cargo.toml"))
+                .unwrap()
+                .contains("day7 = { path = \"../day7\" }\nshared")
+        );
+        assert!(
+            fs::read_to_string(root.join("aoc/src/lib.rs"))
+                .unwrap()
+                .contains("; 2] = [\n    &day1::Day1,\n    &day7::Day7,\n];")
+        );
+
+        assert!(matches!(scaffold(root, 1), Err(ScaffoldError::Exists(_))));
+    }
+}
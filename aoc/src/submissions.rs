@@ -0,0 +1,134 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use shared::client::Verdict;
+
+/// Answer sent for one part, and what the site made of it
+#[derive(Debug, PartialEq)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    /// verdict as displayed, such as `incorrect, too high`
+    pub verdict: String,
+}
+
+impl Submission {
+    /// Whether the part needs no more answers
+    pub fn solved(&self) -> bool {
+        self.verdict == Verdict::Correct.to_string()
+            || self.verdict == Verdict::AlreadySolved.to_string()
+    }
+
+    /// Reads a `day part verdict answer` line, tab separated
+    fn parse(line: &str) -> Option<Submission> {
+        let mut fields = line.splitn(4, '\t');
+        Some(Submission {
+            day: fields.next()?.parse().ok()?,
+            part: fields.next()?.parse().ok()?,
+            verdict: String::from(fields.next()?),
+            answer: String::from(fields.next()?),
+        })
+    }
+}
+
+/// Log of every answer sent, kept so none goes in twice
+#[derive(Debug)]
+pub struct Submissions {
+    path: PathBuf,
+    entries: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Reads the log, which starts empty if the file doesn't exist yet
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - file holding the log
+    ///
+    pub fn load(path: &Path) -> io::Result<Submissions> {
+        let entries = match fs::read_to_string(path) {
+            Ok(text) => text.lines().filter_map(Submission::parse).collect(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(Submissions {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    /// Earlier submission that makes sending `answer` pointless
+    ///
+    /// # Returns
+    ///
+    /// * the same answer sent before, or else the submission that solved
+    ///   the part, if there is either
+    ///
+    pub fn previous(&self, day: u8, part: u8, answer: &str) -> Option<&Submission> {
+        let mut sent = self
+            .entries
+            .iter()
+            .filter(|s| s.day == day && s.part == part);
+        sent.clone()
+            .find(|s| s.answer == answer)
+            .or_else(|| sent.find(|s| s.solved()))
+    }
+
+    /// Adds a submission to the log and its file
+    pub fn record(&mut self, submission: Submission) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}",
+            submission.day, submission.part, submission.verdict, submission.answer
+        )?;
+        self.entries.push(submission);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            day: 1,
+            part,
+            answer: String::from(answer),
+            verdict: verdict.to_string(),
+        }
+    }
+
+    #[test]
+    fn log_round_trips_and_blocks_resubmits() {
+        let path = std::env::temp_dir()
+            .join(format!("aoc-submissions-{}", std::process::id()))
+            .join("submissions.tsv");
+        let mut log = Submissions::load(&path).unwrap();
+        assert_eq!(log.previous(1, 1, "42"), None);
+
+        let too_high = Verdict::Incorrect {
+            hint: Some(String::from("too high")),
+        };
+        log.record(submission(1, "42", too_high)).unwrap();
+        log.record(submission(1, "41", Verdict::Correct)).unwrap();
+
+        let log = Submissions::load(&path).unwrap();
+        assert_eq!(
+            log.previous(1, 1, "42").unwrap().verdict,
+            "incorrect, too high"
+        );
+        assert_eq!(log.previous(1, 1, "40").unwrap().answer, "41");
+        assert_eq!(log.previous(1, 2, "42"), None);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
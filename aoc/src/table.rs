@@ -0,0 +1,246 @@
+use std::time::Duration;
+
+use crate::DayResult;
+
+/// Short human readable duration, such as `1.25ms`
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{:.2}s", secs)
+    } else if secs >= 1e-3 {
+        format!("{:.2}ms", secs * 1e3)
+    } else {
+        format!("{:.0}µs", secs * 1e6)
+    }
+}
+
+/// Cell showing how a part went
+fn answer_cell(result: &DayResult, part: u8) -> String {
+    match result.part(part) {
+        _ if !result.has_input() => String::from("no input"),
+        Some(p) => match &p.answer {
+            Ok(answer) => answer.clone(),
+            Err(_) => String::from("error"),
+        },
+        None => String::from("-"),
+    }
+}
+
+/// Cell showing how long a part took
+fn time_cell(result: &DayResult, part: u8) -> String {
+    match result.part(part) {
+        Some(p) if p.elapsed > Duration::ZERO => format_duration(p.elapsed),
+        _ => String::new(),
+    }
+}
+
+/// Lays out rows as columns padded to their widest cell
+///
+/// # Arguments
+///
+/// * `rows` - cells of each row, the first row being the header
+/// * `right` - whether each column is aligned to the right
+///
+pub(crate) fn align(rows: &[Vec<String>], right: &[bool]) -> String {
+    let widths: Vec<usize> = (0..right.len())
+        .map(|c| {
+            rows.iter()
+                .map(|row| row[c].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let mut out = String::new();
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .zip(right)
+            .map(|((cell, &w), &r)| {
+                if r {
+                    format!("{:>w$}", cell)
+                } else {
+                    format!("{:<w$}", cell)
+                }
+            })
+            .collect();
+        out.push_str(cells.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Table of every day's answers and timings, with totals at the bottom
+///
+/// # Arguments
+///
+/// * `results` - days in the order they should be listed
+///
+/// # Returns
+///
+/// * the table, one line per day
+///
+pub fn summary(results: &[DayResult]) -> String {
+    let header = ["Day", "Title", "Part1", "Time", "Part2", "Time", "Total"];
+    let mut rows = vec![header.map(String::from).to_vec()];
+    for result in results {
+        rows.push(vec![
+            result.day.to_string(),
+            String::from(result.title),
+            answer_cell(result, 1),
+            time_cell(result, 1),
+            answer_cell(result, 2),
+            time_cell(result, 2),
+            if result.has_input() {
+                format_duration(result.elapsed())
+            } else {
+                String::new()
+            },
+        ]);
+    }
+    let total = |part: u8| -> Duration {
+        results
+            .iter()
+            .filter_map(|r| r.part(part))
+            .map(|p| p.elapsed)
+            .sum()
+    };
+    let solved = results
+        .iter()
+        .flat_map(|r| &r.parts)
+        .filter(|p| p.answer.is_ok())
+        .count();
+    rows.push(vec![
+        String::from("Total"),
+        format!("{} parts solved", solved),
+        String::new(),
+        format_duration(total(1)),
+        String::new(),
+        format_duration(total(2)),
+        format_duration(total(1) + total(2)),
+    ]);
+    align(&rows, &[true, false, false, true, false, true, true])
+}
+
+/// Escapes text so it stays inside one markdown table cell
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// Markdown table of every day's answers and timings, with totals at the
+/// bottom
+///
+/// # Arguments
+///
+/// * `results` - days in the order they should be listed
+///
+/// # Returns
+///
+/// * the table, with failed parts showing their error
+///
+pub fn markdown(results: &[DayResult]) -> String {
+    let mut out = String::from(
+        "| Day | Title | Part 1 | Time | Part 2 | Time |\n\
+         |----:|:------|:-------|-----:|:-------|-----:|\n",
+    );
+    let answer = |result: &DayResult, part: u8| match result.part(part).map(|p| &p.answer) {
+        _ if !result.has_input() => String::from("*no input*"),
+        Some(Ok(answer)) => format!("`{}`", markdown_cell(answer)),
+        Some(Err(err)) => format!("**error:** {}", markdown_cell(err)),
+        None => String::from("-"),
+    };
+    for result in results {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            result.day,
+            markdown_cell(result.title),
+            answer(result, 1),
+            time_cell(result, 1),
+            answer(result, 2),
+            time_cell(result, 2),
+        ));
+    }
+    let total = |part: u8| -> Duration {
+        results
+            .iter()
+            .filter_map(|r| r.part(part))
+            .map(|p| p.elapsed)
+            .sum()
+    };
+    out.push_str(&format!(
+        "| **Total** | | | {} | | {} |\n",
+        format_duration(total(1)),
+        format_duration(total(2)),
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::PartResult;
+
+    fn part(part: u8, answer: Result<&str, &str>, millis: u64) -> PartResult {
+        PartResult {
+            day: 1,
+            part,
+            answer: answer.map(String::from).map_err(String::from),
+            elapsed: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn format_duration_picks_units() {
+        assert_eq!(format_duration(Duration::from_micros(42)), "42µs");
+        assert_eq!(format_duration(Duration::from_micros(1250)), "1.25ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50s");
+    }
+
+    #[test]
+    fn summary_aligns_columns() {
+        let results = [
+            DayResult {
+                day: 1,
+                title: "Fuel",
+                input: PathBuf::from("day1"),
+                parts: vec![part(1, Ok("3432671"), 2), part(2, Err("boom"), 3)],
+            },
+            DayResult {
+                day: 12,
+                title: "N-Body",
+                input: PathBuf::from("day12"),
+                parts: Vec::new(),
+            },
+        ];
+        assert_eq!(
+            summary(&results).lines().collect::<Vec<_>>(),
+            [
+                "  Day  Title           Part1       Time  Part2       Time   Total",
+                "    1  Fuel            3432671   2.00ms  error     3.00ms  5.00ms",
+                "   12  N-Body          no input          no input",
+                "Total  1 parts solved            2.00ms            3.00ms  5.00ms",
+            ]
+        );
+    }
+
+    #[test]
+    fn markdown_escapes_cells() {
+        let results = [DayResult {
+            day: 2,
+            title: "1202 Program Alarm",
+            input: PathBuf::from("day2"),
+            parts: vec![part(1, Ok("a|b"), 2), part(2, Err("line one\nline two"), 3)],
+        }];
+        assert_eq!(
+            markdown(&results).lines().collect::<Vec<_>>(),
+            [
+                "| Day | Title | Part 1 | Time | Part 2 | Time |",
+                "|----:|:------|:-------|-----:|:-------|-----:|",
+                "| 2 | 1202 Program Alarm | `a\\|b` | 2.00ms | **error:** line one<br>line two | 3.00ms |",
+                "| **Total** | | | 2.00ms | | 3.00ms |",
+            ]
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{DayResult, solution};
+
+/// Known right answers, keyed by day and part
+#[derive(Debug, Default, PartialEq)]
+pub struct Golden {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+impl Golden {
+    /// Reads answers from `day part answer` lines
+    ///
+    /// Blank lines and lines starting with `#` are skipped.
+    ///
+    /// # Returns
+    ///
+    /// * the answers, or the number of the first line that can't be read
+    ///
+    pub fn parse(text: &str) -> Result<Golden, usize> {
+        let mut answers = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(3, char::is_whitespace);
+            let mut number = || fields.next()?.parse::<u8>().ok();
+            let key = number().zip(number()).ok_or(i + 1)?;
+            let answer = fields.next().map(str::trim).ok_or(i + 1)?;
+            answers.insert(key, String::from(answer));
+        }
+        Ok(Golden { answers })
+    }
+
+    /// Reads the answers file
+    pub fn load(path: &Path) -> io::Result<Golden> {
+        let text = fs::read_to_string(path)?;
+        Golden::parse(&text).map_err(|line| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: expected `day part answer`", line),
+            )
+        })
+    }
+
+    /// Right answer for a part, if known
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+}
+
+/// How a part compared with its right answer
+#[derive(Debug, PartialEq)]
+pub enum Check {
+    /// the answer matched
+    Pass,
+    /// the answer differed, or the solver failed
+    Fail {
+        expected: String,
+        got: Result<String, String>,
+    },
+    /// there was nothing to compare, and why
+    Missing(&'static str),
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Check::Pass => write!(f, "pass"),
+            Check::Fail {
+                expected,
+                got: Ok(got),
+            } => write!(f, "FAIL expected {} got {}", expected, got),
+            Check::Fail {
+                expected,
+                got: Err(err),
+            } => write!(f, "FAIL expected {} got error: {}", expected, err),
+            Check::Missing(why) => write!(f, "missing, {}", why),
+        }
+    }
+}
+
+/// Compares every part of some runs with the right answers
+///
+/// # Arguments
+///
+/// * `results` - days as run
+/// * `golden` - right answers
+///
+/// # Returns
+///
+/// * day, part and how it compared, for every part of every day
+///
+pub fn verify(results: &[DayResult], golden: &Golden) -> Vec<(u8, u8, Check)> {
+    let mut checks = Vec::new();
+    for result in results {
+        let parts = solution(result.day).map_or(2, |s| s.parts());
+        for part in 1..=parts {
+            let ran = result.part(part).map(|p| &p.answer);
+            let check = match (golden.get(result.day, part), ran) {
+                (None, _) => Check::Missing("no known answer"),
+                (Some(_), None) => Check::Missing("no input"),
+                (Some(expected), Some(Ok(got))) if expected == got => Check::Pass,
+                (Some(expected), Some(got)) => Check::Fail {
+                    expected: String::from(expected),
+                    got: got.clone(),
+                },
+            };
+            checks.push((result.day, part, check));
+        }
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::PartResult;
+
+    fn day(day: u8, answers: &[Result<&str, &str>]) -> DayResult {
+        DayResult {
+            day,
+            title: "",
+            input: PathBuf::new(),
+            parts: answers
+                .iter()
+                .enumerate()
+                .map(|(i, a)| PartResult {
+                    day,
+                    part: i as u8 + 1,
+                    answer: a.map(String::from).map_err(String::from),
+                    elapsed: Duration::ZERO,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn parse_reads_answers() {
+        let golden = Golden::parse("# day part answer\n\n1 1 3252208\n2 2  6979 \n").unwrap();
+        assert_eq!(golden.get(1, 1), Some("3252208"));
+        assert_eq!(golden.get(2, 2), Some("6979"));
+        assert_eq!(golden.get(1, 2), None);
+        assert_eq!(Golden::parse("1 1 5\n1 x 6\n"), Err(2));
+        assert_eq!(Golden::parse("3 1\n"), Err(1));
+    }
+
+    #[test]
+    fn verify_sorts_parts() {
+        let golden = Golden::parse("1 1 10\n1 2 20\n2 1 30\n5 1 50\n25 1 PASSWORD\n").unwrap();
+        let results = [
+            day(1, &[Ok("10"), Ok("21")]),
+            day(2, &[Err("boom"), Ok("40")]),
+            day(5, &[]),
+            day(25, &[Ok("PASSWORD")]),
+        ];
+        let checks: Vec<String> = verify(&results, &golden)
+            .into_iter()
+            .map(|(d, p, c)| format!("{} {} {}", d, p, c))
+            .collect();
+        assert_eq!(
+            checks,
+            [
+                "1 1 pass",
+                "1 2 FAIL expected 20 got 21",
+                "2 1 FAIL expected 30 got error: boom",
+                "2 2 missing, no known answer",
+                "5 1 missing, no input",
+                "5 2 missing, no known answer",
+                "25 1 pass",
+            ]
+        );
+    }
+}
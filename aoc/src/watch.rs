@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde_json::Value;
+
+use crate::{input_path, workspace_root};
+
+/// Answer or error for each part, keyed by part
+pub type Answers = BTreeMap<u8, Result<String, String>>;
+
+/// Files and directories whose changes affect a day's answers
+///
+/// # Arguments
+///
+/// * `input_dir` - directory inputs are downloaded into
+/// * `day` - day of the calendar
+///
+pub fn watched_paths(input_dir: &Path, day: u8) -> Vec<PathBuf> {
+    let root = workspace_root();
+    let crate_dir = root.join(format!("day{}", day));
+    vec![
+        crate_dir.join("Cargo.toml"),
+        crate_dir.join("src"),
+        root.join("shared").join("src"),
+        input_path(input_dir, day),
+    ]
+}
+
+/// Modification time of every file under some paths
+///
+/// Directories are walked recursively and missing paths are skipped, so
+/// creating one shows up as a change.
+pub fn snapshot(paths: &[PathBuf]) -> BTreeMap<PathBuf, SystemTime> {
+    fn visit(path: &Path, times: &mut BTreeMap<PathBuf, SystemTime>) {
+        let Ok(meta) = fs::metadata(path) else {
+            return;
+        };
+        if meta.is_dir() {
+            for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+                visit(&entry.path(), times);
+            }
+        } else if let Ok(modified) = meta.modified() {
+            times.insert(path.to_path_buf(), modified);
+        }
+    }
+
+    let mut times = BTreeMap::new();
+    for path in paths {
+        visit(path, &mut times);
+    }
+    times
+}
+
+/// Reads the answers out of `aoc run --format json` output for one day
+///
+/// # Returns
+///
+/// * answer or error for each part, or why the output can't be read
+///
+pub fn parse_answers(json: &str) -> Result<Answers, String> {
+    let report: Value = serde_json::from_str(json).map_err(|err| err.to_string())?;
+    let day = &report["days"][0];
+    if let Some(err) = day["error"].as_str() {
+        return Err(String::from(err));
+    }
+    let parts = day["parts"]
+        .as_array()
+        .ok_or("output has no parts for the day")?;
+    Ok(parts
+        .iter()
+        .filter_map(|p| {
+            let part = p["part"].as_u64()? as u8;
+            let answer = match (p["answer"].as_str(), p["error"].as_str()) {
+                (Some(answer), _) => Ok(String::from(answer)),
+                (None, err) => Err(String::from(err.unwrap_or("no answer"))),
+            };
+            Some((part, answer))
+        })
+        .collect())
+}
+
+/// Shows an answer or error
+fn show(answer: &Result<String, String>) -> String {
+    match answer {
+        Ok(answer) => answer.clone(),
+        Err(err) => format!("error: {}", err),
+    }
+}
+
+/// Lines comparing each part's answer with the one before
+///
+/// # Arguments
+///
+/// * `old` - answers from the previous run, if there was one
+/// * `new` - answers from this run
+///
+pub fn diff(old: Option<&Answers>, new: &Answers) -> Vec<String> {
+    new.iter()
+        .map(|(part, answer)| match old.and_then(|o| o.get(part)) {
+            None => format!("Part{}: {}", part, show(answer)),
+            Some(before) if before == answer => {
+                format!("Part{}: {} (unchanged)", part, show(answer))
+            }
+            Some(before) => format!("Part{}: {} -> {}", part, show(before), show(answer)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_sees_changes() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "").unwrap();
+        let paths = [dir.join("src"), dir.join("input.txt")];
+        let before = snapshot(&paths);
+        assert_eq!(before.len(), 1);
+
+        fs::write(dir.join("input.txt"), "1").unwrap();
+        let after = snapshot(&paths);
+        assert_eq!(after.len(), 2);
+        assert_ne!(before, after);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn parse_answers_reads_run_output() {
+        let json = r#"{"days": [{"day": 2, "parts": [
+            {"part": 1, "answer": "3931283", "seconds": 0.1},
+            {"part": 2, "error": "boom", "seconds": 0.1}
+        ]}]}"#;
+        let answers = parse_answers(json).unwrap();
+        assert_eq!(answers[&1], Ok(String::from("3931283")));
+        assert_eq!(answers[&2], Err(String::from("boom")));
+        assert_eq!(
+            parse_answers(r#"{"days": [{"day": 9, "error": "day9/src/input.txt: no such file"}]}"#),
+            Err(String::from("day9/src/input.txt: no such file"))
+        );
+        assert!(parse_answers("error: could not compile").is_err());
+    }
+
+    #[test]
+    fn diff_marks_changes() {
+        let old = Answers::from([(1, Ok(String::from("10"))), (2, Ok(String::from("20")))]);
+        let new = Answers::from([(1, Ok(String::from("10"))), (2, Err(String::from("boom")))]);
+        assert_eq!(
+            diff(Some(&old), &new),
+            ["Part1: 10 (unchanged)", "Part2: 20 -> error: boom"]
+        );
+        assert_eq!(diff(None, &old), ["Part1: 10", "Part2: 20"]);
+    }
+}
@@ -1,4 +1,140 @@
-use shared;
+use std::convert::Infallible;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::iter::Sum;
+use std::num::ParseIntError;
+use std::ops::{Add, AddAssign, Div, Sub};
+use std::str::FromStr;
+
+use rayon::prelude::*;
+use shared::solution::{Answer, Solution, answer};
+
+/// Reasons the checked fuel calculations can fail
+#[derive(Debug, PartialEq)]
+pub enum FuelError<T = u64> {
+    /// the running total no longer fits in its width while adding the fuel
+    /// for the module of this mass
+    Overflow { mass: Mass<T> },
+}
+
+impl<T: fmt::Display> fmt::Display for FuelError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FuelError::Overflow { mass } => {
+                write!(f, "fuel total overflowed at module of mass {}", mass)
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> std::error::Error for FuelError<T> {}
+
+/// Line of input that isn't a valid mass
+#[derive(Debug, PartialEq, Clone)]
+pub struct LineError {
+    /// one based line number
+    pub line: usize,
+    pub content: String,
+    pub reason: String,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {} ({:?}): {}",
+            self.line, self.content, self.reason
+        )
+    }
+}
+
+/// Reasons a scan of the input can fail
+#[derive(Debug)]
+pub enum InputError {
+    Io(io::Error),
+    /// a malformed line in strict mode
+    Line(LineError),
+    /// the fuel total no longer fits while adding the module on this line
+    Overflow {
+        line: usize,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Io(err) => write!(f, "{}", err),
+            InputError::Line(err) => write!(f, "{}", err),
+            InputError::Overflow { line } => write!(f, "line {}: fuel total overflowed", line),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl From<io::Error> for InputError {
+    fn from(err: io::Error) -> Self {
+        InputError::Io(err)
+    }
+}
+
+/// What to do on reaching a malformed line
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub enum LineMode {
+    /// stop with the line as the error
+    #[default]
+    Strict,
+    /// leave the line out of the total and note it in the summary
+    Skip,
+}
+
+/// Outcome of scanning the whole input
+#[derive(Debug, PartialEq)]
+pub struct FuelSummary<T = u64> {
+    /// fuel over every valid line
+    pub total: Fuel<T>,
+    /// count of valid lines
+    pub modules: usize,
+    /// malformed lines left out of the total, in input order
+    pub skipped: Vec<LineError>,
+}
+
+impl<T: FuelInt> FuelSummary<T> {
+    /// Counts the fuel for the module on a line into the total
+    ///
+    /// # Arguments
+    ///
+    /// * `fuel` fuel for the module
+    /// * `line` one based line number of the module
+    ///
+    /// # Returns
+    ///
+    /// * error naming the line if the total no longer fits
+    ///
+    fn add(&mut self, fuel: Fuel<T>, line: usize) -> Result<(), InputError> {
+        self.total = self
+            .total
+            .checked_add(fuel)
+            .ok_or(InputError::Overflow { line })?;
+        self.modules += 1;
+        Ok(())
+    }
+}
+
+/// Converts string into an integer without panicking
+///
+/// # Arguments
+///
+/// * `s` numerical string
+///
+/// # Returns
+///
+/// * converted integer, or why it isn't one
+///
+pub fn try_parse_line<T: FuelInt>(s: &str) -> Result<T, String> {
+    s.parse::<T>().map_err(|err| err.to_string())
+}
 
 /// Converts string into an integer
 ///
@@ -10,10 +146,181 @@ use shared;
 ///
 /// * converted integer
 ///
-pub fn parse_line(s: &String) -> u64 {
+pub fn parse_line(s: &str) -> u64 {
     s.parse::<u64>().unwrap()
 }
 
+/// Unsigned integer width masses and fuel can be counted in, such as u128
+/// for masses beyond the range of u64
+pub trait FuelInt:
+    Copy
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Div<Output = Self>
+    + From<u8>
+    + Sum
+    + FromStr<Err = ParseIntError>
+{
+    /// Adds two values, none if the sum doesn't fit in this width
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_fuel_int {
+    ($($t:ty),*) => {$(
+        impl FuelInt for $t {
+            fn checked_add(self, other: $t) -> Option<$t> {
+                <$t>::checked_add(self, other)
+            }
+        }
+    )*};
+}
+
+impl_fuel_int!(u8, u16, u32, u64, u128, usize);
+
+/// Mass of a module, or of fuel that has to be carried
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mass<T = u64>(pub T);
+
+/// Amount of fuel
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fuel<T = u64>(pub T);
+
+impl<T: FuelInt> Mass<T> {
+    /// Calculates the fuel needed to launch this mass
+    ///
+    /// # Arguments
+    ///
+    /// * `formula` rule for the fuel a mass needs
+    ///
+    pub fn fuel(self, formula: &impl FuelFormula<T>) -> Fuel<T> {
+        formula.fuel(self)
+    }
+}
+
+impl<T: FuelInt> Fuel<T> {
+    /// Adds two amounts of fuel, none if the sum doesn't fit in the width
+    pub fn checked_add(self, other: Fuel<T>) -> Option<Fuel<T>> {
+        self.0.checked_add(other.0).map(Fuel)
+    }
+}
+
+/// Fuel weighs one unit of mass per unit
+impl<T> From<Fuel<T>> for Mass<T> {
+    fn from(fuel: Fuel<T>) -> Self {
+        Mass(fuel.0)
+    }
+}
+
+impl<T: FuelInt> Add for Fuel<T> {
+    type Output = Fuel<T>;
+
+    fn add(self, other: Fuel<T>) -> Fuel<T> {
+        Fuel(self.0 + other.0)
+    }
+}
+
+impl<T: FuelInt> AddAssign for Fuel<T> {
+    fn add_assign(&mut self, other: Fuel<T>) {
+        self.0 = self.0 + other.0;
+    }
+}
+
+impl<T: FuelInt> Sum for Fuel<T> {
+    fn sum<I: Iterator<Item = Fuel<T>>>(iter: I) -> Fuel<T> {
+        Fuel(iter.map(|fuel| fuel.0).sum())
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Mass<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Fuel<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Rule for how much fuel a given mass needs, in any integer width
+pub trait FuelFormula<T: FuelInt = u64> {
+    /// Calculates the fuel needed to launch a mass
+    ///
+    /// # Arguments
+    ///
+    /// * `mass` mass to launch
+    ///
+    /// # Returns
+    ///
+    /// * Fuel required
+    ///
+    fn fuel(&self, mass: Mass<T>) -> Fuel<T>;
+}
+
+/// Formula from the puzzle, a third of the mass rounded down less two
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AocFormula;
+
+impl<T: FuelInt> FuelFormula<T> for AocFormula {
+    fn fuel(&self, mass: Mass<T>) -> Fuel<T> {
+        LinearFormula {
+            divisor: T::from(3),
+            offset: T::from(2),
+        }
+        .fuel(mass)
+    }
+}
+
+/// Mass divided by `divisor` rounded down less `offset`, never below zero
+#[derive(Debug, Clone, Copy)]
+pub struct LinearFormula<T = u64> {
+    pub divisor: T,
+    pub offset: T,
+}
+
+impl<T: FuelInt> FuelFormula<T> for LinearFormula<T> {
+    fn fuel(&self, mass: Mass<T>) -> Fuel<T> {
+        let share = mass.0 / self.divisor;
+        match share > self.offset {
+            true => Fuel(share - self.offset),
+            false => Fuel(T::from(0)),
+        }
+    }
+}
+
+/// Looks the fuel up from the heaviest band a mass reaches
+#[derive(Debug, Clone)]
+pub struct TableFormula<T = u64> {
+    /// (minimum mass, fuel) pairs sorted by minimum mass
+    bands: Vec<(Mass<T>, Fuel<T>)>,
+}
+
+impl<T: FuelInt> TableFormula<T> {
+    /// Constructor for a TableFormula
+    ///
+    /// # Arguments
+    ///
+    /// * `bands` (minimum mass, fuel) pairs in any order, masses below the
+    ///   lightest band need no fuel
+    ///
+    pub fn new(mut bands: Vec<(Mass<T>, Fuel<T>)>) -> Self {
+        bands.sort();
+        TableFormula { bands }
+    }
+}
+
+impl<T: FuelInt> FuelFormula<T> for TableFormula<T> {
+    fn fuel(&self, mass: Mass<T>) -> Fuel<T> {
+        let reached = self.bands.partition_point(|&(min, _)| min <= mass);
+        match reached {
+            0 => Fuel(T::from(0)),
+            _ => self.bands[reached - 1].1,
+        }
+    }
+}
+
 /// Calculates the module fuel required
 ///
 /// # Arguments
@@ -24,12 +331,23 @@ pub fn parse_line(s: &String) -> u64 {
 ///
 /// * Fuel required
 ///
-pub fn fuel_required(mass: u64) -> u64 {
-    let first = mass / 3;
-    if first <= 2 {
-        return 0;
-    }
-    first - 2
+pub fn fuel_required<T: FuelInt>(mass: Mass<T>) -> Fuel<T> {
+    fuel_required_with(&AocFormula, mass)
+}
+
+/// Calculates the module fuel required under any formula
+///
+/// # Arguments
+///
+/// * `formula` rule for the fuel a mass needs
+/// * `mass` modules mass
+///
+/// # Returns
+///
+/// * Fuel required
+///
+pub fn fuel_required_with<T: FuelInt>(formula: &impl FuelFormula<T>, mass: Mass<T>) -> Fuel<T> {
+    mass.fuel(formula)
 }
 
 /// Implements all operations necessary for part1
@@ -44,13 +362,139 @@ pub fn fuel_required(mass: u64) -> u64 {
 ///
 pub fn part1(filename: &str) -> u64 {
     let lines: Vec<String> = shared::ingest_file(filename);
-    lines.iter().map(parse_line).map(fuel_required).sum()
+    let total: Fuel = lines
+        .iter()
+        .map(|l| fuel_required(Mass(parse_line(l))))
+        .sum();
+    total.0
+}
+
+/// Fuel for a module worked out round by round, each round adding the
+/// fuel needed to carry the previous round's fuel
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Refinement<T = u64> {
+    /// fuel summed over every round applied
+    pub fuel: Fuel<T>,
+    /// rounds applied, the first being the fuel for the module itself
+    pub rounds: usize,
+    /// whether the fuel settled before running out of rounds
+    pub converged: bool,
+}
+
+/// Finds the total fuel for a module iteratively, with a cap on rounds
+///
+/// The module mass always gets its fuel. Fuel for fuel stops being added
+/// once the formula asks for none, or for at least as much as the fuel it
+/// has to carry, since the total would never settle otherwise.
+///
+/// # Arguments
+///
+/// * formula - rule for the fuel a mass needs
+/// * mass - modules mass
+/// * max_rounds - most rounds to apply
+///
+/// # Returns
+///
+/// * Total fuel along with how many rounds it took
+///
+pub fn refine_fuel<T: FuelInt>(
+    formula: &impl FuelFormula<T>,
+    mass: Mass<T>,
+    max_rounds: usize,
+) -> Refinement<T> {
+    let Ok(refinement) = refine_fuel_by(formula, mass, max_rounds, |total, fuel| {
+        Ok::<_, Infallible>(total + fuel)
+    });
+    refinement
+}
+
+/// Finds the total fuel for a module iteratively like `refine_fuel`,
+/// failing instead of overflowing
+///
+/// # Arguments
+///
+/// * formula - rule for the fuel a mass needs
+/// * mass - modules mass
+/// * max_rounds - most rounds to apply
+///
+/// # Returns
+///
+/// * Total fuel along with how many rounds it took, or an error if it
+///   doesn't fit in the width
+///
+pub fn checked_refine_fuel<T: FuelInt>(
+    formula: &impl FuelFormula<T>,
+    mass: Mass<T>,
+    max_rounds: usize,
+) -> Result<Refinement<T>, FuelError<T>> {
+    refine_fuel_by(formula, mass, max_rounds, |total, fuel| {
+        total.checked_add(fuel).ok_or(FuelError::Overflow { mass })
+    })
+}
+
+/// Applies rounds of fuel for fuel, adding each round to the running
+/// total with `add`, for `refine_fuel` and `checked_refine_fuel`
+///
+/// # Arguments
+///
+/// * formula - rule for the fuel a mass needs
+/// * mass - modules mass
+/// * max_rounds - most rounds to apply
+/// * add - sums the total so far and a round's fuel
+///
+/// # Returns
+///
+/// * Total fuel along with how many rounds it took, or the first error
+///   from `add`
+///
+fn refine_fuel_by<T: FuelInt, E>(
+    formula: &impl FuelFormula<T>,
+    mass: Mass<T>,
+    max_rounds: usize,
+    add: impl Fn(Fuel<T>, Fuel<T>) -> Result<Fuel<T>, E>,
+) -> Result<Refinement<T>, E> {
+    let zero = Fuel(T::from(0));
+    let mut refinement = Refinement {
+        fuel: zero,
+        rounds: 0,
+        converged: false,
+    };
+    let mut carried = mass;
+    loop {
+        let fuel = carried.fuel(formula);
+        if fuel == zero || (refinement.rounds > 0 && Mass::from(fuel) >= carried) {
+            refinement.converged = true;
+            return Ok(refinement);
+        }
+        if refinement.rounds == max_rounds {
+            return Ok(refinement);
+        }
+        refinement.fuel = add(refinement.fuel, fuel)?;
+        refinement.rounds += 1;
+        carried = Mass::from(fuel);
+    }
+}
+
+/// Finds total fuel mass considering the mass of fuel
+///
+/// # Arguments
+///
+/// * total - accumulator of total fuel mass
+/// * mass - either previously found fuel mass or initial module mass
+///
+/// # Returns
+///
+/// * Total fuel mass
+///
+pub fn recursive_fuel_required<T: FuelInt>(total: Fuel<T>, mass: Mass<T>) -> Fuel<T> {
+    recursive_fuel_required_with(&AocFormula, total, mass)
 }
 
-/// Recursively finds total fuel mass considering the mass of fuel
-/// 
+/// Finds total fuel mass under any formula, see `refine_fuel`
+///
 /// # Arguments
 ///
+/// * formula - rule for the fuel a mass needs
 /// * total - accumulator of total fuel mass
 /// * mass - either previously found fuel mass or initial module mass
 ///
@@ -58,12 +502,376 @@ pub fn part1(filename: &str) -> u64 {
 ///
 /// * Total fuel mass
 ///
-pub fn recursive_fuel_required(total: u64, mass: u64) -> u64 {
-    let fuel_mass = fuel_required(mass);
-    if fuel_mass == 0 {
-        return total;
+pub fn recursive_fuel_required_with<T: FuelInt>(
+    formula: &impl FuelFormula<T>,
+    total: Fuel<T>,
+    mass: Mass<T>,
+) -> Fuel<T> {
+    total + refine_fuel(formula, mass, usize::MAX).fuel
+}
+
+/// Finds total fuel mass, failing instead of overflowing
+///
+/// # Arguments
+///
+/// * total - accumulator of total fuel mass
+/// * mass - either previously found fuel mass or initial module mass
+///
+/// # Returns
+///
+/// * Total fuel mass, or an error if it doesn't fit in the width
+///
+pub fn checked_recursive_fuel_required<T: FuelInt>(
+    total: Fuel<T>,
+    mass: Mass<T>,
+) -> Result<Fuel<T>, FuelError<T>> {
+    checked_recursive_fuel_required_with(&AocFormula, total, mass)
+}
+
+/// Finds total fuel mass under any formula, failing instead of overflowing
+///
+/// # Arguments
+///
+/// * formula - rule for the fuel a mass needs
+/// * total - accumulator of total fuel mass
+/// * mass - either previously found fuel mass or initial module mass
+///
+/// # Returns
+///
+/// * Total fuel mass, or an error if it doesn't fit in the width
+///
+pub fn checked_recursive_fuel_required_with<T: FuelInt>(
+    formula: &impl FuelFormula<T>,
+    total: Fuel<T>,
+    mass: Mass<T>,
+) -> Result<Fuel<T>, FuelError<T>> {
+    let refined = checked_refine_fuel(formula, mass, usize::MAX)?;
+    total
+        .checked_add(refined.fuel)
+        .ok_or(FuelError::Overflow { mass })
+}
+
+/// Implements all operations necessary for part1, failing instead of
+/// overflowing or panicking
+///
+/// # Arguments
+///
+/// * filename - path of file containing input
+///
+/// # Returns
+///
+/// * Answer, or an error if the file can't be read, a line isn't a mass
+///   or the answer doesn't fit in a u64
+///
+pub fn checked_part1(filename: &str) -> Result<Fuel, InputError> {
+    scan_part1(filename, LineMode::Strict).map(|summary| summary.total)
+}
+
+/// Implements all operations necessary for part2, failing instead of
+/// overflowing or panicking
+///
+/// # Arguments
+///
+/// * filename - path of file containing input
+///
+/// # Returns
+///
+/// * Answer, or an error if the file can't be read, a line isn't a mass
+///   or the answer doesn't fit in a u64
+///
+pub fn checked_part2(filename: &str) -> Result<Fuel, InputError> {
+    scan_part2(filename, LineMode::Strict).map(|summary| summary.total)
+}
+
+/// Adds up the fuel for each module read from a stream, dealing with
+/// malformed lines according to `mode`
+///
+/// Only a single line is held in memory at once, so inputs far larger than
+/// memory can be processed. Blank lines are skipped like `ingest_file` does.
+///
+/// # Arguments
+///
+/// * reader - source of module masses, one per line
+/// * fuel - fuel needed by a single module, or an error if it doesn't
+///   fit, its width setting the width masses are read in
+/// * mode - whether malformed lines are fatal or skipped
+///
+/// # Returns
+///
+/// * Total fuel with the lines that were skipped, or the first error,
+///   naming the line whose module overflowed the total if it doesn't fit
+///
+pub fn scan_fuel<R: BufRead, T: FuelInt>(
+    mut reader: R,
+    fuel: impl Fn(Mass<T>) -> Result<Fuel<T>, FuelError<T>>,
+    mode: LineMode,
+) -> Result<FuelSummary<T>, InputError> {
+    let mut summary = FuelSummary {
+        total: Fuel(T::from(0)),
+        modules: 0,
+        skipped: Vec::new(),
+    };
+    let mut line = String::new();
+    let mut number = 0;
+    while reader.read_line(&mut line)? > 0 {
+        number += 1;
+        let trimmed = line.trim();
+        if !trimmed.is_empty() {
+            match try_parse_line(trimmed) {
+                Ok(mass) => {
+                    let fuel =
+                        fuel(Mass(mass)).map_err(|_| InputError::Overflow { line: number })?;
+                    summary.add(fuel, number)?;
+                }
+                Err(reason) => {
+                    let err = LineError {
+                        line: number,
+                        content: line.trim_end_matches(['\r', '\n']).to_string(),
+                        reason,
+                    };
+                    match mode {
+                        LineMode::Strict => return Err(InputError::Line(err)),
+                        LineMode::Skip => summary.skipped.push(err),
+                    }
+                }
+            }
+        }
+        line.clear();
+    }
+    Ok(summary)
+}
+
+/// Implements all operations necessary for part1, dealing with malformed
+/// lines according to `mode`
+///
+/// # Arguments
+///
+/// * filename - path of file containing input
+/// * mode - whether malformed lines are fatal or skipped
+///
+/// # Returns
+///
+/// * Answer with the lines that were skipped, or the first error
+///
+pub fn scan_part1(filename: &str, mode: LineMode) -> Result<FuelSummary, InputError> {
+    scan_fuel(
+        BufReader::new(File::open(filename)?),
+        |mass| Ok(fuel_required(mass)),
+        mode,
+    )
+}
+
+/// Implements all operations necessary for part2, dealing with malformed
+/// lines according to `mode`
+///
+/// # Arguments
+///
+/// * filename - path of file containing input
+/// * mode - whether malformed lines are fatal or skipped
+///
+/// # Returns
+///
+/// * Answer with the lines that were skipped, or the first error
+///
+pub fn scan_part2(filename: &str, mode: LineMode) -> Result<FuelSummary, InputError> {
+    scan_fuel(
+        BufReader::new(File::open(filename)?),
+        |mass| checked_recursive_fuel_required(Fuel(0), mass),
+        mode,
+    )
+}
+
+/// Lines read at a time by the parallel streaming functions
+const BATCH_LINES: usize = 1 << 16;
+
+/// Adds up the fuel for each module read from a stream, spreading each
+/// batch of lines across threads
+///
+/// Batch results are taken in input order, so the answer, and the line
+/// reported or skipped, is the same as `scan_fuel` no matter how the work
+/// is scheduled.
+///
+/// # Arguments
+///
+/// * reader - source of module masses, one per line
+/// * fuel - fuel needed by a single module, or an error if it doesn't fit
+/// * batch - lines held in memory at once
+/// * mode - whether malformed lines are fatal or skipped
+///
+/// # Returns
+///
+/// * Total fuel with the lines that were skipped, or the first error,
+///   naming the line whose module overflowed the total if it doesn't fit
+///
+pub fn par_stream_fuel<R: BufRead>(
+    reader: R,
+    fuel: impl Fn(Mass) -> Result<Fuel, FuelError> + Sync,
+    batch: usize,
+    mode: LineMode,
+) -> Result<FuelSummary, InputError> {
+    let mut lines = reader.lines();
+    let mut summary = FuelSummary {
+        total: Fuel(0),
+        modules: 0,
+        skipped: Vec::new(),
+    };
+    let mut read = 0;
+    loop {
+        let chunk = lines
+            .by_ref()
+            .take(batch.max(1))
+            .collect::<io::Result<Vec<String>>>()?;
+        if chunk.is_empty() {
+            return Ok(summary);
+        }
+        let results: Vec<(usize, Result<Fuel, InputError>)> = chunk
+            .par_iter()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, l)| {
+                let line = read + i + 1;
+                let result = match try_parse_line(l.trim()) {
+                    Ok(mass) => fuel(Mass(mass)).map_err(|_| InputError::Overflow { line }),
+                    Err(reason) => Err(InputError::Line(LineError {
+                        line,
+                        content: l.clone(),
+                        reason,
+                    })),
+                };
+                (line, result)
+            })
+            .collect();
+        read += chunk.len();
+        for (line, result) in results {
+            match (result, mode) {
+                (Ok(fuel), _) => summary.add(fuel, line)?,
+                (Err(InputError::Line(err)), LineMode::Skip) => summary.skipped.push(err),
+                (Err(err), _) => return Err(err),
+            }
+        }
+    }
+}
+
+/// Implements all operations necessary for part1 across threads
+///
+/// # Arguments
+///
+/// * filename - path of file containing input
+/// * mode - whether malformed lines are fatal or skipped
+///
+/// # Returns
+///
+/// * Answer with the lines that were skipped, or the first error
+///
+pub fn par_part1(filename: &str, mode: LineMode) -> Result<FuelSummary, InputError> {
+    let reader = BufReader::new(File::open(filename)?);
+    par_stream_fuel(reader, |mass| Ok(fuel_required(mass)), BATCH_LINES, mode)
+}
+
+/// Implements all operations necessary for part2 across threads
+///
+/// # Arguments
+///
+/// * filename - path of file containing input
+/// * mode - whether malformed lines are fatal or skipped
+///
+/// # Returns
+///
+/// * Answer with the lines that were skipped, or the first error
+///
+pub fn par_part2(filename: &str, mode: LineMode) -> Result<FuelSummary, InputError> {
+    let reader = BufReader::new(File::open(filename)?);
+    let fuel = |mass| checked_recursive_fuel_required(Fuel(0), mass);
+    par_stream_fuel(reader, fuel, BATCH_LINES, mode)
+}
+
+/// Fuel needed by a single module
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ModuleFuel {
+    pub mass: Mass,
+    /// fuel for the module alone, as in part1
+    pub direct: Fuel,
+    /// fuel for the module and the fuel itself, as in part2
+    pub total: Fuel,
+}
+
+impl ModuleFuel {
+    /// Works out the fuel for a module with the puzzle formula
+    ///
+    /// # Arguments
+    ///
+    /// * `mass` modules mass
+    ///
+    pub fn new(mass: Mass) -> Self {
+        ModuleFuel {
+            mass,
+            direct: mass.fuel(&AocFormula),
+            total: recursive_fuel_required(Fuel(0), mass),
+        }
+    }
+}
+
+/// Breaks the fuel down per module
+///
+/// # Arguments
+///
+/// * filename - path of file containing input
+///
+/// # Returns
+///
+/// * one entry per module, in input order, or the first line that isn't
+///   a mass
+///
+pub fn report(filename: &str) -> Result<Vec<ModuleFuel>, InputError> {
+    let mut modules = Vec::new();
+    for (i, line) in BufReader::new(File::open(filename)?).lines().enumerate() {
+        let line = line?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let mass = try_parse_line(trimmed).map_err(|reason| {
+            InputError::Line(LineError {
+                line: i + 1,
+                content: line.clone(),
+                reason,
+            })
+        })?;
+        modules.push(ModuleFuel::new(Mass(mass)));
+    }
+    Ok(modules)
+}
+
+/// Lays a fuel breakdown out as a right aligned table with a totals row
+///
+/// # Arguments
+///
+/// * modules - fuel breakdown to print
+///
+/// # Returns
+///
+/// * table, one line per module
+///
+pub fn report_table(modules: &[ModuleFuel]) -> String {
+    let direct: Fuel = modules.iter().map(|m| m.direct).sum();
+    let total: Fuel = modules.iter().map(|m| m.total).sum();
+    let width = [
+        "direct".len(),
+        direct.to_string().len(),
+        total.to_string().len(),
+    ]
+    .into_iter()
+    .chain(modules.iter().map(|m| m.mass.to_string().len()))
+    .max()
+    .unwrap_or(0);
+    let row = |a: &dyn fmt::Display, b: &dyn fmt::Display, c: &dyn fmt::Display| {
+        format!("{:>w$} {:>w$} {:>w$}\n", a, b, c, w = width)
+    };
+    let mut table = row(&"mass", &"direct", &"total");
+    for m in modules {
+        table += &row(&m.mass, &m.direct, &m.total);
     }
-    recursive_fuel_required(total + fuel_mass, fuel_mass)
+    table + &row(&"sum", &direct, &total)
 }
 
 /// Implements all operations necessary for part2
@@ -78,7 +886,32 @@ pub fn recursive_fuel_required(total: u64, mass: u64) -> u64 {
 ///
 pub fn part2(filename: &str) -> u64 {
     let lines: Vec<String> = shared::ingest_file(filename);
-    lines.iter().map(parse_line).map(|mass| recursive_fuel_required(0, mass)).sum()
+    let total: Fuel = lines
+        .iter()
+        .map(|l| recursive_fuel_required(Fuel(0), Mass(parse_line(l))))
+        .sum();
+    total.0
+}
+
+/// Day 1 as driven by the workspace runner
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "The Tyranny of the Rocket Equation"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(scan_part1(filename, LineMode::Strict).map(|s| s.total))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(scan_part2(filename, LineMode::Strict).map(|s| s.total))
+    }
 }
 
 #[cfg(test)]
@@ -119,7 +952,7 @@ mod tests {
         ];
 
         for tc in tests {
-            assert_eq!(fuel_required(tc.input), tc.expected);
+            assert_eq!(fuel_required(Mass(tc.input)), Fuel(tc.expected));
         }
     }
 
@@ -139,9 +972,372 @@ mod tests {
                 expected: 50346,
             },
         ];
-        
+
         for tc in tests {
-            assert_eq!(recursive_fuel_required(0, tc.input), tc.expected);
+            assert_eq!(
+                recursive_fuel_required(Fuel(0), Mass(tc.input)),
+                Fuel(tc.expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_checked_recursive_fuel_required() {
+        let flat: TableFormula = TableFormula::new(vec![(Mass(1), Fuel(7))]);
+
+        assert_eq!(
+            checked_recursive_fuel_required(Fuel(0), Mass(100756u64)),
+            Ok(Fuel(50346))
+        );
+        assert_eq!(
+            checked_recursive_fuel_required(Fuel(0), Mass(u64::MAX)),
+            Ok(recursive_fuel_required(Fuel(0), Mass(u64::MAX)))
+        );
+        assert_eq!(
+            checked_recursive_fuel_required(Fuel(u64::MAX - 5), Mass(1969)),
+            Err(FuelError::Overflow { mass: Mass(1969) })
+        );
+        assert_eq!(
+            checked_recursive_fuel_required_with(&flat, Fuel(0), Mass(3)),
+            Ok(Fuel(7))
+        );
+    }
+
+    #[test]
+    fn test_checked_parts() {
+        assert_eq!(
+            checked_part1("src/input.txt").unwrap(),
+            Fuel(part1("src/input.txt"))
+        );
+        assert_eq!(
+            checked_part2("src/input.txt").unwrap(),
+            Fuel(part2("src/input.txt"))
+        );
+        assert!(matches!(
+            checked_part1("src/test_overflow.txt"),
+            Err(InputError::Overflow { line: 4 })
+        ));
+        assert!(matches!(
+            checked_part2("src/test_overflow.txt"),
+            Err(InputError::Overflow { line: 3 })
+        ));
+        assert!(matches!(
+            checked_part1("src/missing.txt"),
+            Err(InputError::Io(_))
+        ));
+        let bad = std::env::temp_dir().join(format!("day1-checked-{}.txt", std::process::id()));
+        std::fs::write(&bad, "12\n1x4\n").unwrap();
+        let result = checked_part2(&bad.to_string_lossy());
+        std::fs::remove_file(&bad).unwrap();
+        assert!(matches!(result, Err(InputError::Line(err)) if err.line == 2));
+    }
+
+    #[test]
+    fn test_par_stream_fuel() {
+        let fuel = |mass: Mass| Ok(fuel_required(mass));
+        let input = "12\n14\n\n1969\n100756\n".repeat(50);
+        let expected = scan_fuel(io::Cursor::new(&input), fuel, LineMode::Strict).unwrap();
+        let bad = "12\n\n1x4\n1969\n-5\n".repeat(3);
+        let skipped = scan_fuel(io::Cursor::new(&bad), fuel, LineMode::Skip).unwrap();
+        let huge = format!("1\n\n{0}\n{0}\n{0}\nx\n{0}\n", u64::MAX);
+
+        for batch in [0, 1, 3, 1000] {
+            let par =
+                |input: &str, mode| par_stream_fuel(io::Cursor::new(input), fuel, batch, mode);
+            assert_eq!(par(&input, LineMode::Strict).unwrap(), expected);
+            assert_eq!(par(&bad, LineMode::Skip).unwrap(), skipped);
+            match par(&bad, LineMode::Strict) {
+                Err(InputError::Line(err)) => assert_eq!(err.line, 3),
+                other => panic!("expected a line error, got {:?}", other),
+            }
+            assert!(matches!(
+                par(&huge, LineMode::Skip),
+                Err(InputError::Overflow { line: 7 })
+            ));
+        }
+        assert_eq!(skipped.skipped.last().unwrap().line, 15);
+        assert_eq!(
+            par_part1("src/input.txt", LineMode::Strict).unwrap().total,
+            Fuel(part1("src/input.txt"))
+        );
+        assert_eq!(
+            par_part2("src/input.txt", LineMode::Strict).unwrap().total,
+            Fuel(part2("src/input.txt"))
+        );
+        assert!(matches!(
+            par_part1("src/missing.txt", LineMode::Skip),
+            Err(InputError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_scan_fuel() {
+        let input = "12\n\n1x4\n1969\n-5\n";
+        let padded = io::Cursor::new("12\n  14\n\n1969\r\n100756");
+
+        assert_eq!(
+            scan_fuel(padded, |m: Mass| Ok(fuel_required(m)), LineMode::Strict)
+                .unwrap()
+                .total,
+            Fuel(2 + 2 + 654 + 33583)
+        );
+
+        assert_eq!(
+            scan_fuel(
+                io::Cursor::new(input),
+                |m: Mass| Ok(m.fuel(&AocFormula)),
+                LineMode::Skip
+            )
+            .unwrap(),
+            FuelSummary {
+                total: Fuel(2 + 654),
+                modules: 2,
+                skipped: vec![
+                    LineError {
+                        line: 3,
+                        content: String::from("1x4"),
+                        reason: String::from("invalid digit found in string"),
+                    },
+                    LineError {
+                        line: 5,
+                        content: String::from("-5"),
+                        reason: String::from("invalid digit found in string"),
+                    },
+                ],
+            }
+        );
+        match scan_fuel(
+            io::Cursor::new(input),
+            |m: Mass| Ok(m.fuel(&AocFormula)),
+            LineMode::Strict,
+        ) {
+            Err(InputError::Line(err)) => {
+                assert_eq!(
+                    err.to_string(),
+                    "line 3 (\"1x4\"): invalid digit found in string"
+                )
+            }
+            other => panic!("expected a line error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_scan_parts() {
+        let part1_summary = scan_part1("src/input.txt", LineMode::Strict).unwrap();
+
+        assert_eq!(part1_summary.total, Fuel(part1("src/input.txt")));
+        assert!(part1_summary.skipped.is_empty());
+        assert_eq!(
+            scan_part2("src/input.txt", LineMode::Strict).unwrap().total,
+            Fuel(part2("src/input.txt"))
+        );
+        assert!(matches!(
+            scan_part1("src/missing.txt", LineMode::Skip),
+            Err(InputError::Io(_))
+        ));
+        assert_eq!(
+            Day1.part1("src/input.txt").unwrap(),
+            part1("src/input.txt").to_string()
+        );
+        let bad = std::env::temp_dir().join(format!("day1-solution-{}.txt", std::process::id()));
+        std::fs::write(&bad, "12\n1x4\n").unwrap();
+        let result = Day1.part2(&bad.to_string_lossy());
+        std::fs::remove_file(&bad).unwrap();
+        assert!(result.unwrap_err().to_string().starts_with("line 2 "));
+    }
+
+    #[test]
+    fn test_report() {
+        let modules = report("src/input.txt").unwrap();
+        let direct: Fuel = modules.iter().map(|m| m.direct).sum();
+        let total: Fuel = modules.iter().map(|m| m.total).sum();
+
+        assert_eq!(direct, Fuel(part1("src/input.txt")));
+        assert_eq!(total, Fuel(part2("src/input.txt")));
+        assert!(modules.iter().all(|m| m.total >= m.direct));
+
+        let bad = std::env::temp_dir().join(format!("day1-report-{}.txt", std::process::id()));
+        std::fs::write(&bad, "12\n\n1x4\n").unwrap();
+        let result = report(&bad.to_string_lossy());
+        std::fs::remove_file(&bad).unwrap();
+        match result {
+            Err(InputError::Line(err)) => assert_eq!((err.line, err.content), (3, "1x4".into())),
+            other => panic!("expected a line error, got {:?}", other),
+        }
+        assert!(matches!(report("src/missing.txt"), Err(InputError::Io(_))));
+    }
+
+    #[test]
+    fn test_report_table() {
+        let modules = [Mass(14), Mass(1969)].map(ModuleFuel::new).to_vec();
+
+        assert_eq!(
+            report_table(&modules),
+            "  mass direct  total\n\
+             \x20   14      2      2\n\
+             \x20 1969    654    966\n\
+             \x20  sum    656    968\n"
+        );
+    }
+
+    #[test]
+    fn test_wide_integers() {
+        let huge: u128 = 3 * 10u128.pow(30);
+        let wide = |filename: &str, fuel: fn(Mass<u128>) -> Fuel<u128>| {
+            let reader = BufReader::new(File::open(filename).unwrap());
+            scan_fuel(reader, |mass| Ok(fuel(mass)), LineMode::Strict)
+                .unwrap()
+                .total
+        };
+        let direct = |mass: Mass<u128>| mass.fuel(&AocFormula);
+        let total = |mass: Mass<u128>| refine_fuel(&AocFormula, mass, usize::MAX).fuel;
+
+        assert_eq!(Mass(huge).fuel(&AocFormula), Fuel(10u128.pow(30) - 2));
+        assert_eq!(Mass(1969u32).fuel(&AocFormula), Fuel(654));
+        for mass in [14, 1969, 100756, u64::MAX] {
+            assert_eq!(
+                total(Mass(mass as u128)),
+                Fuel(recursive_fuel_required(Fuel(0), Mass(mass)).0 as u128)
+            );
+        }
+        assert!(total(Mass(u128::MAX)).0 < u128::MAX / 2);
+        assert_eq!(
+            wide("src/input.txt", direct),
+            Fuel(part1("src/input.txt") as u128)
+        );
+        assert_eq!(
+            wide("src/input.txt", total),
+            Fuel(part2("src/input.txt") as u128)
+        );
+        // four modules of u64::MAX overflow a u64 total but not a u128 one
+        assert!(matches!(
+            scan_part1("src/test_overflow.txt", LineMode::Strict),
+            Err(InputError::Overflow { line: 4 })
+        ));
+        assert_eq!(
+            wide("src/test_overflow.txt", direct),
+            Fuel(4 * fuel_required(Mass(u64::MAX)).0 as u128)
+        );
+        assert_eq!(
+            checked_recursive_fuel_required(Fuel(0), Mass(huge)),
+            Ok(total(Mass(huge)))
+        );
+        assert_eq!(
+            checked_recursive_fuel_required(Fuel(u128::MAX - 5), Mass(1969u128)),
+            Err(FuelError::Overflow { mass: Mass(1969) })
+        );
+        assert_eq!(Fuel(u128::MAX).checked_add(Fuel(1)), None);
+    }
+
+    #[test]
+    fn test_refine_fuel() {
+        let flat: TableFormula = TableFormula::new(vec![(Mass(1), Fuel(7))]);
+
+        assert_eq!(
+            refine_fuel(&AocFormula, Mass(1969u64), usize::MAX),
+            Refinement {
+                fuel: Fuel(966),
+                rounds: 5,
+                converged: true,
+            }
+        );
+        assert_eq!(
+            refine_fuel(&AocFormula, Mass(1969u64), 2),
+            Refinement {
+                fuel: Fuel(654 + 216),
+                rounds: 2,
+                converged: false,
+            }
+        );
+        assert!(refine_fuel(&AocFormula, Mass(1969u64), 5).converged);
+        assert_eq!(refine_fuel(&AocFormula, Mass(8u64), 0).rounds, 0);
+        assert!(refine_fuel(&AocFormula, Mass(8u64), 0).converged);
+        assert_eq!(refine_fuel(&flat, Mass(3), 10).rounds, 1);
+        assert_eq!(
+            refine_fuel(&AocFormula, Mass(u64::MAX), usize::MAX).rounds,
+            39
+        );
+        assert_eq!(
+            checked_refine_fuel(&AocFormula, Mass(1969u64), 2),
+            Ok(refine_fuel(&AocFormula, Mass(1969), 2))
+        );
+        // each round needs one less than the last, overflowing on the second
+        let greedy = LinearFormula {
+            divisor: 1,
+            offset: 1,
+        };
+        assert_eq!(
+            checked_refine_fuel(&greedy, Mass(u64::MAX), 2),
+            Err(FuelError::Overflow {
+                mass: Mass(u64::MAX)
+            })
+        );
+    }
+
+    #[test]
+    fn test_units() {
+        let mass: Mass = Mass(1969);
+        let fuel = mass.fuel(&AocFormula);
+
+        assert_eq!(fuel, Fuel(654));
+        assert_eq!(Mass::from(fuel).fuel(&AocFormula), Fuel(216));
+        assert_eq!(fuel + Fuel(1), Fuel(655));
+        assert_eq!(
+            [Fuel(1), Fuel(2), Fuel(3)].into_iter().sum::<Fuel>(),
+            Fuel(6)
+        );
+        assert_eq!(Fuel(u64::MAX).checked_add(Fuel(1)), None);
+        assert_eq!(ModuleFuel::new(mass).total, Fuel(966));
+    }
+
+    #[test]
+    fn test_formulas() {
+        let linear: LinearFormula = LinearFormula {
+            divisor: 3,
+            offset: 2,
+        };
+        let table: TableFormula = TableFormula::new(vec![
+            (Mass(100), Fuel(10)),
+            (Mass(10), Fuel(1)),
+            (Mass(1000), Fuel(50)),
+        ]);
+
+        for mass in [0, 5, 12, 14, 1969, 100756] {
+            assert_eq!(
+                fuel_required_with(&linear, Mass(mass)),
+                fuel_required(Mass(mass))
+            );
         }
+        assert_eq!(fuel_required_with(&table, Mass(9)), Fuel(0));
+        assert_eq!(fuel_required_with(&table, Mass(10)), Fuel(1));
+        assert_eq!(fuel_required_with(&table, Mass(999)), Fuel(10));
+        assert_eq!(fuel_required_with(&table, Mass(5000)), Fuel(50));
+    }
+
+    #[test]
+    fn test_recursive_fuel_required_with() {
+        let half: LinearFormula = LinearFormula {
+            divisor: 2,
+            offset: 0,
+        };
+        // a formula that never shrinks the mass stops after one round
+        let flat: TableFormula = TableFormula::new(vec![(Mass(1), Fuel(7))]);
+
+        assert_eq!(
+            recursive_fuel_required_with(&AocFormula, Fuel(0), Mass(1969u64)),
+            Fuel(966)
+        );
+        assert_eq!(
+            recursive_fuel_required_with(&half, Fuel(0), Mass(100)),
+            Fuel(50 + 25 + 12 + 6 + 3 + 1)
+        );
+        assert_eq!(
+            recursive_fuel_required_with(&flat, Fuel(0), Mass(100)),
+            Fuel(7)
+        );
+        assert_eq!(
+            recursive_fuel_required_with(&flat, Fuel(0), Mass(3)),
+            Fuel(7)
+        );
     }
 }
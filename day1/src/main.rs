@@ -1,5 +1,73 @@
+use std::process::ExitCode;
+use std::time::Instant;
 
-fn main() {
-    println!("Part1: {}", day1::part1("src/input.txt"));
-    println!("Part2: {}", day1::part2("src/input.txt"));
+use clap::Parser;
+use day1::{LineMode, par_part1, par_part2, report, report_table, scan_part1, scan_part2};
+use shared::args::DayArgs;
+
+/// Fuel needed to launch the spacecraft modules
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: DayArgs,
+    /// print how long each part took
+    #[arg(long)]
+    time: bool,
+    /// spread the modules across threads
+    #[arg(long)]
+    parallel: bool,
+    /// leave malformed lines out of the total instead of stopping
+    #[arg(long)]
+    skip_invalid: bool,
+    /// print the fuel for each module as a table
+    #[arg(long, conflicts_with_all = ["part", "time"])]
+    report: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if cli.report {
+        return match report(&cli.args.input) {
+            Ok(modules) => {
+                print!("{}", report_table(&modules));
+                ExitCode::SUCCESS
+            }
+            Err(err) => {
+                eprintln!("{}: {}", cli.args.input, err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let mode = match cli.skip_invalid {
+        true => LineMode::Skip,
+        false => LineMode::Strict,
+    };
+    for part in [1, 2] {
+        if !cli.args.wants(part) {
+            continue;
+        }
+        let start = Instant::now();
+        let summary = match (cli.parallel, part) {
+            (true, 1) => par_part1(&cli.args.input, mode),
+            (true, _) => par_part2(&cli.args.input, mode),
+            (false, 1) => scan_part1(&cli.args.input, mode),
+            (false, _) => scan_part2(&cli.args.input, mode),
+        };
+        let (answer, skipped) = match summary {
+            Ok(summary) => (summary.total, summary.skipped),
+            Err(err) => {
+                eprintln!("{}: {}", cli.args.input, err);
+                return ExitCode::FAILURE;
+            }
+        };
+        for err in &skipped {
+            eprintln!("{}: skipped {}", cli.args.input, err);
+        }
+        match cli.time {
+            true => println!("Part{}: {} ({:.2?})", part, answer, start.elapsed()),
+            false => println!("Part{}: {}", part, answer),
+        }
+    }
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,290 @@
+use std::collections::{BTreeMap, HashSet};
+use std::f64::consts::TAU;
+use std::fmt;
+
+use shared::grid::{Grid, GridError};
+use shared::numtheory::gcd;
+use shared::solution::{Answer, Solution, answer};
+
+/// Position on the map, x to the right and y downwards
+pub type Position = (i64, i64);
+
+/// Reasons the map can't answer a question
+#[derive(Debug, PartialEq)]
+pub enum Day10Error {
+    /// the map isn't a rectangle of `#` and `.`
+    Grid(GridError),
+    /// there is nowhere to build a station
+    NoAsteroids,
+    /// fewer asteroids than needed get vaporized
+    TooFew { wanted: usize, found: usize },
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day10Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day10Error::Grid(err) => write!(f, "{}", err),
+            Day10Error::NoAsteroids => write!(f, "map has no asteroids"),
+            Day10Error::TooFew { wanted, found } => write!(
+                f,
+                "wanted asteroid {} but only {} get vaporized",
+                wanted, found
+            ),
+            Day10Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day10Error {}
+
+impl From<GridError> for Day10Error {
+    fn from(err: GridError) -> Self {
+        Day10Error::Grid(err)
+    }
+}
+
+/// Direction from one position to another reduced to lowest terms, so
+/// every asteroid along the same line of sight shares it
+fn direction(from: Position, to: Position) -> Position {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let g = gcd(dx, dy).max(1);
+    (dx / g, dy / g)
+}
+
+/// Clockwise angle of a direction from straight up, in `[0, TAU)`
+fn angle((dx, dy): Position) -> f64 {
+    (dx as f64).atan2(-dy as f64).rem_euclid(TAU)
+}
+
+/// Asteroid positions in a region of space
+#[derive(Debug, PartialEq)]
+pub struct AsteroidMap {
+    asteroids: Vec<Position>,
+}
+
+impl AsteroidMap {
+    /// Constructor for an AsteroidMap
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - rows of the map, `#` marking an asteroid
+    ///
+    /// # Returns
+    ///
+    /// * asteroids in reading order, or why the map couldn't be read
+    ///
+    pub fn new<S: AsRef<str>>(lines: &[S]) -> Result<AsteroidMap, Day10Error> {
+        let grid = Grid::parse(lines, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        Ok(AsteroidMap {
+            asteroids: grid
+                .iter()
+                .filter(|&(_, &a)| a)
+                .map(|((x, y), _)| (x as i64, y as i64))
+                .collect(),
+        })
+    }
+
+    /// Asteroids in reading order
+    pub fn asteroids(&self) -> &[Position] {
+        &self.asteroids
+    }
+
+    /// Number of other asteroids with nothing blocking the view from
+    /// `station`
+    pub fn visible_from(&self, station: Position) -> usize {
+        self.asteroids
+            .iter()
+            .filter(|&&a| a != station)
+            .map(|&a| direction(station, a))
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Asteroid that can see the most others
+    ///
+    /// # Returns
+    ///
+    /// * position and visible count, ties going to the first in reading
+    ///   order, or none if there are no asteroids
+    ///
+    pub fn best_station(&self) -> Option<(Position, usize)> {
+        self.asteroids
+            .iter()
+            .map(|&a| (a, self.visible_from(a)))
+            .fold(None, |best, (a, seen)| match best {
+                Some((_, most)) if most >= seen => best,
+                _ => Some((a, seen)),
+            })
+    }
+
+    /// Order a laser at `station` rotating clockwise from straight up
+    /// vaporizes every other asteroid
+    ///
+    /// Each sweep only hits the nearest asteroid left along a line of
+    /// sight, those further back waiting for later rotations.
+    ///
+    /// # Arguments
+    ///
+    /// * `station` - position of the laser
+    ///
+    /// # Returns
+    ///
+    /// * every other asteroid in the order it's vaporized
+    ///
+    pub fn vaporization_order(&self, station: Position) -> Vec<Position> {
+        let mut lines: BTreeMap<Position, Vec<Position>> = BTreeMap::new();
+        for &a in self.asteroids.iter().filter(|&&a| a != station) {
+            lines.entry(direction(station, a)).or_default().push(a);
+        }
+        let distance = |a: &Position| (a.0 - station.0).abs() + (a.1 - station.1).abs();
+        let mut lines: Vec<(Position, Vec<Position>)> = lines.into_iter().collect();
+        for (_, targets) in lines.iter_mut() {
+            // furthest first so the nearest can be popped
+            targets.sort_by_key(|a| std::cmp::Reverse(distance(a)));
+        }
+        lines.sort_by(|a, b| angle(a.0).total_cmp(&angle(b.0)));
+
+        let mut order = Vec::with_capacity(self.asteroids.len());
+        while lines.iter().any(|(_, targets)| !targets.is_empty()) {
+            for (_, targets) in lines.iter_mut() {
+                order.extend(targets.pop());
+            }
+        }
+        order
+    }
+}
+
+/// Reads the lines of the puzzle input
+///
+/// # Arguments
+///
+/// * `filename` - path of the input
+///
+fn read_input(filename: &str) -> Result<Vec<String>, Day10Error> {
+    shared::try_ingest_file(filename).map_err(|err| Day10Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * most asteroids visible from any one asteroid
+///
+pub fn part1(filename: &str) -> Result<usize, Day10Error> {
+    let map = AsteroidMap::new(&read_input(filename)?)?;
+    let (_, seen) = map.best_station().ok_or(Day10Error::NoAsteroids)?;
+    Ok(seen)
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * `100 * x + y` of the 200th asteroid vaporized from the best station
+///
+pub fn part2(filename: &str) -> Result<i64, Day10Error> {
+    let map = AsteroidMap::new(&read_input(filename)?)?;
+    let (station, _) = map.best_station().ok_or(Day10Error::NoAsteroids)?;
+    let order = map.vaporization_order(station);
+    let (x, y) = order.get(199).ok_or(Day10Error::TooFew {
+        wanted: 200,
+        found: order.len(),
+    })?;
+    Ok(100 * x + y)
+}
+
+/// Day 10 as driven by the workspace runner
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Monitoring Station"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(filename: &str) -> AsteroidMap {
+        AsteroidMap::new(&shared::ingest_file(filename)).unwrap()
+    }
+
+    #[test]
+    fn part1_works() {
+        assert_eq!(load("src/test.txt").best_station(), Some(((3, 4), 8)));
+        assert_eq!(load("src/test2.txt").best_station(), Some(((11, 13), 210)));
+        assert_eq!(part1("src/test2.txt"), Ok(210));
+    }
+
+    #[test]
+    fn part2_works() {
+        let order = load("src/test2.txt").vaporization_order((11, 13));
+        assert_eq!(order.len(), 299);
+        assert_eq!(&order[..3], &[(11, 12), (12, 1), (12, 2)]);
+        assert_eq!(order[198], (9, 6));
+        assert_eq!(order[199], (8, 2));
+        assert_eq!(order[298], (11, 1));
+        assert_eq!(part2("src/test2.txt"), Ok(802));
+        assert_eq!(
+            part2("src/test.txt"),
+            Err(Day10Error::TooFew {
+                wanted: 200,
+                found: 9
+            })
+        );
+    }
+
+    #[test]
+    fn vaporization_order_rotates_clockwise() {
+        let order = load("src/test3.txt").vaporization_order((8, 3));
+        assert_eq!(
+            &order[..9],
+            &[
+                (8, 1),
+                (9, 0),
+                (9, 1),
+                (10, 0),
+                (9, 2),
+                (11, 1),
+                (12, 1),
+                (11, 2),
+                (15, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn new_rejects_bad_maps() {
+        assert_eq!(
+            AsteroidMap::new(&["#.", "#x"]),
+            Err(Day10Error::Grid(GridError::BadCell {
+                row: 1,
+                col: 1,
+                c: 'x'
+            }))
+        );
+        assert_eq!(AsteroidMap::new(&[".."]).unwrap().best_station(), None);
+    }
+}
@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use shared::grid::Grid;
+use shared::intcode::{IntcodeError, State, Vm, parse_program};
+use shared::ocr;
+use shared::solution::{Answer, Solution, answer};
+
+/// Panel position, x to the right and y downwards
+pub type Position = (i64, i64);
+
+/// Reasons the robot can't finish painting
+#[derive(Debug, PartialEq)]
+pub enum Day11Error {
+    /// the brain's program faulted
+    Intcode(IntcodeError),
+    /// the brain output something other than a colour and a turn
+    BadInstruction { colour: i64, turn: i64 },
+    /// the brain stopped part way through an instruction
+    Truncated,
+    /// the painted identifier isn't made of known letters
+    Unreadable(Grid<bool>),
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day11Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day11Error::Intcode(err) => write!(f, "{}", err),
+            Day11Error::BadInstruction { colour, turn } => {
+                write!(f, "brain asked to paint {} and turn {}", colour, turn)
+            }
+            Day11Error::Truncated => write!(f, "brain output half an instruction"),
+            Day11Error::Unreadable(grid) => write!(f, "can't read identifier:\n{}", grid),
+            Day11Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day11Error {}
+
+impl From<IntcodeError> for Day11Error {
+    fn from(err: IntcodeError) -> Self {
+        Day11Error::Intcode(err)
+    }
+}
+
+/// Hull panels the robot has painted, white being `true`
+///
+/// Panels never painted are black.
+#[derive(Debug, Default, PartialEq)]
+pub struct Hull {
+    panels: HashMap<Position, bool>,
+}
+
+impl Hull {
+    /// Colour of a panel
+    pub fn is_white(&self, position: Position) -> bool {
+        self.panels.get(&position).copied().unwrap_or(false)
+    }
+
+    /// Paints a panel
+    pub fn paint(&mut self, position: Position, white: bool) {
+        self.panels.insert(position, white);
+    }
+
+    /// Number of panels painted at least once, whatever their colour now
+    pub fn painted(&self) -> usize {
+        self.panels.len()
+    }
+
+    /// Draws the smallest rectangle holding every white panel
+    pub fn render(&self) -> Grid<bool> {
+        let white: Vec<Position> = self
+            .panels
+            .iter()
+            .filter(|(_, w)| **w)
+            .map(|(p, _)| *p)
+            .collect();
+        let (Some(min_x), Some(min_y)) = (
+            white.iter().map(|p| p.0).min(),
+            white.iter().map(|p| p.1).min(),
+        ) else {
+            return Grid::new(0, 0, false);
+        };
+        let max_x = white.iter().map(|p| p.0).max().unwrap_or(min_x);
+        let max_y = white.iter().map(|p| p.1).max().unwrap_or(min_y);
+        let mut grid = Grid::new(
+            (max_x - min_x + 1) as usize,
+            (max_y - min_y + 1) as usize,
+            false,
+        );
+        for (x, y) in white {
+            grid.set((x - min_x) as usize, (y - min_y) as usize, true);
+        }
+        grid
+    }
+}
+
+/// Hull painting robot
+#[derive(Debug)]
+pub struct Robot {
+    position: Position,
+    /// unit step the robot moves when going forward
+    facing: Position,
+}
+
+impl Default for Robot {
+    fn default() -> Self {
+        Robot {
+            position: (0, 0),
+            facing: (0, -1),
+        }
+    }
+}
+
+impl Robot {
+    /// Paints the panel underneath, turns, and moves forward one panel
+    ///
+    /// # Arguments
+    ///
+    /// * `hull` - hull being painted
+    /// * `colour` - 0 for black or 1 for white
+    /// * `turn` - 0 to turn left or 1 to turn right
+    ///
+    pub fn execute(&mut self, hull: &mut Hull, colour: i64, turn: i64) -> Result<(), Day11Error> {
+        let white = match colour {
+            0 => false,
+            1 => true,
+            _ => return Err(Day11Error::BadInstruction { colour, turn }),
+        };
+        let (dx, dy) = self.facing;
+        self.facing = match turn {
+            0 => (dy, -dx),
+            1 => (-dy, dx),
+            _ => return Err(Day11Error::BadInstruction { colour, turn }),
+        };
+        hull.paint(self.position, white);
+        self.position = (
+            self.position.0 + self.facing.0,
+            self.position.1 + self.facing.1,
+        );
+        Ok(())
+    }
+
+    /// Panel the robot is over
+    pub fn position(&self) -> Position {
+        self.position
+    }
+}
+
+/// Lets an intcode brain drive the robot until the brain halts
+///
+/// # Arguments
+///
+/// * `program` - brain software
+/// * `start_white` - whether the robot's starting panel is white
+///
+/// # Returns
+///
+/// * hull as the robot left it
+///
+pub fn paint(program: &[i64], start_white: bool) -> Result<Hull, Day11Error> {
+    let mut hull = Hull::default();
+    if start_white {
+        hull.paint((0, 0), true);
+    }
+    let mut robot = Robot::default();
+    let mut brain = Vm::new(program.to_vec());
+    loop {
+        brain.push_input(hull.is_white(robot.position()) as i64);
+        let state = brain.run_until_blocked()?;
+        let output = brain.take_output();
+        if !output.len().is_multiple_of(2) {
+            return Err(Day11Error::Truncated);
+        }
+        for instruction in output.chunks(2) {
+            robot.execute(&mut hull, instruction[0], instruction[1])?;
+        }
+        if state == State::Halted {
+            return Ok(hull);
+        }
+    }
+}
+
+/// Reads the letters painted in white on the hull
+///
+/// # Arguments
+///
+/// * `hull` - hull after painting
+///
+/// # Returns
+///
+/// * letters, or the rendered panels if they can't be read
+///
+pub fn identifier(hull: &Hull) -> Result<String, Day11Error> {
+    let image = hull.render();
+    ocr::read(&image).ok_or(Day11Error::Unreadable(image))
+}
+
+/// Reads a program from the first line of a file
+///
+/// # Arguments
+///
+/// * `filename` - path of file containing the intcode
+///
+pub fn load_program(filename: &str) -> Result<Vec<i64>, Day11Error> {
+    let input = shared::try_ingest_file(filename).map_err(|err| Day11Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })?;
+    Ok(parse_program(input.first().map_or("", |s| s))?)
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * number of panels painted at least once starting on black
+///
+pub fn part1(filename: &str) -> Result<usize, Day11Error> {
+    Ok(paint(&load_program(filename)?, false)?.painted())
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * registration identifier painted starting on white
+///
+pub fn part2(filename: &str) -> Result<String, Day11Error> {
+    identifier(&paint(&load_program(filename)?, true)?)
+}
+
+/// Day 11 as driven by the workspace runner
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Space Police"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Brain that waits for each panel colour then replays `instructions`
+    fn scripted(instructions: &[(i64, i64)]) -> Vec<i64> {
+        let mut program = Vec::new();
+        for &(colour, turn) in instructions {
+            program.extend([3, 0, 104, colour, 104, turn]);
+        }
+        program.push(99);
+        program
+    }
+
+    #[test]
+    fn example_paints_six_panels() {
+        let program = scripted(&[(1, 0), (0, 0), (1, 0), (1, 0), (0, 1), (1, 0), (1, 0)]);
+        let hull = paint(&program, false).unwrap();
+        assert_eq!(hull.painted(), 6);
+        assert!(hull.is_white((1, -1)));
+        assert!(!hull.is_white((0, 0)));
+        assert_eq!(hull.render().to_string(), "  #\n  #\n##\n");
+    }
+
+    #[test]
+    fn brain_sees_panel_colours() {
+        // outputs the colour it reads back as the paint, always turning right
+        let echo = parse_program("3,100,4,100,104,1,1105,1,0").unwrap();
+        let mut hull = Hull::default();
+        let mut robot = Robot::default();
+        let mut brain = Vm::new(echo);
+        for _ in 0..4 {
+            brain.push_input(hull.is_white(robot.position()) as i64);
+            brain.run_until_blocked().unwrap();
+            let out = brain.take_output();
+            robot.execute(&mut hull, out[0], out[1]).unwrap();
+        }
+        assert_eq!(robot.position(), (0, 0));
+        assert_eq!(hull.painted(), 4);
+    }
+
+    #[test]
+    fn identifier_reads_white_panels() {
+        let rows = [
+            "#..#..###",
+            "#..#...#.",
+            "####...#.",
+            "#..#...#.",
+            "#..#...#.",
+            "#..#..###",
+        ];
+        let mut hull = Hull::default();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                hull.paint((x as i64 - 3, y as i64 + 2), c == '#');
+            }
+        }
+        // a stray black panel outside the letters doesn't widen the image
+        hull.paint((-10, 0), false);
+        assert_eq!(identifier(&hull), Ok(String::from("HI")));
+        assert!(matches!(
+            identifier(&Hull::default()),
+            Err(Day11Error::Unreadable(_))
+        ));
+    }
+
+    #[test]
+    fn execute_rejects_bad_instructions() {
+        let program = scripted(&[(2, 0)]);
+        assert_eq!(
+            paint(&program, false),
+            Err(Day11Error::BadInstruction { colour: 2, turn: 0 })
+        );
+        assert_eq!(
+            paint(&[3, 0, 104, 1, 99], false),
+            Err(Day11Error::Truncated)
+        );
+    }
+}
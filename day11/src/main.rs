@@ -0,0 +1,48 @@
+use std::fmt::Display;
+use std::process::ExitCode;
+
+use clap::Parser;
+use day11::{Day11Error, part1, part2};
+use shared::args::DayArgs;
+
+/// Emergency hull painting robot
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: DayArgs,
+}
+
+/// Prints the answer to a part, or its error on stderr
+///
+/// # Returns
+///
+/// * whether the part produced an answer
+///
+fn report<T: Display>(part: u8, result: Result<T, Day11Error>) -> bool {
+    match result {
+        Ok(answer) => {
+            println!("Part{}: {}", part, answer);
+            true
+        }
+        Err(err) => {
+            eprintln!("Part{}: {}", part, err);
+            false
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let mut ok = true;
+    if cli.args.wants(1) {
+        ok &= report(1, part1(&cli.args.input));
+    }
+    if cli.args.wants(2) {
+        ok &= report(2, part2(&cli.args.input));
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,282 @@
+use std::fmt;
+
+use shared::numtheory::lcm;
+use shared::point::Point3;
+use shared::solution::{Answer, Solution, answer};
+
+/// Steps simulated before measuring energy in part1
+pub const ENERGY_STEPS: usize = 1000;
+
+/// Reasons the moon scan can't be read
+#[derive(Debug, PartialEq)]
+pub enum Day12Error {
+    /// a line isn't of the form `<x=1, y=2, z=3>`
+    Malformed { line: usize, content: String },
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day12Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day12Error::Malformed { line, content } => {
+                write!(
+                    f,
+                    "line {}: expected `<x=.., y=.., z=..>`, got {:?}",
+                    line, content
+                )
+            }
+            Day12Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day12Error {}
+
+/// Moon with its position and velocity
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Moon {
+    pub position: Point3,
+    pub velocity: Point3,
+}
+
+impl Moon {
+    /// Constructor for a Moon at rest
+    pub fn new(position: Point3) -> Moon {
+        Moon {
+            position,
+            velocity: Point3::default(),
+        }
+    }
+
+    /// Potential energy times kinetic energy
+    pub fn energy(&self) -> i64 {
+        self.position.manhattan() * self.velocity.manhattan()
+    }
+}
+
+/// Parses a moon's position from a line such as `<x=-1, y=0, z=2>`
+fn parse_position(line: &str) -> Option<Point3> {
+    let inner = line.trim().strip_prefix('<')?.strip_suffix('>')?;
+    let mut axes = inner.split(',').map(|part| part.trim());
+    let mut axis = |name: &str| -> Option<i64> {
+        axes.next()?
+            .strip_prefix(name)?
+            .strip_prefix('=')?
+            .parse()
+            .ok()
+    };
+    let point = Point3::new(axis("x")?, axis("y")?, axis("z")?);
+    axes.next().is_none().then_some(point)
+}
+
+/// Parses a moon per line
+///
+/// # Arguments
+///
+/// * `lines` - moon positions, one `<x=.., y=.., z=..>` per line
+///
+/// # Returns
+///
+/// * moons at rest, or the first line that can't be read
+///
+pub fn parse_moons<S: AsRef<str>>(lines: &[S]) -> Result<Vec<Moon>, Day12Error> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            parse_position(line.as_ref())
+                .map(Moon::new)
+                .ok_or_else(|| Day12Error::Malformed {
+                    line: i + 1,
+                    content: String::from(line.as_ref()),
+                })
+        })
+        .collect()
+}
+
+/// Advances the moons one time step
+///
+/// Every pair of moons pulls each other one unit closer along each axis,
+/// then every moon moves by its velocity.
+///
+/// # Arguments
+///
+/// * `moons` - moons to update in place
+///
+pub fn step(moons: &mut [Moon]) {
+    for i in 0..moons.len() {
+        for j in i + 1..moons.len() {
+            let pull = (moons[j].position - moons[i].position).signum();
+            moons[i].velocity += pull;
+            moons[j].velocity -= pull;
+        }
+    }
+    for moon in moons.iter_mut() {
+        moon.position += moon.velocity;
+    }
+}
+
+/// Total energy in the system after a number of steps
+///
+/// # Arguments
+///
+/// * `moons` - moons at the start
+/// * `steps` - time steps to simulate
+///
+pub fn energy_after(moons: &[Moon], steps: usize) -> i64 {
+    let mut moons = moons.to_vec();
+    for _ in 0..steps {
+        step(&mut moons);
+    }
+    moons.iter().map(Moon::energy).sum()
+}
+
+/// Steps until one axis of the system returns to its starting state
+///
+/// Axes don't affect each other, and every step can be undone, so each
+/// axis cycles back through its starting state.
+///
+/// # Arguments
+///
+/// * `moons` - moons at the start
+/// * `axis` - 0 for x, 1 for y, 2 for z
+///
+fn axis_period(moons: &[Moon], axis: usize) -> i64 {
+    let mut positions: Vec<i64> = moons.iter().map(|m| m.position.axes()[axis]).collect();
+    let mut velocities: Vec<i64> = moons.iter().map(|m| m.velocity.axes()[axis]).collect();
+    let start = (positions.clone(), velocities.clone());
+    let mut steps = 0;
+    loop {
+        for i in 0..positions.len() {
+            for j in i + 1..positions.len() {
+                let pull = (positions[j] - positions[i]).signum();
+                velocities[i] += pull;
+                velocities[j] -= pull;
+            }
+        }
+        for (p, v) in positions.iter_mut().zip(&velocities) {
+            *p += v;
+        }
+        steps += 1;
+        if positions == start.0 && velocities == start.1 {
+            return steps;
+        }
+    }
+}
+
+/// Steps until every moon is back where it started with its starting
+/// velocity
+///
+/// # Arguments
+///
+/// * `moons` - moons at the start
+///
+/// # Returns
+///
+/// * least common multiple of the per axis periods
+///
+pub fn period(moons: &[Moon]) -> i64 {
+    (0..3).map(|axis| axis_period(moons, axis)).fold(1, lcm)
+}
+
+/// Reads the lines of the puzzle input
+///
+/// # Arguments
+///
+/// * `filename` - path of the input
+///
+fn read_input(filename: &str) -> Result<Vec<String>, Day12Error> {
+    shared::try_ingest_file(filename).map_err(|err| Day12Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * total energy after 1000 steps
+///
+pub fn part1(filename: &str) -> Result<i64, Day12Error> {
+    Ok(energy_after(
+        &parse_moons(&read_input(filename)?)?,
+        ENERGY_STEPS,
+    ))
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * steps until the system repeats a previous state
+///
+pub fn part2(filename: &str) -> Result<i64, Day12Error> {
+    Ok(period(&parse_moons(&read_input(filename)?)?))
+}
+
+/// Day 12 as driven by the workspace runner
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn title(&self) -> &'static str {
+        "The N-Body Problem"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(filename: &str) -> Vec<Moon> {
+        parse_moons(&shared::ingest_file(filename)).unwrap()
+    }
+
+    #[test]
+    fn step_works() {
+        let mut moons = load("src/test.txt");
+        step(&mut moons);
+        assert_eq!(moons[0].position, Point3::new(2, -1, 1));
+        assert_eq!(moons[0].velocity, Point3::new(3, -1, -1));
+        assert_eq!(moons[3].position, Point3::new(2, 2, 0));
+        assert_eq!(moons[3].velocity, Point3::new(-1, -3, 1));
+    }
+
+    #[test]
+    fn part1_works() {
+        assert_eq!(energy_after(&load("src/test.txt"), 10), 179);
+        assert_eq!(energy_after(&load("src/test2.txt"), 100), 1940);
+    }
+
+    #[test]
+    fn part2_works() {
+        assert_eq!(part2("src/test.txt"), Ok(2772));
+        assert_eq!(part2("src/test2.txt"), Ok(4686774924));
+    }
+
+    #[test]
+    fn parse_moons_rejects_bad_lines() {
+        assert_eq!(
+            parse_moons(&["<x=1, y=2, z=3>", "<x=1, y=2>"]),
+            Err(Day12Error::Malformed {
+                line: 2,
+                content: String::from("<x=1, y=2>")
+            })
+        );
+        assert!(parse_moons(&["<x=1, y=2, z=3, w=4>"]).is_err());
+        assert!(parse_moons(&["<y=1, x=2, z=3>"]).is_err());
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use shared::intcode::{IntcodeError, State, Vm, parse_program};
+use shared::solution::{Answer, Solution, answer};
+
+/// Screen position, x to the right and y downwards
+pub type Position = (i64, i64);
+
+/// Reasons the arcade cabinet can't produce an answer
+#[derive(Debug, PartialEq)]
+pub enum Day13Error {
+    /// the game's program faulted
+    Intcode(IntcodeError),
+    /// the game drew a tile id that doesn't exist
+    BadTile(i64),
+    /// the game output something other than whole `x, y, tile` triples
+    Truncated,
+    /// the game ended with blocks still standing
+    GameOver { blocks_left: usize },
+    /// the game asked for input with no ball or paddle on screen
+    NothingToTrack,
+    /// the program is empty so there's nowhere to put the quarters
+    EmptyProgram,
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day13Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day13Error::Intcode(err) => write!(f, "{}", err),
+            Day13Error::BadTile(id) => write!(f, "unknown tile id {}", id),
+            Day13Error::Truncated => write!(f, "game output a partial tile"),
+            Day13Error::GameOver { blocks_left } => {
+                write!(f, "game over with {} blocks left", blocks_left)
+            }
+            Day13Error::NothingToTrack => write!(f, "no ball or paddle to track"),
+            Day13Error::EmptyProgram => write!(f, "program is empty"),
+            Day13Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day13Error {}
+
+impl From<IntcodeError> for Day13Error {
+    fn from(err: IntcodeError) -> Self {
+        Day13Error::Intcode(err)
+    }
+}
+
+/// What is drawn at a screen position
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball,
+}
+
+impl Tile {
+    /// Tile for an id the game outputs
+    pub fn from_id(id: i64) -> Option<Tile> {
+        match id {
+            0 => Some(Tile::Empty),
+            1 => Some(Tile::Wall),
+            2 => Some(Tile::Block),
+            3 => Some(Tile::Paddle),
+            4 => Some(Tile::Ball),
+            _ => None,
+        }
+    }
+
+    fn glyph(&self) -> char {
+        match self {
+            Tile::Empty => ' ',
+            Tile::Wall => '#',
+            Tile::Block => '+',
+            Tile::Paddle => '=',
+            Tile::Ball => 'o',
+        }
+    }
+}
+
+/// Arcade screen along with the score display
+#[derive(Debug, Default, PartialEq)]
+pub struct Screen {
+    tiles: HashMap<Position, Tile>,
+    score: i64,
+}
+
+impl Screen {
+    /// Applies draw instructions from the game
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - `x, y, tile id` triples, with `-1, 0, score` updating
+    ///   the score instead
+    ///
+    pub fn apply(&mut self, output: &[i64]) -> Result<(), Day13Error> {
+        if !output.len().is_multiple_of(3) {
+            return Err(Day13Error::Truncated);
+        }
+        for draw in output.chunks(3) {
+            if let [-1, 0, score] = draw {
+                self.score = *score;
+            } else {
+                let tile = Tile::from_id(draw[2]).ok_or(Day13Error::BadTile(draw[2]))?;
+                self.tiles.insert((draw[0], draw[1]), tile);
+            }
+        }
+        Ok(())
+    }
+
+    /// Last score the game displayed
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    /// Number of positions showing `tile`
+    pub fn count(&self, tile: Tile) -> usize {
+        self.tiles.values().filter(|&&t| t == tile).count()
+    }
+
+    /// A position showing `tile`, if any
+    pub fn find(&self, tile: Tile) -> Option<Position> {
+        self.tiles
+            .iter()
+            .find(|(_, t)| **t == tile)
+            .map(|(p, _)| *p)
+    }
+}
+
+impl fmt::Display for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self.tiles.keys().map(|p| p.0 + 1).max().unwrap_or(0);
+        let height = self.tiles.keys().map(|p| p.1 + 1).max().unwrap_or(0);
+        writeln!(f, "score {}", self.score)?;
+        for y in 0..height {
+            let row: String = (0..width)
+                .map(|x| self.tiles.get(&(x, y)).unwrap_or(&Tile::Empty).glyph())
+                .collect();
+            writeln!(f, "{}", row.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs the game without input and captures what it draws
+///
+/// # Arguments
+///
+/// * `program` - game software
+///
+pub fn draw(program: &[i64]) -> Result<Screen, Day13Error> {
+    let mut vm = Vm::new(program.to_vec());
+    vm.run()?;
+    let mut screen = Screen::default();
+    screen.apply(vm.output())?;
+    Ok(screen)
+}
+
+/// Sets the game to free play by putting two quarters in address 0
+///
+/// # Arguments
+///
+/// * `program` - game software
+///
+pub fn insert_quarters(program: &[i64]) -> Result<Vec<i64>, Day13Error> {
+    let mut program = program.to_vec();
+    *program.first_mut().ok_or(Day13Error::EmptyProgram)? = 2;
+    Ok(program)
+}
+
+/// Plays the game, moving the joystick to keep the paddle under the ball
+///
+/// # Arguments
+///
+/// * `program` - game software, already set to free play
+///
+/// # Returns
+///
+/// * final screen once the game halts with every block broken
+///
+pub fn play(program: &[i64]) -> Result<Screen, Day13Error> {
+    let mut vm = Vm::new(program.to_vec());
+    let mut screen = Screen::default();
+    loop {
+        let state = vm.run_until_blocked()?;
+        screen.apply(&vm.take_output())?;
+        if state == State::Halted {
+            let blocks_left = screen.count(Tile::Block);
+            return match blocks_left {
+                0 => Ok(screen),
+                _ => Err(Day13Error::GameOver { blocks_left }),
+            };
+        }
+        let (ball, paddle) = screen
+            .find(Tile::Ball)
+            .zip(screen.find(Tile::Paddle))
+            .ok_or(Day13Error::NothingToTrack)?;
+        vm.push_input((ball.0 - paddle.0).signum());
+    }
+}
+
+/// Reads a program from the first line of a file
+///
+/// # Arguments
+///
+/// * `filename` - path of file containing the intcode
+///
+pub fn load_program(filename: &str) -> Result<Vec<i64>, Day13Error> {
+    let input = shared::try_ingest_file(filename).map_err(|err| Day13Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })?;
+    Ok(parse_program(input.first().map_or("", |s| s))?)
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * number of block tiles on screen when the game exits
+///
+pub fn part1(filename: &str) -> Result<usize, Day13Error> {
+    Ok(draw(&load_program(filename)?)?.count(Tile::Block))
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * score after breaking the last block
+///
+pub fn part2(filename: &str) -> Result<i64, Day13Error> {
+    Ok(play(&insert_quarters(&load_program(filename)?)?)?.score())
+}
+
+/// Day 13 as driven by the workspace runner
+pub struct Day13;
+
+impl Solution for Day13 {
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn title(&self) -> &'static str {
+        "Care Package"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Program that outputs each value then halts
+    fn outputs(values: &[i64]) -> Vec<i64> {
+        let mut program: Vec<i64> = values.iter().flat_map(|&v| [104, v]).collect();
+        program.push(99);
+        program
+    }
+
+    #[test]
+    fn draw_works() {
+        let screen = draw(&outputs(&[1, 2, 3, 6, 5, 4, 0, 0, 2, 1, 0, 2, 1, 0, 0])).unwrap();
+        assert_eq!(screen.count(Tile::Block), 1);
+        assert_eq!(screen.find(Tile::Paddle), Some((1, 2)));
+        assert_eq!(screen.find(Tile::Ball), Some((6, 5)));
+        assert_eq!(screen.to_string(), "score 0\n+\n\n =\n\n\n      o\n");
+    }
+
+    #[test]
+    fn apply_rejects_bad_output() {
+        let mut screen = Screen::default();
+        assert_eq!(screen.apply(&[0, 0, 7]), Err(Day13Error::BadTile(7)));
+        assert_eq!(screen.apply(&[0, 0]), Err(Day13Error::Truncated));
+        assert_eq!(screen.apply(&[-1, 0, 12345]), Ok(()));
+        assert_eq!(screen.score(), 12345);
+    }
+
+    #[test]
+    fn play_tracks_ball() {
+        // paddle at x 1, ball at x 3 and a block, then scores whatever the
+        // joystick says and clears the block
+        let mut program = outputs(&[1, 0, 3, 3, 0, 4, 5, 5, 2]);
+        program.pop();
+        program.extend([3, 100, 104, -1, 104, 0, 4, 100]);
+        program.extend([104, 5, 104, 5, 104, 0, 99]);
+        assert_eq!(play(&program).map(|s| s.score()), Ok(1));
+
+        // never clears the block
+        let stuck = outputs(&[5, 5, 2]);
+        assert_eq!(play(&stuck), Err(Day13Error::GameOver { blocks_left: 1 }));
+    }
+
+    #[test]
+    fn insert_quarters_works() {
+        assert_eq!(insert_quarters(&[1, 0, 0, 0, 99]), Ok(vec![2, 0, 0, 0, 99]));
+        assert_eq!(insert_quarters(&[]), Err(Day13Error::EmptyProgram));
+    }
+}
@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use shared::solution::{Answer, Solution, answer};
+
+/// Raw material every reaction chain starts from
+pub const ORE: &str = "ORE";
+/// Chemical the factory is asked to make
+pub const FUEL: &str = "FUEL";
+/// Ore held in the cargo hold for part2
+pub const CARGO_ORE: u64 = 1_000_000_000_000;
+
+/// Reasons the reaction list can't be used
+#[derive(Debug, PartialEq)]
+pub enum Day14Error {
+    /// a line isn't of the form `7 A, 1 B => 1 C`
+    Malformed { line: usize, content: String },
+    /// two reactions produce the same chemical
+    Duplicate(String),
+    /// a chemical is needed that no reaction produces
+    Unknown(String),
+    /// a chemical is needed, directly or not, to make itself
+    Cycle(String),
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day14Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day14Error::Malformed { line, content } => {
+                write!(f, "line {}: not a reaction: {:?}", line, content)
+            }
+            Day14Error::Duplicate(chemical) => {
+                write!(f, "more than one reaction makes {}", chemical)
+            }
+            Day14Error::Unknown(chemical) => write!(f, "no reaction makes {}", chemical),
+            Day14Error::Cycle(chemical) => write!(f, "{} is needed to make itself", chemical),
+            Day14Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day14Error {}
+
+/// Reaction turning fixed amounts of inputs into a batch of one chemical
+#[derive(Debug, PartialEq)]
+pub struct Reaction {
+    /// units made per batch
+    pub quantity: u64,
+    /// chemicals and units consumed per batch
+    pub inputs: Vec<(String, u64)>,
+}
+
+/// Parses a term such as `7 A`
+fn parse_term(term: &str) -> Option<(String, u64)> {
+    let (quantity, chemical) = term.trim().split_once(' ')?;
+    let chemical = chemical.trim();
+    (!chemical.is_empty()).then_some(())?;
+    Some((String::from(chemical), quantity.parse().ok()?))
+}
+
+/// Result of expanding a fuel order down to ore
+#[derive(Debug, PartialEq)]
+pub struct Production {
+    /// ore consumed
+    pub ore: u64,
+    /// units left over from whole batches, for chemicals with any
+    pub surplus: HashMap<String, u64>,
+}
+
+/// Reactions known to the nanofactory, with an order to expand them in
+#[derive(Debug)]
+pub struct Nanofactory {
+    reactions: HashMap<String, Reaction>,
+    /// every chemical fuel depends on, each before anything it's made from
+    order: Vec<String>,
+}
+
+impl Nanofactory {
+    /// Constructor for a Nanofactory
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - reactions, one `7 A, 1 B => 1 C` per line
+    ///
+    /// # Returns
+    ///
+    /// * factory able to make fuel, or why the reactions can't
+    ///
+    pub fn new<S: AsRef<str>>(lines: &[S]) -> Result<Nanofactory, Day14Error> {
+        let mut reactions = HashMap::new();
+        for (i, line) in lines.iter().enumerate() {
+            let line = line.as_ref();
+            let malformed = || Day14Error::Malformed {
+                line: i + 1,
+                content: String::from(line),
+            };
+            let (inputs, output) = line.split_once("=>").ok_or_else(malformed)?;
+            let (chemical, quantity) = parse_term(output).ok_or_else(malformed)?;
+            let inputs = inputs
+                .split(',')
+                .map(parse_term)
+                .collect::<Option<Vec<_>>>()
+                .filter(|inputs| inputs.iter().all(|(_, q)| *q > 0))
+                .ok_or_else(malformed)?;
+            if quantity == 0 {
+                return Err(malformed());
+            }
+            if reactions
+                .insert(chemical.clone(), Reaction { quantity, inputs })
+                .is_some()
+            {
+                return Err(Day14Error::Duplicate(chemical));
+            }
+        }
+        let order = topological_order(&reactions)?;
+        Ok(Nanofactory { reactions, order })
+    }
+
+    /// Expands an order for fuel into the ore it takes
+    ///
+    /// Chemicals are expanded in an order where every consumer comes
+    /// before its inputs, so each one is made in a single run of whole
+    /// batches and only the leftovers of that run go spare.
+    ///
+    /// # Arguments
+    ///
+    /// * `fuel` - units of fuel wanted
+    ///
+    pub fn produce(&self, fuel: u64) -> Production {
+        let mut needed: HashMap<&str, u64> = HashMap::from([(FUEL, fuel)]);
+        let mut surplus = HashMap::new();
+        for chemical in &self.order {
+            let need = needed.get(chemical.as_str()).copied().unwrap_or(0);
+            let reaction = &self.reactions[chemical];
+            let batches = need.div_ceil(reaction.quantity);
+            let spare = batches * reaction.quantity - need;
+            if spare > 0 {
+                surplus.insert(chemical.clone(), spare);
+            }
+            for (input, amount) in &reaction.inputs {
+                let total = needed.entry(input.as_str()).or_default();
+                *total = total.saturating_add(amount.saturating_mul(batches));
+            }
+        }
+        Production {
+            ore: needed.get(ORE).copied().unwrap_or(0),
+            surplus,
+        }
+    }
+
+    /// Ore needed for some units of fuel
+    pub fn ore_for(&self, fuel: u64) -> u64 {
+        self.produce(fuel).ore
+    }
+
+    /// Most fuel a fixed amount of ore can make
+    ///
+    /// Ore needed only grows with fuel, so the answer is binary searched.
+    ///
+    /// # Arguments
+    ///
+    /// * `ore` - ore available
+    ///
+    pub fn max_fuel(&self, ore: u64) -> u64 {
+        let (mut low, mut high) = (0, 1);
+        while self.ore_for(high) <= ore {
+            low = high;
+            high *= 2;
+        }
+        // ore_for(low) fits and ore_for(high) doesn't
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if self.ore_for(mid) <= ore {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+}
+
+/// Orders every chemical fuel depends on so each comes before the
+/// chemicals it's made from
+fn topological_order(reactions: &HashMap<String, Reaction>) -> Result<Vec<String>, Day14Error> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        Visiting,
+        Done,
+    }
+
+    fn visit(
+        chemical: &str,
+        reactions: &HashMap<String, Reaction>,
+        marks: &mut HashMap<String, Mark>,
+        post_order: &mut Vec<String>,
+    ) -> Result<(), Day14Error> {
+        match marks.get(chemical) {
+            Some(Mark::Done) => return Ok(()),
+            Some(Mark::Visiting) => return Err(Day14Error::Cycle(String::from(chemical))),
+            None => {}
+        }
+        let reaction = reactions
+            .get(chemical)
+            .ok_or_else(|| Day14Error::Unknown(String::from(chemical)))?;
+        marks.insert(String::from(chemical), Mark::Visiting);
+        for (input, _) in &reaction.inputs {
+            if input != ORE {
+                visit(input, reactions, marks, post_order)?;
+            }
+        }
+        marks.insert(String::from(chemical), Mark::Done);
+        post_order.push(String::from(chemical));
+        Ok(())
+    }
+
+    let mut marks = HashMap::new();
+    let mut post_order = Vec::new();
+    visit(FUEL, reactions, &mut marks, &mut post_order)?;
+    post_order.reverse();
+    Ok(post_order)
+}
+
+/// Reads the lines of the puzzle input
+///
+/// # Arguments
+///
+/// * `filename` - path of the input
+///
+fn read_input(filename: &str) -> Result<Vec<String>, Day14Error> {
+    shared::try_ingest_file(filename).map_err(|err| Day14Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * minimum ore needed for one fuel
+///
+pub fn part1(filename: &str) -> Result<u64, Day14Error> {
+    Ok(Nanofactory::new(&read_input(filename)?)?.ore_for(1))
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * most fuel a trillion ore can make
+///
+pub fn part2(filename: &str) -> Result<u64, Day14Error> {
+    Ok(Nanofactory::new(&read_input(filename)?)?.max_fuel(CARGO_ORE))
+}
+
+/// Day 14 as driven by the workspace runner
+pub struct Day14;
+
+impl Solution for Day14 {
+    fn day(&self) -> u8 {
+        14
+    }
+
+    fn title(&self) -> &'static str {
+        "Space Stoichiometry"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_works() {
+        assert_eq!(part1("src/test.txt"), Ok(31));
+        assert_eq!(part1("src/test2.txt"), Ok(165));
+        assert_eq!(part1("src/test3.txt"), Ok(13312));
+    }
+
+    #[test]
+    fn part2_works() {
+        assert_eq!(part2("src/test3.txt"), Ok(82892753));
+    }
+
+    #[test]
+    fn max_fuel_edge_cases() {
+        let factory = Nanofactory::new(&["3 ORE => 2 FUEL"]).unwrap();
+        assert_eq!(factory.max_fuel(2), 0);
+        assert_eq!(factory.max_fuel(3), 2);
+        assert_eq!(factory.max_fuel(7), 4);
+    }
+
+    #[test]
+    fn produce_tracks_surplus() {
+        let factory = Nanofactory::new(&shared::ingest_file("src/test.txt")).unwrap();
+        let production = factory.produce(1);
+        assert_eq!(production.ore, 31);
+        // 28 A are made in three batches of 10
+        assert_eq!(production.surplus, HashMap::from([(String::from("A"), 2)]));
+    }
+
+    #[test]
+    fn new_rejects_bad_reactions() {
+        assert_eq!(
+            Nanofactory::new(&["1 ORE => 1 A", "1 A => FUEL"]).unwrap_err(),
+            Day14Error::Malformed {
+                line: 2,
+                content: String::from("1 A => FUEL")
+            }
+        );
+        assert_eq!(
+            Nanofactory::new(&["1 ORE => 1 A", "2 ORE => 1 A", "1 A => 1 FUEL"]).unwrap_err(),
+            Day14Error::Duplicate(String::from("A"))
+        );
+        assert_eq!(
+            Nanofactory::new(&["1 B => 1 FUEL"]).unwrap_err(),
+            Day14Error::Unknown(String::from("B"))
+        );
+        assert_eq!(
+            Nanofactory::new(&["1 B => 1 A", "1 A => 1 B", "1 A => 1 FUEL"]).unwrap_err(),
+            Day14Error::Cycle(String::from("A"))
+        );
+    }
+}
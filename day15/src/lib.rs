@@ -0,0 +1,378 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+use shared::graph::{bfs, bfs_distance};
+use shared::intcode::{IntcodeError, Vm, parse_program};
+use shared::solution::{Answer, Solution, answer};
+
+/// Position in the section, x to the east and y to the south
+pub type Position = (i64, i64);
+
+/// Reasons the droid can't map the section
+#[derive(Debug, PartialEq)]
+pub enum Day15Error {
+    /// the droid's program faulted
+    Intcode(IntcodeError),
+    /// the droid reported something other than a status code
+    BadStatus(Vec<i64>),
+    /// exploring finished without finding the oxygen system
+    NoOxygenSystem,
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day15Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day15Error::Intcode(err) => write!(f, "{}", err),
+            Day15Error::BadStatus(output) => write!(f, "droid replied {:?}", output),
+            Day15Error::NoOxygenSystem => write!(f, "oxygen system not found"),
+            Day15Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day15Error {}
+
+impl From<IntcodeError> for Day15Error {
+    fn from(err: IntcodeError) -> Self {
+        Day15Error::Intcode(err)
+    }
+}
+
+/// Movement command understood by the droid
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    North = 1,
+    South = 2,
+    West = 3,
+    East = 4,
+}
+
+impl Direction {
+    /// Every direction in command order
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ];
+
+    /// Position one step away in this direction
+    pub fn from(&self, (x, y): Position) -> Position {
+        match self {
+            Direction::North => (x, y - 1),
+            Direction::South => (x, y + 1),
+            Direction::West => (x - 1, y),
+            Direction::East => (x + 1, y),
+        }
+    }
+}
+
+/// What the droid reports after a movement command
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    /// a wall blocked the way, so the droid didn't move
+    Wall,
+    /// the droid moved
+    Moved,
+    /// the droid moved onto the oxygen system
+    Oxygen,
+}
+
+/// Remote controlled droid that can be copied to explore several
+/// branches of the section at once
+pub trait Droid: Clone {
+    /// Tries to move one step
+    fn step(&mut self, direction: Direction) -> Result<Status, Day15Error>;
+}
+
+/// Repair droid driven by its intcode program
+#[derive(Clone, Debug)]
+pub struct IntcodeDroid {
+    vm: Vm,
+}
+
+impl IntcodeDroid {
+    /// Constructor for an IntcodeDroid
+    ///
+    /// # Arguments
+    ///
+    /// * `program` - droid software
+    ///
+    pub fn new(program: &[i64]) -> IntcodeDroid {
+        IntcodeDroid {
+            vm: Vm::new(program.to_vec()),
+        }
+    }
+}
+
+impl Droid for IntcodeDroid {
+    fn step(&mut self, direction: Direction) -> Result<Status, Day15Error> {
+        self.vm.push_input(direction as i64);
+        self.vm.run_until_blocked()?;
+        match self.vm.take_output().as_slice() {
+            [0] => Ok(Status::Wall),
+            [1] => Ok(Status::Moved),
+            [2] => Ok(Status::Oxygen),
+            other => Err(Day15Error::BadStatus(other.to_vec())),
+        }
+    }
+}
+
+/// Open positions found while exploring, the droid starting at (0, 0)
+#[derive(Debug, PartialEq)]
+pub struct SectionMap {
+    open: HashSet<Position>,
+    walls: HashSet<Position>,
+    oxygen: Option<Position>,
+}
+
+impl SectionMap {
+    /// Position of the oxygen system, if found
+    pub fn oxygen(&self) -> Option<Position> {
+        self.oxygen
+    }
+
+    /// Open positions one step from `position`
+    fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .map(move |d| d.from(position))
+            .filter(|p| self.open.contains(p))
+    }
+
+    /// Fewest movement commands from the start to the oxygen system
+    pub fn steps_to_oxygen(&self) -> Result<usize, Day15Error> {
+        let oxygen = self.oxygen.ok_or(Day15Error::NoOxygenSystem)?;
+        bfs_distance((0, 0), |&p| p == oxygen, |&p| self.neighbours(p))
+            .ok_or(Day15Error::NoOxygenSystem)
+    }
+
+    /// Minutes for oxygen to spread from the system to every open position
+    pub fn fill_time(&self) -> Result<usize, Day15Error> {
+        let oxygen = self.oxygen.ok_or(Day15Error::NoOxygenSystem)?;
+        Ok(bfs(oxygen, |&p| self.neighbours(p))
+            .into_values()
+            .max()
+            .unwrap_or(0))
+    }
+}
+
+impl fmt::Display for SectionMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let all = || self.walls.iter().chain(&self.open);
+        let (min_x, max_x) = (all().map(|p| p.0).min(), all().map(|p| p.0).max());
+        let (min_y, max_y) = (all().map(|p| p.1).min(), all().map(|p| p.1).max());
+        let (Some(min_x), Some(max_x), Some(min_y), Some(max_y)) = (min_x, max_x, min_y, max_y)
+        else {
+            return Ok(());
+        };
+        for y in min_y..=max_y {
+            let row: String = (min_x..=max_x)
+                .map(|x| match (x, y) {
+                    p if Some(p) == self.oxygen => 'O',
+                    (0, 0) => 'D',
+                    p if self.walls.contains(&p) => '#',
+                    p if self.open.contains(&p) => '.',
+                    _ => ' ',
+                })
+                .collect();
+            writeln!(f, "{}", row.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// Maps every position reachable from the droid's start
+///
+/// Exploration is breadth first. Each newly reached position keeps its own
+/// copy of the droid, so nothing ever has to backtrack.
+///
+/// # Arguments
+///
+/// * `droid` - droid at the start position
+///
+pub fn explore<D: Droid>(droid: D) -> Result<SectionMap, Day15Error> {
+    let mut map = SectionMap {
+        open: HashSet::from([(0, 0)]),
+        walls: HashSet::new(),
+        oxygen: None,
+    };
+    let mut queue = VecDeque::from([((0, 0), droid)]);
+    while let Some((position, droid)) = queue.pop_front() {
+        for direction in Direction::ALL {
+            let next = direction.from(position);
+            if map.open.contains(&next) || map.walls.contains(&next) {
+                continue;
+            }
+            let mut copy = droid.clone();
+            match copy.step(direction)? {
+                Status::Wall => {
+                    map.walls.insert(next);
+                    continue;
+                }
+                Status::Oxygen => map.oxygen = Some(next),
+                Status::Moved => {}
+            }
+            map.open.insert(next);
+            queue.push_back((next, copy));
+        }
+    }
+    Ok(map)
+}
+
+/// Reads a program from the first line of a file
+///
+/// # Arguments
+///
+/// * `filename` - path of file containing the intcode
+///
+pub fn load_program(filename: &str) -> Result<Vec<i64>, Day15Error> {
+    let input = shared::try_ingest_file(filename).map_err(|err| Day15Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })?;
+    Ok(parse_program(input.first().map_or("", |s| s))?)
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * fewest movement commands from the start to the oxygen system
+///
+pub fn part1(filename: &str) -> Result<usize, Day15Error> {
+    explore(IntcodeDroid::new(&load_program(filename)?))?.steps_to_oxygen()
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * minutes until oxygen fills the section
+///
+pub fn part2(filename: &str) -> Result<usize, Day15Error> {
+    explore(IntcodeDroid::new(&load_program(filename)?))?.fill_time()
+}
+
+/// Day 15 as driven by the workspace runner
+pub struct Day15;
+
+impl Solution for Day15 {
+    fn day(&self) -> u8 {
+        15
+    }
+
+    fn title(&self) -> &'static str {
+        "Oxygen System"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    /// Droid moving around a map drawn as text, `D` marking its start
+    #[derive(Clone)]
+    struct TextDroid {
+        rows: Rc<Vec<Vec<u8>>>,
+        position: Position,
+    }
+
+    impl TextDroid {
+        fn new(rows: &[&str]) -> TextDroid {
+            let rows: Vec<Vec<u8>> = rows.iter().map(|r| r.bytes().collect()).collect();
+            let position = rows
+                .iter()
+                .enumerate()
+                .find_map(|(y, r)| Some((r.iter().position(|&c| c == b'D')? as i64, y as i64)))
+                .unwrap();
+            TextDroid {
+                rows: Rc::new(rows),
+                position,
+            }
+        }
+    }
+
+    impl Droid for TextDroid {
+        fn step(&mut self, direction: Direction) -> Result<Status, Day15Error> {
+            let (x, y) = direction.from(self.position);
+            match self.rows[y as usize][x as usize] {
+                b'#' => Ok(Status::Wall),
+                c => {
+                    self.position = (x, y);
+                    Ok(if c == b'O' {
+                        Status::Oxygen
+                    } else {
+                        Status::Moved
+                    })
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn explore_maps_section() {
+        let droid = TextDroid::new(&[
+            "#######", //
+            "#...#.#", //
+            "#.#.#.#", //
+            "#D#...#", //
+            "####O##", //
+            "#######",
+        ]);
+        let map = explore(droid).unwrap();
+        assert_eq!(map.oxygen(), Some((3, 1)));
+        assert_eq!(map.steps_to_oxygen(), Ok(8));
+        assert_eq!(map.fill_time(), Ok(8));
+        assert_eq!(
+            map.to_string(),
+            " ### #\n#...#.#\n#.#.#.#\n#D#...#\n # #O#\n    #\n"
+        );
+    }
+
+    #[test]
+    fn fill_time_example() {
+        let droid = TextDroid::new(&[
+            " ##   ", //
+            "#..## ", //
+            "#.#..#", //
+            "#DO.# ", //
+            " ###  ",
+        ]);
+        let map = explore(droid).unwrap();
+        assert_eq!(map.steps_to_oxygen(), Ok(1));
+        assert_eq!(map.fill_time(), Ok(4));
+    }
+
+    #[test]
+    fn intcode_droid_reports_status() {
+        // replies 0, then 1, then 2 whatever the command
+        let program = parse_program("3,100,104,0,3,100,104,1,3,100,104,2,99").unwrap();
+        let mut droid = IntcodeDroid::new(&program);
+        assert_eq!(droid.step(Direction::North), Ok(Status::Wall));
+        assert_eq!(droid.step(Direction::East), Ok(Status::Moved));
+        assert_eq!(droid.step(Direction::West), Ok(Status::Oxygen));
+        assert_eq!(
+            droid.step(Direction::South),
+            Err(Day15Error::BadStatus(vec![]))
+        );
+    }
+
+    #[test]
+    fn explore_without_oxygen() {
+        let map = explore(TextDroid::new(&["###", "#D#", "###"])).unwrap();
+        assert_eq!(map.steps_to_oxygen(), Err(Day15Error::NoOxygenSystem));
+        assert_eq!(map.fill_time(), Err(Day15Error::NoOxygenSystem));
+    }
+}
@@ -0,0 +1,33 @@
+use std::process::ExitCode;
+
+use clap::Parser;
+use day15::{Day15Error, part1, part2};
+use shared::args::DayArgs;
+
+type Solver = fn(&str) -> Result<usize, Day15Error>;
+
+/// Repair droid mapping the oxygen system section
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: DayArgs,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let parts: [(u8, Solver); 2] = [(1, part1), (2, part2)];
+    let mut code = ExitCode::SUCCESS;
+    for (part, solve) in parts {
+        if !cli.args.wants(part) {
+            continue;
+        }
+        match solve(&cli.args.input) {
+            Ok(answer) => println!("Part{}: {}", part, answer),
+            Err(err) => {
+                eprintln!("Part{}: {}", part, err);
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+    code
+}
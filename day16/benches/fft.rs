@@ -0,0 +1,42 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use day16::{PHASES, REPEATS, embedded_message, phase, phase_naive};
+
+/// Length of a real puzzle signal
+const SIGNAL_LEN: usize = 650;
+
+/// Deterministic stand in for a puzzle signal, its offset near the end
+/// of the repeated signal like a real one
+fn signal() -> Vec<i32> {
+    let mut state: u32 = 0x2545_f491;
+    let mut digits: Vec<i32> = (0..SIGNAL_LEN)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state % 10) as i32
+        })
+        .collect();
+    digits[..7].copy_from_slice(&[5, 9, 7, 5, 0, 0, 0]);
+    digits
+}
+
+fn bench_phase(c: &mut Criterion) {
+    let signal = signal();
+    let mut group = c.benchmark_group("phase");
+    group.bench_function("naive", |b| b.iter(|| phase_naive(black_box(&signal))));
+    group.bench_function("prefix_sums", |b| b.iter(|| phase(black_box(&signal))));
+    group.finish();
+}
+
+fn bench_embedded(c: &mut Criterion) {
+    let signal = signal();
+    let mut group = c.benchmark_group("embedded");
+    group.sample_size(10);
+    group.bench_function("tail_sums", |b| {
+        b.iter(|| embedded_message(black_box(&signal), REPEATS, PHASES))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_phase, bench_embedded);
+criterion_main!(benches);
@@ -0,0 +1,344 @@
+use std::fmt;
+
+use shared::Progress;
+use shared::solution::{Answer, Solution, answer};
+
+/// Phases applied in both parts
+pub const PHASES: usize = 100;
+/// Times the signal repeats in the real signal for part2
+pub const REPEATS: usize = 10_000;
+/// Digits in a message
+pub const MESSAGE_LEN: usize = 8;
+/// Leading digits of the signal giving the message offset
+pub const OFFSET_DIGITS: usize = 7;
+/// Repeating pattern the output digits are weighted by
+const BASE_PATTERN: [i32; 4] = [0, 1, 0, -1];
+
+/// Reasons a signal can't be processed
+#[derive(Debug, PartialEq)]
+pub enum Day16Error {
+    /// a character of the signal isn't a decimal digit
+    NotADigit { index: usize, c: char },
+    /// the signal is too short to hold what was asked of it
+    TooShort { needed: usize, len: usize },
+    /// the message lies in the first half of the real signal, where the
+    /// partial sums shortcut doesn't hold
+    OffsetTooSmall { offset: usize, len: usize },
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day16Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day16Error::NotADigit { index, c } => {
+                write!(f, "signal has {:?} at {}, not a digit", c, index)
+            }
+            Day16Error::TooShort { needed, len } => {
+                write!(f, "signal is {} digits, needed {}", len, needed)
+            }
+            Day16Error::OffsetTooSmall { offset, len } => write!(
+                f,
+                "offset {} is in the first half of a {} digit signal",
+                offset, len
+            ),
+            Day16Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day16Error {}
+
+/// Reads a signal of decimal digits
+///
+/// # Arguments
+///
+/// * `input` - digits with no separators
+///
+pub fn parse_signal(input: &str) -> Result<Vec<i32>, Day16Error> {
+    input
+        .trim()
+        .chars()
+        .enumerate()
+        .map(|(index, c)| {
+            c.to_digit(10)
+                .map(|d| d as i32)
+                .ok_or(Day16Error::NotADigit { index, c })
+        })
+        .collect()
+}
+
+/// Weight the pattern gives input `j` when computing output `i`
+fn weight(i: usize, j: usize) -> i32 {
+    BASE_PATTERN[((j + 1) / (i + 1)) % BASE_PATTERN.len()]
+}
+
+/// One phase computed straight from the pattern, quadratic in the
+/// signal length
+pub fn phase_naive(signal: &[i32]) -> Vec<i32> {
+    (0..signal.len())
+        .map(|i| {
+            let total: i32 = signal
+                .iter()
+                .enumerate()
+                .map(|(j, d)| d * weight(i, j))
+                .sum();
+            total.abs() % 10
+        })
+        .collect()
+}
+
+/// One phase computed from prefix sums
+///
+/// Output `i` repeats each pattern value `i + 1` times, so it only needs
+/// the sums of runs of that length. Summed over every output that's
+/// `n / 1 + n / 2 + ...` runs, or `O(n log n)` for the phase.
+pub fn phase(signal: &[i32]) -> Vec<i32> {
+    let mut prefix = Vec::with_capacity(signal.len() + 1);
+    prefix.push(0);
+    for d in signal {
+        prefix.push(prefix.last().unwrap() + d);
+    }
+    let run = |from: usize, len: usize| {
+        let to = (from + len).min(signal.len());
+        prefix[to] - prefix[from.min(to)]
+    };
+    (0..signal.len())
+        .map(|i| {
+            let len = i + 1;
+            // the first 1 in the pattern is at input i, after the skipped 0
+            let mut total = 0;
+            let mut start = i;
+            while start < signal.len() {
+                total += run(start, len);
+                total -= run(start + 2 * len, len);
+                start += 4 * len;
+            }
+            total.abs() % 10
+        })
+        .collect()
+}
+
+/// Applies phases to a whole signal
+///
+/// # Arguments
+///
+/// * `signal` - digits to transform
+/// * `phases` - number of phases to apply
+///
+pub fn fft(signal: &[i32], phases: usize) -> Vec<i32> {
+    (0..phases).fold(signal.to_vec(), |s, _| phase(&s))
+}
+
+/// One phase over the back half of a signal, in place
+///
+/// Past the middle every weight from output `i` onwards is 1, so each
+/// output is the sum of the digits after it, built from the end.
+pub fn tail_phase(tail: &mut [i32]) {
+    let mut sum = 0;
+    for d in tail.iter_mut().rev() {
+        sum = (sum + *d) % 10;
+        *d = sum;
+    }
+}
+
+/// Digits of a signal as text
+fn digits(signal: &[i32]) -> String {
+    signal.iter().map(|d| d.to_string()).collect()
+}
+
+/// First message digits of the signal after the phases
+///
+/// # Arguments
+///
+/// * `signal` - digits to transform
+/// * `phases` - number of phases to apply
+///
+pub fn first_message(signal: &[i32], phases: usize) -> Result<String, Day16Error> {
+    if signal.len() < MESSAGE_LEN {
+        return Err(Day16Error::TooShort {
+            needed: MESSAGE_LEN,
+            len: signal.len(),
+        });
+    }
+    Ok(digits(&fft(signal, phases)[..MESSAGE_LEN]))
+}
+
+/// Message embedded in the real signal, the input repeated many times
+///
+/// The offset is taken from the leading digits. Only digits from the
+/// offset onwards affect the message, and in the back half of the signal
+/// those are plain suffix sums, so the repeated signal is never
+/// transformed in full.
+///
+/// # Arguments
+///
+/// * `signal` - digits to transform
+/// * `repeats` - times the signal repeats
+/// * `phases` - number of phases to apply
+///
+pub fn embedded_message(
+    signal: &[i32],
+    repeats: usize,
+    phases: usize,
+) -> Result<String, Day16Error> {
+    if signal.len() < OFFSET_DIGITS {
+        return Err(Day16Error::TooShort {
+            needed: OFFSET_DIGITS,
+            len: signal.len(),
+        });
+    }
+    let offset = signal[..OFFSET_DIGITS]
+        .iter()
+        .fold(0, |acc, &d| acc * 10 + d as usize);
+    let len = signal.len() * repeats;
+    if offset < len / 2 {
+        return Err(Day16Error::OffsetTooSmall { offset, len });
+    }
+    if offset + MESSAGE_LEN > len {
+        return Err(Day16Error::TooShort {
+            needed: offset + MESSAGE_LEN,
+            len,
+        });
+    }
+    let mut tail: Vec<i32> = (offset..len).map(|i| signal[i % signal.len()]).collect();
+    let progress = Progress::auto("phases", phases as u64);
+    for _ in 0..phases {
+        tail_phase(&mut tail);
+        progress.tick();
+    }
+    progress.finish();
+    Ok(digits(&tail[..MESSAGE_LEN]))
+}
+
+/// Reads the signal from the first line of a file
+///
+/// # Arguments
+///
+/// * `filename` - path of file containing the signal
+///
+pub fn load_signal(filename: &str) -> Result<Vec<i32>, Day16Error> {
+    let input = shared::try_ingest_file(filename).map_err(|err| Day16Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })?;
+    parse_signal(input.first().map_or("", |s| s))
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * first eight digits after 100 phases
+///
+pub fn part1(filename: &str) -> Result<String, Day16Error> {
+    first_message(&load_signal(filename)?, PHASES)
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * eight digit message embedded in the real signal
+///
+pub fn part2(filename: &str) -> Result<String, Day16Error> {
+    embedded_message(&load_signal(filename)?, REPEATS, PHASES)
+}
+
+/// Day 16 as driven by the workspace runner
+pub struct Day16;
+
+impl Solution for Day16 {
+    fn day(&self) -> u8 {
+        16
+    }
+
+    fn title(&self) -> &'static str {
+        "Flawed Frequency Transmission"
+    }
+
+    fn long_running(&self, part: u8) -> bool {
+        part == 2
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(s: &str) -> Vec<i32> {
+        parse_signal(s).unwrap()
+    }
+
+    #[test]
+    fn part1_works() {
+        assert_eq!(part1("src/test.txt"), Ok(String::from("24176176")));
+        for (input, message) in [
+            ("19617804207202209144916044189917", "73745418"),
+            ("69317163492948606335995924319873", "52432133"),
+        ] {
+            assert_eq!(first_message(&signal(input), PHASES), Ok(message.into()));
+        }
+    }
+
+    #[test]
+    fn part2_works() {
+        assert_eq!(part2("src/test2.txt"), Ok(String::from("84462026")));
+        for (input, message) in [
+            ("02935109699940807407585447034323", "78725270"),
+            ("03081770884921959731165446850517", "53553731"),
+        ] {
+            assert_eq!(
+                embedded_message(&signal(input), REPEATS, PHASES),
+                Ok(message.into())
+            );
+        }
+    }
+
+    #[test]
+    fn phases_step_by_step() {
+        let mut s = signal("12345678");
+        for expected in ["48226158", "34040438", "03415518", "01029498"] {
+            assert_eq!(phase_naive(&s), signal(expected));
+            s = phase(&s);
+            assert_eq!(s, signal(expected));
+        }
+    }
+
+    #[test]
+    fn phase_matches_naive() {
+        let s: Vec<i32> = (0..97).map(|i| (i * 7 + i / 3) % 10).collect();
+        assert_eq!(phase(&s), phase_naive(&s));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse_signal("12a4"),
+            Err(Day16Error::NotADigit { index: 2, c: 'a' })
+        );
+        assert_eq!(
+            first_message(&signal("123"), 1),
+            Err(Day16Error::TooShort { needed: 8, len: 3 })
+        );
+        assert_eq!(
+            embedded_message(&signal("00000010"), 2, 1),
+            Err(Day16Error::OffsetTooSmall { offset: 1, len: 16 })
+        );
+        assert_eq!(
+            embedded_message(&signal("00000150"), 2, 1),
+            Err(Day16Error::TooShort {
+                needed: 23,
+                len: 16
+            })
+        );
+    }
+}
@@ -0,0 +1,512 @@
+use std::fmt;
+
+use shared::grid::{Grid, GridError};
+use shared::intcode::{IntcodeError, Vm, ascii, parse_program};
+use shared::solution::{Answer, Solution, answer};
+
+/// Position on the camera view, x to the right and y downwards
+pub type Position = (i64, i64);
+
+/// Longest line the movement logic accepts, newline excluded
+pub const MAX_LINE: usize = 20;
+/// Number of movement functions the robot can store
+pub const FUNCTIONS: usize = 3;
+
+/// Reasons the scaffolding can't be surveyed or traversed
+#[derive(Debug, PartialEq)]
+pub enum Day17Error {
+    /// the ASCII program faulted
+    Intcode(IntcodeError),
+    /// the camera view isn't a rectangle of known characters
+    Grid(GridError),
+    /// the camera output a value that isn't an ASCII code
+    NotAscii(i64),
+    /// the camera view has no robot, or more than one
+    NoRobot,
+    /// the path can't be split into the movement functions
+    Incompressible(String),
+    /// the robot finished without reporting any dust, showing this instead
+    NoDust(String),
+    /// the program is empty so there's nowhere to wake the robot
+    EmptyProgram,
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day17Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day17Error::Intcode(err) => write!(f, "{}", err),
+            Day17Error::Grid(err) => write!(f, "{}", err),
+            Day17Error::NotAscii(value) => write!(f, "camera output {}, not ASCII", value),
+            Day17Error::NoRobot => write!(f, "camera shows no single robot"),
+            Day17Error::Incompressible(path) => {
+                write!(f, "path doesn't fit the movement functions: {}", path)
+            }
+            Day17Error::NoDust(text) => write!(f, "robot reported no dust:\n{}", text),
+            Day17Error::EmptyProgram => write!(f, "program is empty"),
+            Day17Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day17Error {}
+
+impl From<IntcodeError> for Day17Error {
+    fn from(err: IntcodeError) -> Self {
+        Day17Error::Intcode(err)
+    }
+}
+
+impl From<GridError> for Day17Error {
+    fn from(err: GridError) -> Self {
+        Day17Error::Grid(err)
+    }
+}
+
+/// Way the robot is facing
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Heading {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Heading {
+    /// Heading drawn by a camera character
+    pub fn from_char(c: char) -> Option<Heading> {
+        match c {
+            '^' => Some(Heading::Up),
+            '>' => Some(Heading::Right),
+            'v' => Some(Heading::Down),
+            '<' => Some(Heading::Left),
+            _ => None,
+        }
+    }
+
+    fn delta(&self) -> Position {
+        match self {
+            Heading::Up => (0, -1),
+            Heading::Right => (1, 0),
+            Heading::Down => (0, 1),
+            Heading::Left => (-1, 0),
+        }
+    }
+
+    fn turned(&self, turn: Turn) -> Heading {
+        const CLOCKWISE: [Heading; 4] = [Heading::Up, Heading::Right, Heading::Down, Heading::Left];
+        let i = CLOCKWISE.iter().position(|h| h == self).unwrap();
+        match turn {
+            Turn::Right => CLOCKWISE[(i + 1) % 4],
+            Turn::Left => CLOCKWISE[(i + 3) % 4],
+        }
+    }
+}
+
+/// Direction of a turn on the spot
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+/// Turn followed by a straight run, written `R,8`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Move {
+    pub turn: Turn,
+    pub distance: usize,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let turn = match self.turn {
+            Turn::Left => 'L',
+            Turn::Right => 'R',
+        };
+        write!(f, "{},{}", turn, self.distance)
+    }
+}
+
+/// Moves written as the robot reads them
+pub fn movement_line(moves: &[Move]) -> String {
+    moves
+        .iter()
+        .map(Move::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Scaffolding and the robot on it as seen by the camera
+#[derive(Debug, PartialEq)]
+pub struct Scaffold {
+    grid: Grid<bool>,
+    robot: Position,
+    heading: Heading,
+}
+
+impl Scaffold {
+    /// Constructor for a Scaffold
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - camera view, `#` scaffold, `.` space and `^>v<` the
+    ///   robot standing on scaffold
+    ///
+    pub fn new<S: AsRef<str>>(lines: &[S]) -> Result<Scaffold, Day17Error> {
+        let view = Grid::parse(lines, |c| match c {
+            '#' | '.' | 'X' => Some(c),
+            c => Heading::from_char(c).map(|_| c),
+        })?;
+        let robots: Vec<_> = view
+            .iter()
+            .filter_map(|((x, y), &c)| Some(((x as i64, y as i64), Heading::from_char(c)?)))
+            .collect();
+        let [(robot, heading)] = robots[..] else {
+            return Err(Day17Error::NoRobot);
+        };
+        Ok(Scaffold {
+            grid: view.map(|&c| c != '.' && c != 'X'),
+            robot,
+            heading,
+        })
+    }
+
+    /// Whether there is scaffold at a position
+    fn is_scaffold(&self, (x, y): Position) -> bool {
+        self.grid.contains(x, y) && self.grid.get(x as usize, y as usize) == Some(&true)
+    }
+
+    /// Scaffold positions with scaffold on all four sides
+    pub fn intersections(&self) -> Vec<Position> {
+        self.grid
+            .iter()
+            .map(|((x, y), _)| (x as i64, y as i64))
+            .filter(|&(x, y)| {
+                [(0, 0), (0, -1), (1, 0), (0, 1), (-1, 0)]
+                    .iter()
+                    .all(|(dx, dy)| self.is_scaffold((x + dx, y + dy)))
+            })
+            .collect()
+    }
+
+    /// Sum of `x * y` over every intersection
+    pub fn alignment_sum(&self) -> i64 {
+        self.intersections().iter().map(|(x, y)| x * y).sum()
+    }
+
+    /// Route covering the scaffold from the robot
+    ///
+    /// The robot goes straight for as long as it can, including across
+    /// intersections, and turns only at corners. It stops at the first dead
+    /// end that isn't where it started.
+    ///
+    /// # Returns
+    ///
+    /// * turns and runs in order
+    ///
+    pub fn path(&self) -> Vec<Move> {
+        let (mut position, mut heading) = (self.robot, self.heading);
+        let ahead = |(x, y): Position, h: Heading| (x + h.delta().0, y + h.delta().1);
+        let mut moves = Vec::new();
+        while let Some(turn) = [Turn::Left, Turn::Right]
+            .into_iter()
+            .find(|&t| self.is_scaffold(ahead(position, heading.turned(t))))
+        {
+            heading = heading.turned(turn);
+            let mut distance = 0;
+            while self.is_scaffold(ahead(position, heading)) {
+                position = ahead(position, heading);
+                distance += 1;
+            }
+            moves.push(Move { turn, distance });
+        }
+        moves
+    }
+}
+
+/// Path split into a main routine calling movement functions
+#[derive(Debug, PartialEq)]
+pub struct Routines {
+    /// indices of the functions called, in order
+    pub main: Vec<usize>,
+    /// moves of each function
+    pub functions: Vec<Vec<Move>>,
+}
+
+impl Routines {
+    /// Main routine as the robot reads it, `A,B,A`
+    pub fn main_line(&self) -> String {
+        self.main
+            .iter()
+            .map(|&i| char::from(b'A' + i as u8).to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Moves the routines make when run
+    pub fn expand(&self) -> Vec<Move> {
+        self.main
+            .iter()
+            .flat_map(|&i| self.functions[i].iter().copied())
+            .collect()
+    }
+
+    /// Everything the robot asks for, one line each
+    ///
+    /// # Arguments
+    ///
+    /// * `video` - whether to ask for the continuous video feed
+    ///
+    pub fn input(&self, video: bool) -> String {
+        let mut lines = vec![self.main_line()];
+        lines.extend((0..FUNCTIONS).map(|i| {
+            self.functions
+                .get(i)
+                .map_or(String::new(), |f| movement_line(f))
+        }));
+        lines.push(String::from(if video { "y" } else { "n" }));
+        lines.iter().map(|l| format!("{}\n", l)).collect()
+    }
+}
+
+/// Splits a path into a main routine and up to three functions, every
+/// line short enough for the robot's memory
+///
+/// Tries each length for the function needed at the first move not yet
+/// covered, reusing any function that already matches there.
+///
+/// # Arguments
+///
+/// * `path` - moves to cover
+///
+/// # Returns
+///
+/// * routines reproducing the path, or none if they can't fit
+///
+pub fn compress(path: &[Move]) -> Option<Routines> {
+    fn search(path: &[Move], functions: &mut Vec<Vec<Move>>, main: &mut Vec<usize>) -> bool {
+        if path.is_empty() {
+            return true;
+        }
+        if 2 * main.len() + 1 > MAX_LINE {
+            return false;
+        }
+        for i in 0..functions.len() {
+            if path.starts_with(&functions[i]) {
+                main.push(i);
+                let len = functions[i].len();
+                if search(&path[len..], functions, main) {
+                    return true;
+                }
+                main.pop();
+            }
+        }
+        if functions.len() < FUNCTIONS {
+            for len in 1..=path.len() {
+                if movement_line(&path[..len]).len() > MAX_LINE {
+                    break;
+                }
+                functions.push(path[..len].to_vec());
+                main.push(functions.len() - 1);
+                if search(&path[len..], functions, main) {
+                    return true;
+                }
+                main.pop();
+                functions.pop();
+            }
+        }
+        false
+    }
+
+    let (mut functions, mut main) = (Vec::new(), Vec::new());
+    search(path, &mut functions, &mut main).then_some(Routines { main, functions })
+}
+
+/// Reads the camera through the ASCII adapter
+///
+/// # Arguments
+///
+/// * `program` - ASCII software
+///
+/// # Returns
+///
+/// * non-empty lines of the camera view
+///
+pub fn camera(program: &[i64]) -> Result<Vec<String>, Day17Error> {
+    let mut vm = Vm::new(program.to_vec());
+    vm.run()?;
+    Ok(decode(vm.output())?
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Output as text, or the first value that isn't ASCII
+fn decode(output: &[i64]) -> Result<String, Day17Error> {
+    ascii(output).ok_or_else(|| {
+        Day17Error::NotAscii(*output.iter().find(|&&v| ascii(&[v]).is_none()).unwrap())
+    })
+}
+
+/// Wakes the robot and has it walk the whole scaffold
+///
+/// # Arguments
+///
+/// * `program` - ASCII software
+/// * `routines` - movement logic to run
+///
+/// # Returns
+///
+/// * dust collected, the one value output that isn't ASCII
+///
+pub fn collect_dust(program: &[i64], routines: &Routines) -> Result<i64, Day17Error> {
+    let mut program = program.to_vec();
+    *program.first_mut().ok_or(Day17Error::EmptyProgram)? = 2;
+    let mut vm = Vm::new(program);
+    vm.push_ascii(&routines.input(false));
+    vm.run()?;
+    match vm.output().split_last() {
+        Some((&dust, _)) if ascii(&[dust]).is_none() => Ok(dust),
+        _ => Err(Day17Error::NoDust(decode(vm.output())?)),
+    }
+}
+
+/// Reads a program from the first line of a file
+///
+/// # Arguments
+///
+/// * `filename` - path of file containing the intcode
+///
+pub fn load_program(filename: &str) -> Result<Vec<i64>, Day17Error> {
+    let input = shared::try_ingest_file(filename).map_err(|err| Day17Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })?;
+    Ok(parse_program(input.first().map_or("", |s| s))?)
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * sum of the alignment parameters of every intersection
+///
+pub fn part1(filename: &str) -> Result<i64, Day17Error> {
+    let view = camera(&load_program(filename)?)?;
+    Ok(Scaffold::new(&view)?.alignment_sum())
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * dust collected walking the whole scaffold
+///
+pub fn part2(filename: &str) -> Result<i64, Day17Error> {
+    let program = load_program(filename)?;
+    let path = Scaffold::new(&camera(&program)?)?.path();
+    let routines =
+        compress(&path).ok_or_else(|| Day17Error::Incompressible(movement_line(&path)))?;
+    collect_dust(&program, &routines)
+}
+
+/// Day 17 as driven by the workspace runner
+pub struct Day17;
+
+impl Solution for Day17 {
+    fn day(&self) -> u8 {
+        17
+    }
+
+    fn title(&self) -> &'static str {
+        "Set and Forget"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(filename: &str) -> Scaffold {
+        Scaffold::new(&shared::ingest_file(filename)).unwrap()
+    }
+
+    #[test]
+    fn alignment_works() {
+        let scaffold = load("src/test.txt");
+        assert_eq!(
+            scaffold.intersections(),
+            vec![(2, 2), (2, 4), (6, 4), (10, 4)]
+        );
+        assert_eq!(scaffold.alignment_sum(), 76);
+    }
+
+    #[test]
+    fn path_works() {
+        let path = load("src/test2.txt").path();
+        assert_eq!(
+            movement_line(&path),
+            "R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2"
+        );
+    }
+
+    #[test]
+    fn compress_works() {
+        let path = load("src/test2.txt").path();
+        let routines = compress(&path).unwrap();
+        assert_eq!(routines.expand(), path);
+        assert!(routines.functions.len() <= FUNCTIONS);
+        let input = routines.input(false);
+        assert!(input.lines().all(|l| l.len() <= MAX_LINE));
+        assert_eq!(input.lines().count(), 5);
+        assert!(input.ends_with("\nn\n"));
+
+        let long = vec![
+            Move {
+                turn: Turn::Left,
+                distance: 10
+            };
+            11
+        ];
+        // eleven calls would overflow the main routine
+        let routines = compress(&long).unwrap();
+        assert_eq!(routines.main_line(), "A,A,A,A,A,A,A,A,A,B");
+        let varied: Vec<Move> = (1..=40)
+            .map(|distance| Move {
+                turn: Turn::Right,
+                distance,
+            })
+            .collect();
+        assert_eq!(compress(&varied), None);
+    }
+
+    #[test]
+    fn camera_reads_ascii() {
+        // prints "#^\n"
+        let program = parse_program("104,35,104,94,104,10,99").unwrap();
+        assert_eq!(camera(&program), Ok(vec![String::from("#^")]));
+        // prints "#" then a value past ASCII
+        let program = parse_program("104,35,104,200,99").unwrap();
+        assert_eq!(camera(&program), Err(Day17Error::NotAscii(200)));
+    }
+
+    #[test]
+    fn new_needs_one_robot() {
+        assert_eq!(Scaffold::new(&["#.#"]), Err(Day17Error::NoRobot));
+        assert_eq!(Scaffold::new(&["^.v"]), Err(Day17Error::NoRobot));
+        assert!(matches!(
+            Scaffold::new(&["#?"]),
+            Err(Day17Error::Grid(GridError::BadCell { .. }))
+        ));
+    }
+}
@@ -0,0 +1,33 @@
+use std::process::ExitCode;
+
+use clap::Parser;
+use day17::{Day17Error, part1, part2};
+use shared::args::DayArgs;
+
+type Solver = fn(&str) -> Result<i64, Day17Error>;
+
+/// Vacuum robot surveying and sweeping the scaffolding
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: DayArgs,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let parts: [(u8, Solver); 2] = [(1, part1), (2, part2)];
+    let mut code = ExitCode::SUCCESS;
+    for (part, solve) in parts {
+        if !cli.args.wants(part) {
+            continue;
+        }
+        match solve(&cli.args.input) {
+            Ok(answer) => println!("Part{}: {}", part, answer),
+            Err(err) => {
+                eprintln!("Part{}: {}", part, err);
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+    code
+}
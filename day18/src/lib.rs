@@ -0,0 +1,463 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use shared::Progress;
+use shared::graph::{bfs, dijkstra};
+use shared::grid::{Grid, GridError};
+use shared::solution::{Answer, Solution, answer};
+
+/// Position in the vault, x to the right and y downwards
+pub type Position = (usize, usize);
+
+/// Set of keys, bit `n` standing for the `n`th letter
+pub type Keys = u32;
+
+/// Most letters a key or door can use
+const LETTERS: usize = 26;
+
+/// Reasons the vault can't be cleared
+#[derive(Debug, PartialEq)]
+pub enum Day18Error {
+    /// the map isn't a rectangle of known characters
+    Grid(GridError),
+    /// the map has no entrance
+    NoEntrance,
+    /// the map has the same key more than once
+    DuplicateKey(char),
+    /// the entrance can't be walled off into four
+    CantSplit,
+    /// no order of moves collects every key
+    Unreachable,
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day18Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day18Error::Grid(err) => write!(f, "{}", err),
+            Day18Error::NoEntrance => write!(f, "vault has no entrance"),
+            Day18Error::DuplicateKey(c) => write!(f, "key {} appears twice", c),
+            Day18Error::CantSplit => write!(f, "need one entrance in open space to split"),
+            Day18Error::Unreachable => write!(f, "not every key can be collected"),
+            Day18Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day18Error {}
+
+impl From<GridError> for Day18Error {
+    fn from(err: GridError) -> Self {
+        Day18Error::Grid(err)
+    }
+}
+
+/// What fills a position in the vault
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tile {
+    Wall,
+    Open,
+    Entrance,
+    /// key numbered by its letter
+    Key(u8),
+    /// door numbered by the letter of the key opening it
+    Door(u8),
+}
+
+impl Tile {
+    fn from_char(c: char) -> Option<Tile> {
+        match c {
+            '#' => Some(Tile::Wall),
+            '.' => Some(Tile::Open),
+            '@' => Some(Tile::Entrance),
+            'a'..='z' => Some(Tile::Key(c as u8 - b'a')),
+            'A'..='Z' => Some(Tile::Door(c as u8 - b'A')),
+            _ => None,
+        }
+    }
+}
+
+/// Route from a point of interest to a key
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Route {
+    /// key at the end
+    pub key: u8,
+    /// steps taken
+    pub steps: usize,
+    /// doors passed through
+    pub doors: Keys,
+    /// keys passed over and so picked up, the destination included
+    pub collects: Keys,
+}
+
+/// Underground vault of keys and doors
+#[derive(Debug)]
+pub struct Vault {
+    grid: Grid<Tile>,
+}
+
+impl Vault {
+    /// Constructor for a Vault
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - map, `#` wall, `.` open, `@` entrance, lowercase keys
+    ///   and uppercase doors
+    ///
+    pub fn new<S: AsRef<str>>(lines: &[S]) -> Result<Vault, Day18Error> {
+        let grid = Grid::parse(lines, Tile::from_char)?;
+        let mut seen: Keys = 0;
+        for (_, tile) in grid.iter() {
+            if let Tile::Key(k) = tile {
+                if seen & 1 << k != 0 {
+                    return Err(Day18Error::DuplicateKey(char::from(b'a' + k)));
+                }
+                seen |= 1 << k;
+            }
+        }
+        Ok(Vault { grid })
+    }
+
+    /// Positions of every entrance in reading order
+    pub fn entrances(&self) -> Vec<Position> {
+        self.grid
+            .iter()
+            .filter(|&(_, &t)| t == Tile::Entrance)
+            .map(|(p, _)| p)
+            .collect()
+    }
+
+    /// Every key in the vault
+    pub fn keys(&self) -> Keys {
+        self.grid.iter().fold(0, |keys, (_, t)| match t {
+            Tile::Key(k) => keys | 1 << k,
+            _ => keys,
+        })
+    }
+
+    /// Walls off the single entrance into four, one per quadrant
+    ///
+    /// ```text
+    /// ...      @#@
+    /// .@.  =>  ###
+    /// ...      @#@
+    /// ```
+    pub fn split(&mut self) -> Result<(), Day18Error> {
+        let [(x, y)] = self.entrances()[..] else {
+            return Err(Day18Error::CantSplit);
+        };
+        let around = |dx: usize, dy: usize| (x + dx).checked_sub(1).zip((y + dy).checked_sub(1));
+        let cells: Vec<Position> = (0..3)
+            .flat_map(|dy| (0..3).map(move |dx| (dx, dy)))
+            .map(|(dx, dy)| around(dx, dy).ok_or(Day18Error::CantSplit))
+            .collect::<Result<_, _>>()?;
+        if cells
+            .iter()
+            .any(|&(cx, cy)| !matches!(self.grid.get(cx, cy), Some(Tile::Open | Tile::Entrance)))
+        {
+            return Err(Day18Error::CantSplit);
+        }
+        for (i, &(cx, cy)) in cells.iter().enumerate() {
+            let corner = i % 2 == 0 && i != 4;
+            self.grid
+                .set(cx, cy, if corner { Tile::Entrance } else { Tile::Wall });
+        }
+        Ok(())
+    }
+
+    /// Open positions one step from `position`
+    fn steps_from(&self, (x, y): Position) -> impl Iterator<Item = Position> + '_ {
+        self.grid
+            .neighbours(x, y)
+            .filter(|&(nx, ny)| self.grid.get(nx, ny) != Some(&Tile::Wall))
+    }
+
+    /// Shortest route from `start` to every key it can reach
+    ///
+    /// Each route keeps the doors and keys along the way, worked out by
+    /// walking back along the search's parent links. Vault corridors don't
+    /// loop, so the shortest route is the only one worth taking.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - position to set out from
+    ///
+    pub fn routes_from(&self, start: Position) -> Vec<Route> {
+        let distances = bfs(start, |&p| self.steps_from(p).collect::<Vec<_>>());
+        let mut routes = Vec::new();
+        for (&position, &steps) in &distances {
+            let Some(&Tile::Key(key)) = self.grid.get(position.0, position.1) else {
+                continue;
+            };
+            if position == start {
+                continue;
+            }
+            let (mut doors, mut collects) = (0, 0);
+            let mut at = position;
+            while at != start {
+                match self.grid.get(at.0, at.1) {
+                    Some(Tile::Door(d)) => doors |= 1 << d,
+                    Some(Tile::Key(k)) => collects |= 1 << k,
+                    _ => {}
+                }
+                let here = distances[&at];
+                at = self
+                    .steps_from(at)
+                    .find(|p| distances.get(p) == Some(&(here - 1)))
+                    .expect("bfs leaves a path back to the start");
+            }
+            routes.push(Route {
+                key,
+                steps,
+                doors,
+                collects,
+            });
+        }
+        routes.sort_by_key(|r| r.key);
+        routes
+    }
+
+    /// Fewest steps for the robots, one per entrance, to collect every key
+    ///
+    /// Only one robot moves at a time, always straight to a key it can
+    /// reach, so the search runs over which key each robot stands on and
+    /// which keys have been collected. Progress counts the most keys any
+    /// state searched so far holds.
+    ///
+    /// # Returns
+    ///
+    /// * total steps, or why the keys can't all be collected
+    ///
+    pub fn collect_all(&self) -> Result<usize, Day18Error> {
+        let entrances = self.entrances();
+        if entrances.is_empty() {
+            return Err(Day18Error::NoEntrance);
+        }
+        // points of interest: keys by letter, then entrances
+        let mut routes: HashMap<usize, Vec<Route>> = HashMap::new();
+        for (i, &entrance) in entrances.iter().enumerate() {
+            routes.insert(LETTERS + i, self.routes_from(entrance));
+        }
+        for (position, tile) in self.grid.iter() {
+            if let &Tile::Key(k) = tile {
+                routes.insert(k as usize, self.routes_from(position));
+            }
+        }
+        let all = self.keys();
+        let start: (Vec<usize>, Keys) = ((LETTERS..LETTERS + entrances.len()).collect(), 0);
+        let progress = Progress::auto("keys", all.count_ones() as u64);
+        let steps = dijkstra(
+            start,
+            |(_, keys)| {
+                progress.reach(keys.count_ones() as u64);
+                *keys == all
+            },
+            |(robots, keys)| {
+                let mut next = Vec::new();
+                for (i, at) in robots.iter().enumerate() {
+                    for route in &routes[at] {
+                        if keys & 1 << route.key != 0 || route.doors & !keys != 0 {
+                            continue;
+                        }
+                        let mut moved = robots.clone();
+                        moved[i] = route.key as usize;
+                        next.push(((moved, keys | route.collects), route.steps));
+                    }
+                }
+                next
+            },
+        );
+        progress.finish();
+        steps.ok_or(Day18Error::Unreachable)
+    }
+}
+
+/// Reads the lines of the puzzle input
+///
+/// # Arguments
+///
+/// * `filename` - path of the input
+///
+fn read_input(filename: &str) -> Result<Vec<String>, Day18Error> {
+    shared::try_ingest_file(filename).map_err(|err| Day18Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * fewest steps to collect every key
+///
+pub fn part1(filename: &str) -> Result<usize, Day18Error> {
+    Vault::new(&read_input(filename)?)?.collect_all()
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * fewest steps for four robots to collect every key
+///
+pub fn part2(filename: &str) -> Result<usize, Day18Error> {
+    let mut vault = Vault::new(&read_input(filename)?)?;
+    if vault.entrances().len() == 1 {
+        vault.split()?;
+    }
+    vault.collect_all()
+}
+
+/// Day 18 as driven by the workspace runner
+pub struct Day18;
+
+impl Solution for Day18 {
+    fn day(&self) -> u8 {
+        18
+    }
+
+    fn title(&self) -> &'static str {
+        "Many-Worlds Interpretation"
+    }
+
+    fn long_running(&self, _part: u8) -> bool {
+        true
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps(lines: &[&str]) -> Result<usize, Day18Error> {
+        Vault::new(lines)?.collect_all()
+    }
+
+    fn split_steps(lines: &[&str]) -> Result<usize, Day18Error> {
+        let mut vault = Vault::new(lines)?;
+        vault.split()?;
+        vault.collect_all()
+    }
+
+    #[test]
+    fn part1_works() {
+        assert_eq!(steps(&["#########", "#b.A.@.a#", "#########"]), Ok(8));
+        assert_eq!(
+            steps(&[
+                "########################",
+                "#f.D.E.e.C.b.A.@.a.B.c.#",
+                "######################.#",
+                "#d.....................#",
+                "########################",
+            ]),
+            Ok(86)
+        );
+        assert_eq!(
+            steps(&[
+                "########################",
+                "#...............b.C.D.f#",
+                "#.######################",
+                "#.....@.a.B.c.d.A.e.F.g#",
+                "########################",
+            ]),
+            Ok(132)
+        );
+        assert_eq!(
+            steps(&[
+                "########################",
+                "#@..............ac.GI.b#",
+                "###d#e#f################",
+                "###A#B#C################",
+                "###g#h#i################",
+                "########################",
+            ]),
+            Ok(81)
+        );
+        assert_eq!(part1("src/test.txt"), Ok(136));
+    }
+
+    #[test]
+    fn part2_works() {
+        assert_eq!(
+            split_steps(&[
+                "#######", "#a.#Cd#", "##...##", "##.@.##", "##...##", "#cB#Ab#", "#######",
+            ]),
+            Ok(8)
+        );
+        assert_eq!(
+            split_steps(&[
+                "###############",
+                "#d.ABC.#.....a#",
+                "######...######",
+                "######.@.######",
+                "######...######",
+                "#b.....#.....c#",
+                "###############",
+            ]),
+            Ok(24)
+        );
+        assert_eq!(
+            split_steps(&[
+                "#############",
+                "#DcBa.#.GhKl#",
+                "#.###...#I###",
+                "#e#d#.@.#j#k#",
+                "###C#...###J#",
+                "#fEbA.#.FgHi#",
+                "#############",
+            ]),
+            Ok(32)
+        );
+        assert_eq!(part2("src/test2.txt"), Ok(72));
+    }
+
+    #[test]
+    fn routes_record_doors_and_keys() {
+        let vault = Vault::new(&["#########", "#b.A.@.a#", "#########"]).unwrap();
+        let routes = vault.routes_from((5, 1));
+        assert_eq!(
+            routes,
+            vec![
+                Route {
+                    key: 0,
+                    steps: 2,
+                    doors: 0,
+                    collects: 1
+                },
+                Route {
+                    key: 1,
+                    steps: 4,
+                    doors: 1,
+                    collects: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn split_walls_off_entrance() {
+        let mut vault = Vault::new(&["#####", "#...#", "#.@.#", "#...#", "#####"]).unwrap();
+        vault.split().unwrap();
+        assert_eq!(vault.entrances(), vec![(1, 1), (3, 1), (1, 3), (3, 3)]);
+        assert_eq!(vault.split(), Err(Day18Error::CantSplit));
+        let mut cramped = Vault::new(&["###", "#@#", "###"]).unwrap();
+        assert_eq!(cramped.split(), Err(Day18Error::CantSplit));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(steps(&["#a.A#"]), Err(Day18Error::NoEntrance));
+        assert_eq!(steps(&["#a@A#b#"]), Err(Day18Error::Unreachable));
+        assert_eq!(steps(&["a@a"]).unwrap_err(), Day18Error::DuplicateKey('a'));
+    }
+}
@@ -0,0 +1,285 @@
+use std::fmt;
+
+use shared::grid::Grid;
+use shared::intcode::{IntcodeError, Vm, parse_program, run_batch};
+use shared::solution::{Answer, Solution, answer};
+
+/// Position in front of the emitter, x to the right and y downwards
+pub type Position = (i64, i64);
+
+/// Side of the area scanned for part1
+pub const SCAN_SIZE: usize = 50;
+/// Side of Santa's ship, which has to fit in the beam for part2
+pub const SHIP_SIZE: i64 = 100;
+/// Furthest row searched before giving up on fitting a square
+pub const MAX_ROW: i64 = 100_000;
+
+/// Reasons the beam can't be surveyed
+#[derive(Debug, PartialEq)]
+pub enum Day19Error {
+    /// the drone program faulted
+    Intcode(IntcodeError),
+    /// the drone reported something other than a single 0 or 1
+    BadReport(Vec<i64>),
+    /// no square of the size fits before `MAX_ROW`
+    NoFit(i64),
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day19Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day19Error::Intcode(err) => write!(f, "{}", err),
+            Day19Error::BadReport(output) => write!(f, "drone reported {:?}", output),
+            Day19Error::NoFit(size) => {
+                write!(f, "no {0}x{0} square fits within {1} rows", size, MAX_ROW)
+            }
+            Day19Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day19Error {}
+
+impl From<IntcodeError> for Day19Error {
+    fn from(err: IntcodeError) -> Self {
+        Day19Error::Intcode(err)
+    }
+}
+
+/// Something that can say where the tractor beam pulls
+pub trait Beam {
+    /// Whether the beam pulls at a position
+    fn pulled(&self, position: Position) -> Result<bool, Day19Error>;
+
+    /// Whether the beam pulls at each of several positions
+    fn pulled_all(&self, positions: &[Position]) -> Result<Vec<bool>, Day19Error> {
+        positions.iter().map(|&p| self.pulled(p)).collect()
+    }
+}
+
+/// Beam as reported by drones the intcode program deploys, one fresh run
+/// per position
+#[derive(Debug)]
+pub struct DroneBeam {
+    program: Vec<i64>,
+}
+
+impl DroneBeam {
+    /// Constructor for a DroneBeam
+    ///
+    /// # Arguments
+    ///
+    /// * `program` - drone system software
+    ///
+    pub fn new(program: Vec<i64>) -> DroneBeam {
+        DroneBeam { program }
+    }
+}
+
+/// Reads a drone's single 0 or 1 report
+fn report(output: Vec<i64>) -> Result<bool, Day19Error> {
+    match output[..] {
+        [0] => Ok(false),
+        [1] => Ok(true),
+        _ => Err(Day19Error::BadReport(output)),
+    }
+}
+
+impl Beam for DroneBeam {
+    fn pulled(&self, (x, y): Position) -> Result<bool, Day19Error> {
+        let mut vm = Vm::new(self.program.clone());
+        vm.push_input(x);
+        vm.push_input(y);
+        vm.run()?;
+        report(vm.take_output())
+    }
+
+    /// Deploys every drone at once across threads
+    fn pulled_all(&self, positions: &[Position]) -> Result<Vec<bool>, Day19Error> {
+        let inputs: Vec<Vec<i64>> = positions.iter().map(|&(x, y)| vec![x, y]).collect();
+        run_batch(&self.program, &inputs)?
+            .into_iter()
+            .map(report)
+            .collect()
+    }
+}
+
+/// Maps the beam over a square area starting at the emitter
+///
+/// # Arguments
+///
+/// * `beam` - beam to survey
+/// * `size` - side of the area
+///
+pub fn scan<B: Beam>(beam: &B, size: usize) -> Result<Grid<bool>, Day19Error> {
+    let positions: Vec<Position> = (0..size as i64)
+        .flat_map(|y| (0..size as i64).map(move |x| (x, y)))
+        .collect();
+    let cells = beam.pulled_all(&positions)?;
+    Ok(Grid::from_cells(size.max(1), cells).unwrap_or_else(|| Grid::new(0, 0, false)))
+}
+
+/// Closest square of the given size entirely inside the beam
+///
+/// The beam widens as it goes, so the search follows its left edge down
+/// row by row. A square whose bottom left corner sits on that edge fits
+/// once its top right corner is also pulled. Rows near the emitter can
+/// miss the beam entirely, which just leaves the edge where it was.
+///
+/// # Arguments
+///
+/// * `beam` - beam to fit the square in
+/// * `size` - side of the square
+///
+/// # Returns
+///
+/// * top left corner of the square, or why none was found
+///
+pub fn fit_square<B: Beam>(beam: &B, size: i64) -> Result<Position, Day19Error> {
+    let size = size.max(1);
+    let mut left = 0;
+    for y in size - 1..MAX_ROW {
+        // the beam edge only moves right, and not by more than the row
+        let Some(x) = (left..=left + y + 1)
+            .map(|x| beam.pulled((x, y)).map(|p| p.then_some(x)))
+            .find_map(Result::transpose)
+            .transpose()?
+        else {
+            continue;
+        };
+        left = x;
+        if beam.pulled((x + size - 1, y + 1 - size))? {
+            return Ok((x, y + 1 - size));
+        }
+    }
+    Err(Day19Error::NoFit(size))
+}
+
+/// Reads a program from the first line of a file
+///
+/// # Arguments
+///
+/// * `filename` - path of file containing the intcode
+///
+pub fn load_program(filename: &str) -> Result<Vec<i64>, Day19Error> {
+    let input = shared::try_ingest_file(filename).map_err(|err| Day19Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })?;
+    Ok(parse_program(input.first().map_or("", |s| s))?)
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * points pulled in the 50x50 area nearest the emitter
+///
+pub fn part1(filename: &str) -> Result<i64, Day19Error> {
+    let beam = DroneBeam::new(load_program(filename)?);
+    Ok(scan(&beam, SCAN_SIZE)?.iter().filter(|&(_, &p)| p).count() as i64)
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * `10000 * x + y` of the top left corner of the closest 100x100 square
+///
+pub fn part2(filename: &str) -> Result<i64, Day19Error> {
+    let beam = DroneBeam::new(load_program(filename)?);
+    let (x, y) = fit_square(&beam, SHIP_SIZE)?;
+    Ok(10000 * x + y)
+}
+
+/// Day 19 as driven by the workspace runner
+pub struct Day19;
+
+impl Solution for Day19 {
+    fn day(&self) -> u8 {
+        19
+    }
+
+    fn title(&self) -> &'static str {
+        "Tractor Beam"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Beam between two lines through the emitter, `low` and `high`
+    /// giving each line's x per y as a fraction
+    struct Wedge {
+        low: (i64, i64),
+        high: (i64, i64),
+    }
+
+    impl Beam for Wedge {
+        fn pulled(&self, (x, y): Position) -> Result<bool, Day19Error> {
+            Ok(x * self.low.1 >= y * self.low.0 && x * self.high.1 <= y * self.high.0)
+        }
+    }
+
+    /// Top left corner of the first fitting square in reading order of
+    /// bottom rows, checking every cell
+    fn brute_force(beam: &Wedge, size: i64) -> Position {
+        (size - 1..)
+            .find_map(|bottom| {
+                let top = bottom + 1 - size;
+                (0..=bottom * 2).map(|x| (x, top)).find(|&(x, top)| {
+                    (top..=bottom).all(|y| (x..x + size).all(|x| beam.pulled((x, y)).unwrap()))
+                })
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn scan_counts_pulled_points() {
+        let beam = Wedge {
+            low: (1, 2),
+            high: (3, 4),
+        };
+        let grid = scan(&beam, 5).unwrap();
+        assert_eq!(grid.to_string(), "#\n\n #\n  #\n  ##\n");
+        assert_eq!(grid.iter().filter(|&(_, &p)| p).count(), 5);
+    }
+
+    #[test]
+    fn fit_square_matches_brute_force() {
+        for (low, high) in [((1, 2), (3, 4)), ((7, 10), (6, 5)), ((1, 1), (5, 3))] {
+            let beam = Wedge { low, high };
+            for size in [1, 2, 5, 10] {
+                assert_eq!(fit_square(&beam, size), Ok(brute_force(&beam, size)));
+            }
+        }
+    }
+
+    #[test]
+    fn drone_beam_runs_program() {
+        // pulled when x < y
+        let beam = DroneBeam::new(parse_program("3,20,3,21,7,20,21,22,4,22,99").unwrap());
+        assert_eq!(beam.pulled((1, 2)), Ok(true));
+        assert_eq!(beam.pulled((2, 2)), Ok(false));
+        let grid = scan(&beam, 3).unwrap();
+        assert_eq!(grid.to_string(), "\n#\n##\n");
+
+        let beam = DroneBeam::new(parse_program("104,2,99").unwrap());
+        assert_eq!(beam.pulled((0, 0)), Err(Day19Error::BadReport(vec![2])));
+        assert_eq!(
+            beam.pulled_all(&[(0, 0)]),
+            Err(Day19Error::BadReport(vec![2]))
+        );
+    }
+}
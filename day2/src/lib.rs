@@ -1,7 +1,111 @@
 use std::cmp;
+use std::fmt;
+use std::ops::Range;
 
+use rayon::prelude::*;
+use shared::Progress;
+use shared::intcode::apply_patches;
+use shared::solution::{Answer, Solution, answer};
+
+/// Reasons day2 can't produce an answer
+#[derive(Debug, PartialEq)]
+pub enum Day2Error {
+    /// no noun and verb in the searched ranges produce the target
+    NotFound {
+        target: u64,
+        nouns: Range<u64>,
+        verbs: Range<u64>,
+    },
+    /// a value in the program isn't a number
+    Malformed(String),
+    /// the input file can't be read as a program
+    Input { filename: String, reason: String },
+    /// the program faulted instead of halting
+    Fault { noun: u64, verb: u64 },
+}
+
+impl fmt::Display for Day2Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day2Error::NotFound {
+                target,
+                nouns,
+                verbs,
+            } => write!(
+                f,
+                "no noun in {:?} and verb in {:?} produce {}",
+                nouns, verbs, target
+            ),
+            Day2Error::Malformed(value) => write!(f, "{:?} isn't a value", value),
+            Day2Error::Input { filename, reason } => write!(f, "{}: {}", filename, reason),
+            Day2Error::Fault { noun, verb } => {
+                write!(f, "program faulted with noun {} and verb {}", noun, verb)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Day2Error {}
+
+/// Noun and verb producing a target, along with the puzzle answer
 #[derive(Debug, PartialEq)]
-struct IntCode {
+pub struct NounVerb {
+    pub noun: u64,
+    pub verb: u64,
+    /// 100 * noun + verb
+    pub answer: u64,
+}
+
+impl NounVerb {
+    /// Constructor for a NounVerb
+    ///
+    /// # Arguments
+    ///
+    /// * `noun` value placed at index 1
+    /// * `verb` value placed at index 2
+    ///
+    /// # Returns
+    ///
+    /// * pair with its answer filled in
+    ///
+    pub fn new(noun: u64, verb: u64) -> NounVerb {
+        NounVerb {
+            noun,
+            verb,
+            answer: 100 * noun + verb,
+        }
+    }
+}
+
+impl fmt::Display for NounVerb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "noun {} verb {}: {}", self.noun, self.verb, self.answer)
+    }
+}
+
+/// Address and the value written to it before a program runs
+pub type Patch = shared::intcode::Patch<u64>;
+
+/// Restores the gravity assist program to the "1202 program alarm" state
+pub const GRAVITY_ASSIST: [Patch; 2] = [(1, 12), (2, 2)];
+
+/// Patches placing a noun and verb at addresses 1 and 2
+///
+/// # Arguments
+///
+/// * `noun` value placed at address 1
+/// * `verb` value placed at address 2
+///
+/// # Returns
+///
+/// * patches to apply before running
+///
+pub fn noun_verb(noun: u64, verb: u64) -> [Patch; 2] {
+    [(1, noun), (2, verb)]
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntCode {
     code: Vec<u64>,
 }
 
@@ -41,6 +145,7 @@ impl IntCode {
         for i in &self.code[pos + 1..cmp::min(pos + 4, self.code.len())] {
             poss.push(*i as usize);
         }
+        log::trace!("{}: {} {:?}", pos, self.code[pos], poss);
         match self.code[pos] {
             1 => {
                 self.add(poss[0], poss[1], poss[2]);
@@ -64,7 +169,7 @@ impl IntCode {
     /// * `pos3` position in intcode to store result
     ///
     pub fn add(&mut self, pos1: usize, pos2: usize, pos3: usize) {
-        let result = &self.code[pos1] + &self.code[pos2];
+        let result = self.code[pos1] + self.code[pos2];
         self.code[pos3] = result;
     }
 
@@ -77,7 +182,7 @@ impl IntCode {
     /// * `pos3` position in intcode to store result
     ///
     pub fn mul(&mut self, pos1: usize, pos2: usize, pos3: usize) {
-        let result = &self.code[pos1] * &self.code[pos2];
+        let result = self.code[pos1] * self.code[pos2];
         self.code[pos3] = result;
     }
 
@@ -99,8 +204,164 @@ impl IntCode {
         }
         self.code[0]
     }
+
+    /// Performs operations until the program halts, without panicking on
+    /// a bad opcode, an address past the end or an overflowing result
+    ///
+    /// # Returns
+    ///
+    /// * value at index 0 after program completion, or none on a fault
+    ///
+    pub fn try_execute(&mut self) -> Option<u64> {
+        let mut i: usize = 0;
+        loop {
+            let op = *self.code.get(i)?;
+            if op == 99 {
+                return self.code.first().copied();
+            }
+            let params: [u64; 3] = self.code.get(i + 1..i + 4)?.try_into().ok()?;
+            let (a, b) = (
+                *self.code.get(params[0] as usize)?,
+                *self.code.get(params[1] as usize)?,
+            );
+            let result = match op {
+                1 => a.checked_add(b)?,
+                2 => a.checked_mul(b)?,
+                _ => return None,
+            };
+            *self.code.get_mut(params[2] as usize)? = result;
+            i += 4;
+        }
+    }
+}
+
+/// Runs a copy of a program with the given patches applied
+///
+/// # Arguments
+///
+/// * `program` intcode to run, left untouched
+/// * `patches` address and value pairs written before running
+///
+/// # Returns
+///
+/// * value at index 0 after program completion
+///
+fn run(program: &IntCode, patches: &[Patch]) -> u64 {
+    let mut ic = program.clone();
+    apply_patches(&mut ic.code, patches).unwrap();
+    ic.execute()
 }
 
+/// Runs a copy of a program with the given noun and verb, reporting a
+/// fault instead of panicking
+///
+/// # Arguments
+///
+/// * `program` intcode to run, left untouched
+/// * `noun` value placed at index 1
+/// * `verb` value placed at index 2
+///
+/// # Returns
+///
+/// * value at index 0 after program completion, or none on a fault
+///
+pub fn try_run(program: &IntCode, noun: u64, verb: u64) -> Option<u64> {
+    let mut ic = program.clone();
+    apply_patches(&mut ic.code, &noun_verb(noun, verb)).ok()?;
+    ic.try_execute()
+}
+
+/// Runs a copy of a program restored to the "1202 program alarm" state,
+/// reporting a fault instead of panicking
+///
+/// # Arguments
+///
+/// * `program` intcode to run, left untouched
+///
+/// # Returns
+///
+/// * value at index 0 after program completion, or the fault
+///
+pub fn gravity_assist(program: &IntCode) -> Result<u64, Day2Error> {
+    let (noun, verb) = (GRAVITY_ASSIST[0].1, GRAVITY_ASSIST[1].1);
+    try_run(program, noun, verb).ok_or(Day2Error::Fault { noun, verb })
+}
+
+/// Reads a program from the first line of a file
+///
+/// # Arguments
+///
+/// * `filename` path of file containing the intcode
+///
+/// # Returns
+///
+/// * initialized intcode object
+///
+pub fn load_program(filename: &str) -> IntCode {
+    let mut input = shared::ingest_file(filename);
+    IntCode::new(input.swap_remove(0))
+}
+
+/// Reads a program from the first line of a file, reporting what's wrong
+/// with the file instead of panicking
+///
+/// # Arguments
+///
+/// * `filename` path of file containing the intcode
+///
+/// # Returns
+///
+/// * initialized intcode object, or why the file isn't a program
+///
+pub fn try_load_program(filename: &str) -> Result<IntCode, Day2Error> {
+    let input = |reason: String| Day2Error::Input {
+        filename: String::from(filename),
+        reason,
+    };
+    let lines = shared::try_ingest_file(filename).map_err(|err| input(err.to_string()))?;
+    let line = lines
+        .first()
+        .ok_or_else(|| input(String::from("no program")))?;
+    try_parse_program(line).map_err(|err| input(err.to_string()))
+}
+
+/// Parses comma separated intcode, reporting a value that isn't a number
+/// instead of panicking
+///
+/// # Arguments
+///
+/// * `program` comma separated intcode
+///
+/// # Returns
+///
+/// * initialized intcode object, or the first value that isn't a number
+///
+pub fn try_parse_program(program: &str) -> Result<IntCode, Day2Error> {
+    let code = program
+        .split(',')
+        .map(|s| s.trim())
+        .map(|s| {
+            s.parse::<u64>()
+                .map_err(|_| Day2Error::Malformed(String::from(s)))
+        })
+        .collect::<Result<Vec<u64>, Day2Error>>()?;
+    Ok(IntCode { code })
+}
+
+/// Restores the gravity assist state of an in-memory program and runs it
+///
+/// # Arguments
+///
+/// * `program` comma separated intcode
+///
+/// # Returns
+///
+/// * value at index 0 after program completion, or why the program
+///   can't be parsed or run
+///
+pub fn solve_part1(program: &str) -> Result<u64, Day2Error> {
+    gravity_assist(&try_parse_program(program)?)
+}
 
 /// Performs all parts necessary for part1
 ///
@@ -109,48 +370,241 @@ impl IntCode {
 /// * value at index 0 after program completion
 ///
 pub fn part1(filename: &str) -> u64 {
-    let mut input = shared::ingest_file(filename);
-    let mut ic = IntCode::new(input.pop().unwrap());
-    ic.code[1] = 12;
-    ic.code[2] = 2;
-    let result = ic.execute();
-    result
+    run(&load_program(filename), &GRAVITY_ASSIST)
+}
+
+/// Tries every noun and verb in turn until the program produces `target`
+///
+/// # Arguments
+///
+/// * `program` intcode to search
+/// * `target` value wanted at position 0
+///
+/// # Returns
+///
+/// * first (noun, verb) found, nouns and then verbs in increasing order
+///
+#[cfg(test)]
+fn brute_force(program: &IntCode, target: u64) -> Option<(u64, u64)> {
+    for noun in 0..100 {
+        for verb in 0..100 {
+            if run(program, &noun_verb(noun, verb)) == target {
+                return Some((noun, verb));
+            }
+        }
+    }
+    None
+}
+
+/// Tries every noun and verb across threads until the program produces
+/// `target`
+///
+/// Combinations that make the program fault count as misses, so ranges
+/// wider than the program is long can be searched safely.
+///
+/// # Arguments
+///
+/// * `program` intcode to search
+/// * `target` value wanted at position 0
+/// * `nouns` nouns to try
+/// * `verbs` verbs to try
+/// * `progress` ticked once per combination tried
+///
+/// # Returns
+///
+/// * (noun, verb) with the lowest `100 * noun + verb`, ties going to the
+///   lower noun, regardless of which thread finds it first
+///
+fn par_brute_force(
+    program: &IntCode,
+    target: u64,
+    nouns: Range<u64>,
+    verbs: Range<u64>,
+    progress: &Progress,
+) -> Option<(u64, u64)> {
+    nouns
+        .into_par_iter()
+        .flat_map(|noun| verbs.clone().into_par_iter().map(move |verb| (noun, verb)))
+        .filter(|&(noun, verb)| {
+            progress.tick();
+            try_run(program, noun, verb) == Some(target)
+        })
+        .min_by_key(|&(noun, verb)| (100 * noun as u128 + verb as u128, noun))
+}
+
+/// Solves for the noun and verb assuming position 0 ends up as
+/// `c + a * noun + b * verb`
+///
+/// Three probe runs give `a`, `b` and `c`. Each solution of the linear
+/// equation is confirmed by running the program, so a program that isn't
+/// actually linear never yields a pair that misses the target. Such a
+/// program can still fit the model at some points, in which case a pair
+/// it doesn't predict may produce the target with a lower answer.
+///
+/// # Arguments
+///
+/// * `program` intcode to search
+/// * `target` value wanted at position 0
+///
+/// # Returns
+///
+/// * (noun, verb) with the lowest `100 * noun + verb` among the model's
+///   solutions that the program confirms, or none if there are none
+///
+fn solve_linear(
+    program: &IntCode,
+    target: u64,
+    nouns: Range<u64>,
+    verbs: Range<u64>,
+) -> Option<(u64, u64)> {
+    let c = try_run(program, 0, 0)? as i128;
+    let a = try_run(program, 1, 0)? as i128 - c;
+    let b = try_run(program, 0, 1)? as i128 - c;
+    nouns
+        .filter_map(|noun| {
+            let rest = target as i128 - c - a * noun as i128;
+            let verb = match b {
+                0 if rest == 0 => verbs.start as i128,
+                0 => return None,
+                _ if rest % b != 0 => return None,
+                _ => rest / b,
+            };
+            let verb = u64::try_from(verb).ok().filter(|v| verbs.contains(v))?;
+            (try_run(program, noun, verb) == Some(target)).then_some((noun, verb))
+        })
+        .min_by_key(|&(noun, verb)| (100 * noun as u128 + verb as u128, noun))
+}
+
+/// Finds the noun and verb that make a program produce `target`
+///
+/// The linear model is tried first and the ranges are only swept when it
+/// finds nothing. The pair is the lowest `100 * noun + verb` overall
+/// unless the program isn't linear yet happens to fit the model somewhere.
+///
+/// # Arguments
+///
+/// * `program` intcode to search
+/// * `target` value wanted at position 0
+/// * `nouns` nouns to try
+/// * `verbs` verbs to try
+///
+/// # Returns
+///
+/// * (noun, verb) producing the target, or an error if no combination
+///   does
+///
+pub fn find_noun_verb(
+    program: &IntCode,
+    target: u64,
+    nouns: Range<u64>,
+    verbs: Range<u64>,
+) -> Result<(u64, u64), Day2Error> {
+    find_noun_verb_with(program, target, nouns, verbs, false)
+}
+
+/// Finds the noun and verb that make a program produce `target`,
+/// optionally showing a live counter on stderr while sweeping
+///
+/// Searches the same way as [`find_noun_verb`].
+///
+/// # Arguments
+///
+/// * `program` intcode to search
+/// * `target` value wanted at position 0
+/// * `nouns` nouns to try
+/// * `verbs` verbs to try
+/// * `show_progress` draw progress if the search falls back to a sweep,
+///   even when no runner has asked for progress
+///
+/// # Returns
+///
+/// * (noun, verb) producing the target, or an error if no combination
+///   does
+///
+pub fn find_noun_verb_with(
+    program: &IntCode,
+    target: u64,
+    nouns: Range<u64>,
+    verbs: Range<u64>,
+    show_progress: bool,
+) -> Result<(u64, u64), Day2Error> {
+    solve_linear(program, target, nouns.clone(), verbs.clone())
+        .or_else(|| {
+            let total = (nouns.end.saturating_sub(nouns.start))
+                .saturating_mul(verbs.end.saturating_sub(verbs.start));
+            log::debug!("program isn't linear, sweeping {} combinations", total);
+            let progress = if show_progress {
+                Progress::new("noun/verb sweep", total)
+            } else {
+                Progress::auto("noun/verb sweep", total)
+            };
+            let found = par_brute_force(program, target, nouns.clone(), verbs.clone(), &progress);
+            progress.finish();
+            found
+        })
+        .ok_or(Day2Error::NotFound {
+            target,
+            nouns,
+            verbs,
+        })
 }
 
 /// Performs all parts necessary for part2
 /// Main objective is to find the values of *noun* and *verb* which
-/// are the combination of values in index 1 and 2 respectively that 
+/// are the combination of values in index 1 and 2 respectively that
 /// create 19690720 at position 0 when the intcode is executed
 ///
+/// The answer is solved for directly when the program is linear in noun
+/// and verb, falling back to trying every combination otherwise.
+///
 /// # Returns
 ///
-/// * 100 * noun + verb
-///
-pub fn part2(filename: &str) -> u64 {
-    let input = shared::ingest_file(filename);
-    let mut noun: Option<u64> = None;
-    let mut verb: Option<u64> = None;
-    let mut found = false;
-    for i in 0..100 {
-        for j in 0..100 {
-            let mut ic = IntCode::new(input[0].clone());
-            ic.code[1] = i;
-            ic.code[2] = j;
-            let result = ic.execute();
-            if result == 19690720 {
-                found = true;
-                noun = Some(i as u64);
-                verb = Some(j as u64);
-                break;
-            }
-            if found {
-                break;
-            }
-        };
-    };
-    match  (noun, verb) {
-        (Some(n), Some(v)) => 100 * n + v,
-        _ => panic!("encountered some error: Noun = {:?}, Verb = {:?}", noun, verb)
+/// * noun, verb and 100 * noun + verb, or an error if no pair matches
+///
+pub fn part2(filename: &str) -> Result<NounVerb, Day2Error> {
+    let (noun, verb) = find_noun_verb(&try_load_program(filename)?, 19690720, 0..100, 0..100)?;
+    Ok(NounVerb::new(noun, verb))
+}
+
+/// Finds the noun and verb that make an in-memory program produce `target`
+///
+/// # Arguments
+///
+/// * `program` comma separated intcode
+/// * `target` value wanted at position 0
+///
+/// # Returns
+///
+/// * noun, verb and 100 * noun + verb, or an error if the program can't
+///   be parsed or no pair in 0..100 matches
+///
+pub fn solve_part2(program: &str, target: u64) -> Result<NounVerb, Day2Error> {
+    let (noun, verb) = find_noun_verb(&try_parse_program(program)?, target, 0..100, 0..100)?;
+    Ok(NounVerb::new(noun, verb))
+}
+
+/// Day 2 as driven by the workspace runner
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "1202 Program Alarm"
+    }
+
+    fn long_running(&self, part: u8) -> bool {
+        part == 2
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(try_load_program(filename).and_then(|program| gravity_assist(&program)))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename).map(|nv| nv.answer))
     }
 }
 
@@ -160,8 +614,185 @@ mod tests {
 
     #[test]
     fn part1_works() {
-        let result = part1("src/test.txt");
-        assert_eq!(result, 3500);
+        let mut input = shared::ingest_file("src/test.txt");
+        let mut ic = IntCode::new(input.pop().unwrap());
+        assert_eq!(ic.execute(), 3500);
+        assert_eq!(part1("src/input.txt"), 3931283);
+    }
+
+    #[test]
+    fn part2_works() {
+        assert_eq!(
+            part2("src/input.txt"),
+            Ok(NounVerb {
+                noun: 69,
+                verb: 79,
+                answer: 6979
+            })
+        );
+    }
+
+    #[test]
+    fn try_load_program_reports_bad_input() {
+        assert_eq!(
+            try_load_program("src/input.txt"),
+            Ok(load_program("src/input.txt"))
+        );
+        assert!(matches!(
+            try_load_program("src/missing.txt"),
+            Err(Day2Error::Input { .. })
+        ));
+        let err = try_load_program("src/lib.rs").unwrap_err();
+        assert!(err.to_string().starts_with("src/lib.rs: "), "{}", err);
+        assert!(Day2.part1("src/missing.txt").is_err());
+        assert!(Day2.part2("src/missing.txt").is_err());
+        assert_eq!(Day2.part1("src/input.txt").unwrap(), "3931283");
+    }
+
+    #[test]
+    fn solve_parts_from_str() {
+        // position 0 ends up as code[noun] + code[verb]
+        let program = "1,0,0,0,99,10,20,30,40";
+        // code[12] + code[2] with the verb 2 written at address 2
+        assert_eq!(solve_part1("1,0,0,0,99,0,0,0,0,0,0,0,7"), Ok(9));
+        assert_eq!(
+            solve_part1("1,0,x"),
+            Err(Day2Error::Malformed(String::from("x")))
+        );
+        assert_eq!(
+            solve_part1("1,0,0,0,99"),
+            Err(Day2Error::Fault { noun: 12, verb: 2 })
+        );
+        assert_eq!(
+            solve_part1("7,1,2,0,99"),
+            Err(Day2Error::Fault { noun: 12, verb: 2 })
+        );
+        assert_eq!(solve_part2("", 1), Err(Day2Error::Malformed(String::new())));
+        assert_eq!(solve_part2(program, 50), Ok(NounVerb::new(5, 8)));
+        assert_eq!(
+            solve_part2(program, 1000),
+            Err(Day2Error::NotFound {
+                target: 1000,
+                nouns: 0..100,
+                verbs: 0..100
+            })
+        );
+    }
+
+    #[test]
+    fn solve_linear_matches_brute_force() {
+        let input = shared::ingest_file("src/input.txt");
+        let ic = IntCode::new(input[0].clone());
+
+        for target in [
+            19690720,
+            run(&ic, &noun_verb(0, 0)),
+            run(&ic, &noun_verb(12, 2)),
+            run(&ic, &noun_verb(99, 99)),
+        ] {
+            assert_eq!(
+                solve_linear(&ic, target, 0..100, 0..100),
+                brute_force(&ic, target)
+            );
+        }
+        assert_eq!(solve_linear(&ic, 1, 0..100, 0..100), None);
+        assert_eq!(brute_force(&ic, 1), None);
+    }
+
+    #[test]
+    fn par_brute_force_matches_brute_force() {
+        let input = shared::ingest_file("src/input.txt");
+        let ic = IntCode::new(input[0].clone());
+
+        for target in [
+            19690720,
+            run(&ic, &noun_verb(0, 0)),
+            run(&ic, &noun_verb(45, 3)),
+            1,
+        ] {
+            let progress = Progress::hidden(10_000);
+            assert_eq!(
+                par_brute_force(&ic, target, 0..100, 0..100, &progress),
+                brute_force(&ic, target)
+            );
+            assert_eq!(progress.done(), 10_000);
+        }
+    }
+
+    #[test]
+    fn find_noun_verb_works() {
+        let input = shared::ingest_file("src/input.txt");
+        let ic = IntCode::new(input[0].clone());
+        let target = run(&ic, &noun_verb(30, 40));
+
+        assert_eq!(find_noun_verb(&ic, 19690720, 0..100, 0..100), Ok((69, 79)));
+        assert_eq!(find_noun_verb(&ic, target, 0..100, 0..100), Ok((30, 40)));
+        assert_eq!(find_noun_verb(&ic, target, 20..35, 35..45), Ok((30, 40)));
+        assert_eq!(
+            find_noun_verb(&ic, target, 0..30, 0..100),
+            Err(Day2Error::NotFound {
+                target,
+                nouns: 0..30,
+                verbs: 0..100
+            })
+        );
+    }
+
+    #[test]
+    fn find_noun_verb_falls_back_when_not_linear() {
+        // position 0 ends up as code[noun] * code[verb]
+        let mut code = vec![2, 0, 0, 0, 99];
+        code.extend(5..50);
+        let ic = IntCode { code };
+
+        // 2 * 21 with the opcode itself at address 0 beats 6 * 7
+        assert_eq!(solve_linear(&ic, 42, 0..50, 0..50), None);
+        assert_eq!(find_noun_verb(&ic, 42, 0..50, 0..50), Ok((0, 21)));
+        assert_eq!(find_noun_verb(&ic, 42, 5..50, 5..50), Ok((6, 7)));
+    }
+
+    #[test]
+    fn par_brute_force_handles_wide_ranges() {
+        // position 0 ends up as code[noun] + code[verb], with code[k] = k
+        // past the halt so most of the range lands on real addresses
+        let mut code = vec![1, 0, 0, 0, 99];
+        code.extend(5..500);
+        let ic = IntCode { code };
+
+        // 1 + 299 at (0, 299) beats 5 + 295 at (5, 295)
+        assert_eq!(
+            par_brute_force(&ic, 300, 0..1000, 0..1000, &Progress::hidden(0)),
+            Some((0, 299))
+        );
+        assert_eq!(
+            par_brute_force(&ic, 300, 5..1000, 5..1000, &Progress::hidden(0)),
+            Some((5, 295))
+        );
+        assert_eq!(
+            par_brute_force(&ic, 2000, 0..1000, 0..1000, &Progress::hidden(0)),
+            None
+        );
+    }
+
+    #[test]
+    fn try_execute_reports_faults() {
+        let mut ok = IntCode {
+            code: vec![1, 0, 0, 0, 99],
+        };
+        let mut bad_address = IntCode {
+            code: vec![1, 50, 0, 0, 99],
+        };
+        let mut bad_opcode = IntCode {
+            code: vec![3, 0, 0, 0, 99],
+        };
+        let mut runs_off_end = IntCode {
+            code: vec![1, 0, 0, 0],
+        };
+
+        assert_eq!(ok.try_execute(), Some(2));
+        assert_eq!(bad_address.try_execute(), None);
+        assert_eq!(bad_opcode.try_execute(), None);
+        assert_eq!(runs_off_end.try_execute(), None);
     }
 }
 #[cfg(test)]
@@ -186,6 +817,16 @@ mod tests_intcode {
         assert_eq!(ic.code, vec![1, 5, 0, 4]);
     }
 
+    #[test]
+    fn patches_apply_to_intcode() {
+        let mut ic = IntCode::new(String::from("1, 5, 9, 4"));
+        apply_patches(&mut ic.code, &GRAVITY_ASSIST).unwrap();
+        assert_eq!(ic.code, vec![1, 12, 2, 4]);
+        // address 4 is past the end once the noun is read as one
+        assert_eq!(try_run(&ic, 4, 0), None);
+        assert_eq!(try_run(&IntCode { code: vec![99] }, 0, 0), None);
+    }
+
     #[test]
     fn add_single_op() {
         let mut ic = IntCode {
@@ -224,8 +865,8 @@ mod tests_intcode {
 
     #[test]
     fn vector_slicing() {
-        let input = vec![5, 6, 7, 8, 9];
-        assert_eq!(input[1..3], vec![6, 7]);
+        let input = [5, 6, 7, 8, 9];
+        assert_eq!(input[1..3], [6, 7]);
     }
 
     #[test]
@@ -253,10 +894,8 @@ mod tests_intcode {
             },
         ];
         for tc in test_cases {
-            let mut ic = IntCode {
-                code: tc.input
-            };
-            let result = ic.execute();
+            let mut ic = IntCode { code: tc.input };
+            ic.execute();
             assert_eq!(ic.code, tc.expected);
         }
     }
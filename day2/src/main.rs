@@ -1,6 +1,80 @@
-use day2::{part1, part2};
+use std::process::ExitCode;
 
-fn main () {
-    println!("Part1: {}", part1("src/input.txt"));
-    println!("Part2: {}", part2("src/input.txt"));
+use clap::Parser;
+use day2::{
+    Day2Error, NounVerb, find_noun_verb_with, gravity_assist, part2, try_load_program, try_run,
+};
+use shared::args::DEFAULT_INPUT;
+
+/// Gravity assist program for the 1202 program alarm
+#[derive(Parser)]
+struct Cli {
+    /// file holding the intcode program
+    #[arg(long, default_value = DEFAULT_INPUT)]
+    input: String,
+    /// run once with this noun and print position 0
+    #[arg(long, requires = "verb", conflicts_with = "target")]
+    noun: Option<u64>,
+    /// run once with this verb and print position 0
+    #[arg(long, requires = "noun")]
+    verb: Option<u64>,
+    /// search for the noun and verb producing this value
+    #[arg(long)]
+    target: Option<u64>,
+    /// show a live counter and ETA on stderr while sweeping
+    #[arg(long, requires = "target")]
+    progress: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let program = match try_load_program(&cli.input) {
+        Ok(program) => program,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let (Some(noun), Some(verb)) = (cli.noun, cli.verb) {
+        return match try_run(&program, noun, verb) {
+            Some(result) => {
+                println!("{}", result);
+                ExitCode::SUCCESS
+            }
+            None => {
+                eprintln!("{}", Day2Error::Fault { noun, verb });
+                ExitCode::FAILURE
+            }
+        };
+    }
+    if let Some(target) = cli.target {
+        return match find_noun_verb_with(&program, target, 0..100, 0..100, cli.progress) {
+            Ok((noun, verb)) => {
+                println!("{}", NounVerb::new(noun, verb));
+                ExitCode::SUCCESS
+            }
+            Err(err) => {
+                eprintln!("{}", err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let mut code = ExitCode::SUCCESS;
+    match gravity_assist(&program) {
+        Ok(answer) => println!("Part1: {}", answer),
+        Err(err) => {
+            eprintln!("Part1: {}", err);
+            code = ExitCode::FAILURE;
+        }
+    }
+    match part2(&cli.input) {
+        Ok(found) => println!("Part2: {}", found.answer),
+        Err(err) => {
+            eprintln!("Part2: {}", err);
+            code = ExitCode::FAILURE;
+        }
+    }
+    code
 }
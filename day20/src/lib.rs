@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use shared::graph::{Graph, bfs_distance};
+use shared::grid::{Grid, GridError};
+use shared::solution::{Answer, Solution, answer};
+
+/// Position in the maze, x to the right and y downwards
+pub type Position = (usize, usize);
+
+/// Label of the tile the walk starts from
+pub const START: &str = "AA";
+/// Label of the tile the walk ends at
+pub const END: &str = "ZZ";
+
+/// Reasons the maze can't be walked
+#[derive(Debug, PartialEq)]
+pub enum Day20Error {
+    /// the map has characters that aren't walls, passages, spaces or labels
+    Grid(GridError),
+    /// the start or end label is missing
+    Missing(&'static str),
+    /// a portal label appears other than exactly twice
+    Unpaired(String),
+    /// the end can't be reached from the start
+    Unreachable,
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day20Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day20Error::Grid(err) => write!(f, "{}", err),
+            Day20Error::Missing(label) => write!(f, "maze has no {} tile", label),
+            Day20Error::Unpaired(label) => write!(f, "portal {} isn't a pair", label),
+            Day20Error::Unreachable => write!(f, "{} can't be reached from {}", END, START),
+            Day20Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day20Error {}
+
+impl From<GridError> for Day20Error {
+    fn from(err: GridError) -> Self {
+        Day20Error::Grid(err)
+    }
+}
+
+/// Far side of a portal
+#[derive(Clone, Debug, PartialEq)]
+pub struct Portal {
+    /// passage tile stepped out onto
+    pub to: Position,
+    /// whether the portal is on the outer edge of the donut
+    pub outer: bool,
+}
+
+/// Donut shaped maze with portals between labelled tiles
+#[derive(Debug)]
+pub struct DonutMaze {
+    passages: Graph<Position>,
+    portals: HashMap<Position, Portal>,
+    start: Position,
+    end: Position,
+}
+
+impl DonutMaze {
+    /// Constructor for a DonutMaze
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - map with leading spaces kept, `#` wall, `.` passage and
+    ///   two letter labels beside the passages they name
+    ///
+    pub fn new<S: AsRef<str>>(lines: &[S]) -> Result<DonutMaze, Day20Error> {
+        // trailing spaces often get lost, so pad back to a rectangle
+        let width = lines.iter().map(|l| l.as_ref().len()).max().unwrap_or(0);
+        let padded: Vec<String> = lines
+            .iter()
+            .map(|l| format!("{:width$}", l.as_ref()))
+            .collect();
+        let grid = Grid::parse(&padded, |c| {
+            matches!(c, '#' | '.' | ' ' | 'A'..='Z').then_some(c)
+        })?;
+        let at = |x: usize, y: usize, dx: i64, dy: i64| {
+            let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+            grid.contains(nx, ny)
+                .then(|| *grid.get(nx as usize, ny as usize).unwrap())
+        };
+
+        let maze_cells: Vec<Position> = grid
+            .iter()
+            .filter(|&(_, &c)| c == '#' || c == '.')
+            .map(|(p, _)| p)
+            .collect();
+        let min_x = maze_cells.iter().map(|p| p.0).min().unwrap_or(0);
+        let max_x = maze_cells.iter().map(|p| p.0).max().unwrap_or(0);
+        let min_y = maze_cells.iter().map(|p| p.1).min().unwrap_or(0);
+        let max_y = maze_cells.iter().map(|p| p.1).max().unwrap_or(0);
+
+        let mut passages = Graph::new();
+        let mut labels: HashMap<String, Vec<Position>> = HashMap::new();
+        for (x, y) in maze_cells
+            .into_iter()
+            .filter(|&(x, y)| grid.get(x, y) == Some(&'.'))
+        {
+            for (dx, dy) in [(1, 0), (0, 1)] {
+                if at(x, y, dx, dy) == Some('.') {
+                    passages.add_undirected_edge(
+                        (x, y),
+                        ((x as i64 + dx) as usize, (y as i64 + dy) as usize),
+                    );
+                }
+            }
+            for (dx, dy) in [(0, -1), (1, 0), (0, 1), (-1, 0)] {
+                let (Some(near), Some(far)) = (at(x, y, dx, dy), at(x, y, 2 * dx, 2 * dy)) else {
+                    continue;
+                };
+                if near.is_ascii_uppercase() && far.is_ascii_uppercase() {
+                    // labels read left to right or top to bottom
+                    let label = if dx + dy < 0 {
+                        format!("{}{}", far, near)
+                    } else {
+                        format!("{}{}", near, far)
+                    };
+                    labels.entry(label).or_default().push((x, y));
+                }
+            }
+        }
+
+        let mut lone = |label: &'static str| match labels.remove(label).as_deref() {
+            Some(&[p]) => Ok(p),
+            Some(_) => Err(Day20Error::Unpaired(String::from(label))),
+            None => Err(Day20Error::Missing(label)),
+        };
+        let (start, end) = (lone(START)?, lone(END)?);
+        let is_outer = |(x, y): Position| x == min_x || x == max_x || y == min_y || y == max_y;
+        let mut portals = HashMap::new();
+        let mut labels: Vec<_> = labels.into_iter().collect();
+        labels.sort();
+        for (label, ends) in labels {
+            let [a, b] = ends[..] else {
+                return Err(Day20Error::Unpaired(label));
+            };
+            for (from, to) in [(a, b), (b, a)] {
+                portals.insert(
+                    from,
+                    Portal {
+                        to,
+                        outer: is_outer(from),
+                    },
+                );
+            }
+        }
+        Ok(DonutMaze {
+            passages,
+            portals,
+            start,
+            end,
+        })
+    }
+
+    /// Portal leaving from a tile, if any
+    pub fn portal(&self, position: Position) -> Option<&Portal> {
+        self.portals.get(&position)
+    }
+
+    /// Fewest steps from start to end, a trip through a portal being one
+    /// step
+    pub fn shortest_path(&self) -> Result<usize, Day20Error> {
+        bfs_distance(
+            self.start,
+            |&p| p == self.end,
+            |p| {
+                let mut next = self.passages.neighbours(p).to_vec();
+                next.extend(self.portal(*p).map(|portal| portal.to));
+                next
+            },
+        )
+        .ok_or(Day20Error::Unreachable)
+    }
+
+    /// Fewest steps from start to end when each portal leads to a copy of
+    /// the maze
+    ///
+    /// Inner portals go one level deeper and outer ones come back up.
+    /// Outer portals are walls at the outermost level, and the end only
+    /// counts there. No shortest route needs to go deeper than there are
+    /// portals, which bounds the search when there is no route.
+    pub fn shortest_recursive_path(&self) -> Result<usize, Day20Error> {
+        let max_level = self.portals.len();
+        bfs_distance(
+            (self.start, 0),
+            |&(p, level)| p == self.end && level == 0,
+            |&(p, level)| {
+                let mut next: Vec<(Position, usize)> = self
+                    .passages
+                    .neighbours(&p)
+                    .iter()
+                    .map(|&n| (n, level))
+                    .collect();
+                match self.portal(p) {
+                    Some(&Portal { to, outer: true }) if level > 0 => next.push((to, level - 1)),
+                    Some(&Portal { to, outer: false }) if level < max_level => {
+                        next.push((to, level + 1))
+                    }
+                    _ => {}
+                }
+                next
+            },
+        )
+        .ok_or(Day20Error::Unreachable)
+    }
+}
+
+/// Reads the lines of the puzzle input
+///
+/// # Arguments
+///
+/// * `filename` - path of the input
+///
+fn read_input(filename: &str) -> Result<Vec<String>, Day20Error> {
+    shared::try_ingest_file_untrimmed(filename).map_err(|err| Day20Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * fewest steps from AA to ZZ
+///
+pub fn part1(filename: &str) -> Result<usize, Day20Error> {
+    DonutMaze::new(&read_input(filename)?)?.shortest_path()
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * fewest steps from AA to ZZ through the recursive levels
+///
+pub fn part2(filename: &str) -> Result<usize, Day20Error> {
+    DonutMaze::new(&read_input(filename)?)?.shortest_recursive_path()
+}
+
+/// Day 20 as driven by the workspace runner
+pub struct Day20;
+
+impl Solution for Day20 {
+    fn day(&self) -> u8 {
+        20
+    }
+
+    fn title(&self) -> &'static str {
+        "Donut Maze"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reaches ZZ only by going down a level that can't be climbed back
+    const ONE_WAY: [&str; 14] = [
+        "      A",
+        "      A",
+        "  ####.#######",
+        "  ####.#######",
+        "  ####.#######",
+        "  ####.#######",
+        "  ####X   ####",
+        "  ####Y   ####",
+        "  ############",
+        "  ############",
+        "XY........####",
+        "  #######.####",
+        "         Z",
+        "         Z",
+    ];
+
+    #[test]
+    fn part1_works() {
+        assert_eq!(part1("src/test.txt"), Ok(23));
+        assert_eq!(DonutMaze::new(&ONE_WAY).unwrap().shortest_path(), Ok(12));
+        assert!(matches!(
+            part1("src/no_such_file.txt"),
+            Err(Day20Error::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn part2_works() {
+        assert_eq!(part2("src/test.txt"), Ok(26));
+        assert_eq!(
+            DonutMaze::new(&ONE_WAY).unwrap().shortest_recursive_path(),
+            Err(Day20Error::Unreachable)
+        );
+    }
+
+    #[test]
+    fn portals_know_their_side() {
+        let maze = DonutMaze::new(&shared::ingest_file_untrimmed("src/test.txt")).unwrap();
+        // BC inner below the top half, outer on the left
+        assert_eq!(
+            maze.portal((9, 6)),
+            Some(&Portal {
+                to: (2, 8),
+                outer: false
+            })
+        );
+        assert_eq!(
+            maze.portal((2, 8)),
+            Some(&Portal {
+                to: (9, 6),
+                outer: true
+            })
+        );
+        assert_eq!(maze.portal((9, 2)), None);
+    }
+
+    #[test]
+    fn new_rejects_bad_labels() {
+        let mut lines = ONE_WAY.to_vec();
+        lines[0] = "      B";
+        assert_eq!(
+            DonutMaze::new(&lines).unwrap_err(),
+            Day20Error::Missing(START)
+        );
+        lines[0] = "      A";
+        lines[10] = "XZ........####";
+        assert_eq!(
+            DonutMaze::new(&lines).unwrap_err(),
+            Day20Error::Unpaired(String::from("XY"))
+        );
+    }
+}
@@ -0,0 +1,588 @@
+use std::fmt;
+
+use shared::intcode::{IntcodeError, Vm, ascii, parse_program};
+use shared::solution::{Answer, Solution, answer};
+
+/// Most instructions the springdroid's memory holds
+pub const MAX_INSTRUCTIONS: usize = 15;
+/// Tiles a jump carries the droid forward
+pub const JUMP: usize = 4;
+
+/// Jump when there's a hole in the next three tiles and ground to land on
+pub const WALK_SCRIPT: &str = "\
+NOT A J
+NOT B T
+OR T J
+NOT C T
+OR T J
+AND D J
+WALK
+";
+
+/// As for walking, but only when the landing tile can be left again,
+/// either by stepping on or by jumping straight away
+pub const RUN_SCRIPT: &str = "\
+NOT A J
+NOT B T
+OR T J
+NOT C T
+OR T J
+AND D J
+NOT E T
+NOT T T
+OR H T
+AND T J
+RUN
+";
+
+/// Reasons a springscript program can't be built or run
+#[derive(Debug, PartialEq)]
+pub enum Day21Error {
+    /// the ASCII program faulted
+    Intcode(IntcodeError),
+    /// a line isn't an instruction or a mode
+    Parse { line: usize, content: String },
+    /// more instructions than fit in memory
+    TooLong(usize),
+    /// an instruction writes to a sensor
+    ReadOnly { index: usize, register: Register },
+    /// an instruction reads a sensor only available when running
+    OutOfRange { index: usize, register: Register },
+    /// the droid fell into space
+    Fell(FailureTrace),
+    /// the droid finished without reporting hull damage
+    NoReport(String),
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day21Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day21Error::Intcode(err) => write!(f, "{}", err),
+            Day21Error::Parse { line, content } => {
+                write!(f, "line {}: not springscript: {:?}", line, content)
+            }
+            Day21Error::TooLong(len) => {
+                write!(f, "{} instructions, only {} fit", len, MAX_INSTRUCTIONS)
+            }
+            Day21Error::ReadOnly { index, register } => {
+                write!(f, "instruction {} writes to sensor {}", index, register)
+            }
+            Day21Error::OutOfRange { index, register } => write!(
+                f,
+                "instruction {} reads {}, which only exists when running",
+                index, register
+            ),
+            Day21Error::Fell(trace) => write!(f, "droid fell:\n{}", trace),
+            Day21Error::NoReport(text) => write!(f, "no hull damage reported:\n{}", text),
+            Day21Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day21Error {}
+
+impl From<IntcodeError> for Day21Error {
+    fn from(err: IntcodeError) -> Self {
+        Day21Error::Intcode(err)
+    }
+}
+
+/// Springscript register, the sensors `A` to `I` being read only
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Register {
+    /// ground sensor this many tiles ahead, from 1
+    Sensor(u8),
+    /// temporary value
+    T,
+    /// jump flag
+    J,
+}
+
+impl Register {
+    fn parse(s: &str) -> Option<Register> {
+        match s.as_bytes() {
+            [b'T'] => Some(Register::T),
+            [b'J'] => Some(Register::J),
+            &[c @ b'A'..=b'I'] => Some(Register::Sensor(c - b'A' + 1)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Register::Sensor(n) => write!(f, "{}", char::from(b'A' + n - 1)),
+            Register::T => write!(f, "T"),
+            Register::J => write!(f, "J"),
+        }
+    }
+}
+
+/// Springscript operation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Op {
+    And,
+    Or,
+    Not,
+}
+
+/// How far the droid moves and so how far it can see
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    /// sensors `A` to `D`
+    Walk,
+    /// sensors `A` to `I`
+    Run,
+}
+
+impl Mode {
+    /// Farthest sensor available
+    pub fn range(&self) -> u8 {
+        match self {
+            Mode::Walk => 4,
+            Mode::Run => 9,
+        }
+    }
+
+    fn command(&self) -> &'static str {
+        match self {
+            Mode::Walk => "WALK",
+            Mode::Run => "RUN",
+        }
+    }
+}
+
+/// Single springscript instruction
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Instruction {
+    pub op: Op,
+    pub src: Register,
+    pub dst: Register,
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let op = match self.op {
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Not => "NOT",
+        };
+        write!(f, "{} {} {}", op, self.src, self.dst)
+    }
+}
+
+/// Springscript program, built up an instruction at a time
+///
+/// ```
+/// use day21::{Mode, Register::*, Script};
+///
+/// let script = Script::new(Mode::Walk).not(Sensor(1), J).and(Sensor(4), J);
+/// assert!(script.validate().is_ok());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Script {
+    mode: Mode,
+    instructions: Vec<Instruction>,
+}
+
+impl Script {
+    /// Constructor for an empty Script
+    pub fn new(mode: Mode) -> Script {
+        Script {
+            mode,
+            instructions: Vec::new(),
+        }
+    }
+
+    /// Constructor for a Script from its text, ending with `WALK` or `RUN`
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - one instruction per line, then the mode
+    ///
+    /// # Returns
+    ///
+    /// * valid script, or why it isn't one
+    ///
+    pub fn parse(text: &str) -> Result<Script, Day21Error> {
+        let lines: Vec<&str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect();
+        let malformed = |i: usize| Day21Error::Parse {
+            line: i + 1,
+            content: String::from(lines.get(i).copied().unwrap_or("")),
+        };
+        let (mode, body) = match lines.split_last() {
+            Some((&"WALK", body)) => (Mode::Walk, body),
+            Some((&"RUN", body)) => (Mode::Run, body),
+            _ => return Err(malformed(lines.len().saturating_sub(1))),
+        };
+        let mut script = Script::new(mode);
+        for (i, line) in body.iter().enumerate() {
+            let instruction = match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [op, src, dst] => {
+                    let op = match op {
+                        "AND" => Op::And,
+                        "OR" => Op::Or,
+                        "NOT" => Op::Not,
+                        _ => return Err(malformed(i)),
+                    };
+                    Register::parse(src)
+                        .zip(Register::parse(dst))
+                        .map(|(src, dst)| Instruction { op, src, dst })
+                        .ok_or_else(|| malformed(i))?
+                }
+                _ => return Err(malformed(i)),
+            };
+            script.instructions.push(instruction);
+        }
+        script.validate()?;
+        Ok(script)
+    }
+
+    fn push(mut self, op: Op, src: Register, dst: Register) -> Script {
+        self.instructions.push(Instruction { op, src, dst });
+        self
+    }
+
+    /// Appends `AND src dst`
+    pub fn and(self, src: Register, dst: Register) -> Script {
+        self.push(Op::And, src, dst)
+    }
+
+    /// Appends `OR src dst`
+    pub fn or(self, src: Register, dst: Register) -> Script {
+        self.push(Op::Or, src, dst)
+    }
+
+    /// Appends `NOT src dst`
+    pub fn not(self, src: Register, dst: Register) -> Script {
+        self.push(Op::Not, src, dst)
+    }
+
+    /// Mode the script ends with
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Checks the script would be accepted by the droid
+    ///
+    /// # Returns
+    ///
+    /// * first problem found, if any
+    ///
+    pub fn validate(&self) -> Result<(), Day21Error> {
+        if self.instructions.len() > MAX_INSTRUCTIONS {
+            return Err(Day21Error::TooLong(self.instructions.len()));
+        }
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            if let register @ Register::Sensor(_) = instruction.dst {
+                return Err(Day21Error::ReadOnly { index, register });
+            }
+            if let register @ Register::Sensor(n) = instruction.src
+                && n > self.mode.range()
+            {
+                return Err(Day21Error::OutOfRange { index, register });
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the script jumps given what the sensors see
+    ///
+    /// # Arguments
+    ///
+    /// * `ground` - whether there is ground at each tile ahead, from 1
+    ///
+    pub fn jumps(&self, ground: impl Fn(u8) -> bool) -> bool {
+        let (mut t, mut j) = (false, false);
+        for instruction in &self.instructions {
+            let src = match instruction.src {
+                Register::Sensor(n) => ground(n),
+                Register::T => t,
+                Register::J => j,
+            };
+            let dst = if instruction.dst == Register::T {
+                &mut t
+            } else {
+                &mut j
+            };
+            *dst = match instruction.op {
+                Op::And => src && *dst,
+                Op::Or => src || *dst,
+                Op::Not => !src,
+            };
+        }
+        j
+    }
+
+    /// Walks the script over a hull without the droid's program
+    ///
+    /// # Arguments
+    ///
+    /// * `hull` - tiles from the droid's start, `#` ground and `.` hole,
+    ///   ground continuing past the end
+    ///
+    /// # Returns
+    ///
+    /// * tile the droid falls into, or none if it makes it across
+    ///
+    pub fn falls_at(&self, hull: &str) -> Option<usize> {
+        let ground = |i: usize| hull.as_bytes().get(i) != Some(&b'.');
+        let mut at = 0;
+        while at < hull.len() {
+            let sees = |n: u8| ground(at + n as usize);
+            at += if self.jumps(sees) { JUMP } else { 1 };
+            if !ground(at) {
+                return Some(at);
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Display for Script {
+    /// Writes the script as the droid reads it
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for instruction in &self.instructions {
+            writeln!(f, "{}", instruction)?;
+        }
+        writeln!(f, "{}", self.mode.command())
+    }
+}
+
+/// Last moments of a droid that fell, as drawn by its camera
+#[derive(Debug, PartialEq)]
+pub struct FailureTrace {
+    frames: Vec<Vec<String>>,
+}
+
+impl FailureTrace {
+    /// Header the droid prints before the frames
+    const HEADER: &str = "Didn't make it across:";
+
+    /// Reads the frames from the droid's output
+    ///
+    /// # Returns
+    ///
+    /// * trace, or none if the text doesn't report a fall
+    ///
+    pub fn parse(text: &str) -> Option<FailureTrace> {
+        let (_, frames) = text.split_once(Self::HEADER)?;
+        let frames = frames
+            .split("\n\n")
+            .map(|frame| {
+                frame
+                    .lines()
+                    .filter(|l| !l.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .filter(|frame: &Vec<String>| !frame.is_empty())
+            .collect();
+        Some(FailureTrace { frames })
+    }
+
+    /// Frames in order, each a few rows of the hull
+    pub fn frames(&self) -> &[Vec<String>] {
+        &self.frames
+    }
+}
+
+impl fmt::Display for FailureTrace {
+    /// Draws the frames side by side, oldest first
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows = self.frames.iter().map(Vec::len).max().unwrap_or(0);
+        for row in 0..rows {
+            let line: Vec<&str> = self
+                .frames
+                .iter()
+                .map(|frame| frame.get(row).map_or("", String::as_str))
+                .collect();
+            writeln!(f, "{}", line.join(" | ").trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// Feeds a script to the springdroid and sends it across the hull
+///
+/// # Arguments
+///
+/// * `program` - ASCII software
+/// * `script` - springscript to run
+///
+/// # Returns
+///
+/// * hull damage reported, or how the droid fell
+///
+pub fn survey(program: &[i64], script: &Script) -> Result<i64, Day21Error> {
+    script.validate()?;
+    let mut vm = Vm::new(program.to_vec());
+    vm.push_ascii(&script.to_string());
+    vm.run()?;
+    let output = vm.take_output();
+    if let Some((&damage, _)) = output.split_last()
+        && ascii(&[damage]).is_none()
+    {
+        return Ok(damage);
+    }
+    let text = ascii(&output).unwrap_or_default();
+    Err(FailureTrace::parse(&text).map_or(Day21Error::NoReport(text), Day21Error::Fell))
+}
+
+/// Reads a program from the first line of a file
+///
+/// # Arguments
+///
+/// * `filename` - path of file containing the intcode
+///
+pub fn load_program(filename: &str) -> Result<Vec<i64>, Day21Error> {
+    let input = shared::try_ingest_file(filename).map_err(|err| Day21Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })?;
+    Ok(parse_program(input.first().map_or("", |s| s))?)
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * hull damage reported after walking
+///
+pub fn part1(filename: &str) -> Result<i64, Day21Error> {
+    survey(&load_program(filename)?, &Script::parse(WALK_SCRIPT)?)
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * hull damage reported after running
+///
+pub fn part2(filename: &str) -> Result<i64, Day21Error> {
+    survey(&load_program(filename)?, &Script::parse(RUN_SCRIPT)?)
+}
+
+/// Day 21 as driven by the workspace runner
+pub struct Day21;
+
+impl Solution for Day21 {
+    fn day(&self) -> u8 {
+        21
+    }
+
+    fn title(&self) -> &'static str {
+        "Springdroid Adventure"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Register::*;
+
+    /// Intcode printing `text` then halting
+    fn printer(text: &str) -> Vec<i64> {
+        let mut program: Vec<i64> = text.bytes().flat_map(|b| [104, b as i64]).collect();
+        program.push(99);
+        program
+    }
+
+    #[test]
+    fn embedded_scripts_cross_hulls() {
+        let walk = Script::parse(WALK_SCRIPT).unwrap();
+        let run = Script::parse(RUN_SCRIPT).unwrap();
+        for hull in ["#####.####", "#####..#.####", "#####...####"] {
+            assert_eq!(walk.falls_at(hull), None, "{}", hull);
+            assert_eq!(run.falls_at(hull), None, "{}", hull);
+        }
+        // walking jumps early and lands facing a hole it can't clear
+        let hull = "#####.#.##..#.###";
+        assert_eq!(walk.falls_at(hull), Some(7));
+        assert_eq!(run.falls_at(hull), None);
+    }
+
+    #[test]
+    fn builder_matches_text() {
+        let built = Script::new(Mode::Walk)
+            .not(Sensor(1), J)
+            .not(Sensor(2), T)
+            .or(T, J)
+            .not(Sensor(3), T)
+            .or(T, J)
+            .and(Sensor(4), J);
+        assert_eq!(built.to_string(), WALK_SCRIPT);
+        assert_eq!(Script::parse(WALK_SCRIPT), Ok(built));
+    }
+
+    #[test]
+    fn validate_catches_mistakes() {
+        let script = Script::new(Mode::Walk).or(T, Sensor(1));
+        assert_eq!(
+            script.validate(),
+            Err(Day21Error::ReadOnly {
+                index: 0,
+                register: Sensor(1)
+            })
+        );
+        assert_eq!(
+            Script::parse("OR E J\nWALK"),
+            Err(Day21Error::OutOfRange {
+                index: 0,
+                register: Sensor(5)
+            })
+        );
+        assert!(Script::parse("OR E J\nRUN").is_ok());
+        let long = (0..16).fold(Script::new(Mode::Run), |s, _| s.or(T, J));
+        assert_eq!(long.validate(), Err(Day21Error::TooLong(16)));
+        assert_eq!(
+            Script::parse("XOR A J\nWALK"),
+            Err(Day21Error::Parse {
+                line: 1,
+                content: String::from("XOR A J")
+            })
+        );
+        assert_eq!(
+            Script::parse("OR A J"),
+            Err(Day21Error::Parse {
+                line: 1,
+                content: String::from("OR A J")
+            })
+        );
+    }
+
+    #[test]
+    fn survey_reports_damage_or_fall() {
+        let script = Script::parse(WALK_SCRIPT).unwrap();
+        let mut program = printer("Walking...\n\n");
+        program.insert(program.len() - 1, 104);
+        program.insert(program.len() - 1, 19_349_939);
+        assert_eq!(survey(&program, &script), Ok(19_349_939));
+
+        let fall = "Walking...\n\n\nDidn't make it across:\n\n..\n@.\n#.\n\n..\n.@\n#.\n\n";
+        let Err(Day21Error::Fell(trace)) = survey(&printer(fall), &script) else {
+            panic!("expected a fall");
+        };
+        assert_eq!(trace.frames().len(), 2);
+        assert_eq!(trace.to_string(), ".. | ..\n@. | .@\n#. | #.\n");
+        assert_eq!(
+            survey(&printer("huh\n"), &script),
+            Err(Day21Error::NoReport(String::from("huh\n")))
+        );
+    }
+}
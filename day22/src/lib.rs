@@ -0,0 +1,369 @@
+use std::fmt;
+
+use shared::numtheory::{gcd, modinverse, modpow};
+use shared::solution::{Answer, Solution, answer};
+
+/// Cards in the deck for part1
+pub const SMALL_DECK: i64 = 10_007;
+/// Card followed for part1
+pub const TRACKED_CARD: i64 = 2019;
+/// Cards in the deck for part2, a prime
+pub const HUGE_DECK: i64 = 119_315_717_514_047;
+/// Times the shuffle is repeated for part2
+pub const REPEATS: u64 = 101_741_582_076_661;
+/// Position asked about for part2
+pub const TRACKED_POSITION: i64 = 2020;
+
+/// Reasons a shuffle can't be worked out
+#[derive(Debug, PartialEq)]
+pub enum Day22Error {
+    /// a line isn't a known technique
+    Malformed { line: usize, content: String },
+    /// dealing with this increment would land cards on the same position
+    Increment { increment: i64, deck: i64 },
+    /// the deck has no cards
+    EmptyDeck,
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day22Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day22Error::Malformed { line, content } => {
+                write!(f, "line {}: unknown technique {:?}", line, content)
+            }
+            Day22Error::Increment { increment, deck } => write!(
+                f,
+                "increment {} shares a factor with {} cards",
+                increment, deck
+            ),
+            Day22Error::EmptyDeck => write!(f, "deck has no cards"),
+            Day22Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day22Error {}
+
+/// Shuffle technique from the puzzle
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Technique {
+    /// reverses the deck
+    NewStack,
+    /// moves the top `n` cards to the bottom, or the bottom `-n` to the top
+    Cut(i64),
+    /// deals cards `n` positions apart, wrapping round
+    Increment(i64),
+}
+
+impl Technique {
+    /// Reads a technique such as `cut -2`
+    pub fn parse(line: &str) -> Option<Technique> {
+        let line = line.trim();
+        if line == "deal into new stack" {
+            return Some(Technique::NewStack);
+        }
+        if let Some(n) = line.strip_prefix("cut ") {
+            return n.parse().ok().map(Technique::Cut);
+        }
+        let n = line.strip_prefix("deal with increment ")?;
+        n.parse().ok().map(Technique::Increment)
+    }
+}
+
+/// Position map `x -> a * x + b` on a deck of `m` cards
+///
+/// Every technique moves the card at position `x` to such a position, and
+/// composing two keeps the form, so a whole shuffle collapses to one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affine {
+    pub a: i64,
+    pub b: i64,
+    pub m: i64,
+}
+
+impl Affine {
+    /// Map leaving every card where it is
+    pub fn identity(m: i64) -> Affine {
+        Affine { a: 1, b: 0, m }
+    }
+
+    /// Map a single technique makes
+    ///
+    /// # Returns
+    ///
+    /// * the map, or an error for an increment that isn't a shuffle
+    ///
+    pub fn of(technique: Technique, m: i64) -> Result<Affine, Day22Error> {
+        let (a, b) = match technique {
+            Technique::NewStack => (-1, -1),
+            Technique::Cut(n) => (1, -n),
+            Technique::Increment(n) if gcd(n, m) == 1 => (n, 0),
+            Technique::Increment(increment) => {
+                return Err(Day22Error::Increment { increment, deck: m });
+            }
+        };
+        Ok(Affine {
+            a: a.rem_euclid(m),
+            b: b.rem_euclid(m),
+            m,
+        })
+    }
+
+    /// Map doing `self` and then `next`
+    pub fn then(&self, next: &Affine) -> Affine {
+        let m = self.m as i128;
+        let (a, b) = (self.a as i128, self.b as i128);
+        let (c, d) = (next.a as i128, next.b as i128);
+        Affine {
+            a: (c * a % m) as i64,
+            b: ((c * b + d) % m) as i64,
+            m: self.m,
+        }
+    }
+
+    /// Map doing `self` `times` times over
+    ///
+    /// Repeating gives `a^n * x + b * (a^n - 1) / (a - 1)`, worked out
+    /// with a modular power and inverse. When `a - 1` has no inverse the
+    /// map is squared up instead.
+    pub fn repeat(&self, times: u64) -> Affine {
+        let m = self.m as i128;
+        let an = modpow(self.a, times, self.m) as i128;
+        let series = if self.a == 1 {
+            Some(times as i128 % m)
+        } else {
+            modinverse(self.a - 1, self.m).map(|inv| (an - 1).rem_euclid(m) * inv as i128 % m)
+        };
+        if let Some(series) = series {
+            return Affine {
+                a: an as i64,
+                b: (self.b as i128 * series % m) as i64,
+                m: self.m,
+            };
+        }
+        let (mut result, mut square, mut times) = (Affine::identity(self.m), *self, times);
+        while times > 0 {
+            if times & 1 == 1 {
+                result = result.then(&square);
+            }
+            square = square.then(&square);
+            times >>= 1;
+        }
+        result
+    }
+
+    /// Map undoing `self`, which exists while `a` is coprime to the deck
+    pub fn inverse(&self) -> Option<Affine> {
+        let inv = modinverse(self.a, self.m)? as i128;
+        let m = self.m as i128;
+        Some(Affine {
+            a: inv as i64,
+            b: ((-inv * self.b as i128).rem_euclid(m)) as i64,
+            m: self.m,
+        })
+    }
+
+    /// Position `x` is moved to
+    pub fn apply(&self, x: i64) -> i64 {
+        ((self.a as i128 * x as i128 + self.b as i128).rem_euclid(self.m as i128)) as i64
+    }
+}
+
+/// Works out the map a list of techniques makes
+///
+/// # Arguments
+///
+/// * `lines` - one technique per line
+/// * `deck` - number of cards
+///
+pub fn shuffle<S: AsRef<str>>(lines: &[S], deck: i64) -> Result<Affine, Day22Error> {
+    if deck < 1 {
+        return Err(Day22Error::EmptyDeck);
+    }
+    lines
+        .iter()
+        .enumerate()
+        .try_fold(Affine::identity(deck), |map, (i, line)| {
+            let technique =
+                Technique::parse(line.as_ref()).ok_or_else(|| Day22Error::Malformed {
+                    line: i + 1,
+                    content: String::from(line.as_ref()),
+                })?;
+            Ok(map.then(&Affine::of(technique, deck)?))
+        })
+}
+
+/// Cards from top to bottom after a shuffle of a factory order deck
+pub fn deck_after(map: &Affine) -> Vec<i64> {
+    let undo = map.inverse().expect("shuffles are invertible");
+    (0..map.m).map(|position| undo.apply(position)).collect()
+}
+
+/// Reads the lines of the puzzle input
+///
+/// # Arguments
+///
+/// * `filename` - path of the input
+///
+fn read_input(filename: &str) -> Result<Vec<String>, Day22Error> {
+    shared::try_ingest_file(filename).map_err(|err| Day22Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * position of card 2019 after one shuffle
+///
+pub fn part1(filename: &str) -> Result<i64, Day22Error> {
+    Ok(shuffle(&read_input(filename)?, SMALL_DECK)?.apply(TRACKED_CARD))
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * card ending at position 2020 after the huge deck is shuffled over
+///   and over
+///
+pub fn part2(filename: &str) -> Result<i64, Day22Error> {
+    let map = shuffle(&read_input(filename)?, HUGE_DECK)?;
+    let undo = map.inverse().expect("shuffles are invertible");
+    Ok(undo.repeat(REPEATS).apply(TRACKED_POSITION))
+}
+
+/// Day 22 as driven by the workspace runner
+pub struct Day22;
+
+impl Solution for Day22 {
+    fn day(&self) -> u8 {
+        22
+    }
+
+    fn title(&self) -> &'static str {
+        "Slam Shuffle"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deck(lines: &[&str]) -> Vec<i64> {
+        deck_after(&shuffle(lines, 10).unwrap())
+    }
+
+    /// Shuffles a deck by following the techniques literally
+    fn simulate(lines: &[&str], size: usize) -> Vec<i64> {
+        let mut cards: Vec<i64> = (0..size as i64).collect();
+        for line in lines {
+            match Technique::parse(line).unwrap() {
+                Technique::NewStack => cards.reverse(),
+                Technique::Cut(n) => cards.rotate_left(n.rem_euclid(size as i64) as usize),
+                Technique::Increment(n) => {
+                    let mut dealt = vec![0; size];
+                    for (i, &card) in cards.iter().enumerate() {
+                        dealt[i * n as usize % size] = card;
+                    }
+                    cards = dealt;
+                }
+            }
+        }
+        cards
+    }
+
+    #[test]
+    fn examples_work() {
+        assert_eq!(
+            deck(&[
+                "deal with increment 7",
+                "deal into new stack",
+                "deal into new stack"
+            ]),
+            vec![0, 3, 6, 9, 2, 5, 8, 1, 4, 7]
+        );
+        assert_eq!(
+            deck(&["cut 6", "deal with increment 7", "deal into new stack"]),
+            vec![3, 0, 7, 4, 1, 8, 5, 2, 9, 6]
+        );
+        assert_eq!(
+            deck(&["deal with increment 7", "deal with increment 9", "cut -2"]),
+            vec![6, 3, 0, 7, 4, 1, 8, 5, 2, 9]
+        );
+        let lines = shared::ingest_file("src/test.txt");
+        assert_eq!(
+            deck_after(&shuffle(&lines, 10).unwrap()),
+            vec![9, 2, 5, 8, 1, 4, 7, 0, 3, 6]
+        );
+    }
+
+    #[test]
+    fn matches_simulation() {
+        let lines: Vec<String> = shared::ingest_file("src/test.txt");
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let map = shuffle(&lines, 101).unwrap();
+        assert_eq!(deck_after(&map), simulate(&lines, 101));
+        let twice: Vec<&str> = lines.iter().chain(&lines).copied().collect();
+        assert_eq!(deck_after(&map.repeat(2)), simulate(&twice, 101));
+        let undo = map.inverse().unwrap();
+        assert_eq!(map.then(&undo), Affine::identity(101));
+        assert_eq!(
+            map.repeat(1234).then(&undo.repeat(1234)),
+            Affine::identity(101)
+        );
+    }
+
+    #[test]
+    fn repeat_matches_composing() {
+        let composed =
+            |map: &Affine, times| (0..times).fold(Affine::identity(map.m), |r, _| r.then(map));
+        for map in [
+            Affine { a: 3, b: 5, m: 101 },
+            Affine { a: 1, b: 7, m: 101 },
+            // a - 1 = 4 has no inverse mod 10, so this one is squared up
+            Affine { a: 5, b: 3, m: 10 },
+        ] {
+            for times in [0, 1, 2, 7, 100] {
+                assert_eq!(
+                    map.repeat(times),
+                    composed(&map, times),
+                    "{:?} {}",
+                    map,
+                    times
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            shuffle(&["cut 1", "shuffle nicely"], 10),
+            Err(Day22Error::Malformed {
+                line: 2,
+                content: String::from("shuffle nicely")
+            })
+        );
+        assert_eq!(
+            shuffle(&["deal with increment 4"], 10),
+            Err(Day22Error::Increment {
+                increment: 4,
+                deck: 10
+            })
+        );
+        assert_eq!(shuffle(&["cut 1"], 0), Err(Day22Error::EmptyDeck));
+    }
+}
@@ -0,0 +1,299 @@
+use std::fmt;
+
+use shared::intcode::{IntcodeError, Scheduler, Vm, parse_program};
+use shared::solution::{Answer, Solution, answer};
+
+/// Computers on the network
+pub const NETWORK_SIZE: usize = 50;
+/// Address the NAT listens on
+pub const NAT_ADDRESS: i64 = 255;
+/// Input a computer reads when no packet is waiting
+pub const NO_PACKET: i64 = -1;
+/// Rounds run before giving up on an answer
+pub const MAX_ROUNDS: usize = 1_000_000;
+
+/// Reasons the network can't produce an answer
+#[derive(Debug, PartialEq)]
+pub enum Day23Error {
+    /// a computer's program faulted
+    Intcode(IntcodeError),
+    /// a packet went to an address with no computer
+    BadAddress(Packet),
+    /// the network went idle before the NAT had a packet to send
+    NothingToWake,
+    /// every computer halted
+    Halted,
+    /// no answer within `MAX_ROUNDS` rounds
+    Stalled,
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day23Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day23Error::Intcode(err) => write!(f, "{}", err),
+            Day23Error::BadAddress(packet) => write!(f, "no computer for {}", packet),
+            Day23Error::NothingToWake => write!(f, "network idle and the NAT has no packet"),
+            Day23Error::Halted => write!(f, "every computer halted"),
+            Day23Error::Stalled => write!(f, "no answer after {} rounds", MAX_ROUNDS),
+            Day23Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day23Error {}
+
+impl From<IntcodeError> for Day23Error {
+    fn from(err: IntcodeError) -> Self {
+        Day23Error::Intcode(err)
+    }
+}
+
+/// Packet travelling between computers
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Packet {
+    pub dest: i64,
+    pub x: i64,
+    pub y: i64,
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "packet to {} ({}, {})", self.dest, self.x, self.y)
+    }
+}
+
+/// Network of computers running the same software
+#[derive(Debug)]
+pub struct Network {
+    computers: Scheduler,
+    /// output not yet making up a whole packet, by computer
+    partial: Vec<Vec<i64>>,
+}
+
+impl Network {
+    /// Boots every computer with its address
+    ///
+    /// # Arguments
+    ///
+    /// * `program` - network interface software
+    /// * `size` - number of computers
+    ///
+    pub fn boot(program: &[i64], size: usize) -> Network {
+        let vms = (0..size)
+            .map(|address| {
+                let mut vm = Vm::new(program.to_vec());
+                vm.push_input(address as i64);
+                vm
+            })
+            .collect();
+        Network {
+            computers: Scheduler::new(vms, Some(NO_PACKET)),
+            partial: vec![Vec::new(); size],
+        }
+    }
+
+    /// Queues a packet's values on its destination
+    fn deliver(&mut self, packet: Packet) -> Result<(), Day23Error> {
+        let dest = usize::try_from(packet.dest)
+            .ok()
+            .filter(|&d| d < self.computers.len())
+            .ok_or(Day23Error::BadAddress(packet))?;
+        self.computers.push_input(dest, packet.x);
+        self.computers.push_input(dest, packet.y);
+        Ok(())
+    }
+
+    /// Gives every computer a turn and routes what they sent
+    ///
+    /// # Returns
+    ///
+    /// * packets for the NAT in the order sent, and whether the network
+    ///   was idle
+    ///
+    pub fn round(&mut self) -> Result<(Vec<Packet>, bool), Day23Error> {
+        let round = self.computers.round()?;
+        if round.halted {
+            return Err(Day23Error::Halted);
+        }
+        let mut packets = Vec::new();
+        for (partial, output) in self.partial.iter_mut().zip(round.outputs) {
+            partial.extend(output);
+            let whole = partial.len() - partial.len() % 3;
+            packets.extend(
+                partial
+                    .drain(..whole)
+                    .collect::<Vec<_>>()
+                    .chunks(3)
+                    .map(|c| Packet {
+                        dest: c[0],
+                        x: c[1],
+                        y: c[2],
+                    }),
+            );
+        }
+        let mut to_nat = Vec::new();
+        for packet in packets {
+            if packet.dest == NAT_ADDRESS {
+                to_nat.push(packet);
+            } else {
+                self.deliver(packet)?;
+            }
+        }
+        let idle = round.idle && self.partial.iter().all(Vec::is_empty);
+        Ok((to_nat, idle))
+    }
+}
+
+/// Runs the network until a packet is sent to the NAT address
+///
+/// # Returns
+///
+/// * first packet sent to address 255
+///
+pub fn first_nat_packet(network: &mut Network) -> Result<Packet, Day23Error> {
+    for _ in 0..MAX_ROUNDS {
+        if let Some(&packet) = network.round()?.0.first() {
+            return Ok(packet);
+        }
+    }
+    Err(Day23Error::Stalled)
+}
+
+/// Runs the network with the NAT waking computer 0 whenever it goes idle
+///
+/// The NAT remembers only the last packet sent to it, and resends that
+/// to computer 0 each time every computer is idle.
+///
+/// # Returns
+///
+/// * first Y value the NAT delivers twice in a row
+///
+pub fn repeated_nat_y(network: &mut Network) -> Result<i64, Day23Error> {
+    let (mut held, mut last_sent): (Option<Packet>, Option<i64>) = (None, None);
+    for _ in 0..MAX_ROUNDS {
+        let (to_nat, idle) = network.round()?;
+        held = to_nat.last().copied().or(held);
+        if !idle {
+            continue;
+        }
+        let packet = held.ok_or(Day23Error::NothingToWake)?;
+        if last_sent == Some(packet.y) {
+            return Ok(packet.y);
+        }
+        network.deliver(Packet { dest: 0, ..packet })?;
+        last_sent = Some(packet.y);
+    }
+    Err(Day23Error::Stalled)
+}
+
+/// Reads a program from the first line of a file
+///
+/// # Arguments
+///
+/// * `filename` - path of file containing the intcode
+///
+pub fn load_program(filename: &str) -> Result<Vec<i64>, Day23Error> {
+    let input = shared::try_ingest_file(filename).map_err(|err| Day23Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })?;
+    Ok(parse_program(input.first().map_or("", |s| s))?)
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * Y value of the first packet sent to address 255
+///
+pub fn part1(filename: &str) -> Result<i64, Day23Error> {
+    let mut network = Network::boot(&load_program(filename)?, NETWORK_SIZE);
+    Ok(first_nat_packet(&mut network)?.y)
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * first Y value the NAT delivers to computer 0 twice in a row
+///
+pub fn part2(filename: &str) -> Result<i64, Day23Error> {
+    let mut network = Network::boot(&load_program(filename)?, NETWORK_SIZE);
+    repeated_nat_y(&mut network)
+}
+
+/// Day 23 as driven by the workspace runner
+pub struct Day23;
+
+impl Solution for Day23 {
+    fn day(&self) -> u8 {
+        23
+    }
+
+    fn title(&self) -> &'static str {
+        "Category Six"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Computer 0 sends (0, 0) to computer 1. Each computer then passes
+    /// packets on to the next address with y one higher, computer 3
+    /// sending to the NAT instead.
+    const RING: &str = "3,100,1005,100,11,104,1,104,0,104,0,\
+        3,101,1008,101,-1,102,1005,102,11,3,103,1001,103,1,103,\
+        1001,100,1,104,1007,100,3,102,1005,102,41,1101,0,255,104,\
+        4,104,4,101,4,103,1105,1,11";
+
+    /// Every computer sends (7, 42) to the NAT once, then only listens
+    const SHOUT: &str = "3,100,104,255,104,7,104,42,3,101,1105,1,8";
+
+    fn network(program: &str, size: usize) -> Network {
+        Network::boot(&parse_program(program).unwrap(), size)
+    }
+
+    #[test]
+    fn packets_are_routed() {
+        assert_eq!(
+            first_nat_packet(&mut network(RING, 4)),
+            Ok(Packet {
+                dest: 255,
+                x: 0,
+                y: 3
+            })
+        );
+        assert_eq!(
+            first_nat_packet(&mut network(RING, 3)),
+            Err(Day23Error::BadAddress(Packet {
+                dest: 3,
+                x: 0,
+                y: 2
+            }))
+        );
+    }
+
+    #[test]
+    fn nat_wakes_idle_network() {
+        assert_eq!(repeated_nat_y(&mut network(SHOUT, 5)), Ok(42));
+        assert_eq!(
+            repeated_nat_y(&mut network("3,100,3,101,1105,1,2", 2)),
+            Err(Day23Error::NothingToWake)
+        );
+        assert_eq!(
+            repeated_nat_y(&mut network("3,100,99", 2)),
+            Err(Day23Error::Halted)
+        );
+    }
+}
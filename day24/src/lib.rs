@@ -0,0 +1,362 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use shared::levels::LevelStack;
+use shared::solution::{Answer, Solution, answer};
+
+/// Side of the square of tiles
+pub const SIZE: usize = 5;
+/// Tile holding the inner level in the recursive layout
+pub const CENTRE: (usize, usize) = (2, 2);
+/// Minutes simulated for part2
+pub const MINUTES: usize = 200;
+
+/// Reasons the scan can't be read
+#[derive(Debug, PartialEq)]
+pub enum Day24Error {
+    /// the scan isn't five rows of five `#` and `.`
+    Malformed { row: usize, content: String },
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day24Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day24Error::Malformed { row, content } => {
+                write!(f, "row {}: expected five of # and .: {:?}", row, content)
+            }
+            Day24Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day24Error {}
+
+/// Bit of a tile in a layout, reading order from the top left
+fn bit(x: usize, y: usize) -> u32 {
+    1 << (y * SIZE + x)
+}
+
+/// Whether a bug on a tile lives on, or an empty tile gets infested, given
+/// how many of its neighbours have bugs
+fn survives(bug: bool, neighbours: u32) -> bool {
+    neighbours == 1 || (!bug && neighbours == 2)
+}
+
+/// Bugs on the 5x5 tiles, one bit per tile in reading order
+///
+/// The bits read as a number are the layout's biodiversity rating.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Layout(pub u32);
+
+impl Layout {
+    /// Reads a scan of five rows, `#` marking a bug
+    pub fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Layout, Day24Error> {
+        let malformed = |row: usize| Day24Error::Malformed {
+            row,
+            content: lines
+                .get(row)
+                .map_or(String::new(), |l| String::from(l.as_ref())),
+        };
+        if lines.len() != SIZE {
+            return Err(malformed(lines.len().min(SIZE)));
+        }
+        let mut bugs = 0;
+        for (y, line) in lines.iter().enumerate() {
+            let line = line.as_ref();
+            if line.len() != SIZE {
+                return Err(malformed(y));
+            }
+            for (x, c) in line.chars().enumerate() {
+                match c {
+                    '#' => bugs |= bit(x, y),
+                    '.' => {}
+                    _ => return Err(malformed(y)),
+                }
+            }
+        }
+        Ok(Layout(bugs))
+    }
+
+    /// Whether there is a bug at `(x, y)`
+    pub fn has_bug(&self, x: usize, y: usize) -> bool {
+        self.0 & bit(x, y) != 0
+    }
+
+    /// Number of bugs
+    pub fn count(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Bugs in one row or column, `edge` picking which
+    fn count_edge(&self, edge: Edge) -> u32 {
+        (0..SIZE)
+            .filter(|&i| {
+                let (x, y) = match edge {
+                    Edge::Top => (i, 0),
+                    Edge::Bottom => (i, SIZE - 1),
+                    Edge::Left => (0, i),
+                    Edge::Right => (SIZE - 1, i),
+                };
+                self.has_bug(x, y)
+            })
+            .count() as u32
+    }
+
+    /// Layout a minute later with no levels above or below
+    pub fn step(&self) -> Layout {
+        let mut next = 0;
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let neighbours = [(0, -1), (1, 0), (0, 1), (-1, 0)]
+                    .iter()
+                    .map(|&(dx, dy)| (x as i64 + dx, y as i64 + dy))
+                    .filter(|&(nx, ny)| {
+                        (0..SIZE as i64).contains(&nx)
+                            && (0..SIZE as i64).contains(&ny)
+                            && self.has_bug(nx as usize, ny as usize)
+                    })
+                    .count() as u32;
+                if survives(self.has_bug(x, y), neighbours) {
+                    next |= bit(x, y);
+                }
+            }
+        }
+        Layout(next)
+    }
+
+    /// First layout to appear twice as the bugs spread
+    pub fn first_repeat(&self) -> Layout {
+        let mut seen = HashSet::new();
+        let mut layout = *self;
+        while seen.insert(layout) {
+            layout = layout.step();
+        }
+        layout
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..SIZE {
+            let row: String = (0..SIZE)
+                .map(|x| if self.has_bug(x, y) { '#' } else { '.' })
+                .collect();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Side of a layout bordering a tile next to the centre
+#[derive(Clone, Copy)]
+enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// Bugs on every level of the recursive layout, the centre of each
+/// level holding the next level in
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecursiveEris {
+    levels: LevelStack<Layout>,
+}
+
+impl RecursiveEris {
+    /// Constructor for a RecursiveEris with bugs only on level 0
+    pub fn new(start: Layout) -> RecursiveEris {
+        RecursiveEris {
+            levels: LevelStack::new(Layout(start.0 & !bit(CENTRE.0, CENTRE.1))),
+        }
+    }
+
+    /// Bugs next to `(x, y)` on level `depth`, including those on the
+    /// levels outside and inside
+    fn neighbours(&self, depth: i64, x: usize, y: usize) -> u32 {
+        let level = |d| self.levels.get(d).copied().unwrap_or_default();
+        let (here, outer, inner) = (level(depth), level(depth - 1), level(depth + 1));
+        let mut count = 0;
+        for (dx, dy, edge) in [
+            (0, -1, Edge::Bottom),
+            (1, 0, Edge::Left),
+            (0, 1, Edge::Top),
+            (-1, 0, Edge::Right),
+        ] {
+            let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+            count += if !(0..SIZE as i64).contains(&nx) || !(0..SIZE as i64).contains(&ny) {
+                // off the edge lands on a tile beside the outer centre
+                let (ox, oy) = (CENTRE.0 as i64 + dx, CENTRE.1 as i64 + dy);
+                outer.has_bug(ox as usize, oy as usize) as u32
+            } else if (nx as usize, ny as usize) == CENTRE {
+                // the centre is the whole facing edge of the inner level
+                inner.count_edge(edge)
+            } else {
+                here.has_bug(nx as usize, ny as usize) as u32
+            };
+        }
+        count
+    }
+
+    /// Layouts a minute later
+    ///
+    /// Bugs spread at most one level per minute, so an empty level is
+    /// added at each end first whenever the end level has any bugs.
+    pub fn step(&mut self) {
+        let outermost = self.levels.get(self.levels.outermost()).copied();
+        if outermost.is_some_and(|l| l.count() > 0) {
+            self.levels.push_outer(Layout::default());
+        }
+        let innermost = self.levels.get(self.levels.innermost()).copied();
+        if innermost.is_some_and(|l| l.count() > 0) {
+            self.levels.push_inner(Layout::default());
+        }
+        self.levels = self.levels.map(|depth, layout| {
+            let mut next = 0;
+            for y in 0..SIZE {
+                for x in 0..SIZE {
+                    if (x, y) != CENTRE
+                        && survives(layout.has_bug(x, y), self.neighbours(depth, x, y))
+                    {
+                        next |= bit(x, y);
+                    }
+                }
+            }
+            Layout(next)
+        });
+    }
+
+    /// Bugs on every level
+    pub fn count(&self) -> u32 {
+        self.levels.iter().map(|(_, l)| l.count()).sum()
+    }
+
+    /// Layouts by level, outermost first
+    pub fn levels(&self) -> &LevelStack<Layout> {
+        &self.levels
+    }
+}
+
+/// Reads the lines of the puzzle input
+///
+/// # Arguments
+///
+/// * `filename` - path of the input
+///
+fn read_input(filename: &str) -> Result<Vec<String>, Day24Error> {
+    shared::try_ingest_file(filename).map_err(|err| Day24Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * biodiversity rating of the first layout to appear twice
+///
+pub fn part1(filename: &str) -> Result<u32, Day24Error> {
+    Ok(Layout::parse(&read_input(filename)?)?.first_repeat().0)
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * bugs across every level after 200 minutes
+///
+pub fn part2(filename: &str) -> Result<u32, Day24Error> {
+    let mut eris = RecursiveEris::new(Layout::parse(&read_input(filename)?)?);
+    for _ in 0..MINUTES {
+        eris.step();
+    }
+    Ok(eris.count())
+}
+
+/// Day 24 as driven by the workspace runner
+pub struct Day24;
+
+impl Solution for Day24 {
+    fn day(&self) -> u8 {
+        24
+    }
+
+    fn title(&self) -> &'static str {
+        "Planet of Discord"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load() -> Layout {
+        Layout::parse(&shared::ingest_file("src/test.txt")).unwrap()
+    }
+
+    #[test]
+    fn part1_works() {
+        let layout = load();
+        assert_eq!(
+            layout.step().to_string(),
+            "#..#.\n####.\n###.#\n##.##\n.##..\n"
+        );
+        let repeat = layout.first_repeat();
+        assert_eq!(repeat.to_string(), ".....\n.....\n.....\n#....\n.#...\n");
+        assert_eq!(repeat.0, 2129920);
+        assert_eq!(part1("src/test.txt"), Ok(2129920));
+    }
+
+    #[test]
+    fn part2_works() {
+        let mut eris = RecursiveEris::new(load());
+        for _ in 0..10 {
+            eris.step();
+        }
+        assert_eq!(eris.count(), 99);
+        let infested: Vec<i64> = eris
+            .levels()
+            .iter()
+            .filter(|(_, l)| l.count() > 0)
+            .map(|(depth, _)| depth)
+            .collect();
+        assert_eq!(infested, (-5..=5).collect::<Vec<_>>());
+        assert_eq!(
+            eris.levels().get(5).unwrap().to_string(),
+            "####.\n#..#.\n#..#.\n####.\n.....\n"
+        );
+        assert_eq!(
+            eris.levels().get(-5).unwrap().to_string(),
+            "..#..\n.#.#.\n....#\n.#.#.\n..#..\n"
+        );
+    }
+
+    #[test]
+    fn parse_rejects_bad_scans() {
+        assert_eq!(
+            Layout::parse(&["....."]),
+            Err(Day24Error::Malformed {
+                row: 1,
+                content: String::new()
+            })
+        );
+        assert_eq!(
+            Layout::parse(&[".....", "....", ".....", ".....", "....."]),
+            Err(Day24Error::Malformed {
+                row: 1,
+                content: String::from("....")
+            })
+        );
+    }
+}
@@ -0,0 +1,510 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use shared::intcode::{IntcodeError, Vm, ascii, parse_program};
+use shared::solution::{Answer, Solution, answer};
+
+/// Items that end the game or hang the droid when taken
+pub const DANGEROUS: [&str; 5] = [
+    "escape pod",
+    "giant electromagnet",
+    "infinite loop",
+    "molten lava",
+    "photons",
+];
+/// Room holding the weight check
+pub const CHECKPOINT: &str = "Security Checkpoint";
+/// Prompt the droid prints when waiting for a command
+const PROMPT: &str = "Command?";
+
+/// Reasons the password can't be found
+#[derive(Debug, PartialEq)]
+pub enum Day25Error {
+    /// the droid's program faulted
+    Intcode(IntcodeError),
+    /// the droid output a value that isn't an ASCII code
+    NotAscii,
+    /// the game ended, showing this
+    GameOver(String),
+    /// the droid printed something that isn't a room
+    Unrecognised(String),
+    /// exploring never reached the checkpoint's pressure plate
+    NoCheckpoint,
+    /// no combination of items gets past the checkpoint
+    Rejected,
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day25Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day25Error::Intcode(err) => write!(f, "{}", err),
+            Day25Error::NotAscii => write!(f, "droid output a value that isn't ASCII"),
+            Day25Error::GameOver(text) => write!(f, "game over:\n{}", text),
+            Day25Error::Unrecognised(text) => write!(f, "not a room:\n{}", text),
+            Day25Error::NoCheckpoint => write!(f, "never found the pressure plate"),
+            Day25Error::Rejected => write!(f, "no set of items passes the checkpoint"),
+            Day25Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day25Error {}
+
+impl From<IntcodeError> for Day25Error {
+    fn from(err: IntcodeError) -> Self {
+        Day25Error::Intcode(err)
+    }
+}
+
+/// Text link to the droid, one command at a time
+pub trait Console {
+    /// Powers the droid on
+    ///
+    /// # Returns
+    ///
+    /// * what it prints before its first prompt
+    ///
+    fn start(&mut self) -> Result<String, Day25Error>;
+
+    /// Sends one command
+    ///
+    /// # Returns
+    ///
+    /// * what the droid prints in reply
+    ///
+    fn send(&mut self, command: &str) -> Result<String, Day25Error>;
+}
+
+/// Droid driven by its intcode program through the ASCII terminal
+#[derive(Debug)]
+pub struct IntcodeConsole {
+    vm: Vm,
+}
+
+impl IntcodeConsole {
+    /// Constructor for an IntcodeConsole
+    ///
+    /// # Arguments
+    ///
+    /// * `program` - droid software
+    ///
+    pub fn new(program: &[i64]) -> IntcodeConsole {
+        IntcodeConsole {
+            vm: Vm::new(program.to_vec()),
+        }
+    }
+
+    /// Whether the game has ended
+    pub fn is_halted(&self) -> bool {
+        self.vm.is_halted()
+    }
+
+    /// Runs until the droid wants a command, returning what it printed
+    fn read(&mut self) -> Result<String, Day25Error> {
+        self.vm.run_until_blocked()?;
+        ascii(&self.vm.take_output()).ok_or(Day25Error::NotAscii)
+    }
+}
+
+impl Console for IntcodeConsole {
+    fn start(&mut self) -> Result<String, Day25Error> {
+        self.read()
+    }
+
+    fn send(&mut self, command: &str) -> Result<String, Day25Error> {
+        if self.vm.is_halted() {
+            return Err(Day25Error::GameOver(String::new()));
+        }
+        self.vm.push_ascii(command);
+        self.vm.push_ascii("\n");
+        self.read()
+    }
+}
+
+/// Room as described by the droid
+#[derive(Clone, Debug, PartialEq)]
+pub struct Room {
+    pub name: String,
+    pub doors: Vec<String>,
+    pub items: Vec<String>,
+}
+
+impl Room {
+    /// Reads the last room described in some output
+    ///
+    /// Being thrown back from the pressure plate describes two rooms, and
+    /// the droid ends up in the second.
+    ///
+    /// # Returns
+    ///
+    /// * room, or none if the text describes no room
+    ///
+    pub fn parse(text: &str) -> Option<Room> {
+        let start = text.rfind("== ")?;
+        let mut lines = text[start..].lines();
+        let name = lines
+            .next()?
+            .trim()
+            .strip_prefix("== ")?
+            .strip_suffix(" ==")?;
+        let (mut doors, mut items) = (Vec::new(), Vec::new());
+        let mut list = None;
+        for line in lines {
+            match line.trim() {
+                "Doors here lead:" => list = Some(&mut doors),
+                "Items here:" => list = Some(&mut items),
+                "" => list = None,
+                line => {
+                    if let (Some(list), Some(entry)) = (list.as_mut(), line.strip_prefix("- ")) {
+                        list.push(String::from(entry));
+                    }
+                }
+            }
+        }
+        Some(Room {
+            name: String::from(name),
+            doors,
+            items,
+        })
+    }
+}
+
+/// Door leading back the way a door goes
+pub fn opposite(door: &str) -> &'static str {
+    match door {
+        "north" => "south",
+        "south" => "north",
+        "east" => "west",
+        _ => "east",
+    }
+}
+
+/// Password from the airlock message, the number after `typing`
+pub fn password(text: &str) -> Option<String> {
+    let (_, rest) = text.split_once("typing ")?;
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    (!digits.is_empty()).then_some(digits)
+}
+
+/// Droid working its way round the ship
+#[derive(Debug)]
+pub struct Explorer<C: Console> {
+    console: C,
+    visited: HashSet<String>,
+    held: Vec<String>,
+    /// doors from the start to the checkpoint, then the door to the plate
+    checkpoint: Option<(Vec<String>, String)>,
+}
+
+impl<C: Console> Explorer<C> {
+    /// Constructor for an Explorer
+    pub fn new(console: C) -> Explorer<C> {
+        Explorer {
+            console,
+            visited: HashSet::new(),
+            held: Vec::new(),
+            checkpoint: None,
+        }
+    }
+
+    /// Items picked up so far
+    pub fn held(&self) -> &[String] {
+        &self.held
+    }
+
+    /// Sends a command the game must answer with a prompt
+    fn command(&mut self, command: &str) -> Result<String, Day25Error> {
+        let reply = self.console.send(command)?;
+        if !reply.contains(PROMPT) {
+            return Err(Day25Error::GameOver(reply));
+        }
+        Ok(reply)
+    }
+
+    /// Moves through a door
+    fn go(&mut self, door: &str) -> Result<Room, Day25Error> {
+        let reply = self.command(door)?;
+        Room::parse(&reply).ok_or(Day25Error::Unrecognised(reply))
+    }
+
+    /// Explores every room reachable from `room` and comes back
+    fn explore(&mut self, room: Room, path: &mut Vec<String>) -> Result<(), Day25Error> {
+        self.visited.insert(room.name.clone());
+        for item in &room.items {
+            if !DANGEROUS.contains(&item.as_str()) {
+                self.command(&format!("take {}", item))?;
+                self.held.push(item.clone());
+            }
+        }
+        let came_through = path.last().map(|door| opposite(door));
+        for door in room
+            .doors
+            .iter()
+            .filter(|&d| Some(d.as_str()) != came_through)
+        {
+            let next = self.go(door)?;
+            if next.name == room.name {
+                // thrown back off the pressure plate
+                self.checkpoint = Some((path.clone(), door.clone()));
+                continue;
+            }
+            if !self.visited.contains(&next.name) {
+                path.push(door.clone());
+                self.explore(next, path)?;
+                path.pop();
+            }
+            self.go(opposite(door))?;
+        }
+        Ok(())
+    }
+
+    /// Explores the ship, picking up everything safe, then tries sets of
+    /// items on the pressure plate until one weighs just right
+    ///
+    /// Sets are tried in Gray code order so each try only takes or drops
+    /// a single item.
+    ///
+    /// # Returns
+    ///
+    /// * airlock password
+    ///
+    pub fn solve(&mut self) -> Result<String, Day25Error> {
+        let intro = self.console.start()?;
+        let start = Room::parse(&intro).ok_or(Day25Error::Unrecognised(intro))?;
+        self.explore(start, &mut Vec::new())?;
+        let (path, plate) = self.checkpoint.clone().ok_or(Day25Error::NoCheckpoint)?;
+        for door in &path {
+            self.go(door)?;
+        }
+
+        let items = self.held.clone();
+        let mut holding: u64 = (1 << items.len()) - 1;
+        for i in 0..1u64 << items.len() {
+            let wanted = i ^ (i >> 1);
+            for (bit, item) in items.iter().enumerate() {
+                match (holding >> bit & 1, wanted >> bit & 1) {
+                    (1, 0) => self.command(&format!("drop {}", item))?,
+                    (0, 1) => self.command(&format!("take {}", item))?,
+                    _ => continue,
+                };
+            }
+            holding = wanted;
+            let reply = self.console.send(&plate)?;
+            if let Some(password) = password(&reply) {
+                self.held = items
+                    .iter()
+                    .enumerate()
+                    .filter(|&(bit, _)| holding >> bit & 1 == 1)
+                    .map(|(_, item)| item.clone())
+                    .collect();
+                return Ok(password);
+            }
+            if !reply.contains(PROMPT) {
+                return Err(Day25Error::GameOver(reply));
+            }
+        }
+        Err(Day25Error::Rejected)
+    }
+}
+
+/// Reads a program from the first line of a file
+///
+/// # Arguments
+///
+/// * `filename` - path of file containing the intcode
+///
+pub fn load_program(filename: &str) -> Result<Vec<i64>, Day25Error> {
+    let input = shared::try_ingest_file(filename).map_err(|err| Day25Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })?;
+    Ok(parse_program(input.first().map_or("", |s| s))?)
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * password for the main airlock
+///
+pub fn part1(filename: &str) -> Result<String, Day25Error> {
+    Explorer::new(IntcodeConsole::new(&load_program(filename)?)).solve()
+}
+
+/// Day 25 as driven by the workspace runner
+pub struct Day25;
+
+impl Solution for Day25 {
+    fn day(&self) -> u8 {
+        25
+    }
+
+    fn title(&self) -> &'static str {
+        "Cryostasis"
+    }
+
+    fn parts(&self) -> u8 {
+        1
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Doors with the rooms they lead to, and items lying in a room
+    type FakeRoom = (Vec<(&'static str, &'static str)>, Vec<&'static str>);
+
+    /// Tiny ship in the game's own words, the plate wanting the mug and
+    /// the ring
+    struct FakeShip {
+        rooms: HashMap<&'static str, FakeRoom>,
+        at: &'static str,
+        held: Vec<&'static str>,
+    }
+
+    impl FakeShip {
+        fn new() -> FakeShip {
+            let rooms = HashMap::from([
+                (
+                    "Hull Breach",
+                    (vec![("north", "Kitchen"), ("east", "Hallway")], vec![]),
+                ),
+                (
+                    "Kitchen",
+                    (vec![("south", "Hull Breach")], vec!["mug", "photons"]),
+                ),
+                (
+                    "Hallway",
+                    (
+                        vec![
+                            ("west", "Hull Breach"),
+                            ("east", CHECKPOINT),
+                            ("south", "Storage"),
+                        ],
+                        vec!["coin"],
+                    ),
+                ),
+                (
+                    "Storage",
+                    (vec![("north", "Hallway")], vec!["ring", "infinite loop"]),
+                ),
+                (
+                    CHECKPOINT,
+                    (
+                        vec![("west", "Hallway"), ("north", "Pressure-Sensitive Floor")],
+                        vec![],
+                    ),
+                ),
+            ]);
+            FakeShip {
+                rooms,
+                at: "Hull Breach",
+                held: Vec::new(),
+            }
+        }
+
+        fn describe(&self, name: &str) -> String {
+            let (doors, items) = &self.rooms[name];
+            let mut text = format!("\n\n\n== {} ==\nA room.\n\nDoors here lead:\n", name);
+            for (door, _) in doors {
+                text += &format!("- {}\n", door);
+            }
+            if !items.is_empty() {
+                text += "\nItems here:\n";
+                for item in items {
+                    text += &format!("- {}\n", item);
+                }
+            }
+            text + "\nCommand?\n"
+        }
+    }
+
+    impl Console for FakeShip {
+        fn start(&mut self) -> Result<String, Day25Error> {
+            Ok(self.describe(self.at))
+        }
+
+        fn send(&mut self, command: &str) -> Result<String, Day25Error> {
+            if let Some(item) = command.strip_prefix("take ") {
+                assert!(!DANGEROUS.contains(&item), "took {}", item);
+                let items = &mut self.rooms.get_mut(self.at).unwrap().1;
+                let i = items.iter().position(|&it| it == item).unwrap();
+                self.held.push(items.remove(i));
+                return Ok(format!("\nYou take the {}.\n\nCommand?\n", item));
+            }
+            if let Some(item) = command.strip_prefix("drop ") {
+                let i = self.held.iter().position(|&it| it == item).unwrap();
+                let item = self.held.remove(i);
+                self.rooms.get_mut(self.at).unwrap().1.push(item);
+                return Ok(format!("\nYou drop the {}.\n\nCommand?\n", item));
+            }
+            let (_, to) = *self.rooms[self.at]
+                .0
+                .iter()
+                .find(|(door, _)| *door == command)
+                .unwrap();
+            if to != "Pressure-Sensitive Floor" {
+                self.at = to;
+                return Ok(self.describe(to));
+            }
+            let mut held = self.held.clone();
+            held.sort();
+            if held == ["mug", "ring"] {
+                return Ok(String::from(
+                    "\n\n\n== Pressure-Sensitive Floor ==\n\"Oh, hello! You should be able \
+                     to get in by typing 12345 on the keypad at the main airlock.\"\n",
+                ));
+            }
+            Ok(format!(
+                "\n\n\n== Pressure-Sensitive Floor ==\nAnalyzing...\n\nDoors here lead:\n\
+                 - south\n\nA loud, robotic voice says \"Alert! Droids on this ship are \
+                 lighter than the detected value!\" and you are ejected back to the \
+                 checkpoint.\n{}",
+                self.describe(CHECKPOINT)
+            ))
+        }
+    }
+
+    #[test]
+    fn solver_finds_password() {
+        let mut explorer = Explorer::new(FakeShip::new());
+        assert_eq!(explorer.solve(), Ok(String::from("12345")));
+        let mut held = explorer.held().to_vec();
+        held.sort();
+        assert_eq!(held, vec!["mug", "ring"]);
+    }
+
+    #[test]
+    fn room_parse_takes_last_room() {
+        let ship = FakeShip::new();
+        let room = Room::parse(&ship.describe("Storage")).unwrap();
+        assert_eq!(
+            room,
+            Room {
+                name: String::from("Storage"),
+                doors: vec![String::from("north")],
+                items: vec![String::from("ring"), String::from("infinite loop")],
+            }
+        );
+        let text = ship.describe("Kitchen") + &ship.describe("Hallway");
+        assert_eq!(Room::parse(&text).unwrap().name, "Hallway");
+        assert_eq!(Room::parse("Command?\n"), None);
+    }
+
+    #[test]
+    fn password_is_read() {
+        assert_eq!(
+            password("You should be able to get in by typing 2424308736 on the keypad"),
+            Some(String::from("2424308736"))
+        );
+        assert_eq!(password("typing nothing"), None);
+    }
+}
@@ -0,0 +1,57 @@
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+
+use clap::Parser;
+use day25::{Console, IntcodeConsole, load_program, part1};
+use shared::args::DEFAULT_INPUT;
+
+/// Droid searching the cryostasis ship for the airlock password
+#[derive(Parser)]
+struct Cli {
+    /// file holding the droid software
+    #[arg(long, default_value = DEFAULT_INPUT)]
+    input: String,
+    /// play the adventure yourself instead of solving it
+    #[arg(long)]
+    interactive: bool,
+}
+
+/// Relays commands from stdin to the droid until the game ends
+fn play(console: &mut IntcodeConsole) -> Result<(), Box<dyn std::error::Error>> {
+    print!("{}", console.start()?);
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
+        if console.is_halted() {
+            break;
+        }
+        print!("{}", console.send(line?.trim())?);
+        io::stdout().flush()?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if cli.interactive {
+        let result = load_program(&cli.input)
+            .map_err(Into::into)
+            .and_then(|program| play(&mut IntcodeConsole::new(&program)));
+        return match result {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("{}", err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+    match part1(&cli.input) {
+        Ok(answer) => {
+            println!("Part1: {}", answer);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Part1: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
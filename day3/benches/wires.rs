@@ -0,0 +1,69 @@
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use day3::{Command, Coord, Direction, Wire, part2};
+
+const INPUT: &str = "src/input.txt";
+
+/// Builds a staircase wire of `len` commands that zigzags across the other
+/// one, so crossovers grow with the size of the wires
+///
+/// # Arguments
+///
+/// * len - number of commands in the wire
+/// * first - heads right then up when true, otherwise up then right
+fn synthetic_wire(len: usize, first: bool) -> Wire {
+    let (a, b) = match first {
+        true => (Direction::Right, Direction::Up),
+        false => (Direction::Up, Direction::Right),
+    };
+    let cmds = (0..len)
+        .map(|i| Command {
+            dir: if i % 2 == 0 { a } else { b },
+            count: 10 + (i % 7) as u32,
+        })
+        .collect();
+    Wire::from_commands(cmds)
+}
+
+fn input_wires() -> (Wire, Wire) {
+    let input = shared::ingest_file(INPUT);
+    (Wire::new(&input[0]).unwrap(), Wire::new(&input[1]).unwrap())
+}
+
+fn bench_input(c: &mut Criterion) {
+    let (wire_one, wire_two) = input_wires();
+    let origin = Coord { x: 0, y: 0 };
+    c.bench_function("input/trace", |b| {
+        b.iter(|| black_box(&wire_one).trace(origin))
+    });
+    c.bench_function("input/trace_corners", |b| {
+        b.iter(|| black_box(&wire_one).trace_corners(origin))
+    });
+    c.bench_function("input/crossovers", |b| {
+        b.iter(|| black_box(&wire_one).crossovers(black_box(&wire_two)))
+    });
+    c.bench_function("input/part2", |b| b.iter(|| part2(black_box(INPUT))));
+}
+
+fn bench_synthetic(c: &mut Criterion) {
+    let mut group = c.benchmark_group("synthetic");
+    let origin = Coord { x: 0, y: 0 };
+    for len in [100, 300, 1000] {
+        let wire_one = synthetic_wire(len, true);
+        let wire_two = synthetic_wire(len, false);
+        group.bench_with_input(BenchmarkId::new("trace", len), &wire_one, |b, wire| {
+            b.iter(|| wire.trace(origin))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("trace_corners", len),
+            &wire_one,
+            |b, wire| b.iter(|| wire.trace_corners(origin)),
+        );
+        group.bench_with_input(BenchmarkId::new("crossovers", len), &wire_one, |b, wire| {
+            b.iter(|| wire.crossovers(&wire_two))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_input, bench_synthetic);
+criterion_main!(benches);
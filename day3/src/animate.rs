@@ -0,0 +1,209 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::{Cell, Coord, Panel, Raster, ViewOptions};
+
+/// Snapshot of the panel part way through laying down the wires
+pub struct Frame<'a> {
+    /// zero based number of the frame
+    pub index: usize,
+    /// steps each wire has taken so far
+    pub steps: usize,
+    raster: &'a Raster,
+}
+
+impl Frame<'_> {
+    /// Renders the frame from the top of the region down
+    ///
+    /// # Arguments
+    ///
+    /// * no_color - skip ANSI escapes
+    pub fn lines(&self, no_color: bool) -> Vec<String> {
+        self.raster.lines(no_color)
+    }
+
+    /// Encodes the frame as a binary PPM image, one pixel per block
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let height = self.raster.rows.len();
+        let width = self.raster.rows.first().map_or(0, |r| r.len());
+        let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        for row in self.raster.rows.iter().rev() {
+            for cell in row {
+                image.extend(cell.rgb());
+            }
+        }
+        image
+    }
+}
+
+impl Panel {
+    /// Replays both wires being laid down in lockstep
+    ///
+    /// A crossover is drawn once both wires have reached it. The final
+    /// frame is always emitted and matches the fully traced panel.
+    ///
+    /// # Arguments
+    ///
+    /// * opts - view options for each frame
+    /// * every - steps between frames
+    /// * on_frame - sink receiving each frame
+    pub fn animate<F>(&self, opts: &ViewOptions, every: usize, mut on_frame: F) -> io::Result<()>
+    where
+        F: FnMut(&Frame) -> io::Result<()>,
+    {
+        let every = every.max(1);
+        let steps_one = self.0.step_map(self.0.start());
+        let steps_two = self.1.step_map(self.1.start());
+        let mut appearances: Vec<(usize, Coord)> = self
+            .0
+            .crossovers(&self.1)
+            .into_iter()
+            .filter_map(|c| Some((*steps_one.get(&c)?.max(steps_two.get(&c)?) as usize, c)))
+            .collect();
+        appearances.sort();
+        let mut appearances = appearances.into_iter().peekable();
+
+        let mut raster = self.blank_raster(opts);
+        raster.mark(self.0.start(), Cell::Origin);
+        raster.mark(self.1.start(), Cell::Origin);
+        let mut trace_one = self.0.trace_iter(self.0.start());
+        let mut trace_two = self.1.trace_iter(self.1.start());
+        let mut steps = 0;
+        let mut index = 0;
+        loop {
+            let (next_one, next_two) = (trace_one.next(), trace_two.next());
+            if next_one.is_none() && next_two.is_none() {
+                break;
+            }
+            steps += 1;
+            if let Some(coord) = next_one {
+                raster.mark(coord, Cell::WireOne);
+            }
+            if let Some(coord) = next_two {
+                raster.mark(coord, Cell::WireTwo);
+            }
+            while let Some((_, coord)) = appearances.next_if(|(step, _)| *step <= steps) {
+                raster.mark(coord, Cell::Crossover);
+            }
+            if steps % every == 0 {
+                on_frame(&Frame {
+                    index,
+                    steps,
+                    raster: &raster,
+                })?;
+                index += 1;
+            }
+        }
+        if steps % every != 0 || steps == 0 {
+            on_frame(&Frame {
+                index,
+                steps,
+                raster: &raster,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Sink drawing each frame over the previous one using ANSI escapes
+///
+/// # Arguments
+///
+/// * out - terminal to draw to
+/// * no_color - draw plain glyphs without ANSI colors
+pub fn ansi_sink<W: Write>(
+    out: &mut W,
+    no_color: bool,
+) -> impl FnMut(&Frame) -> io::Result<()> + '_ {
+    move |frame| {
+        write!(out, "\x1b[H\x1b[2J")?;
+        for line in frame.lines(no_color) {
+            writeln!(out, "{}", line)?;
+        }
+        writeln!(out, "step {}", frame.steps)?;
+        out.flush()
+    }
+}
+
+/// Sink writing each frame to `dir` as a numbered PPM image
+///
+/// # Arguments
+///
+/// * dir - existing directory to write `frame_NNNNN.ppm` files into
+pub fn ppm_sink(dir: &Path) -> impl FnMut(&Frame) -> io::Result<()> + '_ {
+    move |frame| {
+        let path = dir.join(format!("frame_{:05}.ppm", frame.index));
+        fs::write(path, frame.to_ppm())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_wires;
+
+    fn test_panel() -> Panel {
+        let (wire_one, wire_two) = load_wires("src/test.txt").unwrap();
+        Panel(wire_one, wire_two)
+    }
+
+    #[test]
+    fn animate_final_frame_matches_raster() {
+        let panel = test_panel();
+        let opts = ViewOptions::default();
+        let mut frames = Vec::new();
+        panel
+            .animate(&opts, 100, |frame| {
+                frames.push((frame.index, frame.steps, frame.lines(true)));
+                Ok(())
+            })
+            .unwrap();
+
+        let steps: Vec<usize> = frames.iter().map(|f| f.1).collect();
+        assert_eq!(steps, vec![100, 200, 300, 400, 484]);
+        assert_eq!(frames.last().unwrap().0, 4);
+        assert_eq!(frames.last().unwrap().2, panel.raster(&opts).lines(true));
+    }
+
+    #[test]
+    fn animate_draws_crossover_once_both_wires_arrive() {
+        let panel = test_panel();
+        let crossover_frames: Vec<usize> = {
+            let mut counts = Vec::new();
+            panel
+                .animate(&ViewOptions::default(), 1, |frame| {
+                    let cells = frame.raster.rows.iter().flatten();
+                    counts.push(cells.filter(|&&c| c == Cell::Crossover).count());
+                    Ok(())
+                })
+                .unwrap();
+            counts
+        };
+
+        // (146, 46) is reached after 290 steps by wire one and 334 by wire two
+        assert_eq!(crossover_frames[332], 0);
+        assert_eq!(crossover_frames[333], 1);
+        assert_eq!(*crossover_frames.last().unwrap(), 4);
+    }
+
+    #[test]
+    fn frame_to_ppm_works() {
+        let panel = test_panel();
+        let opts = ViewOptions {
+            max_width: Some(20),
+            ..ViewOptions::default()
+        };
+        let mut image = Vec::new();
+        panel
+            .animate(&opts, 10_000, |frame| {
+                image = frame.to_ppm();
+                Ok(())
+            })
+            .unwrap();
+
+        let header = b"P6\n20 13\n255\n";
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(image.len(), header.len() + 20 * 13 * 3);
+    }
+}
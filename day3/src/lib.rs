@@ -1,30 +1,132 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Display;
+use std::io;
+use std::iter;
+use std::num::ParseIntError;
 
-#[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
-enum Direction {
+use serde::{Deserialize, Serialize};
+use shared::solution::{Answer, Solution, answer};
+
+mod animate;
+mod viewer;
+
+pub use animate::{Frame, ansi_sink, ppm_sink};
+
+/// Direction a wire travels in for a single command
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Hash, Copy, Clone, Serialize, Deserialize)]
+pub enum Direction {
     Up,
     Down,
     Left,
     Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// Change in x and y for a single step in this direction
+    pub fn delta(&self) -> (i64, i64) {
+        match self {
+            Direction::Up => (0, 1),
+            Direction::Down => (0, -1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::UpLeft => (-1, 1),
+            Direction::UpRight => (1, 1),
+            Direction::DownLeft => (-1, -1),
+            Direction::DownRight => (1, -1),
+        }
+    }
+
+    /// Direction after a quarter turn anticlockwise
+    pub fn rotate90(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+            Direction::UpLeft => Direction::DownLeft,
+            Direction::DownLeft => Direction::DownRight,
+            Direction::DownRight => Direction::UpRight,
+            Direction::UpRight => Direction::UpLeft,
+        }
+    }
+
+    /// Direction after mirroring across an axis
+    ///
+    /// # Arguments
+    ///
+    /// * `axis` - axis to mirror across
+    pub fn mirror(&self, axis: Axis) -> Direction {
+        match (axis, self) {
+            (Axis::X, Direction::Up) => Direction::Down,
+            (Axis::X, Direction::Down) => Direction::Up,
+            (Axis::X, Direction::UpLeft) => Direction::DownLeft,
+            (Axis::X, Direction::DownLeft) => Direction::UpLeft,
+            (Axis::X, Direction::UpRight) => Direction::DownRight,
+            (Axis::X, Direction::DownRight) => Direction::UpRight,
+            (Axis::Y, Direction::Left) => Direction::Right,
+            (Axis::Y, Direction::Right) => Direction::Left,
+            (Axis::Y, Direction::UpLeft) => Direction::UpRight,
+            (Axis::Y, Direction::UpRight) => Direction::UpLeft,
+            (Axis::Y, Direction::DownLeft) => Direction::DownRight,
+            (Axis::Y, Direction::DownRight) => Direction::DownLeft,
+            (_, dir) => *dir,
+        }
+    }
 }
 
 impl Display for Direction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let ch = match self {
-            Direction::Up => 'U',
-            Direction::Down => 'D',
-            Direction::Left => 'L',
-            Direction::Right => 'R',
+        let s = match self {
+            Direction::Up => "U",
+            Direction::Down => "D",
+            Direction::Left => "L",
+            Direction::Right => "R",
+            Direction::UpLeft => "UL",
+            Direction::UpRight => "UR",
+            Direction::DownLeft => "DL",
+            Direction::DownRight => "DR",
         };
-        write!(f, "{}", ch)
+        write!(f, "{}", s)
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd, Ord, Eq, Copy, Clone, Hash)]
-struct Coord {
-    x: i64,
-    y: i64,
+/// Axis a wire is mirrored across
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Axis {
+    /// horizontal axis, flipping y
+    X,
+    /// vertical axis, flipping x
+    Y,
+}
+
+/// Location on the panel, with y increasing upwards
+#[derive(
+    Debug, Default, PartialEq, PartialOrd, Ord, Eq, Copy, Clone, Hash, Serialize, Deserialize,
+)]
+pub struct Coord {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Coord {
+    /// Constructor for a Coord
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - horizontal position
+    /// * `y` - vertical position
+    pub fn new(x: i64, y: i64) -> Self {
+        Coord { x, y }
+    }
+
+    /// Whether this is the central port the wires usually start from
+    pub fn is_origin(&self) -> bool {
+        *self == Coord::default()
+    }
 }
 
 impl Display for Coord {
@@ -33,28 +135,65 @@ impl Display for Coord {
     }
 }
 
+/// Distance metric used when ranking crossovers
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub enum Metric {
+    /// |dx| + |dy|
+    #[default]
+    Manhattan,
+    /// max(|dx|, |dy|)
+    Chebyshev,
+    /// dx² + dy²
+    EuclideanSquared,
+}
+
+impl Metric {
+    /// Determines the distance between two points
+    ///
+    /// # Arguments
+    ///
+    /// * a - first point
+    /// * b - second point
+    ///
+    /// # Returns
+    ///
+    /// * distance under this metric
+    pub fn distance(&self, a: Coord, b: Coord) -> i64 {
+        let (dx, dy) = ((a.x - b.x).abs(), (a.y - b.y).abs());
+        match self {
+            Metric::Manhattan => dx + dy,
+            Metric::Chebyshev => dx.max(dy),
+            Metric::EuclideanSquared => dx * dx + dy * dy,
+        }
+    }
+}
+
+/// Line an interval runs along
 #[derive(Debug, PartialEq)]
-enum Orientation {
+pub enum Orientation {
     Horizontal,
     Vertical,
+    /// 45° line where y increases with x
+    Rising,
+    /// 45° line where y decreases as x increases
+    Falling,
 }
 
 impl Display for Orientation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            if *self == Orientation::Vertical {
-                "Vertical"
-            } else {
-                "Horizontal"
-            }
-        )
+        let s = match self {
+            Orientation::Horizontal => "Horizontal",
+            Orientation::Vertical => "Vertical",
+            Orientation::Rising => "Rising",
+            Orientation::Falling => "Falling",
+        };
+        write!(f, "{}", s)
     }
 }
 
+/// Straight interval of a wire between two corners, inclusive
 #[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Copy, Clone)]
-struct CornerPair(Coord, Coord);
+pub struct CornerPair(pub Coord, pub Coord);
 
 impl Display for CornerPair {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -63,8 +202,39 @@ impl Display for CornerPair {
 }
 
 impl CornerPair {
+    /// Constructor for a CornerPair
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - corner the interval starts at
+    /// * `end` - corner the interval ends at, either sharing an axis with
+    ///   `start` or lying on a 45° diagonal from it
+    pub fn new(start: Coord, end: Coord) -> Self {
+        CornerPair(start, end)
+    }
+
+    /// Unit step from the first corner towards the second and the number
+    /// of steps between them
+    fn step(&self) -> ((i64, i64), i64) {
+        let (dx, dy) = (self.1.x - self.0.x, self.1.y - self.0.y);
+        ((dx.signum(), dy.signum()), dx.abs().max(dy.abs()))
+    }
+
+    /// Every point on the interval, including both corners
+    fn points(&self) -> impl Iterator<Item = Coord> + use<> {
+        let ((dx, dy), len) = self.step();
+        let start = self.0;
+        (0..=len).map(move |t| Coord {
+            x: start.x + t * dx,
+            y: start.y + t * dy,
+        })
+    }
+
     /// Determines the intersecting point of two intervals
     ///
+    /// Only crossings strictly inside both intervals count, points where
+    /// either interval starts or ends are excluded.
+    ///
     /// # Arguments
     ///
     /// * other - the other CornerPair to compare against
@@ -72,59 +242,141 @@ impl CornerPair {
     /// # Returns
     ///
     /// * point of intersection or none
-    fn intersection(&self, other: CornerPair) -> Option<Coord> {
-        match (self.orientation(), other.orientation()) {
-            (Orientation::Horizontal, Orientation::Vertical) => {
-                let (x1, x2) = (self.0.x, self.1.x);
-                let (y1, y2) = (other.0.y, other.1.y);
-                if ((x1.min(x2) + 1)..x1.max(x2)).contains(&other.0.x)
-                    && ((y1.min(y2) + 1)..y1.max(y2)).contains(&self.0.y)
-                {
-                    return Some(Coord {
-                        x: other.0.x,
-                        y: self.0.y,
-                    });
-                }
-                None
-            }
-            (Orientation::Vertical, Orientation::Horizontal) => {
-                let (y1, y2) = (self.0.y, self.1.y);
-                let (x1, x2) = (other.0.x, other.1.x);
-                if ((y1.min(y2) + 1)..y1.max(y2)).contains(&other.0.y)
-                    && ((x1.min(x2) + 1)..x1.max(x2)).contains(&self.0.x)
-                {
-                    return Some(Coord {
-                        x: self.0.x,
-                        y: other.0.y,
-                    });
-                }
-                None
-            }
-            _ => None,
+    pub fn intersection(&self, other: CornerPair) -> Option<Coord> {
+        self.intersection_with(other, Endpoints::Excluded)
+    }
+
+    /// Determines the intersecting point of two intervals
+    ///
+    /// Parallel intervals never intersect, even when they overlap.
+    ///
+    /// # Arguments
+    ///
+    /// * other - the other CornerPair to compare against
+    /// * endpoints - whether points where either interval starts or ends
+    ///   count
+    ///
+    /// # Returns
+    ///
+    /// * point of intersection or none
+    pub fn intersection_with(&self, other: CornerPair, endpoints: Endpoints) -> Option<Coord> {
+        let ((dx1, dy1), len1) = self.step();
+        let ((dx2, dy2), len2) = other.step();
+        // solve self.0 + t * d1 == other.0 + u * d2 for t and u
+        let det = dx2 * dy1 - dx1 * dy2;
+        if det == 0 {
+            return None;
+        }
+        let (rx, ry) = (other.0.x - self.0.x, other.0.y - self.0.y);
+        let t_num = dx2 * ry - dy2 * rx;
+        let u_num = dx1 * ry - dy1 * rx;
+        if t_num % det != 0 || u_num % det != 0 {
+            // diagonals crossing between cells never share a coordinate
+            return None;
+        }
+        let (t, u) = (t_num / det, u_num / det);
+        let within = |t: i64, len: i64| match endpoints {
+            Endpoints::Excluded => (1..len).contains(&t),
+            Endpoints::Included => (0..=len).contains(&t),
+        };
+        if within(t, len1) && within(u, len2) {
+            return Some(Coord {
+                x: self.0.x + t * dx1,
+                y: self.0.y + t * dy1,
+            });
+        }
+        None
+    }
+
+    /// Determines where two intervals meet and how
+    ///
+    /// Parallel intervals are left to `overlap`.
+    ///
+    /// # Arguments
+    ///
+    /// * other - the other CornerPair to compare against
+    ///
+    /// # Returns
+    ///
+    /// * meeting point and whether the intervals cross there or one merely
+    ///   touches the other with a corner, or none
+    pub fn contact(&self, other: CornerPair) -> Option<Intersection> {
+        let point = self.intersection_with(other, Endpoints::Included)?;
+        let kind = match self.intersection(other) {
+            Some(_) => IntersectionKind::Cross,
+            None => IntersectionKind::TouchEndpoint,
+        };
+        Some(Intersection { point, kind })
+    }
+
+    /// Determines the stretch two intervals share when they run along the
+    /// same row, column or diagonal
+    ///
+    /// Intervals only touching at a single point do not overlap.
+    ///
+    /// # Arguments
+    ///
+    /// * other - the other CornerPair to compare against
+    ///
+    /// # Returns
+    ///
+    /// * shared interval, running the same way as `self`, or none
+    pub fn overlap(&self, other: CornerPair) -> Option<CornerPair> {
+        let ((dx, dy), len) = self.step();
+        if len == 0 {
+            return None;
+        }
+        // position of a point along self, if it lies on the same line
+        let along = |point: Coord| {
+            let (rx, ry) = (point.x - self.0.x, point.y - self.0.y);
+            let t = if dx != 0 { rx * dx } else { ry * dy };
+            (rx == t * dx && ry == t * dy).then_some(t)
+        };
+        let (t1, t2) = (along(other.0)?, along(other.1)?);
+        let (lo, hi) = (t1.min(t2).max(0), t1.max(t2).min(len));
+        if lo >= hi {
+            return None;
         }
+        let at = |t: i64| Coord {
+            x: self.0.x + t * dx,
+            y: self.0.y + t * dy,
+        };
+        Some(CornerPair(at(lo), at(hi)))
     }
 
-    fn orientation(&self) -> Orientation {
+    /// Classifies the line the interval runs along
+    ///
+    /// # Returns
+    ///
+    /// * horizontal, vertical, or the direction of a 45° diagonal
+    pub fn orientation(&self) -> Orientation {
         let CornerPair(c1, c2) = self;
         if c1.x == c2.x {
             return Orientation::Vertical;
         }
-        return Orientation::Horizontal;
+        if c1.y == c2.y {
+            return Orientation::Horizontal;
+        }
+        if (c2.x - c1.x).signum() == (c2.y - c1.y).signum() {
+            return Orientation::Rising;
+        }
+        Orientation::Falling
     }
 
+    /// Determines whether a point lies on the interval, corners included
+    ///
+    /// # Arguments
+    ///
+    /// * point - location to test
+    ///
+    /// # Returns
+    ///
+    /// * true if the point is on the interval
     pub fn on_interval(&self, point: Coord) -> bool {
-        match self.orientation() {
-            Orientation::Vertical => {
-                let miny = self.0.y.min(self.1.y);
-                let maxy = self.0.y.max(self.1.y);
-                self.0.x == point.x && (miny..=maxy).contains(&point.y)
-            }
-            Orientation::Horizontal => {
-                let minx = self.0.x.min(self.1.x);
-                let maxx = self.0.x.max(self.1.x);
-                self.0.y == point.y && (minx..=maxx).contains(&point.x)
-            }
-        }
+        let ((dx, dy), len) = self.step();
+        let (rx, ry) = (point.x - self.0.x, point.y - self.0.y);
+        let t = if dx != 0 { rx * dx } else { ry * dy };
+        (0..=len).contains(&t) && rx == t * dx && ry == t * dy
     }
 
     /// Takes a point and returns char representation
@@ -135,27 +387,151 @@ impl CornerPair {
     ///
     /// # Returns
     ///
-    /// * either - | or +
+    /// * either - |, /, \\ or +
     pub fn char_point(&self, point: Coord, curr: Option<char>) -> char {
         if self.0 == point || self.1 == point {
             return '+';
         }
 
-        match (self.on_interval(point), self.orientation(), curr) {
-            (true, Orientation::Vertical, Some('.')) => '|',
-            (true, Orientation::Horizontal, Some('.')) => '=',
-            (true, Orientation::Vertical, None) => '|',
-            (true, Orientation::Horizontal, None) => '=',
-            (false, _, Some(c)) => c,
+        let line = match self.orientation() {
+            Orientation::Vertical => '|',
+            Orientation::Horizontal => '=',
+            Orientation::Rising => '/',
+            Orientation::Falling => '\\',
+        };
+        match (self.on_interval(point), curr) {
+            (true, Some('.')) | (true, None) => line,
+            (false, Some(c)) => c,
             _ => '.',
         }
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
-struct Command {
-    dir: Direction,
-    count: u32,
+/// Reason a command token failed to parse
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParseErrorReason {
+    /// token contained no characters
+    Empty,
+    /// leading letters aren't one of U, D, L, R, UL, UR, DL or DR
+    UnknownDirection(String),
+    /// count following the direction isn't a valid number
+    InvalidCount(ParseIntError),
+    /// input didn't contain a line for the wire
+    MissingWire,
+    /// input file couldn't be read, with the reason
+    Io(String),
+}
+
+impl Display for ParseErrorReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorReason::Empty => write!(f, "empty command"),
+            ParseErrorReason::UnknownDirection(dir) => write!(f, "unknown direction {}", dir),
+            ParseErrorReason::InvalidCount(err) => write!(f, "invalid count ({})", err),
+            ParseErrorReason::MissingWire => write!(f, "missing wire"),
+            ParseErrorReason::Io(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Failure to parse a wire from its input line
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseError {
+    /// offending token
+    pub token: String,
+    /// zero based index of the input line
+    pub line: usize,
+    /// zero based byte offset of the token within the line
+    pub position: usize,
+    pub reason: ParseErrorReason,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let ParseErrorReason::Io(_) = self.reason {
+            return write!(f, "{}: {}", self.token, self.reason);
+        }
+        write!(
+            f,
+            "line {}, column {}: {} in token '{}'",
+            self.line + 1,
+            self.position + 1,
+            self.reason,
+            self.token
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Whether the corners of an interval take part in intersections
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub enum Endpoints {
+    /// only points strictly inside both intervals count
+    #[default]
+    Excluded,
+    /// corners count too, so touching wires cross
+    Included,
+}
+
+/// How two wires meet at an intersection, strongest first
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Copy, Clone)]
+pub enum IntersectionKind {
+    /// the wires pass through each other away from any corner
+    Cross,
+    /// a corner or end of one wire lies on the other
+    TouchEndpoint,
+    /// the wires run along each other
+    Overlap,
+}
+
+/// Point where two wires meet
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Copy, Clone)]
+pub struct Intersection {
+    pub point: Coord,
+    pub kind: IntersectionKind,
+}
+
+/// Rules deciding which intersections of two wires are crossovers
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub struct CrossoverRules {
+    /// whether intersections at the corners of either wire count
+    pub endpoints: Endpoints,
+    /// drop intersections whose manhattan distance from the start of the
+    /// first wire is at most this, so `Some(0)` drops the central port
+    /// and `Some(1)` also the cells next to it
+    pub exclude_within: Option<i64>,
+}
+
+/// Stretch where two wires run along the same row, column or diagonal
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Overlap {
+    /// shared interval, running the same way as the first wire
+    pub interval: CornerPair,
+    /// number of steps along the shared interval
+    pub length: u64,
+}
+
+/// Summary figures describing the shape of a wire
+#[derive(Debug, PartialEq, Clone)]
+pub struct WireStats {
+    /// steps taken over the whole wire
+    pub total_length: u64,
+    /// number of commands
+    pub segments: usize,
+    /// number of cells in the bounding box, including the start
+    pub bounding_area: u64,
+    /// number of commands heading in each direction
+    pub directions: BTreeMap<Direction, usize>,
+    /// first of the longest commands, none for an empty wire
+    pub longest: Option<Command>,
+}
+
+/// Single move of a wire, such as `U32`
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Serialize, Deserialize)]
+pub struct Command {
+    pub dir: Direction,
+    pub count: u32,
 }
 
 impl Display for Command {
@@ -165,20 +541,52 @@ impl Display for Command {
 }
 
 impl Command {
-    pub fn new(cmd_s: &str) -> Self {
-        let (dir_s, count_s) = cmd_s.split_at(1);
+    /// Constructor for a Command
+    ///
+    /// # Arguments
+    ///
+    /// * `cmd_s` - token such as `U32`
+    ///
+    /// # Returns
+    ///
+    /// * parsed command, or the reason the token is malformed
+    pub fn new(cmd_s: &str) -> Result<Self, ParseError> {
+        let error = |reason| ParseError {
+            token: String::from(cmd_s),
+            line: 0,
+            position: 0,
+            reason,
+        };
+        if cmd_s.is_empty() {
+            return Err(error(ParseErrorReason::Empty));
+        }
+        let split = cmd_s
+            .find(|ch: char| !ch.is_ascii_alphabetic())
+            .unwrap_or(cmd_s.len());
+        let (dir_s, count_s) = cmd_s.split_at(split);
         let dir = match dir_s {
             "U" => Direction::Up,
             "D" => Direction::Down,
             "L" => Direction::Left,
             "R" => Direction::Right,
+            "UL" => Direction::UpLeft,
+            "UR" => Direction::UpRight,
+            "DL" => Direction::DownLeft,
+            "DR" => Direction::DownRight,
+            "" => {
+                let first = cmd_s.chars().take(1).collect();
+                return Err(error(ParseErrorReason::UnknownDirection(first)));
+            }
             _ => {
-                panic!("unknown direction char {}", dir_s)
+                let dir = String::from(dir_s);
+                return Err(error(ParseErrorReason::UnknownDirection(dir)));
             }
         };
-        let count = count_s.parse::<u32>().unwrap();
+        let count = count_s
+            .parse::<u32>()
+            .map_err(|err| error(ParseErrorReason::InvalidCount(err)))?;
 
-        Command { dir, count }
+        Ok(Command { dir, count })
     }
 
     /// Returns the coords of all points when carrying out the command
@@ -192,73 +600,120 @@ impl Command {
     /// # Returns
     ///
     /// iterator of all coords visited, excluding start.
-    pub fn coords(&self, start: Coord) -> Box<dyn Iterator<Item = Coord>> {
-        match self.dir {
-            Direction::Up => Box::new(
-                (start.y + 1..=start.y + self.count as i64).map(move |y| Coord { x: start.x, y }),
-            ),
+    pub fn coords(&self, start: Coord) -> CoordIter {
+        CoordIter {
+            current: start,
+            delta: self.dir.delta(),
+            remaining: self.count,
+        }
+    }
 
-            Direction::Down => Box::new(
-                (start.y - self.count as i64..start.y)
-                    .rev()
-                    .map(move |y| Coord { x: start.x, y }),
-            ),
+    pub fn last_coord(&self, start: Coord) -> Coord {
+        let (dx, dy) = self.dir.delta();
+        Coord {
+            x: start.x + self.count as i64 * dx,
+            y: start.y + self.count as i64 * dy,
+        }
+    }
+}
 
-            Direction::Left => Box::new(
-                (start.x - self.count as i64..start.x)
-                    .rev()
-                    .map(move |x| Coord { x, y: start.y }),
-            ),
+/// Coordinates visited while carrying out a single command
+#[derive(Debug, Clone)]
+pub struct CoordIter {
+    /// last coordinate handed out, or the start
+    current: Coord,
+    delta: (i64, i64),
+    remaining: u32,
+}
 
-            Direction::Right => Box::new(
-                (start.x + 1..=start.x + self.count as i64).map(move |x| Coord { x, y: start.y }),
-            ),
+impl Iterator for CoordIter {
+    type Item = Coord;
+
+    fn next(&mut self) -> Option<Coord> {
+        if self.remaining == 0 {
+            return None;
         }
+        self.remaining -= 1;
+        self.current = Coord {
+            x: self.current.x + self.delta.0,
+            y: self.current.y + self.delta.1,
+        };
+        Some(self.current)
     }
 
-    pub fn last_coord(&self, start: Coord) -> Coord {
-        match self.dir {
-            Direction::Up => {
-                return Coord {
-                    x: start.x,
-                    y: start.y + self.count as i64,
-                };
-            }
-            Direction::Down => {
-                return Coord {
-                    x: start.x,
-                    y: start.y - self.count as i64,
-                };
-            }
-            Direction::Left => {
-                return Coord {
-                    x: start.x - self.count as i64,
-                    y: start.y,
-                };
-            }
-            Direction::Right => {
-                return Coord {
-                    x: start.x + self.count as i64,
-                    y: start.y,
-                };
-            }
-        }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
-struct Wire {
+impl ExactSizeIterator for CoordIter {}
+
+/// Path of a wire as a sequence of commands, starting from the origin
+/// unless it has been translated
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Wire {
     cmds: Vec<Command>,
+    #[serde(default, skip_serializing_if = "Coord::is_origin")]
+    start: Coord,
 }
 
 impl Wire {
-    pub fn new(cmds_s: &str) -> Self {
-        let dirs = cmds_s.split(',').map(Command::new).collect();
-        Wire { cmds: dirs }
+    /// Constructor for a Wire from already parsed commands
+    ///
+    /// # Arguments
+    ///
+    /// * `cmds` - commands in the order they are carried out
+    pub fn from_commands(cmds: Vec<Command>) -> Self {
+        Wire {
+            cmds,
+            start: Coord::default(),
+        }
+    }
+
+    /// Commands making up the wire
+    pub fn commands(&self) -> &[Command] {
+        &self.cmds
+    }
+
+    /// Coordinate the wire sets off from
+    pub fn start(&self) -> Coord {
+        self.start
+    }
+
+    /// Lays the same commands out from another port
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - coordinate the wire sets off from
+    pub fn with_start(self, start: Coord) -> Self {
+        Wire { start, ..self }
+    }
+
+    /// Constructor for a Wire
+    ///
+    /// # Arguments
+    ///
+    /// * `cmds_s` - comma separated command tokens
+    ///
+    /// # Returns
+    ///
+    /// * parsed wire, or the first malformed token
+    pub fn new(cmds_s: &str) -> Result<Self, ParseError> {
+        let mut dirs = Vec::new();
+        let mut position = 0;
+        for token in cmds_s.split(',') {
+            let cmd = Command::new(token).map_err(|err| ParseError { position, ..err })?;
+            dirs.push(cmd);
+            position += token.len() + 1;
+        }
+        Ok(Wire::from_commands(dirs))
     }
 
     /// Takes a collection of cmds and returns all coordinates
     ///
+    /// Materializes `trace_iter`, prefer that when the coordinates only
+    /// need to be walked once.
+    ///
     /// # Arguments
     ///
     /// * `start` - starting coordinate of the trace.
@@ -267,16 +722,27 @@ impl Wire {
     ///
     /// All coordinates visited
     pub fn trace(&self, start: Coord) -> Vec<Coord> {
-        let mut cmd_coords: Vec<Coord> = Vec::new();
-        let mut current = start;
-        for cmd in &self.cmds {
-            let new_coords: Vec<Coord> = cmd.coords(current).collect();
-            cmd_coords.extend(&new_coords);
-            if let Some(last) = new_coords.last() {
-                current = *last;
-            }
-        }
-        cmd_coords
+        self.trace_iter(start).collect()
+    }
+
+    /// Lazily walks every coordinate visited by the wire
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - starting coordinate of the trace.
+    ///
+    /// # Returns
+    ///
+    /// iterator of all coordinates visited, excluding start.
+    pub fn trace_iter(&self, start: Coord) -> impl Iterator<Item = Coord> + '_ {
+        self.cmds
+            .iter()
+            .scan(start, |current, cmd| {
+                let from = *current;
+                *current = cmd.last_coord(from);
+                Some(cmd.coords(from))
+            })
+            .flatten()
     }
 
     /// Takes a collection of cmds and returns all corner coordinates
@@ -299,98 +765,769 @@ impl Wire {
         cnr_coords
     }
 
-    /// Determines all crossovers with another wire
+    /// Produces a shorter wire by merging and cancelling commands, with
+    /// the same crossovers and steps against another wire
+    ///
+    /// Consecutive commands in the same direction are merged and empty
+    /// commands dropped. `intersection` ignores interval endpoints, so the
+    /// corner between two merged commands is kept wherever the other wire
+    /// passes through it.
+    ///
+    /// A command immediately followed by one in the opposite direction is
+    /// cancelled down to their difference, dropping the retraced spur.
+    /// That only happens when the other wire never reaches the spur and
+    /// every crossover is first reached before it, since steps to anything
+    /// past the spur would shrink by twice its length.
     ///
     /// # Arguments
     ///
-    /// * other - the wire to compare with
+    /// * other - the wire crossovers and steps are kept against
     ///
     /// # Returns
     ///
-    /// all crossover coordinates
-    pub fn crossovers(&self, other: &Wire) -> Vec<Coord> {
-        let this_trace_corners: Vec<CornerPair> = self.trace_corners(Coord { x: 0, y: 0 });
-        let other_trace_corners: Vec<CornerPair> = other.trace_corners(Coord { x: 0, y: 0 });
-        let mut all_crossovers: Vec<Coord> = Vec::new();
-        for cpi in this_trace_corners {
-            for cpj in &other_trace_corners {
-                if let Some(coord) = cpi.intersection(*cpj) {
-                    if (cpi.0.y == 0 && cpi.1.y == 0) || (cpj.0.y == 0 && cpj.1.y == 0) {
-                        println!("cpi: {}; cpj: {}; coord: {}", cpi, cpj, coord);
+    /// simplified wire
+    pub fn simplify(&self, other: &Wire) -> Wire {
+        let touched: HashSet<Coord> = iter::once(other.start)
+            .chain(other.trace_iter(other.start))
+            .collect();
+        let first_steps = self.step_map(self.start);
+        let last_crossover = self
+            .crossovers(other)
+            .iter()
+            .filter_map(|c| first_steps.get(c).copied())
+            .max()
+            .unwrap_or(0);
+
+        let mut cmds: Vec<Command> = Vec::new();
+        // end of the simplified wire so far and the steps taken to get there
+        let (mut at, mut kept) = (self.start, 0u64);
+        for cmd in &self.cmds {
+            let mut pending = *cmd;
+            while pending.count > 0 {
+                let Some(top) = cmds.last_mut() else {
+                    cmds.push(pending);
+                    break;
+                };
+                if top.dir == pending.dir {
+                    match top.count.checked_add(pending.count) {
+                        Some(count) if !touched.contains(&at) => top.count = count,
+                        _ => cmds.push(pending),
                     }
-                    all_crossovers.push(coord);
+                    break;
+                }
+                let (dx, dy) = top.dir.delta();
+                let back = pending.count.min(top.count);
+                let spur_clear = pending.dir.delta() == (-dx, -dy)
+                    && last_crossover <= kept - back as u64
+                    && (0..=back as i64).all(|k| {
+                        !touched.contains(&Coord {
+                            x: at.x - k * dx,
+                            y: at.y - k * dy,
+                        })
+                    });
+                if !spur_clear {
+                    cmds.push(pending);
+                    break;
+                }
+                at = Coord {
+                    x: at.x - back as i64 * dx,
+                    y: at.y - back as i64 * dy,
+                };
+                kept -= back as u64;
+                pending.count -= back;
+                top.count -= back;
+                if top.count == 0 {
+                    // the spur is fully retraced, continue with what's left
+                    cmds.pop();
                 }
             }
+            // whatever is left of the command was pushed or merged
+            at = pending.last_coord(at);
+            kept += pending.count as u64;
+        }
+        Wire {
+            cmds,
+            start: self.start,
         }
-        all_crossovers
     }
 
-    /// Determines the amount of steps taken to reach the given crossover
+    /// Shifts the whole wire, including its start
     ///
     /// # Arguments
     ///
-    /// * other - the wire that crossover occurs with
-    /// * point - the particular crossover to consider
+    /// * `dx` - horizontal shift
+    /// * `dy` - vertical shift
     ///
     /// # Returns
     ///
-    /// steps that it takes the trace to reach that point
-    pub fn steps_to_crossover(&self, other: &Wire, point: Coord) -> Result<u64, String> {
-        if !self.crossovers(other).contains(&point) {
-            return Err(format!("point not a crossover; {}", point));
-        }
-        let mut current = Coord { x: 0, y: 0 };
-        let mut count: u64 = 0;
-        for cmd in &self.cmds {
-            for coord in cmd.coords(current) {
-                count += 1;
-                if coord == (point) {
-                    return Ok(count);
-                }
-            }
-            current = match cmd.coords(current).last() {
-                Some(c) => c,
-                None => unreachable!("a last coordinate wasn't returned"),
-            };
+    /// translated wire
+    pub fn translate(&self, dx: i64, dy: i64) -> Wire {
+        Wire {
+            cmds: self.cmds.clone(),
+            start: Coord {
+                x: self.start.x + dx,
+                y: self.start.y + dy,
+            },
         }
-        
-        Err(String::from("the crossover was never reached"))
     }
-}
 
-struct Panel(Wire, Wire);
-
-impl Panel {
-    fn generate(&self) -> (HashMap<(i64, i64), char>, Coord, Coord) {
-        let mut cnrs: Vec<Coord> = self
-            .0
-            .trace_corners(Coord { x: 0, y: 0 })
+    /// Turns the wire a quarter anticlockwise about the origin
+    ///
+    /// # Returns
+    ///
+    /// rotated wire
+    pub fn rotate90(&self) -> Wire {
+        Wire {
+            cmds: self
+                .cmds
+                .iter()
+                .map(|cmd| Command {
+                    dir: cmd.dir.rotate90(),
+                    count: cmd.count,
+                })
+                .collect(),
+            start: Coord {
+                x: -self.start.y,
+                y: self.start.x,
+            },
+        }
+    }
+
+    /// Reflects the wire across an axis through the origin
+    ///
+    /// # Arguments
+    ///
+    /// * `axis` - axis to mirror across
+    ///
+    /// # Returns
+    ///
+    /// mirrored wire
+    pub fn mirror(&self, axis: Axis) -> Wire {
+        let start = match axis {
+            Axis::X => Coord {
+                x: self.start.x,
+                y: -self.start.y,
+            },
+            Axis::Y => Coord {
+                x: -self.start.x,
+                y: self.start.y,
+            },
+        };
+        Wire {
+            cmds: self
+                .cmds
+                .iter()
+                .map(|cmd| Command {
+                    dir: cmd.dir.mirror(axis),
+                    count: cmd.count,
+                })
+                .collect(),
+            start,
+        }
+    }
+
+    /// Summarizes the length, extent and shape of the wire
+    ///
+    /// # Returns
+    ///
+    /// statistics for the wire
+    pub fn stats(&self) -> WireStats {
+        let (min_bounds, max_bounds) = self.bounds();
+        let mut directions = BTreeMap::new();
+        for cmd in &self.cmds {
+            *directions.entry(cmd.dir).or_insert(0) += 1;
+        }
+        WireStats {
+            total_length: self.cmds.iter().map(|cmd| cmd.count as u64).sum(),
+            segments: self.cmds.len(),
+            bounding_area: (max_bounds.x - min_bounds.x + 1) as u64
+                * (max_bounds.y - min_bounds.y + 1) as u64,
+            directions,
+            longest: self.cmds.iter().rev().max_by_key(|cmd| cmd.count).copied(),
+        }
+    }
+
+    /// Determines the bounding box of the wire from its corners
+    ///
+    /// # Returns
+    ///
+    /// minimum and maximum corners of the box, including the start
+    pub fn bounds(&self) -> (Coord, Coord) {
+        let mut current = self.start;
+        let (mut min_bounds, mut max_bounds) = (current, current);
+        for cmd in &self.cmds {
+            current = cmd.last_coord(current);
+            min_bounds.x = min_bounds.x.min(current.x);
+            min_bounds.y = min_bounds.y.min(current.y);
+            max_bounds.x = max_bounds.x.max(current.x);
+            max_bounds.y = max_bounds.y.max(current.y);
+        }
+        (min_bounds, max_bounds)
+    }
+
+    /// Maps every visited coordinate to the steps taken to first reach it
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - starting coordinate of the trace.
+    ///
+    /// # Returns
+    ///
+    /// steps to the first visit of each coordinate
+    pub fn step_map(&self, start: Coord) -> HashMap<Coord, u64> {
+        let mut steps: HashMap<Coord, u64> = HashMap::new();
+        for (index, coord) in self.trace_iter(start).enumerate() {
+            steps.entry(coord).or_insert(index as u64 + 1);
+        }
+        steps
+    }
+
+    /// Determines all crossovers with another wire
+    ///
+    /// # Arguments
+    ///
+    /// * other - the wire to compare with
+    ///
+    /// # Returns
+    ///
+    /// all crossover coordinates
+    pub fn crossovers(&self, other: &Wire) -> Vec<Coord> {
+        self.crossovers_with(other, CrossoverRules::default())
+    }
+
+    /// Determines all crossovers with another wire under custom rules
+    ///
+    /// `Endpoints::Included` adds the points `intersections` reports as
+    /// touches to the true crossings.
+    ///
+    /// # Arguments
+    ///
+    /// * other - the wire to compare with
+    /// * rules - which intersections count as crossovers
+    ///
+    /// # Returns
+    ///
+    /// all crossover coordinates, each listed once when endpoints count
+    pub fn crossovers_with(&self, other: &Wire, rules: CrossoverRules) -> Vec<Coord> {
+        let this_trace_corners: Vec<CornerPair> = self.trace_corners(self.start);
+        let other_trace_corners: Vec<CornerPair> = other.trace_corners(other.start);
+        let mut all_crossovers: Vec<Coord> = Vec::new();
+        for cpi in this_trace_corners {
+            for cpj in &other_trace_corners {
+                if let Some(coord) = cpi.intersection_with(*cpj, rules.endpoints) {
+                    if rules
+                        .exclude_within
+                        .is_some_and(|d| Metric::Manhattan.distance(coord, self.start) <= d)
+                    {
+                        continue;
+                    }
+                    log::debug!("{} crosses {} at {}", cpi, cpj, coord);
+                    all_crossovers.push(coord);
+                }
+            }
+        }
+        if rules.endpoints == Endpoints::Included {
+            // a shared corner is hit once from each interval meeting there
+            all_crossovers.sort();
+            all_crossovers.dedup();
+        }
+        log::debug!("{} crossovers", all_crossovers.len());
+        all_crossovers
+    }
+
+    /// Determines every point where the wire meets another one
+    ///
+    /// Points met in several ways are reported once, with the strongest
+    /// kind. Both wires leaving the same port touch there.
+    ///
+    /// # Arguments
+    ///
+    /// * other - the wire to compare with
+    ///
+    /// # Returns
+    ///
+    /// all intersections, ordered by point
+    pub fn intersections(&self, other: &Wire) -> Vec<Intersection> {
+        let this_trace_corners = self.trace_corners(self.start);
+        let other_trace_corners = other.trace_corners(other.start);
+        let mut found: BTreeMap<Coord, IntersectionKind> = BTreeMap::new();
+        let mut add = |point: Coord, kind: IntersectionKind| {
+            let entry = found.entry(point).or_insert(kind);
+            *entry = (*entry).min(kind);
+        };
+        for cpi in &this_trace_corners {
+            for cpj in &other_trace_corners {
+                if let Some(contact) = cpi.contact(*cpj) {
+                    add(contact.point, contact.kind);
+                }
+                if let Some(shared) = cpi.overlap(*cpj) {
+                    for point in shared.points() {
+                        add(point, IntersectionKind::Overlap);
+                    }
+                }
+            }
+        }
+        found
+            .into_iter()
+            .map(|(point, kind)| Intersection { point, kind })
+            .collect()
+    }
+
+    /// Determines every stretch shared with another wire
+    ///
+    /// Shared stretches never count as crossovers, so this is the only way
+    /// to find wires running on top of each other.
+    ///
+    /// # Arguments
+    ///
+    /// * other - the wire to compare with
+    ///
+    /// # Returns
+    ///
+    /// all overlaps, in the order this wire reaches them
+    pub fn overlaps(&self, other: &Wire) -> Vec<Overlap> {
+        let other_trace_corners = other.trace_corners(other.start);
+        self.trace_corners(self.start)
+            .into_iter()
+            .flat_map(|cpi| {
+                other_trace_corners
+                    .iter()
+                    .filter_map(move |cpj| cpi.overlap(*cpj))
+            })
+            .map(|interval| Overlap {
+                interval,
+                length: interval.step().1 as u64,
+            })
+            .collect()
+    }
+
+    /// Determines the amount of steps taken to reach the given crossover
+    ///
+    /// # Arguments
+    ///
+    /// * other - the wire that crossover occurs with
+    /// * point - the particular crossover to consider
+    ///
+    /// # Returns
+    ///
+    /// steps that it takes the trace to reach that point
+    pub fn steps_to_crossover(&self, other: &Wire, point: Coord) -> Result<u64, String> {
+        if !self.crossovers(other).contains(&point) {
+            return Err(format!("point not a crossover; {}", point));
+        }
+        self.steps_to(point)
+            .ok_or_else(|| String::from("the crossover was never reached"))
+    }
+
+    /// Determines the steps taken to first reach a point
+    ///
+    /// Works from the corners and cumulative command lengths alone, so the
+    /// cost does not depend on how many steps the wire takes.
+    ///
+    /// # Arguments
+    ///
+    /// * point - the coordinate to reach
+    ///
+    /// # Returns
+    ///
+    /// steps to the first visit, or none if the wire never gets there
+    pub fn steps_to(&self, point: Coord) -> Option<u64> {
+        let mut walked: u64 = 0;
+        for cp in self.trace_corners(self.start) {
+            let ((dx, dy), len) = cp.step();
+            let (rx, ry) = (point.x - cp.0.x, point.y - cp.0.y);
+            let t = if dx != 0 { rx * dx } else { ry * dy };
+            // the corner a command starts from was counted by the one before
+            if (1..=len).contains(&t) && rx == t * dx && ry == t * dy {
+                return Some(walked + t as u64);
+            }
+            walked += len as u64;
+        }
+        None
+    }
+}
+
+/// Point a centred viewport is positioned around
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Center {
+    /// A fixed coordinate
+    Point(Coord),
+    /// The crossover closest to the origin by manhattan distance
+    NearestCrossover,
+}
+
+/// Region of the panel to print
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub enum Viewport {
+    /// Bounding box of both wires
+    #[default]
+    Full,
+    /// Rectangle spanning the two given corners, inclusive
+    Crop(Coord, Coord),
+    /// Rectangle of `width` x `height` cells centred on a point
+    Centered {
+        center: Center,
+        width: i64,
+        height: i64,
+    },
+}
+
+/// Options controlling how a panel gets printed
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub struct ViewOptions {
+    /// Region of the panel to print
+    pub viewport: Viewport,
+    /// Maximum number of columns, wider regions get downsampled so that
+    /// one char represents an NxN block of cells
+    pub max_width: Option<usize>,
+    /// Print plain glyphs without ANSI colors
+    pub no_color: bool,
+}
+
+/// Contents of a rasterized block, ordered by display priority
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Copy, Clone)]
+enum Cell {
+    Empty,
+    WireOne,
+    WireTwo,
+    /// visited by both wires without being a crossover
+    Both,
+    /// on a stretch both wires run along
+    Overlap,
+    Origin,
+    Crossover,
+}
+
+impl Cell {
+    /// Combines two cells falling into the same block
+    fn merge(self, other: Cell) -> Cell {
+        match (self, other) {
+            (Cell::WireOne, Cell::WireTwo) | (Cell::WireTwo, Cell::WireOne) => Cell::Both,
+            _ => self.max(other),
+        }
+    }
+
+    fn glyph(self) -> char {
+        match self {
+            Cell::Empty => '.',
+            Cell::WireOne | Cell::WireTwo | Cell::Both => '5',
+            Cell::Overlap => '=',
+            Cell::Origin => 'O',
+            Cell::Crossover => 'X',
+        }
+    }
+
+    /// ANSI SGR parameters used to color the glyph
+    fn ansi(self) -> Option<&'static str> {
+        match self {
+            Cell::Empty => None,
+            Cell::WireOne => Some("31"),
+            Cell::WireTwo => Some("34"),
+            Cell::Both => Some("35"),
+            Cell::Overlap => Some("1;36"),
+            Cell::Origin => Some("1;32"),
+            Cell::Crossover => Some("1;33"),
+        }
+    }
+
+    /// Color used when the cell is drawn as a pixel
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            Cell::Empty => [0, 0, 0],
+            Cell::WireOne => [205, 49, 49],
+            Cell::WireTwo => [36, 114, 200],
+            Cell::Both => [188, 63, 188],
+            Cell::Overlap => [17, 168, 205],
+            Cell::Origin => [35, 209, 139],
+            Cell::Crossover => [245, 245, 67],
+        }
+    }
+
+    /// Renders the cell as a printable string
+    ///
+    /// # Arguments
+    ///
+    /// * no_color - skip ANSI escapes
+    fn render(self, no_color: bool) -> String {
+        match (self.ansi(), no_color) {
+            (Some(code), false) => format!("\x1b[{}m{}\x1b[0m", code, self.glyph()),
+            _ => self.glyph().to_string(),
+        }
+    }
+}
+
+/// Rasterized region of a panel
+#[derive(Debug, PartialEq)]
+struct Raster {
+    min_bounds: Coord,
+    max_bounds: Coord,
+    /// side length of the block of cells each char represents
+    scale: i64,
+    /// rows of cells, starting from `min_bounds.y`
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Raster {
+    /// Merges `cell` into the block containing `point`
+    fn mark(&mut self, point: Coord, cell: Cell) {
+        if point.x < self.min_bounds.x
+            || point.x > self.max_bounds.x
+            || point.y < self.min_bounds.y
+            || point.y > self.max_bounds.y
+        {
+            return;
+        }
+        let row = ((point.y - self.min_bounds.y) / self.scale) as usize;
+        let col = ((point.x - self.min_bounds.x) / self.scale) as usize;
+        let block = &mut self.rows[row][col];
+        *block = block.merge(cell);
+    }
+
+    /// Renders the rows from the top of the region down
+    ///
+    /// # Arguments
+    ///
+    /// * no_color - skip ANSI escapes
+    fn lines(&self, no_color: bool) -> Vec<String> {
+        self.rows
+            .iter()
+            .rev()
+            .map(|row| row.iter().map(|cell| cell.render(no_color)).collect())
+            .collect()
+    }
+}
+
+/// Number of wire visits to each block of a panel region
+#[derive(Debug, PartialEq)]
+pub struct Heatmap {
+    pub min_bounds: Coord,
+    pub max_bounds: Coord,
+    /// side length of the block of cells each count covers
+    pub scale: i64,
+    /// visit counts per block, starting from `min_bounds.y`
+    pub rows: Vec<Vec<u32>>,
+}
+
+impl Heatmap {
+    /// Formats the counts as CSV, from the top of the region down
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        for row in self.rows.iter().rev() {
+            let values: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+            csv += &values.join(",");
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Encodes the counts as a grayscale PNG, brightest where the wires
+    /// visit most
+    ///
+    /// # Returns
+    ///
+    /// * PNG file contents
+    pub fn to_png(&self) -> Result<Vec<u8>, png::EncodingError> {
+        let height = self.rows.len() as u32;
+        let width = self.rows.first().map_or(0, |r| r.len()) as u32;
+        let max = self
+            .rows
             .iter()
-            .map(|x| vec![x.0, x.1])
             .flatten()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(1) as u64;
+        let pixels: Vec<u8> = self
+            .rows
+            .iter()
+            .rev()
+            .flatten()
+            .map(|&v| (v as u64 * 255 / max) as u8)
             .collect();
-        cnrs.extend(
-            self.1
-                .trace_corners(Coord { x: 0, y: 0 })
-                .iter()
-                .map(|x| vec![x.0, x.1])
-                .flatten(),
-        );
-        let mut min_bounds = Coord { x: 0, y: 0 };
-        let mut max_bounds = Coord { x: 0, y: 0 };
 
-        let crossovers = self.0.crossovers(&self.1);
+        let mut image = Vec::new();
+        let mut encoder = png::Encoder::new(&mut image, width, height);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&pixels)?;
+        Ok(image)
+    }
+}
+
+/// Crossovers bucketed into square blocks around a port, for repeated
+/// distance queries
+#[derive(Debug, PartialEq)]
+pub struct CrossoverIndex {
+    port: Coord,
+    /// crossovers keyed by the block they fall in
+    buckets: HashMap<(i64, i64), Vec<Coord>>,
+}
+
+impl CrossoverIndex {
+    /// Side length of each block
+    const BUCKET_SIZE: i64 = 64;
+
+    /// Constructor for a CrossoverIndex
+    ///
+    /// # Arguments
+    ///
+    /// * port - point distances are measured from
+    /// * crossovers - crossovers to index
+    pub fn new(port: Coord, crossovers: &[Coord]) -> Self {
+        let mut buckets: HashMap<(i64, i64), Vec<Coord>> = HashMap::new();
+        for &c in crossovers {
+            buckets.entry(Self::bucket(c)).or_default().push(c);
+        }
+        CrossoverIndex { port, buckets }
+    }
+
+    fn bucket(c: Coord) -> (i64, i64) {
+        (
+            c.x.div_euclid(Self::BUCKET_SIZE),
+            c.y.div_euclid(Self::BUCKET_SIZE),
+        )
+    }
 
-        // Determine bounds of trace
-        for cnr in cnrs {
-            min_bounds.x = min_bounds.x.min(cnr.x);
-            min_bounds.y = min_bounds.y.min(cnr.y);
-            max_bounds.x = max_bounds.x.max(cnr.x);
-            max_bounds.y = max_bounds.y.max(cnr.y);
+    /// Blocks holding crossovers that overlap the diamond of points
+    /// within a manhattan distance of the port
+    ///
+    /// Only blocks overlapping the diamond are visited, unless the diamond
+    /// covers more blocks than hold crossovers, in which case every
+    /// occupied block is checked instead.
+    ///
+    /// # Arguments
+    ///
+    /// * d - greatest distance to include
+    fn blocks_within(&self, d: i64) -> Vec<(i64, i64)> {
+        if d < 0 {
+            return Vec::new();
+        }
+        let size = Self::BUCKET_SIZE;
+        // manhattan distance from the port to the nearest point of a block
+        let gap = |bx: i64, by: i64| {
+            let (min_x, min_y) = (bx * size, by * size);
+            (self.port.x.clamp(min_x, min_x + size - 1) - self.port.x).abs()
+                + (self.port.y.clamp(min_y, min_y + size - 1) - self.port.y).abs()
+        };
+        let column = |x: i64| x.div_euclid(size);
+        let (low_x, high_x) = (
+            column(self.port.x.saturating_sub(d)),
+            column(self.port.x.saturating_add(d)),
+        );
+        let span = (high_x - low_x + 1) as u128;
+        if span * span > self.buckets.len() as u128 {
+            let mut blocks: Vec<(i64, i64)> = self
+                .buckets
+                .keys()
+                .copied()
+                .filter(|&(bx, by)| gap(bx, by) <= d)
+                .collect();
+            blocks.sort();
+            return blocks;
+        }
+        let mut blocks = Vec::new();
+        for bx in low_x..=high_x {
+            let rest = d - gap(bx, column(self.port.y));
+            if rest < 0 {
+                continue;
+            }
+            for by in column(self.port.y - rest)..=column(self.port.y + rest) {
+                if self.buckets.contains_key(&(bx, by)) {
+                    blocks.push((bx, by));
+                }
+            }
         }
+        blocks
+    }
+
+    /// Finds every crossover within a manhattan distance of the port
+    ///
+    /// # Arguments
+    ///
+    /// * d - greatest distance to include
+    ///
+    /// # Returns
+    ///
+    /// * crossovers and their distances, closest first
+    pub fn crossovers_within(&self, d: i64) -> Vec<(Coord, i64)> {
+        let mut found: Vec<(Coord, i64)> = self
+            .blocks_within(d)
+            .iter()
+            .flat_map(|block| &self.buckets[block])
+            .map(|&c| (c, Metric::Manhattan.distance(c, self.port)))
+            .filter(|&(_, distance)| distance <= d)
+            .collect();
+        found.sort_by_key(|&(c, distance)| (distance, c));
+        found
+    }
+}
+
+/// Pair of wires laid out on the front panel
+pub struct Panel(pub Wire, pub Wire);
+
+impl Panel {
+    /// Central port the panel is measured from, where the first wire starts
+    pub fn port(&self) -> Coord {
+        self.0.start
+    }
+
+    /// Determines the bounding box of both wires, including their starts
+    ///
+    /// # Returns
+    ///
+    /// * minimum and maximum corners of the bounding box
+    fn bounds(&self) -> (Coord, Coord) {
+        let (min_one, max_one) = self.0.bounds();
+        let (min_two, max_two) = self.1.bounds();
+        (
+            Coord {
+                x: min_one.x.min(min_two.x),
+                y: min_one.y.min(min_two.y),
+            },
+            Coord {
+                x: max_one.x.max(max_two.x),
+                y: max_one.y.max(max_two.y),
+            },
+        )
+    }
+
+    /// Indexes the crossovers by their position around the port
+    ///
+    /// Finding the crossovers is the costly part, so build the index once
+    /// and keep it for repeated `crossovers_within` queries.
+    pub fn crossover_index(&self) -> CrossoverIndex {
+        CrossoverIndex::new(self.port(), &self.0.crossovers(&self.1))
+    }
+
+    /// Finds the crossover closest to an arbitrary point
+    ///
+    /// Ties are broken by picking the smallest coordinate.
+    ///
+    /// # Arguments
+    ///
+    /// * point - location to measure from
+    /// * metric - distance metric to rank crossovers by
+    ///
+    /// # Returns
+    ///
+    /// * closest crossover and its distance, or none if the wires never cross
+    pub fn nearest_crossover(&self, point: Coord, metric: Metric) -> Option<(Coord, u64)> {
+        self.0
+            .crossovers(&self.1)
+            .into_iter()
+            .map(|c| (c, metric.distance(c, point) as u64))
+            .min_by_key(|&(c, distance)| (distance, c))
+    }
 
-        let mut intervals: Vec<CornerPair> = self.0.trace_corners(Coord { x: 0, y: 0 });
-        intervals.extend(self.1.trace_corners(Coord { x: 0, y: 0 }));
+    /// Reference rendering built from the wire intervals, kept to check
+    /// the raster against
+    #[cfg(test)]
+    fn generate(&self) -> (HashMap<(i64, i64), char>, Coord, Coord) {
+        let (min_bounds, max_bounds) = self.bounds();
+        let crossovers: HashSet<Coord> = self.0.crossovers(&self.1).into_iter().collect();
+
+        let mut intervals: Vec<CornerPair> = self.0.trace_corners(self.0.start);
+        intervals.extend(self.1.trace_corners(self.1.start));
 
         let mut display: HashMap<(i64, i64), char> = HashMap::new();
         for i in min_bounds.y..=max_bounds.y {
@@ -415,12 +1552,21 @@ impl Panel {
         (display, min_bounds, max_bounds)
     }
 
+    /// Renders the panel by walking every point each wire visits
+    ///
+    /// # Returns
+    ///
+    /// * character for each location within the bounds, and the minimum
+    ///   and maximum bounds
     pub fn generate_from_trace(&self) -> (HashMap<(i64, i64), char>, Coord, Coord) {
-        let mut all_trace: Vec<Coord> = self.1.trace(Coord { x: 0, y: 0 });
-        all_trace.extend(self.0.trace(Coord { x: 0, y: 0 }));
-        let (_, min_bounds, max_bounds) = self.generate();
+        let all_trace: HashSet<Coord> = self
+            .1
+            .trace_iter(self.1.start)
+            .chain(self.0.trace_iter(self.0.start))
+            .collect();
+        let (min_bounds, max_bounds) = self.bounds();
         let mut display: HashMap<(i64, i64), char> = HashMap::new();
-        let cos = self.0.crossovers(&self.1);
+        let cos: HashSet<Coord> = self.0.crossovers(&self.1).into_iter().collect();
         for i in min_bounds.y..=max_bounds.y {
             for j in min_bounds.x..=max_bounds.x {
                 let cd = Coord { x: j, y: i };
@@ -433,8 +1579,7 @@ impl Panel {
                 };
                 match ch {
                     Some(c) => {
-                        const ORIGIN: Coord = Coord { x: 0, y: 0 };
-                        if cd == ORIGIN {
+                        if cd == self.port() {
                             display.insert((j, i), 'O');
                         } else {
                             display.insert((j, i), c);
@@ -447,44 +1592,335 @@ impl Panel {
         (display, min_bounds, max_bounds)
     }
 
-    pub fn print_panel(&self) {
-        let (disp, min_bounds, max_bounds) = self.generate();
-        const COL_WIDTH: usize = 1;
-        let mut first_row = String::from(" ".repeat(6));
-        println!("{} -> {}", min_bounds, max_bounds);
-        for j in min_bounds.x..=max_bounds.x {
-            first_row = format!("{}{:^width$}", first_row, j % 10, width = COL_WIDTH);
-        }
-        let mut lines: Vec<String> = Vec::new();
-        println!("{}", first_row);
-        for i in min_bounds.y..=max_bounds.y {
-            let mut line = String::from(format!("{:>5} ", i));
-            for j in min_bounds.x..=max_bounds.x {
-                let k = (j, i);
-                if let Some(ch) = disp.get(&k) {
-                    line = format!("{}{:^width$}", line, ch, width = COL_WIDTH);
-                } else {
-                    unreachable!("character wasn't generated");
-                }
+    /// Resolves a viewport into the rectangle of cells it covers
+    ///
+    /// # Arguments
+    ///
+    /// * viewport - region requested
+    ///
+    /// # Returns
+    ///
+    /// * minimum and maximum corners of the region
+    fn viewport_bounds(&self, viewport: Viewport) -> (Coord, Coord) {
+        match viewport {
+            Viewport::Full => self.bounds(),
+            Viewport::Crop(c1, c2) => (
+                Coord {
+                    x: c1.x.min(c2.x),
+                    y: c1.y.min(c2.y),
+                },
+                Coord {
+                    x: c1.x.max(c2.x),
+                    y: c1.y.max(c2.y),
+                },
+            ),
+            Viewport::Centered {
+                center,
+                width,
+                height,
+            } => {
+                let point = match center {
+                    Center::Point(c) => c,
+                    Center::NearestCrossover => self
+                        .nearest_crossover(self.port(), Metric::Manhattan)
+                        .map_or(self.port(), |(c, _)| c),
+                };
+                let min_bounds = Coord {
+                    x: point.x - (width.max(1) - 1) / 2,
+                    y: point.y - (height.max(1) - 1) / 2,
+                };
+                let max_bounds = Coord {
+                    x: min_bounds.x + width.max(1) - 1,
+                    y: min_bounds.y + height.max(1) - 1,
+                };
+                (min_bounds, max_bounds)
+            }
+        }
+    }
+
+    /// Rasterizes the region of the panel selected by `opts`
+    ///
+    /// Only the cells the wires actually visit are walked, so the cost
+    /// is bounded by the trace length rather than the region area.
+    ///
+    /// # Arguments
+    ///
+    /// * opts - view options
+    ///
+    /// # Returns
+    ///
+    /// * rasterized region
+    fn raster(&self, opts: &ViewOptions) -> Raster {
+        let mut raster = self.blank_raster(opts);
+        for coord in self.0.trace_iter(self.0.start) {
+            raster.mark(coord, Cell::WireOne);
+        }
+        for coord in self.1.trace_iter(self.1.start) {
+            raster.mark(coord, Cell::WireTwo);
+        }
+        for overlap in self.0.overlaps(&self.1) {
+            for coord in overlap.interval.points() {
+                raster.mark(coord, Cell::Overlap);
+            }
+        }
+        raster.mark(self.0.start, Cell::Origin);
+        raster.mark(self.1.start, Cell::Origin);
+        for coord in self.0.crossovers(&self.1) {
+            raster.mark(coord, Cell::Crossover);
+        }
+        raster
+    }
+
+    /// Counts how often the wires visit each block of the region selected
+    /// by `opts`
+    ///
+    /// Every step counts, so a wire retracing itself adds to the count.
+    ///
+    /// # Arguments
+    ///
+    /// * opts - view options, `no_color` is ignored
+    ///
+    /// # Returns
+    ///
+    /// * visit counts per block
+    pub fn heatmap(&self, opts: &ViewOptions) -> Heatmap {
+        let blank = self.blank_raster(opts);
+        let (min_bounds, max_bounds, scale) = (blank.min_bounds, blank.max_bounds, blank.scale);
+        let mut rows: Vec<Vec<u32>> = blank.rows.iter().map(|row| vec![0; row.len()]).collect();
+        let visits = self
+            .0
+            .trace_iter(self.0.start)
+            .chain(self.1.trace_iter(self.1.start));
+        for point in visits {
+            if point.x < min_bounds.x
+                || point.x > max_bounds.x
+                || point.y < min_bounds.y
+                || point.y > max_bounds.y
+            {
+                continue;
+            }
+            let row = ((point.y - min_bounds.y) / scale) as usize;
+            let col = ((point.x - min_bounds.x) / scale) as usize;
+            rows[row][col] += 1;
+        }
+        Heatmap {
+            min_bounds,
+            max_bounds,
+            scale,
+            rows,
+        }
+    }
+
+    /// Creates an empty raster covering the region selected by `opts`
+    ///
+    /// # Arguments
+    ///
+    /// * opts - view options
+    ///
+    /// # Returns
+    ///
+    /// * raster with every block empty
+    fn blank_raster(&self, opts: &ViewOptions) -> Raster {
+        let (min_bounds, max_bounds) = self.viewport_bounds(opts.viewport);
+        let width = max_bounds.x - min_bounds.x + 1;
+        let scale = match opts.max_width {
+            Some(max_width) if max_width > 0 => {
+                let max_width = max_width as i64;
+                ((width + max_width - 1) / max_width).max(1)
             }
-            lines.push(line);
+            _ => 1,
+        };
+        let cols = ((width + scale - 1) / scale) as usize;
+        let rows = ((max_bounds.y - min_bounds.y + scale) / scale) as usize;
+        Raster {
+            min_bounds,
+            max_bounds,
+            scale,
+            rows: vec![vec![Cell::Empty; cols]; rows],
+        }
+    }
+
+    /// Prints the whole panel with the default view options
+    pub fn print_panel(&self) {
+        self.print_view(&ViewOptions::default());
+    }
+
+    /// Prints the region of the panel selected by `opts`
+    ///
+    /// # Arguments
+    ///
+    /// * opts - view options
+    pub fn print_view(&self, opts: &ViewOptions) {
+        let raster = self.raster(opts);
+        let (min_bounds, max_bounds) = (raster.min_bounds, raster.max_bounds);
+        const COL_WIDTH: usize = 1;
+        let mut first_row = " ".repeat(6);
+        if raster.scale == 1 {
+            println!("{} -> {}", min_bounds, max_bounds);
+        } else {
+            println!("{} -> {} (1:{})", min_bounds, max_bounds, raster.scale);
+        }
+        for col in 0..raster.rows.first().map_or(0, |r| r.len()) as i64 {
+            let j = min_bounds.x + col * raster.scale;
+            first_row = format!("{}{:^width$}", first_row, j % 10, width = COL_WIDTH);
         }
-        for line in lines.iter().rev() {
-            println!("{}", line)
+        println!("{}", first_row);
+        let lines = raster.lines(opts.no_color);
+        for (row, line) in lines.iter().enumerate() {
+            let i = min_bounds.y + (lines.len() - 1 - row) as i64 * raster.scale;
+            println!("{:>5} {}", i, line)
         }
         println!("{}", first_row);
     }
+
+    /// Draws both wires as an SVG document, with y increasing upwards
+    ///
+    /// # Returns
+    ///
+    /// * SVG markup covering the bounding box of both wires
+    pub fn to_svg(&self) -> String {
+        let (min_bounds, max_bounds) = self.bounds();
+        let (width, height) = (
+            max_bounds.x - min_bounds.x + 2,
+            max_bounds.y - min_bounds.y + 2,
+        );
+        let radius = (width.max(height) as f64 / 200.0).max(0.5);
+        let color = |cell: Cell| {
+            let [r, g, b] = cell.rgb();
+            format!("#{:02x}{:02x}{:02x}", r, g, b)
+        };
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+            min_bounds.x - 1,
+            -max_bounds.y - 1,
+            width,
+            height
+        );
+        svg += &format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+            min_bounds.x - 1,
+            -max_bounds.y - 1,
+            width,
+            height,
+            color(Cell::Empty)
+        );
+        for (wire, cell) in [(&self.0, Cell::WireOne), (&self.1, Cell::WireTwo)] {
+            let points: Vec<String> = std::iter::once(wire.start)
+                .chain(wire.trace_corners(wire.start).into_iter().map(|cp| cp.1))
+                .map(|c| format!("{},{}", c.x, -c.y))
+                .collect();
+            svg += &format!(
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" \
+                 stroke-width=\"1\" vector-effect=\"non-scaling-stroke\"/>\n",
+                points.join(" "),
+                color(cell)
+            );
+        }
+        for overlap in self.0.overlaps(&self.1) {
+            let CornerPair(start, end) = overlap.interval;
+            svg += &format!(
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" \
+                 stroke-width=\"2\" vector-effect=\"non-scaling-stroke\"/>\n",
+                start.x,
+                -start.y,
+                end.x,
+                -end.y,
+                color(Cell::Overlap)
+            );
+        }
+        let mut markers = vec![(self.0.start, Cell::Origin), (self.1.start, Cell::Origin)];
+        markers.dedup();
+        markers.extend(
+            self.0
+                .crossovers(&self.1)
+                .into_iter()
+                .map(|c| (c, Cell::Crossover)),
+        );
+        for (coord, cell) in markers {
+            svg += &format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>\n",
+                coord.x,
+                -coord.y,
+                radius,
+                color(cell)
+            );
+        }
+        svg + "</svg>\n"
+    }
 }
 
-pub fn part1(filename: &str) -> Option<i64> {
-    let input = shared::ingest_file(filename);
-    let wire_one = Wire::new(&input[0]);
-    let wire_two = Wire::new(&input[1]);
-    wire_one
+/// Parses both wires from the input file
+///
+/// # Arguments
+///
+/// * filename - name of input file
+///
+/// # Returns
+///
+/// * both wires, or the first parse failure encountered, which for a
+///   file that can't be read has the filename as its token
+fn load_wires(filename: &str) -> Result<(Wire, Wire), ParseError> {
+    let input = shared::try_ingest_file(filename).map_err(|err| ParseError {
+        token: filename.to_string(),
+        line: 0,
+        position: 0,
+        reason: ParseErrorReason::Io(err.to_string()),
+    })?;
+    let wire = |line: usize| match input.get(line) {
+        Some(cmds_s) => Wire::new(cmds_s).map_err(|err| ParseError { line, ..err }),
+        None => Err(ParseError {
+            token: String::new(),
+            line,
+            position: 0,
+            reason: ParseErrorReason::MissingWire,
+        }),
+    };
+    Ok((wire(0)?, wire(1)?))
+}
+
+/// Performs all operations necessary for part1
+///
+/// # Arguments
+///
+/// * filename - name of input file
+///
+/// # Returns
+///
+/// * manhattan distance to the closest crossover, none if the wires never
+///   cross, or the parse failure
+pub fn part1(filename: &str) -> Result<Option<i64>, ParseError> {
+    closest_crossover(filename, Metric::Manhattan)
+}
+
+/// Finds the distance from the origin to the closest crossover
+///
+/// # Arguments
+///
+/// * filename - name of input file
+/// * metric - distance metric to rank crossovers by
+///
+/// # Returns
+///
+/// * distance to the closest crossover, none if the wires never cross, or
+///   the parse failure
+pub fn closest_crossover(filename: &str, metric: Metric) -> Result<Option<i64>, ParseError> {
+    let (wire_one, wire_two) = load_wires(filename)?;
+    Ok(wire_one
         .crossovers(&wire_two)
         .into_iter()
-        .map(|c| c.x.abs() + c.y.abs())
-        .min()
+        .map(|c| metric.distance(c, wire_one.start))
+        .min())
+}
+
+/// Answer to part2 along with the crossovers behind it
+#[derive(Debug, PartialEq)]
+pub struct Part2Answer {
+    /// fewest combined steps both wires take to reach a crossover
+    pub steps: u64,
+    /// every crossover reached in exactly `steps`
+    pub winners: Vec<Coord>,
+    /// next fewest combined steps and the crossovers reached in them
+    pub runner_up: Option<(u64, Vec<Coord>)>,
 }
 
 /// Performs all operations necessary for part2
@@ -495,30 +1931,324 @@ pub fn part1(filename: &str) -> Option<i64> {
 ///
 /// # Returns
 ///
-/// * count of steps taken to crossover if successful, or
-/// * error message
-pub fn part2(filename: &str) -> Result<u64, String> {
-    let input = shared::ingest_file(filename);
-    let wire_one = Wire::new(&input[0]);
-    let wire_two = Wire::new(&input[1]);
-    let crossovers = wire_one.crossovers(&wire_two);
-    let distances_one = crossovers.iter().map(|co| wire_one.steps_to_crossover(&wire_two, *co));
-    let distances_two = crossovers.iter().map(|co| wire_two.steps_to_crossover(&wire_one, *co));
+/// * fewest combined steps and which crossovers attain it, none if the
+///   wires never cross, or the parse failure
+pub fn part2(filename: &str) -> Result<Option<Part2Answer>, ParseError> {
+    let (wire_one, wire_two) = load_wires(filename)?;
+    let mut res: Vec<(u64, Coord)> = reports(&wire_one, &wire_two)
+        .into_iter()
+        .map(|r| (r.steps, r.point))
+        .collect();
+    res.sort();
+
+    // group crossovers sharing the same step count, fewest first
+    let mut groups: Vec<(u64, Vec<Coord>)> = Vec::new();
+    for (steps, coord) in res {
+        match groups.last_mut() {
+            Some((last, coords)) if *last == steps => coords.push(coord),
+            _ => groups.push((steps, vec![coord])),
+        }
+    }
+    let mut groups = groups.into_iter();
+    Ok(groups.next().map(|(steps, winners)| Part2Answer {
+        steps,
+        winners,
+        runner_up: groups.next(),
+    }))
+}
 
-    let res = distances_one.zip(distances_two).map(|(d1, d2)| d1.unwrap() + d2.unwrap()).min();
-    
-    match res {
-        Some(d) => Ok(d),
-        None => Err(String::from("no distance returned"))
+/// Answers to both parts of the puzzle
+#[derive(Debug, PartialEq)]
+pub struct Day3Answer {
+    /// manhattan distance to the closest crossover
+    pub closest_manhattan: Option<i64>,
+    /// fewest combined steps both wires take to reach a crossover
+    pub fewest_steps: Option<u64>,
+    /// every crossover between the wires
+    pub crossovers: Vec<Coord>,
+}
+
+/// Summary of a single crossover between the wires
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct CrossoverReport {
+    pub point: Coord,
+    /// manhattan distance from the start of the first wire
+    pub manhattan: i64,
+    /// steps the first wire takes to reach the point
+    pub steps_one: u64,
+    /// steps the second wire takes to reach the point
+    pub steps_two: u64,
+    /// combined steps both wires take to reach the point
+    pub steps: u64,
+}
+
+/// Column of a crossover report, used to order exported rows
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub enum ReportColumn {
+    X,
+    Y,
+    #[default]
+    Manhattan,
+    StepsOne,
+    StepsTwo,
+    Steps,
+}
+
+impl std::str::FromStr for ReportColumn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "x" => Ok(ReportColumn::X),
+            "y" => Ok(ReportColumn::Y),
+            "manhattan" => Ok(ReportColumn::Manhattan),
+            "steps_one" => Ok(ReportColumn::StepsOne),
+            "steps_two" => Ok(ReportColumn::StepsTwo),
+            "steps" => Ok(ReportColumn::Steps),
+            _ => Err(format!(
+                "unknown column {}, expected one of x, y, manhattan, steps_one, steps_two, steps",
+                s
+            )),
+        }
+    }
+}
+
+impl CrossoverReport {
+    /// Value of a single column, for sorting
+    fn column(&self, column: ReportColumn) -> i64 {
+        match column {
+            ReportColumn::X => self.point.x,
+            ReportColumn::Y => self.point.y,
+            ReportColumn::Manhattan => self.manhattan,
+            ReportColumn::StepsOne => self.steps_one as i64,
+            ReportColumn::StepsTwo => self.steps_two as i64,
+            ReportColumn::Steps => self.steps as i64,
+        }
+    }
+}
+
+/// Formats crossover reports as CSV with a header row
+///
+/// # Arguments
+///
+/// * reports - crossovers to write, one row each
+/// * sort_by - column rows are ordered by, ties broken by the point
+///
+/// # Returns
+///
+/// * CSV text
+pub fn reports_to_csv(reports: &[CrossoverReport], sort_by: ReportColumn) -> String {
+    let mut rows = reports.to_vec();
+    rows.sort_by_key(|r| (r.column(sort_by), r.point));
+    let mut csv = String::from("x,y,manhattan,steps_one,steps_two,steps\n");
+    for r in rows {
+        csv += &format!(
+            "{},{},{},{},{},{}\n",
+            r.point.x, r.point.y, r.manhattan, r.steps_one, r.steps_two, r.steps
+        );
     }
+    csv
+}
+
+/// Summarizes every crossover between two wires
+///
+/// Steps come from `Wire::steps_to`, so memory stays proportional to the
+/// number of commands rather than the length of the wires.
+///
+/// # Arguments
+///
+/// * wire_one - first wire
+/// * wire_two - second wire
+///
+/// # Returns
+///
+/// * one report per crossover
+fn reports(wire_one: &Wire, wire_two: &Wire) -> Vec<CrossoverReport> {
+    let port = wire_one.start;
+    wire_one
+        .crossovers(wire_two)
+        .into_iter()
+        .filter_map(|point| {
+            let (one, two) = (wire_one.steps_to(point)?, wire_two.steps_to(point)?);
+            Some(CrossoverReport {
+                point,
+                manhattan: Metric::Manhattan.distance(point, port),
+                steps_one: one,
+                steps_two: two,
+                steps: one + two,
+            })
+        })
+        .collect()
+}
+
+/// Summarizes every crossover between the wires in the input file
+///
+/// # Arguments
+///
+/// * filename - name of input file
+///
+/// # Returns
+///
+/// * one report per crossover, or the parse failure
+pub fn crossover_reports(filename: &str) -> Result<Vec<CrossoverReport>, ParseError> {
+    let (wire_one, wire_two) = load_wires(filename)?;
+    Ok(reports(&wire_one, &wire_two))
+}
+
+/// Exports every crossover between the wires in the input file as CSV
+///
+/// # Arguments
+///
+/// * filename - name of input file
+/// * sort_by - column rows are ordered by
+///
+/// # Returns
+///
+/// * CSV text, or the parse failure
+pub fn crossover_csv(filename: &str, sort_by: ReportColumn) -> Result<String, ParseError> {
+    Ok(reports_to_csv(&crossover_reports(filename)?, sort_by))
+}
+
+/// Computes both parts from a single parse and trace of the wires
+///
+/// # Arguments
+///
+/// * filename - name of input file
+///
+/// # Returns
+///
+/// * answers to both parts, or the parse failure
+pub fn solve(filename: &str) -> Result<Day3Answer, ParseError> {
+    let (wire_one, wire_two) = load_wires(filename)?;
+    let reports = reports(&wire_one, &wire_two);
+
+    Ok(Day3Answer {
+        closest_manhattan: reports.iter().map(|r| r.manhattan).min(),
+        fewest_steps: reports.iter().map(|r| r.steps).min(),
+        crossovers: reports.iter().map(|r| r.point).collect(),
+    })
 }
 
-pub fn printer(filename: &str) {
-    let input = shared::ingest_file(filename);
-    let wire_one = Wire::new(&input[0]);
-    let wire_two = Wire::new(&input[1]);
+/// Prints the whole panel for the wires in the input file
+///
+/// # Arguments
+///
+/// * filename - name of input file
+///
+/// # Returns
+///
+/// * nothing, or the parse failure
+pub fn printer(filename: &str) -> Result<(), ParseError> {
+    let (wire_one, wire_two) = load_wires(filename)?;
     let panel = Panel(wire_one, wire_two);
     panel.print_panel();
+    Ok(())
+}
+
+/// Summarizes both wires in the input file
+///
+/// # Arguments
+///
+/// * filename - name of input file
+///
+/// # Returns
+///
+/// * statistics for each wire, or the parse failure
+pub fn wire_stats(filename: &str) -> Result<(WireStats, WireStats), ParseError> {
+    let (wire_one, wire_two) = load_wires(filename)?;
+    Ok((wire_one.stats(), wire_two.stats()))
+}
+
+/// Counts wire visits per block of the panel in the input file
+///
+/// # Arguments
+///
+/// * filename - name of input file
+/// * opts - view options selecting the region and block size
+///
+/// # Returns
+///
+/// * visit counts per block, or the parse failure
+pub fn heatmap(filename: &str, opts: &ViewOptions) -> Result<Heatmap, ParseError> {
+    let (wire_one, wire_two) = load_wires(filename)?;
+    Ok(Panel(wire_one, wire_two).heatmap(opts))
+}
+
+/// Renders the panel as an SVG document
+///
+/// # Arguments
+///
+/// * filename - name of input file
+///
+/// # Returns
+///
+/// * SVG markup, or the parse failure
+pub fn svg(filename: &str) -> Result<String, ParseError> {
+    let (wire_one, wire_two) = load_wires(filename)?;
+    Ok(Panel(wire_one, wire_two).to_svg())
+}
+
+/// Opens the interactive terminal viewer on the panel
+///
+/// # Arguments
+///
+/// * filename - name of input file
+/// * no_color - draw plain glyphs without ANSI colors
+pub fn view(filename: &str, no_color: bool) -> io::Result<()> {
+    let (wire_one, wire_two) =
+        load_wires(filename).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    viewer::run(Panel(wire_one, wire_two), no_color)
+}
+
+/// Replays both wires being laid down, one frame per `every` steps
+///
+/// # Arguments
+///
+/// * filename - name of input file
+/// * opts - view options for each frame
+/// * every - steps between frames
+/// * on_frame - sink receiving each frame, such as `ansi_sink` or `ppm_sink`
+pub fn animate<F>(filename: &str, opts: &ViewOptions, every: usize, on_frame: F) -> io::Result<()>
+where
+    F: FnMut(&Frame) -> io::Result<()>,
+{
+    let (wire_one, wire_two) =
+        load_wires(filename).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    Panel(wire_one, wire_two).animate(opts, every, on_frame)
+}
+
+/// Prints a restricted or downsampled view of the panel
+///
+/// # Arguments
+///
+/// * filename - name of input file
+/// * opts - view options
+pub fn printer_with(filename: &str, opts: &ViewOptions) -> Result<(), ParseError> {
+    let (wire_one, wire_two) = load_wires(filename)?;
+    let panel = Panel(wire_one, wire_two);
+    panel.print_view(opts);
+    Ok(())
+}
+
+/// Day 3 as driven by the workspace runner
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Crossed Wires"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename)?.ok_or("wires never cross"))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename)?.map(|a| a.steps).ok_or("wires never cross"))
+    }
 }
 
 #[cfg(test)]
@@ -527,43 +2257,241 @@ mod tests {
 
     #[test]
     fn part1_works() {
-        let actual = part1("src/test.txt").unwrap();
+        let actual = part1("src/test.txt").unwrap().unwrap();
         assert_eq!(actual, 159);
     }
 
     #[test]
     fn part1_works_second_test() {
-        let actual = part1("src/test2.txt").unwrap();
+        let actual = part1("src/test2.txt").unwrap().unwrap();
         assert_eq!(actual, 135);
     }
 
     #[test]
-    fn part2_works() {
-        let actual = part2("src/test.txt").unwrap();
-        assert_eq!(actual, 610)
+    fn load_wires_reports_unreadable_file() {
+        let err = load_wires("src/no_such_file.txt").unwrap_err();
+        assert!(matches!(err.reason, ParseErrorReason::Io(_)));
+        assert_eq!(err.token, "src/no_such_file.txt");
+        assert!(err.to_string().starts_with("src/no_such_file.txt: "));
+        assert!(part1("src/no_such_file.txt").is_err());
+        assert!(part2("src/no_such_file.txt").is_err());
     }
 
     #[test]
-    fn part2_works_second_test() {
-        let actual = part2("src/test2.txt").unwrap();
-        assert_eq!(actual, 410)
+    fn closest_crossover_works_for_each_metric() {
+        assert_eq!(
+            closest_crossover("src/test.txt", Metric::Manhattan).unwrap(),
+            Some(159)
+        );
+        assert_eq!(
+            closest_crossover("src/test.txt", Metric::Chebyshev).unwrap(),
+            Some(146)
+        );
+        assert_eq!(
+            closest_crossover("src/test.txt", Metric::EuclideanSquared).unwrap(),
+            Some(146 * 146 + 46 * 46)
+        );
     }
-}
 
-#[cfg(test)]
-mod test_command {
-    use super::*;
+    #[test]
+    fn metric_distance_works() {
+        let a = Coord { x: -3, y: 4 };
+        let b = Coord { x: 2, y: 2 };
+        assert_eq!(Metric::Manhattan.distance(a, b), 7);
+        assert_eq!(Metric::Chebyshev.distance(a, b), 5);
+        assert_eq!(Metric::EuclideanSquared.distance(a, b), 29);
+    }
 
     #[test]
-    fn command_constructor_works() {
-        let input = "U32";
-        let expected = Command {
-            dir: Direction::Up,
-            count: 32,
-        };
-        let actual = Command::new(input);
+    fn solve_matches_parts() {
+        for filename in ["src/test.txt", "src/test2.txt"] {
+            let actual = solve(filename).unwrap();
+            assert_eq!(actual.closest_manhattan, part1(filename).unwrap());
+            assert_eq!(
+                actual.fewest_steps,
+                part2(filename).unwrap().map(|a| a.steps)
+            );
+        }
+    }
 
-        assert_eq!(actual, expected);
+    #[test]
+    fn solve_reports_crossovers() {
+        let actual = solve("src/test.txt").unwrap();
+        assert_eq!(actual.crossovers.len(), 4);
+        assert!(actual.crossovers.contains(&Coord { x: 155, y: 4 }));
+    }
+
+    #[test]
+    fn crossover_reports_works() {
+        let actual = crossover_reports("src/test.txt").unwrap();
+        let expected = CrossoverReport {
+            point: Coord { x: 155, y: 4 },
+            manhattan: 159,
+            steps_one: 341,
+            steps_two: 385,
+            steps: 726,
+        };
+        assert_eq!(actual.len(), 4);
+        assert!(actual.contains(&expected));
+    }
+
+    #[test]
+    fn crossover_csv_works() {
+        let actual = crossover_csv("src/test.txt", ReportColumn::Steps).unwrap();
+        let expected = "x,y,manhattan,steps_one,steps_two,steps\n\
+                        158,-12,170,206,404,610\n\
+                        146,46,192,290,334,624\n\
+                        155,4,159,341,385,726\n\
+                        155,11,166,472,378,850\n";
+
+        assert_eq!(actual, expected);
+        let by_x = crossover_csv("src/test.txt", ReportColumn::X).unwrap();
+        assert!(by_x.lines().nth(1).unwrap().starts_with("146,46,"));
+        assert_eq!("steps_two".parse(), Ok(ReportColumn::StepsTwo));
+        assert!("z".parse::<ReportColumn>().is_err());
+    }
+
+    #[test]
+    fn crossover_report_json_round_trip() {
+        let report = CrossoverReport {
+            point: Coord { x: -3, y: 7 },
+            manhattan: 10,
+            steps_one: 30,
+            steps_two: 12,
+            steps: 42,
+        };
+        let json = serde_json::to_string(&report).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"point":{"x":-3,"y":7},"manhattan":10,"steps_one":30,"steps_two":12,"steps":42}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<CrossoverReport>(&json).unwrap(),
+            report
+        );
+    }
+
+    #[test]
+    fn wire_json_round_trip() {
+        let wire = Wire::new("U7,DL3").unwrap();
+        let json = serde_json::to_string(&wire).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"cmds":[{"dir":"Up","count":7},{"dir":"DownLeft","count":3}]}"#
+        );
+        assert_eq!(serde_json::from_str::<Wire>(&json).unwrap(), wire);
+    }
+
+    #[test]
+    fn part2_works() {
+        let actual = part2("src/test.txt").unwrap().unwrap();
+        let expected = Part2Answer {
+            steps: 610,
+            winners: vec![Coord { x: 158, y: -12 }],
+            runner_up: Some((624, vec![Coord { x: 146, y: 46 }])),
+        };
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn part2_works_second_test() {
+        let actual = part2("src/test2.txt").unwrap().unwrap();
+        assert_eq!(actual.steps, 410);
+        assert_eq!(actual.winners, vec![Coord { x: 107, y: 47 }]);
+        assert_eq!(actual.runner_up, Some((516, vec![Coord { x: 124, y: 11 }])));
+    }
+
+    #[test]
+    fn part2_reports_ties() {
+        let answer = part2("src/test_ties.txt").unwrap().unwrap();
+
+        assert_eq!(answer.steps, 6);
+        assert_eq!(
+            answer.winners,
+            vec![Coord { x: 0, y: -1 }, Coord { x: 1, y: 0 }]
+        );
+        assert_eq!(answer.runner_up, Some((8, vec![Coord { x: 1, y: -1 }])));
+    }
+
+    #[test]
+    fn part2_none_without_crossovers() {
+        let path = std::env::temp_dir().join(format!("day3-parallel-{}", std::process::id()));
+        std::fs::write(&path, "U5\nD5\n").unwrap();
+
+        let actual = part2(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(actual.unwrap(), None);
+    }
+}
+
+#[cfg(test)]
+mod test_command {
+    use super::*;
+
+    #[test]
+    fn command_constructor_works() {
+        let input = "U32";
+        let expected = Command {
+            dir: Direction::Up,
+            count: 32,
+        };
+        let actual = Command::new(input).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn command_constructor_works_diagonal() {
+        let expected = Command {
+            dir: Direction::DownLeft,
+            count: 3,
+        };
+        let actual = Command::new("DL3").unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(actual.to_string(), "DL3");
+        assert_eq!(
+            Command::new("LU3").unwrap_err().reason,
+            ParseErrorReason::UnknownDirection(String::from("LU"))
+        );
+    }
+
+    #[test]
+    fn coords_diagonal_works() {
+        let input = Command {
+            dir: Direction::UpLeft,
+            count: 3,
+        };
+        let expected = vec![
+            Coord { x: 4, y: 6 },
+            Coord { x: 3, y: 7 },
+            Coord { x: 2, y: 8 },
+        ];
+        let actual: Vec<Coord> = input.coords(Coord { x: 5, y: 5 }).collect();
+
+        assert_eq!(actual, expected);
+        assert_eq!(input.last_coord(Coord { x: 5, y: 5 }), Coord { x: 2, y: 8 });
+    }
+
+    #[test]
+    fn command_constructor_rejects_malformed_tokens() {
+        let unknown = Command::new("Q5").unwrap_err();
+        assert_eq!(
+            unknown.reason,
+            ParseErrorReason::UnknownDirection(String::from("Q"))
+        );
+        assert_eq!(unknown.token, "Q5");
+
+        let empty = Command::new("").unwrap_err();
+        assert_eq!(empty.reason, ParseErrorReason::Empty);
+
+        let count = Command::new("U1x").unwrap_err();
+        assert!(matches!(count.reason, ParseErrorReason::InvalidCount(_)));
+
+        let missing = Command::new("R").unwrap_err();
+        assert!(matches!(missing.reason, ParseErrorReason::InvalidCount(_)));
     }
 
     #[test]
@@ -581,110 +2509,632 @@ mod test_command {
     }
 
     #[test]
-    fn coords_down_works() {
-        let input = Command {
-            dir: Direction::Down,
-            count: 10,
-        };
-        let mut expected: Vec<Coord> = Vec::new();
-        for i in 1..=10 {
-            expected.push(Coord { x: 5, y: 5 - i });
-        }
-        let mut actual: Vec<Coord> = input.coords(Coord { x: 5, y: 5 }).collect();
-        assert_eq!(actual.sort(), expected.sort());
+    fn coords_down_works() {
+        let input = Command {
+            dir: Direction::Down,
+            count: 10,
+        };
+        let mut expected: Vec<Coord> = Vec::new();
+        for i in 1..=10 {
+            expected.push(Coord { x: 5, y: 5 - i });
+        }
+        let mut actual: Vec<Coord> = input.coords(Coord { x: 5, y: 5 }).collect();
+        actual.sort();
+        expected.sort();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn coords_right_works() {
+        let input = Command {
+            dir: Direction::Right,
+            count: 71,
+        };
+        let mut expected: Vec<Coord> = Vec::new();
+        for i in 1..=71 {
+            expected.push(Coord { y: 4, x: 146 + i });
+        }
+        let actual: Vec<Coord> = input.coords(Coord { x: 146, y: 4 }).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn coords_left_works() {
+        let input = Command {
+            dir: Direction::Left,
+            count: 10,
+        };
+        let mut expected: Vec<Coord> = Vec::new();
+        for i in 1..=10 {
+            expected.push(Coord { y: 5, x: 5 - i });
+        }
+        let mut actual: Vec<Coord> = input.coords(Coord { x: 5, y: 5 }).collect();
+        actual.sort();
+        expected.sort();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn coords_reports_exact_len() {
+        let input = Command::new("DL4").unwrap();
+        let mut coords = input.coords(Coord { x: 0, y: 0 });
+
+        assert_eq!(coords.len(), 4);
+        coords.next();
+        assert_eq!(coords.len(), 3);
+        assert_eq!(coords.last(), Some(Coord { x: -4, y: -4 }));
+        assert_eq!(
+            Command::new("U0")
+                .unwrap()
+                .coords(Coord { x: 1, y: 1 })
+                .next(),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_wire {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn wire_constructor_works() {
+        let input = "U32,D15,L16,R240";
+        let expected = Wire {
+            cmds: vec![
+                Command {
+                    dir: Direction::Up,
+                    count: 32,
+                },
+                Command {
+                    dir: Direction::Down,
+                    count: 15,
+                },
+                Command {
+                    dir: Direction::Left,
+                    count: 16,
+                },
+                Command {
+                    dir: Direction::Right,
+                    count: 240,
+                },
+            ],
+            start: Coord::default(),
+        };
+
+        let actual = Wire::new(input).unwrap();
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn from_commands_matches_parsed_wire() {
+        let cmds = vec![
+            Command {
+                dir: Direction::Right,
+                count: 8,
+            },
+            Command {
+                dir: Direction::Up,
+                count: 5,
+            },
+        ];
+        let actual = Wire::from_commands(cmds.clone());
+
+        assert_eq!(actual, Wire::new("R8,U5").unwrap());
+        assert_eq!(actual.commands(), &cmds[..]);
+    }
+
+    #[test]
+    fn wire_constructor_reports_token_position() {
+        let actual = Wire::new("U32,D15,X16,R240").unwrap_err();
+        assert_eq!(actual.token, "X16");
+        assert_eq!(actual.position, 8);
+        assert_eq!(
+            actual.reason,
+            ParseErrorReason::UnknownDirection(String::from("X"))
+        );
+        assert_eq!(
+            actual.to_string(),
+            "line 1, column 9: unknown direction X in token 'X16'"
+        );
+    }
+
+    #[test]
+    fn trace_works() {
+        let input: Wire = Wire {
+            cmds: vec![
+                Command {
+                    dir: Direction::Up,
+                    count: 7,
+                },
+                Command {
+                    dir: Direction::Left,
+                    count: 3,
+                },
+            ],
+            start: Coord::default(),
+        };
+        let mut expected: Vec<Coord> = vec![
+            Coord { x: 0, y: 1 },
+            Coord { x: 0, y: 2 },
+            Coord { x: 0, y: 3 },
+            Coord { x: 0, y: 4 },
+            Coord { x: 0, y: 5 },
+            Coord { x: 0, y: 6 },
+            Coord { x: 0, y: 7 },
+            Coord { x: -1, y: 7 },
+            Coord { x: -2, y: 7 },
+            Coord { x: -3, y: 7 },
+        ];
+
+        let mut actual = input.trace(Coord { x: 0, y: 0 });
+        actual.sort();
+        expected.sort();
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn trace_iter_matches_trace() {
+        let input = shared::ingest_file("src/test2.txt");
+        for line in input {
+            let wire = Wire::new(&line).unwrap();
+            let start = Coord { x: 3, y: -2 };
+            let streamed: Vec<Coord> = wire.trace_iter(start).collect();
+            assert_eq!(streamed, wire.trace(start));
+            assert_eq!(
+                streamed.len() as u64,
+                wire.cmds.iter().map(|c| c.count as u64).sum::<u64>()
+            );
+        }
+    }
+
+    /// Wire well away from anything the simplify tests lay out
+    fn far_wire() -> Wire {
+        Wire::new("R1").unwrap().translate(1000, 1000)
+    }
+
+    /// Reports sorted by point, as the order crossovers are found in
+    /// follows the wires' commands
+    fn sorted_reports(wire_one: &Wire, wire_two: &Wire) -> Vec<CrossoverReport> {
+        let mut rows = reports(wire_one, wire_two);
+        rows.sort_by_key(|r| (r.point, r.steps));
+        rows
+    }
+
+    /// Simplifies both wires, each against the other
+    fn simplify_both(wire_one: &Wire, wire_two: &Wire) -> (Wire, Wire) {
+        let simple_one = wire_one.simplify(wire_two);
+        let simple_two = wire_two.simplify(&simple_one);
+        (simple_one, simple_two)
+    }
+
+    #[test]
+    fn simplify_works() {
+        let cases = vec![
+            ("U3,U2,R1", "U5,R1"),
+            ("U5,D3,R1", "U2,R1"),
+            ("U3,D5,L1", "D2,L1"),
+            ("R2,U3,D3,R4", "R6"),
+            ("R2,L0,R1,DL2,UR2,U1", "R3,U1"),
+            ("U4,D4", ""),
+        ];
+        for (input, expected) in cases {
+            let actual = Wire::new(input).unwrap().simplify(&far_wire());
+            let expected = match expected {
+                "" => Wire::from_commands(Vec::new()),
+                cmds_s => Wire::new(cmds_s).unwrap(),
+            };
+            assert_eq!(actual, expected, "failed with input: {}", input);
+        }
+    }
+
+    #[test]
+    fn simplify_merging_keeps_trace() {
+        let wire = Wire::new("R3,R0,R4,U2,U2,L1,DL3,DL2").unwrap();
+        let simplified = wire.simplify(&far_wire());
+        let origin = Coord { x: 0, y: 0 };
+
+        assert_eq!(simplified.cmds.len(), 4);
+        assert_eq!(simplified.trace(origin), wire.trace(origin));
+    }
+
+    #[test]
+    fn simplify_keeps_corner_the_other_wire_crosses() {
+        let wire_one = Wire::new("R3,R3").unwrap();
+        let wire_two = Wire::new("R3,U1,D2").unwrap();
+
+        assert!(wire_one.crossovers(&wire_two).is_empty());
+        let simplified = wire_one.simplify(&wire_two);
+        assert_eq!(simplified, wire_one);
+        assert!(simplified.crossovers(&wire_two).is_empty());
+        assert_eq!(wire_one.simplify(&far_wire()), Wire::new("R6").unwrap());
+    }
+
+    #[test]
+    fn simplify_keeps_crossovers_and_steps() {
+        let pairs = vec![
+            ("R3,R5,U2,U3,L5,L1,D3", "U1,U6,R4,D1,D6"),
+            ("U3,U4,L3", "L2,U5,U3,D0"),
+        ];
+        for (one, two) in pairs {
+            let (wire_one, wire_two) = (Wire::new(one).unwrap(), Wire::new(two).unwrap());
+            let (simple_one, simple_two) = simplify_both(&wire_one, &wire_two);
+            assert!(simple_one.cmds.len() < wire_one.cmds.len());
+
+            let crossovers = wire_one.crossovers(&wire_two);
+            assert_eq!(simple_one.crossovers(&simple_two), crossovers);
+            for point in crossovers {
+                assert_eq!(
+                    simple_one.steps_to_crossover(&simple_two, point),
+                    wire_one.steps_to_crossover(&wire_two, point)
+                );
+                assert_eq!(
+                    simple_two.steps_to_crossover(&simple_one, point),
+                    wire_two.steps_to_crossover(&wire_one, point)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn simplify_keeps_spur_before_crossover() {
+        let wire_one = Wire::new("U5,D2,R4").unwrap();
+        let wire_two = Wire::new("R2,U6").unwrap();
+        let point = Coord { x: 2, y: 3 };
+
+        // cancelling the spur would cut the steps to the crossover from 9 to 5
+        let simplified = wire_one.simplify(&wire_two);
+        assert_eq!(simplified, wire_one);
+        assert_eq!(simplified.steps_to_crossover(&wire_two, point), Ok(9));
+
+        // past the last crossover the spur can go
+        let wire_one = Wire::new("R4,U5,D2,L1").unwrap();
+        let wire_two = Wire::new("R2,D1,U3").unwrap();
+        let simplified = wire_one.simplify(&wire_two);
+        assert_eq!(simplified, Wire::new("R4,U3,L1").unwrap());
+        assert_eq!(
+            simplified.crossovers(&wire_two),
+            wire_one.crossovers(&wire_two)
+        );
+    }
+
+    #[test]
+    fn simplify_keeps_answers_for_inputs() {
+        for filename in ["src/test.txt", "src/test2.txt", "src/input.txt"] {
+            let (wire_one, wire_two) = load_wires(filename).unwrap();
+            let (simple_one, simple_two) = simplify_both(&wire_one, &wire_two);
+            assert_eq!(
+                sorted_reports(&simple_one, &simple_two),
+                sorted_reports(&wire_one, &wire_two)
+            );
+        }
+    }
+
+    fn command() -> impl Strategy<Value = Command> {
+        let dirs = [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+            Direction::UpLeft,
+            Direction::DownRight,
+        ];
+        (0..dirs.len(), 0u32..5).prop_map(move |(d, count)| Command {
+            dir: dirs[d],
+            count,
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn simplify_keeps_crossovers_and_answers(
+            one in prop::collection::vec(command(), 1..12),
+            two in prop::collection::vec(command(), 1..12),
+        ) {
+            let (wire_one, wire_two) = (Wire::from_commands(one), Wire::from_commands(two));
+            let (simple_one, simple_two) = simplify_both(&wire_one, &wire_two);
+            prop_assert!(simple_one.cmds.len() <= wire_one.cmds.len());
+            prop_assert!(simple_two.cmds.len() <= wire_two.cmds.len());
+
+            let mut crossovers = wire_one.crossovers(&wire_two);
+            let mut simple_crossovers = simple_one.crossovers(&simple_two);
+            crossovers.sort();
+            simple_crossovers.sort();
+            prop_assert_eq!(simple_crossovers, crossovers);
+
+            // part1 and part2 are the least manhattan distance and steps
+            let original = sorted_reports(&wire_one, &wire_two);
+            let simplified = sorted_reports(&simple_one, &simple_two);
+            prop_assert_eq!(
+                simplified.iter().map(|r| r.manhattan).min(),
+                original.iter().map(|r| r.manhattan).min()
+            );
+            prop_assert_eq!(
+                simplified.iter().map(|r| r.steps).min(),
+                original.iter().map(|r| r.steps).min()
+            );
+            prop_assert_eq!(simplified, original);
+        }
+    }
+
+    #[test]
+    fn crossovers_with_endpoints() {
+        let wire_one = Wire::new("R4,U2").unwrap();
+        let wire_two = Wire::new("U2,R4").unwrap();
+        let inclusive = CrossoverRules {
+            endpoints: Endpoints::Included,
+            exclude_within: None,
+        };
+
+        assert!(wire_one.crossovers(&wire_two).is_empty());
+        assert_eq!(
+            wire_one.crossovers_with(&wire_two, inclusive),
+            vec![Coord { x: 0, y: 0 }, Coord { x: 4, y: 2 }]
+        );
+        assert_eq!(
+            wire_one.crossovers_with(
+                &wire_two,
+                CrossoverRules {
+                    exclude_within: Some(0),
+                    ..inclusive
+                }
+            ),
+            vec![Coord { x: 4, y: 2 }]
+        );
+    }
+
+    #[test]
+    fn crossovers_with_excludes_near_origin() {
+        let wire_one = Wire::new("U2").unwrap();
+        let wire_two = Wire::new("R1,U1,L2").unwrap();
+        let within = |d| CrossoverRules {
+            exclude_within: Some(d),
+            ..CrossoverRules::default()
+        };
+
+        assert_eq!(wire_one.crossovers(&wire_two), vec![Coord { x: 0, y: 1 }]);
+        assert_eq!(
+            wire_one.crossovers_with(&wire_two, within(0)),
+            vec![Coord { x: 0, y: 1 }]
+        );
+        assert!(wire_one.crossovers_with(&wire_two, within(1)).is_empty());
+    }
+
+    #[test]
+    fn stats_works() {
+        let wire = Wire::new("R8,U5,L12,DR20,U5").unwrap();
+        let stats = wire.stats();
+
+        assert_eq!(stats.total_length, 50);
+        assert_eq!(stats.segments, 5);
+        assert_eq!(stats.bounding_area, 21 * 21);
+        assert_eq!(
+            stats.directions.into_iter().collect::<Vec<_>>(),
+            vec![
+                (Direction::Up, 2),
+                (Direction::Left, 1),
+                (Direction::Right, 1),
+                (Direction::DownRight, 1)
+            ]
+        );
+        assert_eq!(stats.longest, Command::new("DR20").ok());
+
+        let empty = Wire::from_commands(Vec::new()).stats();
+        assert_eq!((empty.total_length, empty.bounding_area), (0, 1));
+        assert_eq!(empty.longest, None);
+    }
+
+    #[test]
+    fn intersections_works() {
+        let wire_one = Wire::new("R6,U4").unwrap();
+        let wire_two = Wire::new("U2,R3,D3,R3,U1").unwrap();
+        let kinds: Vec<(Coord, IntersectionKind)> = wire_one
+            .intersections(&wire_two)
+            .into_iter()
+            .map(|i| (i.point, i.kind))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                (Coord { x: 0, y: 0 }, IntersectionKind::TouchEndpoint),
+                (Coord { x: 3, y: 0 }, IntersectionKind::Cross),
+                (Coord { x: 6, y: 0 }, IntersectionKind::TouchEndpoint),
+            ]
+        );
+        assert_eq!(wire_one.crossovers(&wire_two), vec![Coord { x: 3, y: 0 }]);
+
+        let overlapping = Wire::new("U1,R2,D1,R2").unwrap();
+        let kinds: Vec<IntersectionKind> = wire_one
+            .intersections(&overlapping)
+            .into_iter()
+            .map(|i| i.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                IntersectionKind::TouchEndpoint,
+                IntersectionKind::TouchEndpoint,
+                IntersectionKind::Overlap,
+                IntersectionKind::Overlap,
+            ]
+        );
+    }
+
+    #[test]
+    fn overlaps_works() {
+        let wire_one = Wire::new("R8,U5").unwrap();
+        let wire_two = Wire::new("U2,R3,D2,R7").unwrap();
+        let expected = vec![Overlap {
+            interval: CornerPair(Coord { x: 3, y: 0 }, Coord { x: 8, y: 0 }),
+            length: 5,
+        }];
+
+        assert_eq!(wire_one.overlaps(&wire_two), expected);
+        assert!(wire_one.crossovers(&wire_two).is_empty());
+
+        let wire_one = Wire::new("U5").unwrap();
+        let wire_two = Wire::new("R1,U3,L1,D2").unwrap();
+        let expected = vec![Overlap {
+            interval: CornerPair(Coord { x: 0, y: 1 }, Coord { x: 0, y: 3 }),
+            length: 2,
+        }];
+        assert_eq!(wire_one.overlaps(&wire_two), expected);
+    }
+
+    #[test]
+    fn translate_works() {
+        let wire = Wire::new("R2,U1").unwrap().translate(3, -1);
+        let expected = vec![
+            Coord { x: 4, y: -1 },
+            Coord { x: 5, y: -1 },
+            Coord { x: 5, y: 0 },
+        ];
+
+        assert_eq!(wire.start(), Coord { x: 3, y: -1 });
+        assert_eq!(wire.trace(wire.start()), expected);
+        assert_eq!(wire.bounds(), (Coord { x: 3, y: -1 }, Coord { x: 5, y: 0 }));
+    }
+
+    #[test]
+    fn rotate90_works() {
+        let wire = Wire::new("R2,U1,DL1").unwrap().translate(1, 2);
+        let rotated = wire.rotate90();
+        let expected: Vec<Coord> = wire
+            .trace(wire.start())
+            .into_iter()
+            .map(|c| Coord { x: -c.y, y: c.x })
+            .collect();
+
+        assert_eq!(rotated, Wire::new("U2,L1,DR1").unwrap().translate(-2, 1));
+        assert_eq!(rotated.trace(rotated.start()), expected);
+        assert_eq!(rotated.rotate90().rotate90().rotate90(), wire);
+    }
+
+    #[test]
+    fn mirror_works() {
+        let wire = Wire::new("R2,UR1,D3").unwrap();
+
+        assert_eq!(wire.mirror(Axis::X), Wire::new("R2,DR1,U3").unwrap());
+        assert_eq!(wire.mirror(Axis::Y), Wire::new("L2,UL1,D3").unwrap());
+        assert_eq!(wire.mirror(Axis::Y).mirror(Axis::Y), wire);
+    }
+
+    #[test]
+    fn transforms_keep_crossover_distances() {
+        let (wire_one, wire_two) = load_wires("src/test.txt").unwrap();
+        let distances = |one: &Wire, two: &Wire| {
+            let mut distances: Vec<i64> = one
+                .crossovers(two)
+                .into_iter()
+                .map(|c| Metric::Manhattan.distance(c, Coord { x: 0, y: 0 }))
+                .collect();
+            distances.sort();
+            distances
+        };
+        let expected = distances(&wire_one, &wire_two);
+
+        assert_eq!(
+            distances(&wire_one.rotate90(), &wire_two.rotate90()),
+            expected
+        );
+        assert_eq!(
+            distances(&wire_one.mirror(Axis::X), &wire_two.mirror(Axis::X)),
+            expected
+        );
+        assert_eq!(
+            wire_one
+                .translate(5, 5)
+                .crossovers(&wire_two.translate(5, 5))
+                .len(),
+            expected.len()
+        );
+    }
+
+    #[test]
+    fn bounds_works() {
+        let wire = Wire::new("R8,U5,L12,DR20").unwrap();
+        let expected = (Coord { x: -4, y: -15 }, Coord { x: 16, y: 5 });
+
+        assert_eq!(wire.bounds(), expected);
+        assert_eq!(
+            Wire::new("U3").unwrap().bounds(),
+            (Coord { x: 0, y: 0 }, Coord { x: 0, y: 3 })
+        );
     }
 
     #[test]
-    fn coords_right_works() {
-        let input = Command {
-            dir: Direction::Right,
-            count: 71,
-        };
-        let mut expected: Vec<Coord> = Vec::new();
-        for i in 1..=71 {
-            expected.push(Coord { y: 4, x: 146 + i });
+    fn steps_to_matches_step_map() {
+        let wire = Wire::new("R5,U2,L3,DR2,D4,UL6,U3").unwrap();
+        let steps = wire.step_map(wire.start());
+        for coord in wire.trace(wire.start()) {
+            assert_eq!(wire.steps_to(coord), Some(steps[&coord]), "{}", coord);
         }
-        let actual: Vec<Coord> = input.coords(Coord { x: 146, y: 4 }).collect();
-        assert_eq!(actual, expected);
+        assert_eq!(wire.steps_to(Coord { x: 40, y: 40 }), None);
+        assert_eq!(
+            Wire::new("U2").unwrap().steps_to(Coord { x: 0, y: 0 }),
+            None
+        );
+        assert_eq!(
+            Wire::new("U2,D2").unwrap().steps_to(Coord { x: 0, y: 0 }),
+            Some(4)
+        );
     }
 
     #[test]
-    fn coords_left_works() {
-        let input = Command {
-            dir: Direction::Left,
-            count: 10,
-        };
-        let mut expected: Vec<Coord> = Vec::new();
-        for i in 1..=10 {
-            expected.push(Coord { y: 5, x: 5 - i });
+    fn steps_to_matches_trace_for_inputs() {
+        for filename in ["src/test.txt", "src/test2.txt", "src/input.txt"] {
+            let (wire_one, wire_two) = load_wires(filename).unwrap();
+            let steps_one = wire_one.step_map(wire_one.start());
+            let steps_two = wire_two.step_map(wire_two.start());
+            for point in wire_one.crossovers(&wire_two) {
+                assert_eq!(wire_one.steps_to(point), steps_one.get(&point).copied());
+                assert_eq!(wire_two.steps_to(point), steps_two.get(&point).copied());
+            }
         }
-        let mut actual: Vec<Coord> = input.coords(Coord { x: 5, y: 5 }).collect();
-        assert_eq!(actual.sort(), expected.sort());
     }
-}
-
-#[cfg(test)]
-mod test_wire {
-    use super::*;
 
     #[test]
-    fn wire_constructor_works() {
-        let input = "U32,D15,L16,R240";
-        let expected = Wire {
-            cmds: vec![
-                Command {
-                    dir: Direction::Up,
-                    count: 32,
-                },
-                Command {
-                    dir: Direction::Down,
-                    count: 15,
-                },
-                Command {
-                    dir: Direction::Left,
-                    count: 16,
-                },
-                Command {
-                    dir: Direction::Right,
-                    count: 240,
-                },
-            ],
-        };
+    fn reports_never_walk_long_wires() {
+        let wire_one = Wire::new("U10,R1000000000").unwrap();
+        let wire_two = Wire::new("R500000000,U20").unwrap();
+        let expected = vec![CrossoverReport {
+            point: Coord {
+                x: 500_000_000,
+                y: 10,
+            },
+            manhattan: 500_000_010,
+            steps_one: 500_000_010,
+            steps_two: 500_000_010,
+            steps: 1_000_000_020,
+        }];
+
+        assert_eq!(reports(&wire_one, &wire_two), expected);
+    }
 
-        let actual = Wire::new(input);
-        assert_eq!(actual, expected)
+    #[test]
+    fn step_map_keeps_first_visit() {
+        let wire = Wire::new("R2,U1,L1,D2").unwrap();
+        let steps = wire.step_map(Coord { x: 0, y: 0 });
+        assert_eq!(steps[&Coord { x: 1, y: 0 }], 1);
+        assert_eq!(steps[&Coord { x: 1, y: 1 }], 4);
+        assert_eq!(steps[&Coord { x: 1, y: -1 }], 6);
+        assert_eq!(steps.len(), 5);
     }
 
     #[test]
-    fn trace_works() {
-        let input: Wire = Wire {
-            cmds: vec![
-                Command {
-                    dir: Direction::Up,
-                    count: 7,
-                },
-                Command {
-                    dir: Direction::Left,
-                    count: 3,
-                },
-            ],
-        };
-        let mut expected: Vec<Coord> = vec![
+    fn trace_iter_skips_empty_commands() {
+        let wire = Wire::new("U2,L0,R1").unwrap();
+        let actual: Vec<Coord> = wire.trace_iter(Coord { x: 0, y: 0 }).collect();
+        let expected = vec![
             Coord { x: 0, y: 1 },
             Coord { x: 0, y: 2 },
-            Coord { x: 0, y: 3 },
-            Coord { x: 0, y: 4 },
-            Coord { x: 0, y: 5 },
-            Coord { x: 0, y: 6 },
-            Coord { x: 0, y: 7 },
-            Coord { x: -1, y: 7 },
-            Coord { x: -2, y: 7 },
-            Coord { x: -3, y: 7 },
+            Coord { x: 1, y: 2 },
         ];
-
-        let mut actual = input.trace(Coord { x: 0, y: 0 });
-
-        assert_eq!(actual.sort(), expected.sort())
+        assert_eq!(actual, expected);
     }
 
     #[test]
@@ -700,6 +3150,7 @@ mod test_wire {
                     count: 3,
                 },
             ],
+            start: Coord::default(),
         };
         let wire_two = Wire {
             cmds: vec![
@@ -712,6 +3163,7 @@ mod test_wire {
                     count: 8,
                 },
             ],
+            start: Coord::default(),
         };
         let expected: Vec<Coord> = vec![Coord { x: -2, y: 7 }];
         let actual: Vec<Coord> = wire_one.crossovers(&wire_two);
@@ -736,21 +3188,38 @@ mod test_wire {
                     count: 14,
                 },
             ],
+            start: Coord::default(),
         };
-        let mut expected = vec![
-            Coord { x: 0, y: 7 },
-            Coord { x: 3, y: 7 },
-            Coord { x: 3, y: -7 },
+        let expected = vec![
+            CornerPair(Coord { x: 0, y: 0 }, Coord { x: 0, y: 7 }),
+            CornerPair(Coord { x: 0, y: 7 }, Coord { x: -3, y: 7 }),
+            CornerPair(Coord { x: -3, y: 7 }, Coord { x: -3, y: -7 }),
         ];
 
-        let mut actual = input.trace_corners(Coord { x: 0, y: 0 });
-        assert_eq!(actual.sort(), expected.sort());
+        let actual = input.trace_corners(Coord { x: 0, y: 0 });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn crossovers_works_diagonal() {
+        let wire_one = Wire::new("UR6,R2").unwrap();
+        let wire_two = Wire::new("U8,DR8").unwrap();
+
+        assert_eq!(wire_one.crossovers(&wire_two), vec![Coord { x: 4, y: 4 }]);
+        assert_eq!(
+            wire_one.steps_to_crossover(&wire_two, Coord { x: 4, y: 4 }),
+            Ok(4)
+        );
+        assert_eq!(
+            wire_two.steps_to_crossover(&wire_one, Coord { x: 4, y: 4 }),
+            Ok(12)
+        );
     }
 
     #[test]
     fn trace_corners_works_test_input() {
         let input = shared::ingest_file("src/test.txt");
-        let wire_one = Wire::new(&input[0]);
+        let wire_one = Wire::new(&input[0]).unwrap();
         let actual = wire_one.trace_corners(Coord { x: 0, y: 0 });
         let expected = vec![
             CornerPair(Coord { x: 0, y: 0 }, Coord { x: 75, y: 0 }),
@@ -782,6 +3251,7 @@ mod test_wire {
                     count: 3,
                 },
             ],
+            start: Coord::default(),
         };
         let wire_two = Wire {
             cmds: vec![
@@ -802,6 +3272,7 @@ mod test_wire {
                     count: 15,
                 },
             ],
+            start: Coord::default(),
         };
         let crossover: Coord = Coord { x: -2, y: 7 };
         let expected_one: Result<u64, String> = Ok(9);
@@ -817,6 +3288,76 @@ mod test_wire {
 #[cfg(test)]
 mod test_corner_pair {
     use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn intersection_with_endpoints_works() {
+        let first = CornerPair(Coord { x: 0, y: 0 }, Coord { x: 4, y: 0 });
+        let second = CornerPair(Coord { x: 2, y: 0 }, Coord { x: 2, y: 3 });
+
+        assert_eq!(first.intersection(second), None);
+        assert_eq!(
+            first.intersection_with(second, Endpoints::Included),
+            Some(Coord { x: 2, y: 0 })
+        );
+    }
+
+    #[test]
+    fn contact_works() {
+        let first = CornerPair(Coord { x: 0, y: 0 }, Coord { x: 4, y: 0 });
+        let kind = |other: CornerPair| first.contact(other).map(|i| (i.point, i.kind));
+
+        assert_eq!(
+            kind(CornerPair(Coord { x: 2, y: -1 }, Coord { x: 2, y: 3 })),
+            Some((Coord { x: 2, y: 0 }, IntersectionKind::Cross))
+        );
+        assert_eq!(
+            kind(CornerPair(Coord { x: 2, y: 0 }, Coord { x: 2, y: 3 })),
+            Some((Coord { x: 2, y: 0 }, IntersectionKind::TouchEndpoint))
+        );
+        assert_eq!(
+            kind(CornerPair(Coord { x: 4, y: -2 }, Coord { x: 4, y: 3 })),
+            Some((Coord { x: 4, y: 0 }, IntersectionKind::TouchEndpoint))
+        );
+        assert_eq!(
+            kind(CornerPair(Coord { x: 2, y: 0 }, Coord { x: 6, y: 0 })),
+            None
+        );
+    }
+
+    #[test]
+    fn overlap_works() {
+        let pair = CornerPair(Coord { x: 0, y: 0 }, Coord { x: 8, y: 0 });
+        let cases = vec![
+            (
+                CornerPair(Coord { x: 3, y: 0 }, Coord { x: 10, y: 0 }),
+                Some(CornerPair(Coord { x: 3, y: 0 }, Coord { x: 8, y: 0 })),
+            ),
+            (
+                CornerPair(Coord { x: 6, y: 0 }, Coord { x: -2, y: 0 }),
+                Some(CornerPair(Coord { x: 0, y: 0 }, Coord { x: 6, y: 0 })),
+            ),
+            (
+                CornerPair(Coord { x: 8, y: 0 }, Coord { x: 12, y: 0 }),
+                None,
+            ),
+            (CornerPair(Coord { x: 2, y: 1 }, Coord { x: 6, y: 1 }), None),
+            (
+                CornerPair(Coord { x: 2, y: -3 }, Coord { x: 2, y: 3 }),
+                None,
+            ),
+        ];
+        for (other, expected) in cases {
+            assert_eq!(pair.overlap(other), expected, "{}", other);
+        }
+
+        let rising = CornerPair(Coord { x: 0, y: 0 }, Coord { x: 4, y: 4 });
+        assert_eq!(
+            rising.overlap(CornerPair(Coord { x: 2, y: 2 }, Coord { x: 1, y: 1 })),
+            Some(CornerPair(Coord { x: 1, y: 1 }, Coord { x: 2, y: 2 }))
+        );
+    }
 
     #[test]
     fn orientation_works() {
@@ -842,4 +3383,410 @@ mod test_corner_pair {
 
         assert_eq!(actual, None);
     }
+
+    #[test]
+    fn intersection_works_diagonal() {
+        let rising = CornerPair(Coord { x: 0, y: 0 }, Coord { x: 6, y: 6 });
+        let vertical = CornerPair(Coord { x: 2, y: 5 }, Coord { x: 2, y: -1 });
+        let falling = CornerPair(Coord { x: 0, y: 8 }, Coord { x: 8, y: 0 });
+
+        assert_eq!(rising.intersection(vertical), Some(Coord { x: 2, y: 2 }));
+        assert_eq!(vertical.intersection(rising), Some(Coord { x: 2, y: 2 }));
+        assert_eq!(rising.intersection(falling), Some(Coord { x: 4, y: 4 }));
+    }
+
+    #[test]
+    fn intersection_diagonals_between_cells() {
+        let rising = CornerPair(Coord { x: 0, y: 0 }, Coord { x: 6, y: 6 });
+        let falling = CornerPair(Coord { x: 0, y: 7 }, Coord { x: 7, y: 0 });
+
+        assert_eq!(rising.intersection(falling), None);
+    }
+
+    #[test]
+    fn orientation_works_diagonal() {
+        let rising = CornerPair(Coord { x: 3, y: 3 }, Coord { x: 0, y: 0 });
+        let falling = CornerPair(Coord { x: 0, y: 0 }, Coord { x: -2, y: 2 });
+
+        assert_eq!(rising.orientation(), Orientation::Rising);
+        assert_eq!(falling.orientation(), Orientation::Falling);
+    }
+
+    #[test]
+    fn on_interval_works_diagonal() {
+        let pair = CornerPair(Coord { x: 0, y: 0 }, Coord { x: -3, y: 3 });
+
+        assert!(pair.on_interval(Coord { x: -2, y: 2 }));
+        assert!(pair.on_interval(Coord { x: -3, y: 3 }));
+        assert!(!pair.on_interval(Coord { x: -4, y: 4 }));
+        assert!(!pair.on_interval(Coord { x: -2, y: 1 }));
+        assert_eq!(pair.char_point(Coord { x: -1, y: 1 }, None), '\\');
+    }
+
+    /// Axis aligned segment in a small region, so endpoint touches and
+    /// overlaps come up often
+    fn segment() -> impl Strategy<Value = CornerPair> {
+        (-5i64..=5, -5i64..=5, any::<bool>(), -6i64..=6).prop_map(|(x, y, vertical, len)| {
+            let start = Coord { x, y };
+            let end = if vertical {
+                Coord { x, y: y + len }
+            } else {
+                Coord { x: x + len, y }
+            };
+            CornerPair(start, end)
+        })
+    }
+
+    /// Every point strictly between the corners of a segment
+    fn interior(pair: CornerPair) -> HashSet<Coord> {
+        let (min_x, max_x) = (pair.0.x.min(pair.1.x), pair.0.x.max(pair.1.x));
+        let (min_y, max_y) = (pair.0.y.min(pair.1.y), pair.0.y.max(pair.1.y));
+        let mut points: HashSet<Coord> = (min_x..=max_x)
+            .flat_map(|x| (min_y..=max_y).map(move |y| Coord { x, y }))
+            .collect();
+        points.remove(&pair.0);
+        points.remove(&pair.1);
+        points
+    }
+
+    proptest! {
+        #[test]
+        fn intersection_matches_brute_force(first in segment(), second in segment()) {
+            let shared: Vec<Coord> = interior(first)
+                .intersection(&interior(second))
+                .copied()
+                .collect();
+            let expected = match first.orientation() == second.orientation() {
+                // parallel segments never cross, even when they overlap
+                true => None,
+                false => shared.first().copied(),
+            };
+
+            prop_assert!(shared.len() <= 1 || expected.is_none());
+            prop_assert_eq!(first.intersection(second), expected);
+        }
+
+        #[test]
+        fn intersection_is_symmetric(first in segment(), second in segment()) {
+            prop_assert_eq!(first.intersection(second), second.intersection(first));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_panel {
+    use super::*;
+
+    fn test_panel() -> Panel {
+        let (wire_one, wire_two) = load_wires("src/test.txt").unwrap();
+        Panel(wire_one, wire_two)
+    }
+
+    #[test]
+    fn raster_draws_overlaps() {
+        let panel = Panel(
+            Wire::new("R8,U2").unwrap(),
+            Wire::new("U1,R3,D1,R6").unwrap(),
+        );
+        let lines = panel.raster(&ViewOptions::default()).lines(true);
+
+        assert_eq!(lines, vec!["........5.", "5555....5.", "O55======5"]);
+    }
+
+    #[test]
+    fn crossovers_within_works() {
+        let index = test_panel().crossover_index();
+
+        assert_eq!(index.crossovers_within(158), Vec::new());
+        assert_eq!(
+            index.crossovers_within(166),
+            vec![
+                (Coord { x: 155, y: 4 }, 159),
+                (Coord { x: 155, y: 11 }, 166)
+            ]
+        );
+        assert_eq!(index.crossovers_within(1000).len(), 4);
+        assert_eq!(index.crossovers_within(i64::MAX).len(), 4);
+        assert_eq!(index.crossovers_within(-1), Vec::new());
+    }
+
+    #[test]
+    fn crossover_index_skips_far_blocks() {
+        let size = CrossoverIndex::BUCKET_SIZE;
+        // one crossover in every block of a 30 by 30 square of them
+        let crossovers: Vec<Coord> = (-15..15)
+            .flat_map(|bx| {
+                (-15..15).map(move |by| Coord {
+                    x: bx * size + 1,
+                    y: by * size + 1,
+                })
+            })
+            .collect();
+        let index = CrossoverIndex::new(Coord { x: 0, y: 0 }, &crossovers);
+
+        assert_eq!(index.blocks_within(0), vec![(0, 0)]);
+        assert_eq!(
+            index.blocks_within(size),
+            vec![(-1, -1), (-1, 0), (0, -1), (0, 0), (0, 1), (1, 0)]
+        );
+        let blocks = index.blocks_within(5 * size);
+        assert!(blocks.len() < 80);
+        assert!(blocks.contains(&(-5, 0)) && blocks.contains(&(2, -3)));
+        assert!(!blocks.contains(&(3, 3)) && !blocks.contains(&(10, 10)));
+        let origin = Coord { x: 0, y: 0 };
+        let mut expected: Vec<(Coord, i64)> = crossovers
+            .iter()
+            .map(|&c| (c, Metric::Manhattan.distance(c, origin)))
+            .filter(|&(_, distance)| distance <= 2 * size)
+            .collect();
+        expected.sort_by_key(|&(c, distance)| (distance, c));
+        assert_eq!(expected.len(), 10);
+        assert_eq!(index.crossovers_within(2 * size), expected);
+    }
+
+    #[test]
+    fn crossover_index_matches_filtering() {
+        let (wire_one, wire_two) = load_wires("src/input.txt").unwrap();
+        let panel = Panel(wire_one, wire_two);
+        let index = panel.crossover_index();
+        let crossovers = panel.0.crossovers(&panel.1);
+        let origin = Coord { x: 0, y: 0 };
+
+        for d in [0, 2129, 2500, 5000, 20000] {
+            let mut expected: Vec<(Coord, i64)> = crossovers
+                .iter()
+                .map(|&c| (c, Metric::Manhattan.distance(c, origin)))
+                .filter(|&(_, distance)| distance <= d)
+                .collect();
+            expected.sort_by_key(|&(c, distance)| (distance, c));
+            assert_eq!(index.crossovers_within(d), expected);
+        }
+    }
+
+    #[test]
+    fn heatmap_works() {
+        let panel = Panel(
+            Wire::new("R3,U2,L2,D3").unwrap(),
+            Wire::new("U1,R2").unwrap(),
+        );
+        let heatmap = panel.heatmap(&ViewOptions::default());
+
+        assert_eq!(heatmap.min_bounds, Coord { x: 0, y: -1 });
+        assert_eq!(heatmap.to_csv(), "0,1,1,1\n1,2,1,1\n0,2,1,1\n0,1,0,0\n");
+
+        let scaled = panel.heatmap(&ViewOptions {
+            max_width: Some(2),
+            ..ViewOptions::default()
+        });
+        assert_eq!(scaled.scale, 2);
+        assert_eq!(scaled.to_csv(), "4,4\n3,2\n");
+    }
+
+    #[test]
+    fn heatmap_to_png_works() {
+        let heatmap = test_panel().heatmap(&ViewOptions {
+            max_width: Some(20),
+            ..ViewOptions::default()
+        });
+        let image = heatmap.to_png().unwrap();
+
+        assert_eq!(&image[..8], b"\x89PNG\r\n\x1a\n");
+        let decoder = png::Decoder::new(image.as_slice());
+        let reader = decoder.read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (20, 13));
+    }
+
+    #[test]
+    fn panel_from_other_port() {
+        let port = Coord { x: 10, y: 10 };
+        let panel = Panel(
+            Wire::new("R8,U5,L5,D3").unwrap().with_start(port),
+            Wire::new("U7,R6,D4,L4").unwrap().with_start(port),
+        );
+        let raster = panel.raster(&ViewOptions::default());
+
+        assert_eq!(panel.port(), port);
+        assert_eq!(
+            panel.nearest_crossover(port, Metric::Manhattan),
+            Some((Coord { x: 13, y: 13 }, 6))
+        );
+        assert_eq!(raster.min_bounds, port);
+        assert!(raster.lines(true).last().unwrap().starts_with('O'));
+        let manhattan: Vec<i64> = reports(&panel.0, &panel.1)
+            .iter()
+            .map(|r| r.manhattan)
+            .collect();
+        assert_eq!(manhattan, vec![11, 6]);
+    }
+
+    #[test]
+    fn to_svg_works() {
+        let panel = Panel(
+            Wire::new("R8,U5,L5,D3").unwrap(),
+            Wire::new("U7,R6,D4,L4").unwrap(),
+        );
+        let svg = panel.to_svg();
+
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-1 -8 10 9\">")
+        );
+        assert!(svg.contains("points=\"0,0 8,0 8,-5 3,-5 3,-2\""));
+        assert!(svg.contains("points=\"0,0 0,-7 6,-7 6,-3 2,-3\""));
+        assert!(svg.contains("<circle cx=\"3\" cy=\"-3\""));
+        assert!(svg.contains("<circle cx=\"6\" cy=\"-5\""));
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn nearest_crossover_works() {
+        let panel = test_panel();
+
+        assert_eq!(
+            panel.nearest_crossover(Coord { x: 0, y: 0 }, Metric::Manhattan),
+            Some((Coord { x: 155, y: 4 }, 159))
+        );
+        assert_eq!(
+            panel.nearest_crossover(Coord { x: 150, y: 40 }, Metric::Chebyshev),
+            Some((Coord { x: 146, y: 46 }, 6))
+        );
+        assert_eq!(
+            panel.nearest_crossover(Coord { x: 160, y: -10 }, Metric::EuclideanSquared),
+            Some((Coord { x: 158, y: -12 }, 8))
+        );
+    }
+
+    #[test]
+    fn nearest_crossover_none_without_crossovers() {
+        let panel = Panel(Wire::new("U5").unwrap(), Wire::new("D5").unwrap());
+
+        assert_eq!(
+            panel.nearest_crossover(Coord { x: 0, y: 0 }, Metric::Manhattan),
+            None
+        );
+    }
+
+    #[test]
+    fn generate_from_trace_works() {
+        let panel = Panel(Wire::new("R4,U3").unwrap(), Wire::new("U2,R6").unwrap());
+        let (display, min_bounds, max_bounds) = panel.generate_from_trace();
+
+        assert_eq!(
+            (min_bounds, max_bounds),
+            (Coord { x: 0, y: 0 }, Coord { x: 6, y: 3 })
+        );
+        assert_eq!(display.len(), 7 * 4);
+        assert_eq!(display[&(0, 0)], 'O');
+        assert_eq!(display[&(4, 2)], '5');
+        assert_eq!(display[&(1, 1)], '.');
+    }
+
+    #[test]
+    fn raster_full_matches_generate() {
+        let panel = test_panel();
+        let (display, min_bounds, max_bounds) = panel.generate();
+        let raster = panel.raster(&ViewOptions::default());
+
+        assert_eq!(
+            (raster.min_bounds, raster.max_bounds),
+            (min_bounds, max_bounds)
+        );
+        for (row, cells) in raster.rows.iter().enumerate() {
+            for (col, cell) in cells.iter().enumerate() {
+                let k = (min_bounds.x + col as i64, min_bounds.y + row as i64);
+                let expected = if k == (0, 0) { 'O' } else { display[&k] };
+                assert_eq!(cell.glyph(), expected, "mismatch at {:?}", k);
+            }
+        }
+    }
+
+    #[test]
+    fn raster_crop_works() {
+        let panel = test_panel();
+        let opts = ViewOptions {
+            viewport: Viewport::Crop(Coord { x: 160, y: 15 }, Coord { x: 150, y: 10 }),
+            max_width: None,
+            no_color: false,
+        };
+        let raster = panel.raster(&opts);
+
+        assert_eq!(raster.min_bounds, Coord { x: 150, y: 10 });
+        assert_eq!(raster.rows.len(), 6);
+        assert!(raster.rows.iter().all(|r| r.len() == 11));
+        assert_eq!(raster.rows[1][5], Cell::Crossover);
+    }
+
+    #[test]
+    fn raster_downsamples_to_max_width() {
+        let panel = test_panel();
+        let opts = ViewOptions {
+            viewport: Viewport::Full,
+            max_width: Some(40),
+            no_color: false,
+        };
+        let raster = panel.raster(&opts);
+
+        assert_eq!(raster.scale, 6);
+        assert!(raster.rows.iter().all(|r| r.len() <= 40));
+        assert_eq!(
+            raster
+                .rows
+                .iter()
+                .flatten()
+                .filter(|&&c| c == Cell::Crossover)
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn raster_centers_on_nearest_crossover() {
+        let panel = test_panel();
+        let opts = ViewOptions {
+            viewport: Viewport::Centered {
+                center: Center::NearestCrossover,
+                width: 5,
+                height: 3,
+            },
+            max_width: None,
+            no_color: false,
+        };
+        let raster = panel.raster(&opts);
+
+        assert_eq!(raster.min_bounds, Coord { x: 153, y: 3 });
+        assert_eq!(raster.max_bounds, Coord { x: 157, y: 5 });
+        assert_eq!(raster.rows[1][2], Cell::Crossover);
+    }
+
+    #[test]
+    fn raster_distinguishes_wires() {
+        let panel = test_panel();
+        let opts = ViewOptions {
+            viewport: Viewport::Crop(Coord { x: 154, y: 3 }, Coord { x: 156, y: 5 }),
+            max_width: None,
+            no_color: false,
+        };
+        let raster = panel.raster(&opts);
+
+        assert_eq!(raster.rows[1][0], Cell::WireOne);
+        assert_eq!(raster.rows[1][1], Cell::Crossover);
+        assert_eq!(raster.rows[0][1], Cell::WireTwo);
+        assert_eq!(raster.rows[0][0], Cell::Empty);
+    }
+
+    #[test]
+    fn cell_merge_works() {
+        assert_eq!(Cell::WireOne.merge(Cell::WireTwo), Cell::Both);
+        assert_eq!(Cell::WireTwo.merge(Cell::WireOne), Cell::Both);
+        assert_eq!(Cell::Both.merge(Cell::WireOne), Cell::Both);
+        assert_eq!(Cell::Crossover.merge(Cell::WireTwo), Cell::Crossover);
+        assert_eq!(Cell::Empty.merge(Cell::Origin), Cell::Origin);
+    }
+
+    #[test]
+    fn cell_render_works() {
+        assert_eq!(Cell::Crossover.render(false), "\x1b[1;33mX\x1b[0m");
+        assert_eq!(Cell::Crossover.render(true), "X");
+        assert_eq!(Cell::Empty.render(false), ".");
+    }
 }
@@ -1,20 +1,366 @@
-use day3::{part1, part2};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
-fn main() {
-    match part1("src/input.txt") {
-        Some(answer) => {
-            println!("Part1: {}", answer);
+use clap::{Parser, Subcommand};
+use day3::{
+    Center, Coord, ParseError, ParseErrorReason, ReportColumn, ViewOptions, Viewport,
+    crossover_csv, heatmap, printer_with, solve, svg, view, wire_stats,
+};
+use shared::args::DEFAULT_INPUT;
+
+/// Crossed wires on the fuel management system front panel
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Cmd>,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    /// Print the puzzle answers
+    Solve {
+        /// input file, may be repeated to solve several files in one go
+        #[arg(long)]
+        input: Vec<String>,
+        /// also solve every file in this directory
+        #[arg(long)]
+        inputs: Option<PathBuf>,
+        /// only print the answer to this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+    },
+    /// Print the panel as text
+    Print {
+        #[arg(long, default_value = DEFAULT_INPUT)]
+        input: String,
+        /// only print the rectangle spanning two corners, as `x1,y1,x2,y2`
+        #[arg(long, value_parser = parse_crop, conflicts_with = "center")]
+        crop: Option<(Coord, Coord)>,
+        /// only print a region centred on `x,y`
+        #[arg(long, value_parser = parse_coord)]
+        center: Option<Coord>,
+        /// width of the centred region
+        #[arg(long, default_value_t = 80, requires = "center")]
+        width: i64,
+        /// height of the centred region
+        #[arg(long, default_value_t = 40, requires = "center")]
+        height: i64,
+        /// downsample regions wider than this many columns
+        #[arg(long)]
+        max_width: Option<usize>,
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Write the panel or its crossovers to files
+    #[command(group(clap::ArgGroup::new("outputs").required(true).multiple(true)))]
+    Export {
+        #[arg(long, default_value = DEFAULT_INPUT)]
+        input: String,
+        /// path of the SVG file to write
+        #[arg(long, group = "outputs")]
+        svg: Option<PathBuf>,
+        /// path of the CSV file to write, one row per crossover
+        #[arg(long, group = "outputs")]
+        csv: Option<PathBuf>,
+        /// column to order CSV rows by: x, y, manhattan, steps_one,
+        /// steps_two or steps
+        #[arg(long, default_value = "manhattan")]
+        sort_by: ReportColumn,
+        /// path of the CSV file to write with wire visits per block
+        #[arg(long, group = "outputs")]
+        heatmap_csv: Option<PathBuf>,
+        /// path of the grayscale PNG file to write with wire visits per block
+        #[arg(long, group = "outputs")]
+        heatmap_png: Option<PathBuf>,
+        /// downsample heatmaps wider than this many blocks
+        #[arg(long, default_value_t = 1000)]
+        heatmap_width: usize,
+    },
+    /// Print the length, extent and shape of each wire
+    Stats {
+        #[arg(long, default_value = DEFAULT_INPUT)]
+        input: String,
+    },
+    /// Explore the panel interactively
+    View {
+        #[arg(long, default_value = DEFAULT_INPUT)]
+        input: String,
+        #[arg(long)]
+        no_color: bool,
+    },
+}
+
+/// Parses a coordinate written as `x,y`
+fn parse_coord(s: &str) -> Result<Coord, String> {
+    let values = parse_values(s, 2)?;
+    Ok(Coord::new(values[0], values[1]))
+}
+
+/// Parses a pair of corners written as `x1,y1,x2,y2`
+fn parse_crop(s: &str) -> Result<(Coord, Coord), String> {
+    let values = parse_values(s, 4)?;
+    Ok((
+        Coord::new(values[0], values[1]),
+        Coord::new(values[2], values[3]),
+    ))
+}
+
+/// Parses exactly `count` comma separated integers
+fn parse_values(s: &str, count: usize) -> Result<Vec<i64>, String> {
+    let values = s
+        .split(',')
+        .map(|v| {
+            v.trim()
+                .parse::<i64>()
+                .map_err(|err| format!("{}: {}", v, err))
+        })
+        .collect::<Result<Vec<i64>, String>>()?;
+    if values.len() != count {
+        return Err(format!("expected {} values, got {}", count, values.len()));
+    }
+    Ok(values)
+}
+
+/// Lists the files directly inside `dir`, sorted by name
+fn dir_files(dir: &Path) -> io::Result<Vec<String>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() {
+            files.push(path.to_string_lossy().into_owned());
         }
-        None => {
-            eprintln!("Part1: Failed to get an answer");
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Describes why an input couldn't be solved
+fn failure(input: &str, err: &ParseError) -> String {
+    match err.reason {
+        ParseErrorReason::Io(_) => format!("Failed to read input: {}", err),
+        _ => format!("{}: failed to parse input: {}", input, err),
+    }
+}
+
+/// Prints a row per input file with its answers and how long solving took
+///
+/// # Arguments
+///
+/// * inputs - files to solve, each failing on its own
+/// * part - only print the answer to this part
+///
+/// # Returns
+///
+/// * whether every file was solved
+fn run_batch(inputs: &[String], part: Option<u8>) -> bool {
+    let parts: Vec<u8> = [1, 2]
+        .into_iter()
+        .filter(|&p| part.is_none_or(|q| q == p))
+        .collect();
+    let width = inputs.iter().map(|i| i.len()).max().unwrap_or(0).max(5);
+    let row = |name: &str, answers: &[String], time: &str| {
+        let answers: String = answers.iter().map(|a| format!("  {:>10}", a)).collect();
+        println!("{:<width$}{}  {:>10}", name, answers, time);
+    };
+    let header: Vec<String> = parts.iter().map(|p| format!("part{}", p)).collect();
+    row("file", &header, "time");
+    let mut total = Duration::ZERO;
+    let mut all_ok = true;
+    for input in inputs {
+        let start = Instant::now();
+        let answer = solve(input);
+        let elapsed = start.elapsed();
+        total += elapsed;
+        let answers: Vec<String> = match answer {
+            Ok(answer) => parts
+                .iter()
+                .map(|&p| match p {
+                    1 => answer.closest_manhattan.map(|a| a.to_string()),
+                    _ => answer.fewest_steps.map(|a| a.to_string()),
+                })
+                .map(|a| a.unwrap_or(String::from("-")))
+                .collect(),
+            Err(err) => {
+                eprintln!("{}", failure(input, &err));
+                all_ok = false;
+                parts.iter().map(|_| String::from("error")).collect()
+            }
+        };
+        row(input, &answers, &format!("{:.2?}", elapsed));
+    }
+    let blanks = vec![String::new(); parts.len()];
+    row("total", &blanks, &format!("{:.2?}", total));
+    all_ok
+}
+
+/// Prints the answers, or only the one for `part`
+///
+/// # Returns
+///
+/// * whether the input could be solved
+fn run_solve(input: &str, part: Option<u8>) -> bool {
+    let answer = match solve(input) {
+        Ok(answer) => answer,
+        Err(err) => {
+            eprintln!("{}", failure(input, &err));
+            return false;
         }
     };
-    match part2("src/input.txt") {
-        Ok(answer) => {
-            println!("Part2: {}", answer);
+    if part != Some(2) {
+        match answer.closest_manhattan {
+            Some(answer) => println!("Part1: {}", answer),
+            None => eprintln!("Part1: Failed to get an answer"),
         }
-        Err(msg) => {
-            eprintln!("Part2: Failed to get an answer with error: {}", msg);
+    }
+    if part != Some(1) {
+        match answer.fewest_steps {
+            Some(answer) => println!("Part2: {}", answer),
+            None => eprintln!("Part2: Failed to get an answer"),
         }
-    };
+    }
+    true
+}
+
+/// Writes rendered output to `path`, reporting any failure
+fn export(path: &Path, rendered: Result<String, ParseError>) {
+    match rendered {
+        Ok(contents) => {
+            if let Err(err) = fs::write(path, contents) {
+                eprintln!("Failed to write {}: {}", path.display(), err);
+            }
+        }
+        Err(err) => eprintln!("Failed to parse input: {}", err),
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command.unwrap_or(Cmd::Solve {
+        input: Vec::new(),
+        inputs: None,
+        part: None,
+    }) {
+        Cmd::Solve {
+            mut input,
+            inputs,
+            part,
+        } => {
+            if let Some(dir) = inputs {
+                match dir_files(&dir) {
+                    Ok(files) => input.extend(files),
+                    Err(err) => {
+                        eprintln!("Failed to read {}: {}", dir.display(), err);
+                        return ExitCode::FAILURE;
+                    }
+                }
+            } else if input.is_empty() {
+                input.push(String::from(DEFAULT_INPUT));
+            }
+            let solved = match input.as_slice() {
+                [single] => run_solve(single, part),
+                inputs => run_batch(inputs, part),
+            };
+            if !solved {
+                return ExitCode::FAILURE;
+            }
+        }
+        Cmd::Print {
+            input,
+            crop,
+            center,
+            width,
+            height,
+            max_width,
+            no_color,
+        } => {
+            let viewport = match (crop, center) {
+                (Some((c1, c2)), _) => Viewport::Crop(c1, c2),
+                (None, Some(center)) => Viewport::Centered {
+                    center: Center::Point(center),
+                    width,
+                    height,
+                },
+                (None, None) => Viewport::Full,
+            };
+            let opts = ViewOptions {
+                viewport,
+                max_width,
+                no_color,
+            };
+            if let Err(err) = printer_with(&input, &opts) {
+                eprintln!("Failed to parse input: {}", err);
+            }
+        }
+        Cmd::Export {
+            input,
+            svg: svg_path,
+            csv: csv_path,
+            sort_by,
+            heatmap_csv,
+            heatmap_png,
+            heatmap_width,
+        } => {
+            if let Some(path) = svg_path {
+                export(&path, svg(&input));
+            }
+            if let Some(path) = csv_path {
+                export(&path, crossover_csv(&input, sort_by));
+            }
+            if heatmap_csv.is_some() || heatmap_png.is_some() {
+                let opts = ViewOptions {
+                    max_width: Some(heatmap_width),
+                    ..ViewOptions::default()
+                };
+                let counts = match heatmap(&input, &opts) {
+                    Ok(counts) => counts,
+                    Err(err) => {
+                        eprintln!("Failed to parse input: {}", err);
+                        return ExitCode::FAILURE;
+                    }
+                };
+                if let Some(path) = heatmap_csv {
+                    export(&path, Ok(counts.to_csv()));
+                }
+                if let Some(path) = heatmap_png {
+                    match counts.to_png() {
+                        Ok(image) => {
+                            if let Err(err) = fs::write(&path, image) {
+                                eprintln!("Failed to write {}: {}", path.display(), err);
+                            }
+                        }
+                        Err(err) => eprintln!("Failed to encode heatmap: {}", err),
+                    }
+                }
+            }
+        }
+        Cmd::Stats { input } => match wire_stats(&input) {
+            Ok((one, two)) => {
+                for (name, stats) in [("wire one", one), ("wire two", two)] {
+                    let directions: Vec<String> = stats
+                        .directions
+                        .iter()
+                        .map(|(dir, count)| format!("{}:{}", dir, count))
+                        .collect();
+                    println!("{}", name);
+                    println!("  length     {}", stats.total_length);
+                    println!("  segments   {}", stats.segments);
+                    println!("  area       {}", stats.bounding_area);
+                    println!("  directions {}", directions.join(" "));
+                    if let Some(longest) = stats.longest {
+                        println!("  longest    {}", longest);
+                    }
+                }
+            }
+            Err(err) => eprintln!("Failed to parse input: {}", err),
+        },
+        Cmd::View { input, no_color } => {
+            if let Err(err) = view(&input, no_color) {
+                eprintln!("Viewer failed with error: {}", err);
+            }
+        }
+    }
+    ExitCode::SUCCESS
 }
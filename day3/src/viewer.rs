@@ -0,0 +1,291 @@
+use std::io::{self, Write};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::{cursor, execute, queue, style, terminal};
+
+use crate::{Center, Coord, CrossoverIndex, Metric, Panel, ViewOptions, Viewport};
+
+/// Coarsest zoom, cells per char side, keeping every viewport size well
+/// inside i64
+const MAX_SCALE: i64 = 1 << 20;
+
+/// State of the interactive panel viewer
+struct Viewer {
+    panel: Panel,
+    /// crossovers around the central port, built once for the status line
+    index: CrossoverIndex,
+    /// crossovers ordered by manhattan distance from the central port
+    crossovers: Vec<Coord>,
+    /// index into `crossovers` of the last one jumped to
+    selected: Option<usize>,
+    center: Coord,
+    /// side length of the block of cells each char represents
+    scale: i64,
+    no_color: bool,
+}
+
+impl Viewer {
+    /// Constructor for a Viewer, zoomed out to fit the whole panel
+    ///
+    /// # Arguments
+    ///
+    /// * panel - panel to explore
+    /// * cols - columns available for drawing
+    /// * rows - rows available for drawing
+    /// * no_color - draw plain glyphs without ANSI colors
+    fn new(panel: Panel, cols: u16, rows: u16, no_color: bool) -> Self {
+        let index = panel.crossover_index();
+        let crossovers = index
+            .crossovers_within(i64::MAX)
+            .into_iter()
+            .map(|(c, _)| c)
+            .collect();
+        let (min_bounds, max_bounds) = panel.bounds();
+        let width = max_bounds.x - min_bounds.x + 1;
+        let height = max_bounds.y - min_bounds.y + 1;
+        let (cols, rows) = (cols.max(1) as i64, rows.max(1) as i64);
+        let scale = ((width + cols - 1) / cols)
+            .max((height + rows - 1) / rows)
+            .clamp(1, MAX_SCALE);
+        Viewer {
+            panel,
+            index,
+            crossovers,
+            selected: None,
+            center: Coord {
+                x: min_bounds.x + width / 2,
+                y: min_bounds.y + height / 2,
+            },
+            scale,
+            no_color,
+        }
+    }
+
+    /// Builds the view options for the current state
+    ///
+    /// # Arguments
+    ///
+    /// * cols - columns available for drawing
+    /// * rows - rows available for drawing
+    fn options(&self, cols: u16, rows: u16) -> ViewOptions {
+        ViewOptions {
+            viewport: Viewport::Centered {
+                center: Center::Point(self.center),
+                width: cols.max(1) as i64 * self.scale,
+                height: rows.max(1) as i64 * self.scale,
+            },
+            max_width: Some(cols.max(1) as usize),
+            no_color: self.no_color,
+        }
+    }
+
+    /// Applies a key press to the viewer state
+    ///
+    /// # Arguments
+    ///
+    /// * code - key pressed
+    /// * cols - columns available for drawing
+    /// * rows - rows available for drawing
+    ///
+    /// # Returns
+    ///
+    /// * false if the viewer should quit
+    fn handle_key(&mut self, code: KeyCode, cols: u16, rows: u16) -> bool {
+        let step_x = (cols as i64 / 4).max(1) * self.scale;
+        let step_y = (rows as i64 / 4).max(1) * self.scale;
+        match code {
+            KeyCode::Left => self.center.x = self.center.x.saturating_sub(step_x),
+            KeyCode::Right => self.center.x = self.center.x.saturating_add(step_x),
+            KeyCode::Up => self.center.y = self.center.y.saturating_add(step_y),
+            KeyCode::Down => self.center.y = self.center.y.saturating_sub(step_y),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.scale = (self.scale / 2).max(1),
+            KeyCode::Char('-') => self.scale = self.scale.saturating_mul(2).min(MAX_SCALE),
+            KeyCode::Char('c') if !self.crossovers.is_empty() => {
+                let next = self.selected.map_or(0, |i| (i + 1) % self.crossovers.len());
+                self.selected = Some(next);
+                self.center = self.crossovers[next];
+            }
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            _ => {}
+        }
+        true
+    }
+
+    /// Number of crossovers no further from the port than the view center
+    fn crossovers_closer(&self) -> usize {
+        let distance = Metric::Manhattan.distance(self.center, self.panel.port());
+        self.index.crossovers_within(distance).len()
+    }
+
+    /// Draws the current view followed by a status line
+    ///
+    /// # Arguments
+    ///
+    /// * out - terminal to draw to
+    /// * cols - columns available for drawing
+    /// * rows - rows available for drawing
+    fn draw(&self, out: &mut impl Write, cols: u16, rows: u16) -> io::Result<()> {
+        let raster = self.panel.raster(&self.options(cols, rows));
+        queue!(out, terminal::Clear(terminal::ClearType::All))?;
+        for (i, line) in raster.lines(self.no_color).into_iter().enumerate() {
+            queue!(out, cursor::MoveTo(0, i as u16), style::Print(line))?;
+        }
+        let selected = match self.selected {
+            Some(i) => format!("crossover {}/{}", i + 1, self.crossovers.len()),
+            None => format!("{} crossovers", self.crossovers.len()),
+        };
+        let status = format!(
+            "{} 1:{} | {}, {} this close | arrows: pan  +/-: zoom  c: next crossover  q: quit",
+            self.center,
+            self.scale,
+            selected,
+            self.crossovers_closer()
+        );
+        queue!(
+            out,
+            cursor::MoveTo(0, rows),
+            style::Print(&status[..status.len().min(cols as usize)])
+        )?;
+        out.flush()
+    }
+}
+
+/// Drawing area of the terminal, leaving one row for the status line
+fn drawing_area() -> io::Result<(u16, u16)> {
+    let (cols, rows) = terminal::size()?;
+    Ok((cols.max(1), rows.saturating_sub(1).max(1)))
+}
+
+/// Runs the event loop until the user quits
+fn event_loop(out: &mut impl Write, panel: Panel, no_color: bool) -> io::Result<()> {
+    let (cols, rows) = drawing_area()?;
+    let mut viewer = Viewer::new(panel, cols, rows, no_color);
+    viewer.draw(out, cols, rows)?;
+    loop {
+        let (cols, rows) = drawing_area()?;
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                if !viewer.handle_key(key.code, cols, rows) {
+                    return Ok(());
+                }
+            }
+            Event::Resize(_, _) => {}
+            _ => continue,
+        }
+        viewer.draw(out, cols, rows)?;
+    }
+}
+
+/// Raw mode and the alternate screen, put back when dropped so the
+/// terminal is restored even if drawing panics
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter(out: &mut impl Write) -> io::Result<RawTerminal> {
+        terminal::enable_raw_mode()?;
+        let guard = RawTerminal;
+        execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Explores the panel interactively in the terminal
+///
+/// # Arguments
+///
+/// * panel - panel to explore
+/// * no_color - draw plain glyphs without ANSI colors
+pub(crate) fn run(panel: Panel, no_color: bool) -> io::Result<()> {
+    let mut out = io::stdout();
+    let _terminal = RawTerminal::enter(&mut out)?;
+    event_loop(&mut out, panel, no_color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cell, load_wires};
+
+    fn test_viewer() -> Viewer {
+        let (wire_one, wire_two) = load_wires("src/test.txt").unwrap();
+        let panel = Panel(wire_one, wire_two);
+        Viewer::new(panel, 40, 20, true)
+    }
+
+    #[test]
+    fn new_fits_panel() {
+        let viewer = test_viewer();
+        let raster = viewer.panel.raster(&viewer.options(40, 20));
+
+        assert_eq!(viewer.scale, 8);
+        assert!(raster.rows.len() <= 20);
+        assert!(raster.rows.iter().all(|r| r.len() <= 40));
+        assert_eq!(
+            raster
+                .rows
+                .iter()
+                .flatten()
+                .filter(|&&c| c == Cell::Crossover)
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn handle_key_cycles_crossovers() {
+        let mut viewer = test_viewer();
+        let mut visited = Vec::new();
+        for _ in 0..5 {
+            assert!(viewer.handle_key(KeyCode::Char('c'), 40, 20));
+            visited.push(viewer.center);
+        }
+
+        assert_eq!(visited[0], Coord { x: 155, y: 4 });
+        assert_eq!(visited[4], visited[0]);
+        viewer.handle_key(KeyCode::Char('c'), 40, 20);
+        assert_eq!(viewer.crossovers_closer(), 2);
+    }
+
+    #[test]
+    fn handle_key_pans_and_zooms() {
+        let mut viewer = test_viewer();
+        let start = viewer.center;
+        viewer.handle_key(KeyCode::Right, 40, 20);
+        viewer.handle_key(KeyCode::Up, 40, 20);
+        viewer.handle_key(KeyCode::Char('+'), 40, 20);
+
+        assert_eq!(
+            viewer.center,
+            Coord {
+                x: start.x + 80,
+                y: start.y + 40
+            }
+        );
+        assert_eq!(viewer.scale, 4);
+        assert!(!viewer.handle_key(KeyCode::Char('q'), 40, 20));
+    }
+
+    #[test]
+    fn handle_key_caps_zoom_and_pan() {
+        let mut viewer = test_viewer();
+        for _ in 0..100 {
+            viewer.handle_key(KeyCode::Char('-'), 40, 20);
+        }
+        assert_eq!(viewer.scale, MAX_SCALE);
+        viewer.center.x = i64::MIN + 1;
+        viewer.handle_key(KeyCode::Left, u16::MAX, 20);
+        assert_eq!(viewer.center.x, i64::MIN);
+        let options = viewer.options(u16::MAX, u16::MAX);
+        assert!(matches!(
+            options.viewport,
+            Viewport::Centered { width, .. } if width == u16::MAX as i64 * MAX_SCALE
+        ));
+    }
+}
@@ -0,0 +1,54 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use day4::{PairRule, Validator, count_valid_dp, read_range, valid_passwords};
+
+const INPUT: &str = "src/input.txt";
+
+/// Synthetic range 10^9 numbers wide, far too large to test one by one
+const WIDE: (u64, u64) = (1_000_000_000, 1_999_999_999);
+
+fn bench_input(c: &mut Criterion) {
+    let (start, end) = read_range(INPUT).unwrap();
+    for (name, validator, pair) in [
+        ("part1", Validator::part1(), PairRule::Any),
+        ("part2", Validator::part2(), PairRule::Exact),
+    ] {
+        let mut group = c.benchmark_group(format!("input/{}", name));
+        group.bench_function("brute_force", |b| {
+            b.iter(|| {
+                (black_box(start)..=black_box(end))
+                    .filter(|&n| validator.is_valid(n))
+                    .count()
+            })
+        });
+        group.bench_function("iterator", |b| {
+            b.iter(|| valid_passwords(black_box(start)..=black_box(end), &validator).count())
+        });
+        group.bench_function("parallel", |b| {
+            b.iter(|| validator.par_count(black_box(start), black_box(end), 4096))
+        });
+        group.bench_function("dp", |b| {
+            b.iter(|| count_valid_dp(black_box(start), black_box(end), pair))
+        });
+        group.finish();
+    }
+}
+
+fn bench_wide(c: &mut Criterion) {
+    let (start, end) = WIDE;
+    let validator = Validator::part2();
+    let mut group = c.benchmark_group("wide");
+    group.sample_size(10);
+    group.bench_function("iterator", |b| {
+        b.iter(|| valid_passwords(black_box(start)..=black_box(end), &validator).count())
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| validator.par_count(black_box(start), black_box(end), 1 << 24))
+    });
+    group.bench_function("dp", |b| {
+        b.iter(|| count_valid_dp(black_box(start), black_box(end), PairRule::Exact))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_input, bench_wide);
+criterion_main!(benches);
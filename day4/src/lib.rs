@@ -1,4 +1,820 @@
-use shared;
+use std::collections::HashMap;
+use std::fmt;
+use std::iter;
+use std::ops::RangeInclusive;
+
+use rayon::prelude::*;
+use shared::solution::{Answer, Solution, answer};
+use shared::try_ingest_file;
+
+/// Splits a number into its decimal digits, most significant first
+///
+/// # Arguments
+///
+/// * num - number to split
+///
+/// # Returns
+///
+/// * digits of `num`
+pub fn digits(num: u64) -> Vec<u8> {
+    num.to_string().bytes().map(|b| b - b'0').collect()
+}
+
+/// Splits a number into its digits in any radix, most significant first
+///
+/// # Arguments
+///
+/// * num - number to split
+/// * radix - base between 2 and 36
+///
+/// # Returns
+///
+/// * digits of `num`
+pub fn digits_in(num: u64, radix: u32) -> Vec<u8> {
+    assert!((2..=36).contains(&radix), "radix {} out of range", radix);
+    if radix == 10 {
+        return digits(num);
+    }
+    let radix = radix as u64;
+    let mut digits = Vec::new();
+    let mut rest = num;
+    loop {
+        digits.push((rest % radix) as u8);
+        rest /= radix;
+        if rest == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    digits
+}
+
+/// Joins digits back into a number
+///
+/// # Arguments
+///
+/// * digits - digits most significant first
+/// * radix - base the digits are in
+///
+/// # Returns
+///
+/// * number, or none if it doesn't fit in a u64
+fn from_digits(digits: &[u8], radix: u32) -> Option<u64> {
+    digits.iter().try_fold(0u64, |acc, &d| {
+        acc.checked_mul(radix as u64)?.checked_add(d as u64)
+    })
+}
+
+/// Writes a number out in any radix, using lowercase letters past 9
+///
+/// # Arguments
+///
+/// * num - number to write
+/// * radix - base between 2 and 36
+pub fn to_radix_string(num: u64, radix: u32) -> String {
+    digits_in(num, radix)
+        .into_iter()
+        .filter_map(|d| char::from_digit(d as u32, radix))
+        .collect()
+}
+
+/// Candidate password, split into digits once up front
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Password {
+    value: u64,
+    digits: Vec<u8>,
+}
+
+impl Password {
+    /// Constructor for a Password in decimal
+    ///
+    /// # Arguments
+    ///
+    /// * value - number the password stands for
+    pub fn new(value: u64) -> Self {
+        Password::in_radix(value, 10)
+    }
+
+    /// Constructor for a Password with its digits in any radix
+    ///
+    /// # Arguments
+    ///
+    /// * value - number the password stands for
+    /// * radix - base between 2 and 36
+    pub fn in_radix(value: u64, radix: u32) -> Self {
+        Password {
+            value,
+            digits: digits_in(value, radix),
+        }
+    }
+
+    /// Number the password stands for
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Digits of the password, most significant first
+    pub fn digits(&self) -> &[u8] {
+        &self.digits
+    }
+
+    /// Runs of equal adjacent digits, from the left
+    pub fn runs(&self) -> impl Iterator<Item = &[u8]> {
+        self.digits.chunk_by(|a, b| a == b)
+    }
+
+    /// Whether the digits never decrease going from left to right
+    pub fn is_monotonic(&self) -> bool {
+        self.digits.windows(2).all(|w| w[0] <= w[1])
+    }
+}
+
+/// Single requirement a password has to meet
+pub trait Rule: Sync {
+    /// Determines if a candidate meets the requirement
+    ///
+    /// # Arguments
+    ///
+    /// * password - candidate under test
+    fn check(&self, password: &Password) -> bool;
+
+    /// Short name of the rule used in reports
+    fn name(&self) -> &'static str {
+        "custom"
+    }
+
+    /// Positions of the digits responsible for a candidate failing the rule
+    ///
+    /// # Arguments
+    ///
+    /// * password - candidate under test
+    ///
+    /// # Returns
+    ///
+    /// * zero based positions, empty if no digit in particular is at fault
+    fn offending(&self, _password: &Password) -> Vec<usize> {
+        Vec::new()
+    }
+
+    /// Whether the rule rejects every number whose digits decrease, which
+    /// lets searches jump over whole runs of candidates
+    fn requires_non_decreasing(&self) -> bool {
+        false
+    }
+}
+
+impl<F: Fn(&Password) -> bool + Sync> Rule for F {
+    fn check(&self, password: &Password) -> bool {
+        self(password)
+    }
+}
+
+/// Digits never decrease going from left to right
+pub struct NonDecreasing;
+
+impl Rule for NonDecreasing {
+    fn check(&self, password: &Password) -> bool {
+        password.is_monotonic()
+    }
+
+    fn name(&self) -> &'static str {
+        Monotonicity::NonDecreasing.name()
+    }
+
+    fn offending(&self, password: &Password) -> Vec<usize> {
+        Monotonicity::NonDecreasing.offending(password)
+    }
+
+    fn requires_non_decreasing(&self) -> bool {
+        true
+    }
+}
+
+/// How each digit has to compare with the one before it
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum Monotonicity {
+    /// digits never decrease, as in the puzzle
+    #[default]
+    NonDecreasing,
+    /// every digit is larger than the one before
+    StrictlyIncreasing,
+    /// digits never increase
+    NonIncreasing,
+    /// digits can come in any order
+    Unconstrained,
+}
+
+impl Monotonicity {
+    /// Determines if `next` may follow `prev`
+    ///
+    /// # Arguments
+    ///
+    /// * prev - earlier digit
+    /// * next - digit immediately after it
+    pub fn allows(self, prev: u8, next: u8) -> bool {
+        match self {
+            Monotonicity::NonDecreasing => prev <= next,
+            Monotonicity::StrictlyIncreasing => prev < next,
+            Monotonicity::NonIncreasing => prev >= next,
+            Monotonicity::Unconstrained => true,
+        }
+    }
+}
+
+impl Rule for Monotonicity {
+    fn check(&self, password: &Password) -> bool {
+        password
+            .digits()
+            .windows(2)
+            .all(|w| self.allows(w[0], w[1]))
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Monotonicity::NonDecreasing => "non-decreasing",
+            Monotonicity::StrictlyIncreasing => "strictly increasing",
+            Monotonicity::NonIncreasing => "non-increasing",
+            Monotonicity::Unconstrained => "unconstrained",
+        }
+    }
+
+    fn offending(&self, password: &Password) -> Vec<usize> {
+        let digits = password.digits();
+        (1..digits.len())
+            .filter(|&i| !self.allows(digits[i - 1], digits[i]))
+            .collect()
+    }
+
+    fn requires_non_decreasing(&self) -> bool {
+        matches!(
+            self,
+            Monotonicity::NonDecreasing | Monotonicity::StrictlyIncreasing
+        )
+    }
+}
+
+/// Two adjacent digits are the same
+pub struct HasPair;
+
+impl Rule for HasPair {
+    fn check(&self, password: &Password) -> bool {
+        password.runs().any(|run| run.len() >= 2)
+    }
+
+    fn name(&self) -> &'static str {
+        "pair"
+    }
+}
+
+/// Two adjacent digits are the same and not part of a longer run
+pub struct HasExactPair;
+
+impl Rule for HasExactPair {
+    fn check(&self, password: &Password) -> bool {
+        password.runs().any(|run| run.len() == 2)
+    }
+
+    fn name(&self) -> &'static str {
+        "exact pair"
+    }
+
+    /// Digits in runs longer than a pair, which don't count towards the rule
+    fn offending(&self, password: &Password) -> Vec<usize> {
+        let mut positions = Vec::new();
+        let mut i = 0;
+        for run in password.runs() {
+            if run.len() > 2 {
+                positions.extend(i..i + run.len());
+            }
+            i += run.len();
+        }
+        positions
+    }
+}
+
+/// Valid passwords found in a range
+#[derive(Debug, PartialEq)]
+pub struct Matches {
+    /// valid passwords in increasing order, up to the requested limit
+    pub passwords: Vec<u64>,
+    /// count of every valid password in the range, including any past the limit
+    pub count: u64,
+}
+
+impl Matches {
+    /// Whether some valid passwords were left out of `passwords`
+    pub fn truncated(&self) -> bool {
+        (self.passwords.len() as u64) < self.count
+    }
+}
+
+/// Candidate contains the digit at least once
+pub struct MustContain(pub u8);
+
+impl Rule for MustContain {
+    fn check(&self, password: &Password) -> bool {
+        password.digits().contains(&self.0)
+    }
+
+    fn name(&self) -> &'static str {
+        "must contain"
+    }
+}
+
+/// Candidate never contains the digit
+pub struct MustNotContain(pub u8);
+
+impl Rule for MustNotContain {
+    fn check(&self, password: &Password) -> bool {
+        !password.digits().contains(&self.0)
+    }
+
+    fn name(&self) -> &'static str {
+        "must not contain"
+    }
+
+    fn offending(&self, password: &Password) -> Vec<usize> {
+        let digits = password.digits();
+        (0..digits.len()).filter(|&i| digits[i] == self.0).collect()
+    }
+}
+
+/// Candidate contains the digit at least the given number of times
+pub struct DigitCountAtLeast(pub u8, pub usize);
+
+impl Rule for DigitCountAtLeast {
+    fn check(&self, password: &Password) -> bool {
+        password.digits().iter().filter(|&&d| d == self.0).count() >= self.1
+    }
+
+    fn name(&self) -> &'static str {
+        "digit count"
+    }
+}
+
+/// Outcome of checking a candidate against a single rule
+#[derive(Debug, PartialEq)]
+pub struct RuleOutcome {
+    pub rule: &'static str,
+    pub passed: bool,
+    /// positions of the digits at fault, empty when the rule passed
+    pub positions: Vec<usize>,
+}
+
+/// Breakdown of which rules a candidate met
+#[derive(Debug, PartialEq)]
+pub struct ValidationReport {
+    pub num: u64,
+    /// base the digit positions refer to
+    pub radix: u32,
+    /// outcomes in the order the rules were added
+    pub outcomes: Vec<RuleOutcome>,
+}
+
+impl ValidationReport {
+    /// Whether the candidate met every rule
+    pub fn is_valid(&self) -> bool {
+        self.outcomes.iter().all(|o| o.passed)
+    }
+
+    /// Outcomes of the rules the candidate failed
+    pub fn failed(&self) -> impl Iterator<Item = &RuleOutcome> {
+        self.outcomes.iter().filter(|o| !o.passed)
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:", to_radix_string(self.num, self.radix))?;
+        for outcome in &self.outcomes {
+            if outcome.passed {
+                write!(f, " {} passed;", outcome.rule)?;
+            } else if outcome.positions.is_empty() {
+                write!(f, " {} failed;", outcome.rule)?;
+            } else {
+                write!(f, " {} failed at {:?};", outcome.rule, outcome.positions)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Most chunks `Validator::par_count` splits a range into
+const MAX_CHUNKS: u128 = 1 << 16;
+
+/// Set of rules a password has to meet all of
+pub struct Validator {
+    rules: Vec<Box<dyn Rule>>,
+    /// base candidates are split into digits in
+    radix: u32,
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        Validator {
+            rules: Vec::new(),
+            radix: 10,
+        }
+    }
+}
+
+impl Validator {
+    /// Constructor for a Validator without any rules
+    pub fn new() -> Self {
+        Validator::default()
+    }
+
+    /// Rules from part1 of the puzzle
+    pub fn part1() -> Self {
+        Validator::new().with(NonDecreasing).with(HasPair)
+    }
+
+    /// Rules from part2 of the puzzle
+    pub fn part2() -> Self {
+        Validator::new().with(NonDecreasing).with(HasExactPair)
+    }
+
+    /// Checks the digits of candidates in another base, such as 16 for hex
+    ///
+    /// # Arguments
+    ///
+    /// * radix - base between 2 and 36
+    pub fn in_radix(mut self, radix: u32) -> Self {
+        assert!((2..=36).contains(&radix), "radix {} out of range", radix);
+        self.radix = radix;
+        self
+    }
+
+    /// Base candidates are split into digits in
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+
+    /// Adds a rule candidates have to meet
+    ///
+    /// # Arguments
+    ///
+    /// * rule - requirement to add
+    pub fn with(mut self, rule: impl Rule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Determines if a number meets every rule
+    ///
+    /// # Arguments
+    ///
+    /// * num - candidate under test
+    ///
+    /// # Returns
+    ///
+    /// * validity result
+    pub fn is_valid(&self, num: u64) -> bool {
+        self.check(&Password::in_radix(num, self.radix))
+    }
+
+    /// Determines if an already split candidate meets every rule
+    ///
+    /// # Arguments
+    ///
+    /// * password - candidate under test, split in this validator's radix
+    ///
+    /// # Returns
+    ///
+    /// * validity result
+    pub fn check(&self, password: &Password) -> bool {
+        self.rules.iter().all(|rule| rule.check(password))
+    }
+
+    /// Checks a number against each rule in turn
+    ///
+    /// # Arguments
+    ///
+    /// * num - candidate under test
+    ///
+    /// # Returns
+    ///
+    /// * outcome of every rule, including the digits at fault
+    pub fn validate(&self, num: u64) -> ValidationReport {
+        let password = Password::in_radix(num, self.radix);
+        let outcomes = self
+            .rules
+            .iter()
+            .map(|rule| {
+                let passed = rule.check(&password);
+                RuleOutcome {
+                    rule: rule.name(),
+                    passed,
+                    positions: if passed {
+                        Vec::new()
+                    } else {
+                        rule.offending(&password)
+                    },
+                }
+            })
+            .collect();
+        ValidationReport {
+            num,
+            radix: self.radix,
+            outcomes,
+        }
+    }
+
+    /// Counts the numbers in a range meeting every rule
+    ///
+    /// # Arguments
+    ///
+    /// * start - first number of the range
+    /// * end - last number of the range, inclusive
+    ///
+    /// # Returns
+    ///
+    /// * count of valid numbers
+    pub fn count(&self, start: u64, end: u64) -> u64 {
+        valid_passwords(start..=end, self).count() as u64
+    }
+
+    /// Collects the numbers in a range meeting every rule
+    ///
+    /// # Arguments
+    ///
+    /// * start - first number of the range
+    /// * end - last number of the range, inclusive
+    /// * limit - most passwords to keep, none to keep them all
+    ///
+    /// # Returns
+    ///
+    /// * kept passwords along with the total count
+    pub fn find_valid(&self, start: u64, end: u64, limit: Option<usize>) -> Matches {
+        let mut passwords = Vec::new();
+        let mut count = 0;
+        for num in valid_passwords(start..=end, self) {
+            if limit.is_none_or(|limit| passwords.len() < limit) {
+                passwords.push(num);
+            }
+            count += 1;
+        }
+        Matches { passwords, count }
+    }
+
+    /// Counts the numbers in a range meeting every rule, splitting the range
+    /// into chunks evaluated across threads
+    ///
+    /// Chunk counts are summed, so the result matches `count` regardless
+    /// of how the chunks get scheduled.
+    ///
+    /// # Arguments
+    ///
+    /// * start - first number of the range
+    /// * end - last number of the range, inclusive
+    /// * chunk_size - numbers handed to a thread at a time, raised when
+    ///   the range would otherwise need more than `MAX_CHUNKS` chunks
+    ///
+    /// # Returns
+    ///
+    /// * count of valid numbers
+    pub fn par_count(&self, start: u64, end: u64, chunk_size: u64) -> u64 {
+        if start > end {
+            return 0;
+        }
+        // the whole u64 range holds one more number than a u64 can count
+        let span = u128::from(end - start) + 1;
+        let chunk_size = u128::from(chunk_size.max(1)).max(span.div_ceil(MAX_CHUNKS));
+        let chunks = span.div_ceil(chunk_size) as u64;
+        (0..chunks)
+            .into_par_iter()
+            .map(|i| {
+                let first = u128::from(start) + u128::from(i) * chunk_size;
+                let last = (first + chunk_size - 1).min(u128::from(end));
+                valid_passwords(first as u64..=last as u64, self).count() as u64
+            })
+            .sum()
+    }
+
+    /// Whether any rule rejects numbers with decreasing digits
+    fn requires_non_decreasing(&self) -> bool {
+        self.rules.iter().any(|rule| rule.requires_non_decreasing())
+    }
+}
+
+/// Summary of the valid passwords in a range
+#[derive(Debug, PartialEq)]
+pub struct PasswordStats {
+    pub count: u64,
+    /// times each digit appears across all the passwords, indexed by digit
+    pub digit_frequency: Vec<u64>,
+    /// passwords starting with each digit, indexed by digit
+    pub leading_digit: Vec<u64>,
+    /// adjacent equal digits starting at each position, indexed by position
+    pub pair_positions: Vec<u64>,
+}
+
+impl fmt::Display for PasswordStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let join = |counts: &[u64]| {
+            counts
+                .iter()
+                .enumerate()
+                .filter(|&(_, &n)| n > 0)
+                .map(|(i, n)| format!("{}:{}", i, n))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        writeln!(f, "count: {}", self.count)?;
+        writeln!(f, "digits: {}", join(&self.digit_frequency))?;
+        writeln!(f, "leading digit: {}", join(&self.leading_digit))?;
+        write!(f, "pair positions: {}", join(&self.pair_positions))
+    }
+}
+
+/// Gathers statistics over the valid passwords in a range
+///
+/// # Arguments
+///
+/// * range - inclusive range of candidates
+/// * rules - rules each password has to meet
+///
+/// # Returns
+///
+/// * digit histogram, leading digit counts and pair positions
+pub fn stats(range: RangeInclusive<u64>, rules: &Validator) -> PasswordStats {
+    let radix = rules.radix() as usize;
+    let mut stats = PasswordStats {
+        count: 0,
+        digit_frequency: vec![0; radix],
+        leading_digit: vec![0; radix],
+        pair_positions: Vec::new(),
+    };
+    for password in valid_passwords(range, rules).passwords() {
+        let digits = password.digits();
+        stats.count += 1;
+        stats.leading_digit[digits[0] as usize] += 1;
+        for &d in digits {
+            stats.digit_frequency[d as usize] += 1;
+        }
+        if stats.pair_positions.len() < digits.len() - 1 {
+            stats.pair_positions.resize(digits.len() - 1, 0);
+        }
+        for (i, w) in digits.windows(2).enumerate() {
+            if w[0] == w[1] {
+                stats.pair_positions[i] += 1;
+            }
+        }
+    }
+    stats
+}
+
+/// Finds the smallest number at least `num` whose digits never decrease
+///
+/// # Arguments
+///
+/// * num - number to start from
+///
+/// # Returns
+///
+/// * next non-decreasing number, or none if it doesn't fit in a u64
+pub fn next_non_decreasing(num: u64) -> Option<u64> {
+    next_non_decreasing_in(num, 10)
+}
+
+/// Finds the smallest number at least `num` whose digits in `radix` never
+/// decrease
+///
+/// # Arguments
+///
+/// * num - number to start from
+/// * radix - base between 2 and 36
+///
+/// # Returns
+///
+/// * next non-decreasing number, or none if it doesn't fit in a u64
+pub fn next_non_decreasing_in(num: u64, radix: u32) -> Option<u64> {
+    let mut digits = digits_in(num, radix);
+    raise_to_non_decreasing(&mut digits);
+    from_digits(&digits, radix)
+}
+
+/// Fills everything after the first decreasing digit with the digit
+/// before it, giving the smallest non-decreasing digits at least as large
+///
+/// # Arguments
+///
+/// * digits - digits most significant first, changed in place
+///
+/// # Returns
+///
+/// * whether any digit changed
+fn raise_to_non_decreasing(digits: &mut [u8]) -> bool {
+    match digits.windows(2).position(|w| w[1] < w[0]) {
+        Some(i) => {
+            let fill = digits[i];
+            digits[i + 1..].fill(fill);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Lazily yields the valid passwords in a range, in increasing order
+pub struct ValidPasswords<'a> {
+    /// next candidate to consider, none once the range is exhausted
+    next: Option<u64>,
+    end: u64,
+    validator: &'a Validator,
+}
+
+impl<'a> ValidPasswords<'a> {
+    /// Yields the passwords along with the digits they were checked with
+    pub fn passwords(mut self) -> impl Iterator<Item = Password> + 'a {
+        iter::from_fn(move || self.next_password())
+    }
+
+    /// Finds the next valid password, moving past it
+    fn next_password(&mut self) -> Option<Password> {
+        let found = next_valid_within(self.next?, self.end, self.validator);
+        self.next = found.as_ref().and_then(|p| p.value.checked_add(1));
+        found
+    }
+}
+
+impl Iterator for ValidPasswords<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        self.next_password().map(|p| p.value)
+    }
+}
+
+/// Finds the smallest valid number in `num..=end`
+///
+/// # Arguments
+///
+/// * num - number to start from
+/// * end - last number to consider, inclusive
+/// * rules - rules the number has to meet
+///
+/// # Returns
+///
+/// * next valid password, split into digits once, or none if the range
+///   has no more
+fn next_valid_within(mut num: u64, end: u64, rules: &Validator) -> Option<Password> {
+    let skip_decreasing = rules.requires_non_decreasing();
+    loop {
+        let mut digits = digits_in(num, rules.radix);
+        if skip_decreasing && raise_to_non_decreasing(&mut digits) {
+            num = from_digits(&digits, rules.radix)?;
+        }
+        if num > end {
+            return None;
+        }
+        let password = Password { value: num, digits };
+        if rules.check(&password) {
+            return Some(password);
+        }
+        num = num.checked_add(1)?;
+    }
+}
+
+/// Finds the smallest valid number at least `num`
+///
+/// When the rules require non-decreasing digits, the first decreasing
+/// digit is bumped up and the rest of the number filled with it before
+/// the remaining rules are checked, so whole runs of candidates are
+/// skipped at once.
+///
+/// # Arguments
+///
+/// * num - number to start from
+/// * rules - rules the number has to meet
+///
+/// # Returns
+///
+/// * next valid number, or none if there isn't one that fits in a u64
+pub fn next_valid(num: u64, rules: &Validator) -> Option<u64> {
+    next_valid_within(num, u64::MAX, rules).map(|p| p.value)
+}
+
+/// Lazily yields the valid passwords in a range
+///
+/// When the rules require non-decreasing digits, candidates with a
+/// decreasing prefix are jumped over instead of tested one by one.
+///
+/// # Arguments
+///
+/// * range - inclusive range of candidates
+/// * rules - rules each password has to meet
+///
+/// # Returns
+///
+/// * iterator of valid passwords, in increasing order
+pub fn valid_passwords(range: RangeInclusive<u64>, rules: &Validator) -> ValidPasswords<'_> {
+    let (start, end) = range.into_inner();
+    ValidPasswords {
+        next: (start <= end).then_some(start),
+        end,
+        validator: rules,
+    }
+}
 
 /// Determines if a number is valid password
 ///
@@ -10,56 +826,358 @@ use shared;
 ///
 /// * validity result
 pub fn is_valid(num: u64) -> bool {
-    let nstr: Vec<char> = num.to_string().chars().collect();
-    let mut has_repeat = false;
-    let mut current = '0';
-    for i in 0..nstr.len() {
-        if nstr[i] < current {
-            return false;
+    Validator::part1().is_valid(num)
+}
+
+/// Determines if a number is a valid password under the stricter part2
+/// rules, where the pair may not be part of a longer run
+///
+/// # Arguments
+///
+/// * num - candidate under test
+///
+/// # Returns
+///
+/// * validity result
+pub fn is_valid_double_pair(num: u64) -> bool {
+    Validator::part2().is_valid(num)
+}
+
+pub fn part1(start: u64, end: u64) -> u64 {
+    valid_passwords(start..=end, &Validator::part1()).count() as u64
+}
+
+pub fn part2(start: u64, end: u64) -> u64 {
+    valid_passwords(start..=end, &Validator::part2()).count() as u64
+}
+
+/// Pair requirement understood by the digit DP counter
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum PairRule {
+    /// any run of two or more equal digits, as in part1
+    Any,
+    /// a run of exactly two equal digits, as in part2
+    Exact,
+}
+
+impl PairRule {
+    /// Determines if a finished run of equal digits satisfies the rule
+    fn satisfied_by(self, run: u8) -> bool {
+        match self {
+            PairRule::Any => run >= 2,
+            PairRule::Exact => run == 2,
+        }
+    }
+}
+
+/// State of the digit DP once the leading digits are fixed
+#[derive(PartialEq, Eq, Hash, Copy, Clone)]
+struct DigitState {
+    pos: usize,
+    /// whether a non-zero digit has been placed yet
+    started: bool,
+    last: u8,
+    /// length of the current run of `last`, capped at 3
+    run: u8,
+    /// whether an earlier run already satisfied the pair rule
+    seen: bool,
+}
+
+/// Counts ordered passwords with a pair in `1..=n`
+///
+/// # Arguments
+///
+/// * n - upper bound, inclusive
+/// * pair - pair requirement
+/// * order - ordering the digits have to follow
+/// * radix - base the digits are in
+fn count_up_to(n: u64, pair: PairRule, order: Monotonicity, radix: u32) -> u64 {
+    fn go(
+        limit: &[u8],
+        state: DigitState,
+        tight: bool,
+        rules: (PairRule, Monotonicity, u8),
+        memo: &mut HashMap<DigitState, u64>,
+    ) -> u64 {
+        let (pair, order, top) = rules;
+        if state.pos == limit.len() {
+            let valid = state.seen || pair.satisfied_by(state.run);
+            return (state.started && valid) as u64;
+        }
+        if !tight && let Some(&count) = memo.get(&state) {
+            return count;
         }
-        current = nstr[i];
-        if i != nstr.len() - 1 && nstr[i] == nstr[i+1] {
-            has_repeat = true;
+        let max = if tight { limit[state.pos] } else { top };
+        let mut count = 0;
+        for d in 0..=max {
+            let next = match (state.started, d) {
+                (false, 0) => DigitState {
+                    pos: state.pos + 1,
+                    ..state
+                },
+                (false, _) => DigitState {
+                    pos: state.pos + 1,
+                    started: true,
+                    last: d,
+                    run: 1,
+                    seen: false,
+                },
+                (true, d) if !order.allows(state.last, d) => continue,
+                (true, d) if d == state.last => DigitState {
+                    pos: state.pos + 1,
+                    run: (state.run + 1).min(3),
+                    ..state
+                },
+                (true, d) => DigitState {
+                    pos: state.pos + 1,
+                    last: d,
+                    run: 1,
+                    seen: state.seen || pair.satisfied_by(state.run),
+                    ..state
+                },
+            };
+            count += go(limit, next, tight && d == max, rules, memo);
         }
+        if !tight {
+            memo.insert(state, count);
+        }
+        count
     }
-    has_repeat
+
+    let start = DigitState {
+        pos: 0,
+        started: false,
+        last: 0,
+        run: 0,
+        seen: false,
+    };
+    let top = (radix - 1) as u8;
+    go(
+        &digits_in(n, radix),
+        start,
+        true,
+        (pair, order, top),
+        &mut HashMap::new(),
+    )
 }
 
-pub fn is_valid_double_pair(num: u64) -> bool {
-    let nstr: Vec<char> = num.to_string().chars().collect();
-    let mut has_repeat = false;
-    let mut current = '0';
-    let mut dup_run: u64 = 0;
-    for i in 0..nstr.len() {
-        if nstr[i] < current {
-            return false;
-        }
-        current = nstr[i];
-        if i != nstr.len() - 1 && nstr[i] == nstr[i+1] {
-            if dup_run == 0 {
-                dup_run = 2
-            } else {
-                dup_run += 1;
+/// Counts valid passwords in a range without testing every number
+///
+/// Uses digit dynamic programming over the position, last digit, run
+/// length and whether a pair has been seen, so the cost only depends on
+/// the number of digits.
+///
+/// # Arguments
+///
+/// * start - first number of the range
+/// * end - last number of the range, inclusive
+/// * pair - pair requirement
+///
+/// # Returns
+///
+/// * count of valid passwords
+pub fn count_valid_dp(start: u64, end: u64, pair: PairRule) -> u64 {
+    count_valid_dp_ordered(start, end, pair, Monotonicity::NonDecreasing)
+}
+
+/// Counts valid passwords in a range under any ordering of the digits
+///
+/// # Arguments
+///
+/// * start - first number of the range
+/// * end - last number of the range, inclusive
+/// * pair - pair requirement
+/// * order - ordering the digits have to follow
+///
+/// # Returns
+///
+/// * count of valid passwords
+pub fn count_valid_dp_ordered(start: u64, end: u64, pair: PairRule, order: Monotonicity) -> u64 {
+    count_valid_dp_in(start, end, pair, order, 10)
+}
+
+/// Counts valid passwords in a range with the digits taken in any radix
+///
+/// # Arguments
+///
+/// * start - first number of the range
+/// * end - last number of the range, inclusive
+/// * pair - pair requirement
+/// * order - ordering the digits have to follow
+/// * radix - base between 2 and 36
+///
+/// # Returns
+///
+/// * count of valid passwords
+pub fn count_valid_dp_in(
+    start: u64,
+    end: u64,
+    pair: PairRule,
+    order: Monotonicity,
+    radix: u32,
+) -> u64 {
+    assert!((2..=36).contains(&radix), "radix {} out of range", radix);
+    if start > end {
+        return 0;
+    }
+    let below = match start {
+        0 => 0,
+        _ => count_up_to(start - 1, pair, order, radix),
+    };
+    count_up_to(end, pair, order, radix) - below
+}
+
+/// Reasons a puzzle range can't be used
+#[derive(Debug, PartialEq)]
+pub enum RangeError {
+    /// line isn't of the form `LOW-HIGH`
+    Malformed(String),
+    /// bound isn't made of decimal digits
+    NotANumber(String),
+    /// bound doesn't fit in a u64
+    TooLarge(String),
+    /// low bound is above the high bound
+    Reversed { low: u64, high: u64 },
+    /// bounds have a different number of digits
+    LengthMismatch { low: u64, high: u64 },
+    /// input file has no range in it
+    Empty(String),
+    /// input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RangeError::Malformed(line) => write!(f, "expected LOW-HIGH, got {:?}", line),
+            RangeError::NotANumber(bound) => write!(f, "bound {:?} isn't a number", bound),
+            RangeError::TooLarge(bound) => write!(f, "bound {} is larger than {}", bound, u64::MAX),
+            RangeError::Reversed { low, high } => {
+                write!(f, "low bound {} is above high bound {}", low, high)
             }
-        } else if dup_run == 2 {
-            has_repeat = true;
-        } else {
-            dup_run = 0;
+            RangeError::LengthMismatch { low, high } => write!(
+                f,
+                "bounds {} and {} have {} and {} digits",
+                low,
+                high,
+                digits(*low).len(),
+                digits(*high).len()
+            ),
+            RangeError::Empty(filename) => write!(f, "{} is empty", filename),
+            RangeError::Io { filename, message } => write!(f, "{}: {}", filename, message),
         }
     }
-    has_repeat
 }
 
-pub fn part1(start: u64, end: u64) -> u64 {
-    (start..=end).filter(|&n| is_valid(n)).count().try_into().unwrap()
+impl std::error::Error for RangeError {}
+
+/// Parses a puzzle range written as `LOW-HIGH`
+///
+/// Both bounds have to be plain decimal numbers with the same number of
+/// digits, and the low bound can't be above the high one.
+///
+/// # Arguments
+///
+/// * line - range such as `124075-580769`
+///
+/// # Returns
+///
+/// * inclusive bounds of the range, or the reason the line is malformed
+pub fn parse_range(line: &str) -> Result<(u64, u64), RangeError> {
+    let (low, high) = line
+        .split_once('-')
+        .ok_or_else(|| RangeError::Malformed(line.to_string()))?;
+    let (low, high) = (low.trim(), high.trim());
+    let parse = |s: &str| {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(RangeError::NotANumber(s.to_string()));
+        }
+        s.parse::<u64>()
+            .map_err(|_| RangeError::TooLarge(s.to_string()))
+    };
+    let (start, end) = (parse(low)?, parse(high)?);
+    if start > end {
+        return Err(RangeError::Reversed {
+            low: start,
+            high: end,
+        });
+    }
+    if digits(start).len() != digits(end).len() {
+        return Err(RangeError::LengthMismatch {
+            low: start,
+            high: end,
+        });
+    }
+    Ok((start, end))
 }
 
-pub fn part2(start: u64, end: u64) -> u64 {
-    (start..=end).filter(|&n| is_valid_double_pair(n)).count().try_into().unwrap()
+/// Reads the puzzle range from the first line of an input file
+///
+/// # Arguments
+///
+/// * filename - name of input file
+///
+/// # Returns
+///
+/// * inclusive bounds of the range, or the reason it can't be read
+pub fn read_range(filename: &str) -> Result<(u64, u64), RangeError> {
+    let lines = try_ingest_file(filename).map_err(|err| RangeError::Io {
+        filename: filename.to_string(),
+        message: err.to_string(),
+    })?;
+    match lines.first() {
+        Some(line) => parse_range(line),
+        None => Err(RangeError::Empty(filename.to_string())),
+    }
+}
+
+/// Performs all operations necessary for part1 on the range in a file
+///
+/// # Arguments
+///
+/// * filename - name of input file
+///
+/// # Returns
+///
+/// * count of valid passwords, or the reason the range can't be read
+pub fn part1_from_file(filename: &str) -> Result<u64, RangeError> {
+    let (start, end) = read_range(filename)?;
+    Ok(part1(start, end))
 }
 
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
+/// Performs all operations necessary for part2 on the range in a file
+///
+/// # Arguments
+///
+/// * filename - name of input file
+///
+/// # Returns
+///
+/// * count of valid passwords, or the reason the range can't be read
+pub fn part2_from_file(filename: &str) -> Result<u64, RangeError> {
+    let (start, end) = read_range(filename)?;
+    Ok(part2(start, end))
+}
+
+/// Day 4 as driven by the workspace runner
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Secure Container"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1_from_file(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2_from_file(filename))
+    }
 }
 
 #[cfg(test)]
@@ -67,9 +1185,16 @@ mod tests {
     use super::*;
 
     #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
+    fn password_works() {
+        let password = Password::new(112333);
+        let runs: Vec<&[u8]> = password.runs().collect();
+
+        assert_eq!(password.value(), 112333);
+        assert_eq!(password.digits(), &[1, 1, 2, 3, 3, 3]);
+        assert_eq!(runs, vec![&[1, 1][..], &[2], &[3, 3, 3]]);
+        assert!(password.is_monotonic());
+        assert!(!Password::new(443444).is_monotonic());
+        assert_eq!(Password::in_radix(0x1aaf, 16).digits(), &[1, 10, 10, 15]);
     }
 
     #[test]
@@ -97,7 +1222,545 @@ mod tests {
         ];
 
         for tc in input {
-            assert_eq!(is_valid_double_pair(tc.0), tc.1, "failed with input: {}", tc.0);
+            assert_eq!(
+                is_valid_double_pair(tc.0),
+                tc.1,
+                "failed with input: {}",
+                tc.0
+            );
+        }
+    }
+
+    #[test]
+    fn rules_work() {
+        let input: Vec<(u64, bool, bool, bool)> = vec![
+            (111111, true, true, false),
+            (223450, false, true, true),
+            (123789, true, false, false),
+            (112233, true, true, true),
+            (111122, true, true, true),
+        ];
+
+        for (num, non_decreasing, pair, exact_pair) in input {
+            let password = Password::new(num);
+            assert_eq!(NonDecreasing.check(&password), non_decreasing, "{}", num);
+            assert_eq!(HasPair.check(&password), pair, "{}", num);
+            assert_eq!(HasExactPair.check(&password), exact_pair, "{}", num);
+        }
+    }
+
+    #[test]
+    fn validator_composes_rules() {
+        let no_sevens = |password: &Password| !password.digits().contains(&7);
+        let validator = Validator::part1().with(no_sevens);
+
+        assert!(validator.is_valid(112233));
+        assert!(!validator.is_valid(112277));
+        assert!(Validator::new().is_valid(987));
+        assert_eq!(validator.count(100, 200), 15);
+    }
+
+    #[test]
+    fn count_valid_dp_matches_brute_force() {
+        let ranges = [
+            (0, 1000),
+            (100, 200),
+            (1, 1),
+            (11, 11),
+            (5000, 25000),
+            (124075, 180769),
+        ];
+        for (start, end) in ranges {
+            assert_eq!(
+                count_valid_dp(start, end, PairRule::Any),
+                part1(start, end),
+                "{}-{}",
+                start,
+                end
+            );
+            assert_eq!(
+                count_valid_dp(start, end, PairRule::Exact),
+                part2(start, end),
+                "{}-{}",
+                start,
+                end
+            );
+        }
+        assert_eq!(count_valid_dp(20, 10, PairRule::Any), 0);
+    }
+
+    #[test]
+    fn count_valid_dp_handles_huge_ranges() {
+        assert_eq!(count_valid_dp(124075, 580769, PairRule::Any), 2150);
+        assert_eq!(count_valid_dp(124075, 580769, PairRule::Exact), 1462);
+        let all = count_valid_dp(0, u64::MAX, PairRule::Any);
+        let per_length: u64 = (1..20)
+            .map(|len| count_valid_dp(10u64.pow(len - 1), 10u64.pow(len) - 1, PairRule::Any))
+            .sum::<u64>()
+            + count_valid_dp(10u64.pow(19), u64::MAX, PairRule::Any);
+        assert_eq!(all, per_length);
+    }
+
+    #[test]
+    fn next_non_decreasing_works() {
+        assert_eq!(next_non_decreasing(124075), Some(124444));
+        assert_eq!(next_non_decreasing(123789), Some(123789));
+        assert_eq!(next_non_decreasing(580769), Some(588888));
+        assert_eq!(next_non_decreasing(90), Some(99));
+        assert_eq!(next_non_decreasing(u64::MAX), None);
+    }
+
+    #[test]
+    fn next_valid_works() {
+        let part1 = Validator::part1();
+        let part2 = Validator::part2();
+
+        assert_eq!(next_valid(124075, &part1), Some(124444));
+        assert_eq!(next_valid(124444, &part1), Some(124444));
+        assert_eq!(next_valid(124075, &part2), Some(124455));
+        assert_eq!(next_valid(443444, &part2), Some(444455));
+        assert_eq!(next_valid(987654321, &part1), Some(999999999));
+        assert_eq!(next_valid(u64::MAX - 1, &part1), None);
+    }
+
+    #[test]
+    fn valid_passwords_works() {
+        let validator = Validator::part1();
+        let first: Vec<u64> = valid_passwords(124075..=580769, &validator)
+            .take(3)
+            .collect();
+
+        assert_eq!(first, vec![124444, 124445, 124446]);
+        assert_eq!(
+            valid_passwords(100..=200, &validator).count(),
+            (100..=200).filter(|&n| is_valid(n)).count()
+        );
+        assert_eq!(
+            valid_passwords(RangeInclusive::new(200, 100), &validator).next(),
+            None
+        );
+        assert_eq!(
+            valid_passwords(u64::MAX - 10..=u64::MAX, &Validator::new()).count(),
+            11
+        );
+    }
+
+    #[test]
+    fn passwords_carry_their_digits() {
+        let validator = Validator::part2().in_radix(16);
+        let passwords: Vec<Password> = valid_passwords(0x100..=0x1ff, &validator)
+            .passwords()
+            .collect();
+        let values: Vec<u64> = valid_passwords(0x100..=0x1ff, &validator).collect();
+
+        assert_eq!(passwords.len(), values.len());
+        for (password, value) in passwords.iter().zip(values) {
+            assert_eq!(password.value(), value);
+            assert_eq!(password.digits(), Password::in_radix(value, 16).digits());
+        }
+    }
+
+    #[test]
+    fn valid_passwords_without_monotonic_rule_tests_everything() {
+        let validator = Validator::new().with(HasPair);
+        let expected: Vec<u64> = (90..=120)
+            .filter(|&n| HasPair.check(&Password::new(n)))
+            .collect();
+
+        assert_eq!(
+            valid_passwords(90..=120, &validator).collect::<Vec<u64>>(),
+            expected
+        );
+        assert_eq!(
+            expected,
+            vec![99, 100, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119]
+        );
+    }
+
+    #[test]
+    fn par_count_matches_count() {
+        let validator = Validator::part2();
+
+        for chunk_size in [0, 1, 7, 1000, 1_000_000] {
+            assert_eq!(validator.par_count(124075, 580769, chunk_size), 1462);
+        }
+        assert_eq!(
+            Validator::part1().par_count(1, 99_999_999, 65_536),
+            count_valid_dp(1, 99_999_999, PairRule::Any)
+        );
+        assert_eq!(validator.par_count(u64::MAX - 5, u64::MAX, 4), 0);
+        let binary = Validator::part1().in_radix(2);
+        assert_eq!(
+            binary.par_count(0, u64::MAX, 1),
+            count_valid_dp_in(0, u64::MAX, PairRule::Any, Monotonicity::NonDecreasing, 2)
+        );
+        assert_eq!(binary.par_count(0, u64::MAX, 1), binary.count(0, u64::MAX));
+        assert_eq!(validator.par_count(10, 1, 4), 0);
+    }
+
+    #[test]
+    fn find_valid_works() {
+        let validator = Validator::part1();
+        let all = validator.find_valid(100, 200, None);
+
+        assert_eq!(all.count, 17);
+        assert_eq!(all.passwords.len(), 17);
+        assert_eq!(&all.passwords[..3], &[111, 112, 113]);
+        assert!(!all.truncated());
+
+        let capped = Validator::part2().find_valid(124075, 580769, Some(2));
+        assert_eq!(
+            capped,
+            Matches {
+                passwords: vec![124455, 124456],
+                count: 1462
+            }
+        );
+        assert!(capped.truncated());
+    }
+
+    #[test]
+    fn validate_reports_each_rule() {
+        let report = Validator::part2().validate(443444);
+
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.outcomes,
+            vec![
+                RuleOutcome {
+                    rule: "non-decreasing",
+                    passed: false,
+                    positions: vec![2],
+                },
+                RuleOutcome {
+                    rule: "exact pair",
+                    passed: true,
+                    positions: vec![],
+                },
+            ]
+        );
+        assert_eq!(
+            report.to_string(),
+            "443444: non-decreasing failed at [2]; exact pair passed;"
+        );
+    }
+
+    #[test]
+    fn validate_points_at_long_runs() {
+        let validator = Validator::part2().with(|p: &Password| p.digits().len() == 6);
+        let report = validator.validate(123444);
+        let failed: Vec<&RuleOutcome> = report.failed().collect();
+
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].rule, "exact pair");
+        assert_eq!(failed[0].positions, vec![3, 4, 5]);
+        assert_eq!(report.outcomes[2].rule, "custom");
+        assert!(Validator::part1().validate(111111).is_valid());
+        assert_eq!(
+            Validator::part1().validate(123789).to_string(),
+            "123789: non-decreasing passed; pair failed;"
+        );
+    }
+
+    #[test]
+    fn monotonicity_modes_work() {
+        assert!(Monotonicity::StrictlyIncreasing.check(&Password::new(13579)));
+        assert!(!Monotonicity::StrictlyIncreasing.check(&Password::new(13379)));
+        assert!(Monotonicity::NonIncreasing.check(&Password::new(997520)));
+        assert_eq!(
+            Monotonicity::NonIncreasing.offending(&Password::new(991529)),
+            vec![3, 5]
+        );
+        assert!(Monotonicity::Unconstrained.check(&Password::new(918273)));
+
+        let report = Validator::new()
+            .with(Monotonicity::NonIncreasing)
+            .with(HasExactPair)
+            .validate(443210);
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn monotonicity_modes_match_brute_force() {
+        let modes = [
+            Monotonicity::NonDecreasing,
+            Monotonicity::StrictlyIncreasing,
+            Monotonicity::NonIncreasing,
+            Monotonicity::Unconstrained,
+        ];
+        for order in modes {
+            for (pair, validator) in [
+                (PairRule::Any, Validator::new().with(order).with(HasPair)),
+                (
+                    PairRule::Exact,
+                    Validator::new().with(order).with(HasExactPair),
+                ),
+            ] {
+                for (start, end) in [(0, 9999), (1234, 98765)] {
+                    let brute = (start..=end).filter(|&n| validator.is_valid(n)).count() as u64;
+                    assert_eq!(validator.count(start, end), brute, "{:?} {:?}", order, pair);
+                    assert_eq!(
+                        count_valid_dp_ordered(start, end, pair, order),
+                        brute,
+                        "{:?} {:?}",
+                        order,
+                        pair
+                    );
+                }
+            }
+        }
+        assert_eq!(
+            count_valid_dp_ordered(
+                1,
+                10u64.pow(12),
+                PairRule::Any,
+                Monotonicity::StrictlyIncreasing
+            ),
+            0
+        );
+    }
+
+    #[test]
+    fn digits_in_works() {
+        assert_eq!(digits_in(0xabc, 16), vec![10, 11, 12]);
+        assert_eq!(digits_in(0, 16), vec![0]);
+        assert_eq!(digits_in(5, 2), vec![1, 0, 1]);
+        assert_eq!(digits_in(124075, 10), digits(124075));
+        assert_eq!(to_radix_string(0x1aaf, 16), "1aaf");
+        assert_eq!(next_non_decreasing_in(0x1a3f, 16), Some(0x1aaa));
+    }
+
+    #[test]
+    fn hex_validator_works() {
+        let validator = Validator::part2().in_radix(16);
+
+        assert!(validator.is_valid(0x1aaf));
+        assert!(validator.is_valid(0x12ff));
+        assert!(!validator.is_valid(0x1aaa));
+        assert!(!validator.is_valid(0x1a3f));
+        assert_eq!(next_valid(0x1a3f, &validator), Some(0x1aab));
+        assert_eq!(
+            validator.validate(0x1a3f).to_string(),
+            "1a3f: non-decreasing failed at [2]; exact pair failed;"
+        );
+    }
+
+    #[test]
+    fn radix_counts_match_brute_force() {
+        for radix in [2, 8, 16, 36] {
+            for (pair, validator) in [
+                (PairRule::Any, Validator::part1().in_radix(radix)),
+                (PairRule::Exact, Validator::part2().in_radix(radix)),
+            ] {
+                let brute = (0..=70_000).filter(|&n| validator.is_valid(n)).count() as u64;
+                assert_eq!(validator.count(0, 70_000), brute);
+                assert_eq!(
+                    count_valid_dp_in(0, 70_000, pair, Monotonicity::NonDecreasing, radix),
+                    brute
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn inclusion_rules_work() {
+        assert!(MustContain(7).check(&Password::new(123789)));
+        assert!(!MustContain(4).check(&Password::new(123789)));
+        assert!(MustNotContain(4).check(&Password::new(123789)));
+        assert_eq!(
+            MustNotContain(3).offending(&Password::new(133789)),
+            vec![1, 2]
+        );
+        assert!(DigitCountAtLeast(3, 2).check(&Password::new(133789)));
+        assert!(!DigitCountAtLeast(3, 3).check(&Password::new(133789)));
+        assert!(DigitCountAtLeast(5, 0).check(&Password::new(133789)));
+    }
+
+    #[test]
+    fn inclusion_rules_compose() {
+        let validator = Validator::part1()
+            .with(MustContain(9))
+            .with(MustNotContain(1))
+            .with(DigitCountAtLeast(9, 2));
+        let expected = (100_000..=999_999)
+            .filter(|&n| {
+                let d = digits(n);
+                is_valid(n) && !d.contains(&1) && d.iter().filter(|&&x| x == 9).count() >= 2
+            })
+            .count() as u64;
+
+        assert_eq!(validator.count(100_000, 999_999), expected);
+        assert!(validator.is_valid(222299));
+        assert!(!validator.is_valid(122299));
+        assert!(!validator.is_valid(222239));
+    }
+
+    #[test]
+    fn stats_works() {
+        let stats = stats(100..=200, &Validator::part1());
+
+        assert_eq!(stats.count, 17);
+        assert_eq!(stats.leading_digit, vec![0, 17, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(stats.digit_frequency, vec![0, 27, 3, 3, 3, 3, 3, 3, 3, 3]);
+        // 111 has both pairs, 11x the first and 1xx the second
+        assert_eq!(stats.pair_positions, vec![9, 9]);
+        assert_eq!(
+            stats.to_string(),
+            "count: 17\n\
+             digits: 1:27 2:3 3:3 4:3 5:3 6:3 7:3 8:3 9:3\n\
+             leading digit: 1:17\n\
+             pair positions: 0:9 1:9"
+        );
+    }
+
+    #[test]
+    fn stats_agrees_with_count() {
+        let validator = Validator::part2();
+        let summary = stats(124075..=580769, &validator);
+        let digits: u64 = summary.digit_frequency.iter().sum();
+
+        assert_eq!(summary.count, 1462);
+        assert_eq!(summary.leading_digit.iter().sum::<u64>(), 1462);
+        assert_eq!(digits, 1462 * 6);
+        assert_eq!(summary.pair_positions.len(), 5);
+        assert_eq!(stats(RangeInclusive::new(10, 1), &validator).count, 0);
+    }
+
+    #[test]
+    fn parse_range_works() {
+        assert_eq!(parse_range("124075-580769"), Ok((124075, 580769)));
+        assert_eq!(parse_range(" 10 - 20 "), Ok((10, 20)));
+        assert_eq!(parse_range("007-009"), Ok((7, 9)));
+        assert_eq!(parse_range("5-5"), Ok((5, 5)));
+    }
+
+    #[test]
+    fn parse_range_rejects_bad_ranges() {
+        assert_eq!(
+            parse_range("124075"),
+            Err(RangeError::Malformed("124075".to_string()))
+        );
+        assert_eq!(
+            parse_range("12a-30"),
+            Err(RangeError::NotANumber("12a".to_string()))
+        );
+        assert_eq!(
+            parse_range("+12-30"),
+            Err(RangeError::NotANumber("+12".to_string()))
+        );
+        assert_eq!(
+            parse_range("-5-10"),
+            Err(RangeError::NotANumber("".to_string()))
+        );
+        assert_eq!(
+            parse_range("1-99999999999999999999"),
+            Err(RangeError::TooLarge("99999999999999999999".to_string()))
+        );
+        assert_eq!(
+            parse_range("580769-124075"),
+            Err(RangeError::Reversed {
+                low: 580769,
+                high: 124075
+            })
+        );
+        let mismatch = parse_range("1-20");
+        assert_eq!(
+            mismatch,
+            Err(RangeError::LengthMismatch { low: 1, high: 20 })
+        );
+        assert_eq!(
+            mismatch.unwrap_err().to_string(),
+            "bounds 1 and 20 have 1 and 2 digits"
+        );
+    }
+
+    #[test]
+    fn from_file_works() {
+        assert_eq!(read_range("src/test.txt"), Ok((100, 200)));
+        assert_eq!(part1_from_file("src/test.txt"), Ok(part1(100, 200)));
+        assert_eq!(part2_from_file("src/test.txt"), Ok(part2(100, 200)));
+        assert_eq!(part1_from_file("src/test.txt"), Ok(17));
+        assert!(matches!(
+            read_range("src/no_such_file.txt"),
+            Err(RangeError::Io { filename, .. }) if filename == "src/no_such_file.txt"
+        ));
+    }
+
+    #[test]
+    fn part1_works() {
+        assert_eq!(part1_from_file("src/input.txt"), Ok(2150));
+    }
+
+    #[test]
+    fn part2_works() {
+        assert_eq!(part2_from_file("src/input.txt"), Ok(1462));
+    }
+}
+
+#[cfg(test)]
+mod test_properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Range of up to 2000 numbers somewhere below 10^8
+    fn sub_range() -> impl Strategy<Value = (u64, u64)> {
+        (0u64..100_000_000, 0u64..2_000).prop_map(|(start, width)| (start, start + width))
+    }
+
+    /// Number built from runs of repeated digits, so runs of every length
+    /// land at the start, middle and end of the number
+    fn runs() -> impl Strategy<Value = u64> {
+        prop::collection::vec((0u8..10, 1usize..5), 1..7).prop_map(|runs| {
+            runs.iter()
+                .flat_map(|&(d, len)| std::iter::repeat_n(d, len))
+                .take(19)
+                .fold(0, |acc, d| acc * 10 + d as u64)
+        })
+    }
+
+    /// Lengths of the runs of equal digits, worked out one digit at a time
+    fn run_lengths(num: u64) -> Vec<usize> {
+        let mut lengths = Vec::new();
+        let mut prev = None;
+        for d in digits(num) {
+            match lengths.last_mut() {
+                Some(len) if prev == Some(d) => *len += 1,
+                _ => lengths.push(1),
+            }
+            prev = Some(d);
+        }
+        lengths
+    }
+
+    proptest! {
+        #[test]
+        fn counts_agree((start, end) in sub_range()) {
+            for (validator, pair) in [
+                (Validator::part1(), PairRule::Any),
+                (Validator::part2(), PairRule::Exact),
+            ] {
+                let brute = (start..=end).filter(|&n| validator.is_valid(n)).count() as u64;
+                prop_assert_eq!(valid_passwords(start..=end, &validator).count() as u64, brute);
+                prop_assert_eq!(count_valid_dp(start, end, pair), brute);
+            }
+        }
+
+        #[test]
+        fn is_valid_double_pair_matches_run_lengths(num in runs()) {
+            let ordered = NonDecreasing.check(&Password::new(num));
+            let lengths = run_lengths(num);
+
+            prop_assert_eq!(is_valid(num), ordered && lengths.iter().any(|&l| l >= 2));
+            prop_assert_eq!(is_valid_double_pair(num), ordered && lengths.contains(&2));
+        }
+
+        #[test]
+        fn next_valid_skips_nothing(num in 0u64..100_000_000) {
+            let validator = Validator::part2();
+            let next = next_valid(num, &validator).unwrap();
+
+            prop_assert!(validator.is_valid(next));
+            prop_assert_eq!(count_valid_dp(num, next, PairRule::Exact), 1);
         }
     }
 }
@@ -1,6 +1,52 @@
-use day4::{part1, part2};
+use std::process::ExitCode;
 
-fn main() {
-    println!("Part1: {}", part1(124075, 580769));
-    println!("Part2: {}", part2(124075, 580769));
+use clap::Parser;
+use day4::{Validator, parse_range, read_range, stats, valid_passwords};
+use shared::args::DayArgs;
+
+/// Counts the passwords to the Venus fuel depot meeting the puzzle rules
+#[derive(Parser)]
+struct Cli {
+    /// range to search, as `LOW-HIGH`
+    #[arg(long, value_parser = parse_range, conflicts_with = "input")]
+    range: Option<(u64, u64)>,
+    #[command(flatten)]
+    args: DayArgs,
+    /// print every matching password before the count
+    #[arg(long, conflicts_with = "stats")]
+    list: bool,
+    /// print digit statistics over the matching passwords
+    #[arg(long)]
+    stats: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let (start, end) = match cli.range.map_or_else(|| read_range(&cli.args.input), Ok) {
+        Ok(range) => range,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let parts = [(1, Validator::part1()), (2, Validator::part2())];
+    for (part, validator) in parts {
+        if !cli.args.wants(part) {
+            continue;
+        }
+        if cli.stats {
+            println!("Part{}:\n{}", part, stats(start..=end, &validator));
+            continue;
+        }
+        let mut count = 0;
+        for password in valid_passwords(start..=end, &validator) {
+            if cli.list {
+                println!("{}", password);
+            }
+            count += 1;
+        }
+        println!("Part{}: {}", part, count);
+    }
+    ExitCode::SUCCESS
 }
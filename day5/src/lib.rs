@@ -0,0 +1,214 @@
+use std::fmt;
+
+use shared::intcode::{IntcodeError, Vm, parse_program};
+use shared::solution::{Answer, Solution, answer};
+
+/// System ID of the ship's air conditioner unit
+pub const AIR_CONDITIONER: i64 = 1;
+/// System ID of the ship's thermal radiator controller
+pub const THERMAL_RADIATOR: i64 = 5;
+
+/// Reasons a diagnostic run can't produce a code
+#[derive(Debug, PartialEq)]
+pub enum Day5Error {
+    /// the program faulted
+    Intcode(IntcodeError),
+    /// a test before the diagnostic code reported a nonzero value
+    FailedTest { index: usize, value: i64 },
+    /// the program halted without any output
+    NoOutput,
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day5Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day5Error::Intcode(err) => write!(f, "{}", err),
+            Day5Error::FailedTest { index, value } => {
+                write!(f, "test {} failed, off by {}", index, value)
+            }
+            Day5Error::NoOutput => write!(f, "program produced no output"),
+            Day5Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day5Error {}
+
+impl From<IntcodeError> for Day5Error {
+    fn from(err: IntcodeError) -> Self {
+        Day5Error::Intcode(err)
+    }
+}
+
+/// Runs a program with a single input and returns everything it outputs
+///
+/// # Arguments
+///
+/// * `program` - intcode to run, left untouched
+/// * `input` - value fed to the first input instruction
+///
+/// # Returns
+///
+/// * output values in order, or the fault that stopped the program
+///
+pub fn run(program: &[i64], input: i64) -> Result<Vec<i64>, IntcodeError> {
+    let mut vm = Vm::new(program.to_vec());
+    vm.push_input(input);
+    vm.run()?;
+    Ok(vm.take_output())
+}
+
+/// Runs the TEST diagnostic program for a system
+///
+/// Every output before the last is a test result that must be zero. The
+/// last output is the diagnostic code.
+///
+/// # Arguments
+///
+/// * `program` - diagnostic program
+/// * `system` - ID of the system under test
+///
+/// # Returns
+///
+/// * diagnostic code, or the first failing test
+///
+pub fn diagnose(program: &[i64], system: i64) -> Result<i64, Day5Error> {
+    let output = run(program, system)?;
+    let (&code, tests) = output.split_last().ok_or(Day5Error::NoOutput)?;
+    if let Some((index, &value)) = tests.iter().enumerate().find(|(_, v)| **v != 0) {
+        return Err(Day5Error::FailedTest { index, value });
+    }
+    Ok(code)
+}
+
+/// Reads a program from the first line of a file
+///
+/// # Arguments
+///
+/// * `filename` - path of file containing the intcode
+///
+/// # Returns
+///
+/// * program values, or why the file can't be read as one
+///
+pub fn load_program(filename: &str) -> Result<Vec<i64>, Day5Error> {
+    let input = shared::try_ingest_file(filename).map_err(|err| Day5Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })?;
+    Ok(parse_program(input.first().map_or("", |s| s))?)
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * diagnostic code for the air conditioner unit
+///
+pub fn part1(filename: &str) -> Result<i64, Day5Error> {
+    diagnose(&load_program(filename)?, AIR_CONDITIONER)
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * diagnostic code for the thermal radiator controller
+///
+pub fn part2(filename: &str) -> Result<i64, Day5Error> {
+    diagnose(&load_program(filename)?, THERMAL_RADIATOR)
+}
+
+/// Day 5 as driven by the workspace runner
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "Sunny with a Chance of Asteroids"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Outputs 999 below 8, 1000 at 8 and 1001 above
+    const AROUND_EIGHT: &str = "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,\
+        1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,\
+        999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99";
+
+    fn program(text: &str) -> Vec<i64> {
+        parse_program(text).unwrap()
+    }
+
+    #[test]
+    fn comparisons_work() {
+        let cases = [
+            ("3,9,8,9,10,9,4,9,99,-1,8", [0, 1, 0]),
+            ("3,9,7,9,10,9,4,9,99,-1,8", [1, 0, 0]),
+            ("3,3,1108,-1,8,3,4,3,99", [0, 1, 0]),
+            ("3,3,1107,-1,8,3,4,3,99", [1, 0, 0]),
+        ];
+        for (text, expected) in cases {
+            for (input, want) in [7, 8, 9].into_iter().zip(expected) {
+                assert_eq!(run(&program(text), input), Ok(vec![want]), "{}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn jumps_work() {
+        for text in [
+            "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9",
+            "3,3,1105,-1,9,1101,0,0,12,4,12,99,1",
+        ] {
+            assert_eq!(run(&program(text), 0), Ok(vec![0]));
+            assert_eq!(run(&program(text), -5), Ok(vec![1]));
+        }
+    }
+
+    #[test]
+    fn larger_example_works() {
+        let p = program(AROUND_EIGHT);
+        assert_eq!(run(&p, 7), Ok(vec![999]));
+        assert_eq!(run(&p, 8), Ok(vec![1000]));
+        assert_eq!(run(&p, 9), Ok(vec![1001]));
+    }
+
+    #[test]
+    fn diagnose_checks_tests() {
+        // outputs 0, 0 then the input times 3
+        let passing = program("3,13,4,14,4,14,1002,13,3,13,4,13,99,0,0");
+        assert_eq!(diagnose(&passing, 5), Ok(15));
+
+        // outputs 2 before the code
+        let failing = program("104,0,104,2,104,7,99");
+        assert_eq!(
+            diagnose(&failing, 1),
+            Err(Day5Error::FailedTest { index: 1, value: 2 })
+        );
+        assert_eq!(diagnose(&program("99"), 1), Err(Day5Error::NoOutput));
+    }
+
+    #[test]
+    fn load_program_reports_unreadable_file() {
+        assert!(matches!(
+            load_program("src/no_such_file.txt"),
+            Err(Day5Error::Io { filename, .. }) if filename == "src/no_such_file.txt"
+        ));
+    }
+}
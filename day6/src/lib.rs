@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use shared::graph::Graph;
+use shared::solution::{Answer, Solution, answer};
+
+/// Object every other object ultimately orbits
+pub const CENTRE_OF_MASS: &str = "COM";
+
+/// Reasons an orbit map can't be read or answered
+#[derive(Debug, PartialEq)]
+pub enum Day6Error {
+    /// a line isn't of the form `A)B`
+    Malformed { line: usize, content: String },
+    /// an object is listed as orbiting two others
+    TwoCentres {
+        object: String,
+        first: String,
+        second: String,
+    },
+    /// an object the puzzle needs isn't in the map
+    Missing(String),
+    /// no chain of orbits joins two objects
+    Unreachable { from: String, to: String },
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day6Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day6Error::Malformed { line, content } => {
+                write!(f, "line {}: expected `A)B`, got {:?}", line, content)
+            }
+            Day6Error::TwoCentres {
+                object,
+                first,
+                second,
+            } => write!(f, "{} orbits both {} and {}", object, first, second),
+            Day6Error::Missing(object) => write!(f, "{} is not in the map", object),
+            Day6Error::Unreachable { from, to } => {
+                write!(f, "no orbits lead from {} to {}", from, to)
+            }
+            Day6Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day6Error {}
+
+/// Local orbit map, each object knowing what it directly orbits
+#[derive(Debug)]
+pub struct OrbitMap {
+    /// object to the object it directly orbits
+    centres: HashMap<String, String>,
+    /// orbits as undirected edges
+    graph: Graph<String>,
+}
+
+impl OrbitMap {
+    /// Constructor for an OrbitMap
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - orbits, one `A)B` per line meaning B orbits A
+    ///
+    /// # Returns
+    ///
+    /// * map of every orbit, or the first line that can't be used
+    ///
+    pub fn new<S: AsRef<str>>(lines: &[S]) -> Result<OrbitMap, Day6Error> {
+        let mut centres = HashMap::new();
+        let mut graph = Graph::new();
+        for (i, line) in lines.iter().enumerate() {
+            let line = line.as_ref();
+            let (centre, object) = line
+                .trim()
+                .split_once(')')
+                .filter(|(a, b)| !a.is_empty() && !b.is_empty())
+                .ok_or_else(|| Day6Error::Malformed {
+                    line: i + 1,
+                    content: String::from(line),
+                })?;
+            if let Some(first) = centres.insert(String::from(object), String::from(centre)) {
+                return Err(Day6Error::TwoCentres {
+                    object: String::from(object),
+                    first,
+                    second: String::from(centre),
+                });
+            }
+            graph.add_undirected_edge(String::from(centre), String::from(object));
+        }
+        Ok(OrbitMap { centres, graph })
+    }
+
+    /// Object that `object` directly orbits, if any
+    pub fn centre(&self, object: &str) -> Option<&str> {
+        self.centres.get(object).map(|s| s.as_str())
+    }
+
+    /// Total number of direct and indirect orbits
+    ///
+    /// Each object's orbit count is its distance from the centre of mass.
+    ///
+    /// # Returns
+    ///
+    /// * sum of every object's orbit count
+    ///
+    pub fn orbit_count(&self) -> Result<usize, Day6Error> {
+        let com = String::from(CENTRE_OF_MASS);
+        if !self.graph.contains(&com) {
+            return Err(Day6Error::Missing(com));
+        }
+        Ok(self.graph.distances_from(&com).values().sum())
+    }
+
+    /// Orbital transfers needed to move from what `from` orbits to what
+    /// `to` orbits
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - object doing the moving
+    /// * `to` - object whose centre is the destination
+    ///
+    /// # Returns
+    ///
+    /// * fewest transfers, or an error if either object or a route is
+    ///   missing
+    ///
+    pub fn transfers(&self, from: &str, to: &str) -> Result<usize, Day6Error> {
+        let start = self
+            .centre(from)
+            .ok_or_else(|| Day6Error::Missing(String::from(from)))?;
+        let goal = self
+            .centre(to)
+            .ok_or_else(|| Day6Error::Missing(String::from(to)))?;
+        self.graph
+            .distance(&String::from(start), &String::from(goal))
+            .ok_or_else(|| Day6Error::Unreachable {
+                from: String::from(from),
+                to: String::from(to),
+            })
+    }
+}
+
+/// Reads the lines of the puzzle input
+///
+/// # Arguments
+///
+/// * `filename` - path of the input
+///
+fn read_input(filename: &str) -> Result<Vec<String>, Day6Error> {
+    shared::try_ingest_file(filename).map_err(|err| Day6Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * total number of direct and indirect orbits
+///
+pub fn part1(filename: &str) -> Result<usize, Day6Error> {
+    OrbitMap::new(&read_input(filename)?)?.orbit_count()
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * orbital transfers between the objects YOU and SAN orbit
+///
+pub fn part2(filename: &str) -> Result<usize, Day6Error> {
+    OrbitMap::new(&read_input(filename)?)?.transfers("YOU", "SAN")
+}
+
+/// Day 6 as driven by the workspace runner
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Universal Orbit Map"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_works() {
+        assert_eq!(part1("src/test.txt"), Ok(42));
+    }
+
+    #[test]
+    fn part2_works() {
+        assert_eq!(part2("src/test2.txt"), Ok(4));
+        assert_eq!(part2("src/test.txt"), Err(Day6Error::Missing("YOU".into())));
+        assert!(matches!(
+            part2("src/no_such_file.txt"),
+            Err(Day6Error::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn new_rejects_bad_maps() {
+        assert_eq!(
+            OrbitMap::new(&["COM)A", "A-B"]).unwrap_err(),
+            Day6Error::Malformed {
+                line: 2,
+                content: "A-B".into()
+            }
+        );
+        assert_eq!(
+            OrbitMap::new(&["COM)A", "COM)B", "A)C", "B)C"]).unwrap_err(),
+            Day6Error::TwoCentres {
+                object: "C".into(),
+                first: "A".into(),
+                second: "B".into()
+            }
+        );
+    }
+
+    #[test]
+    fn transfers_between_separate_systems() {
+        let map = OrbitMap::new(&["COM)A", "A)YOU", "X)B", "B)SAN"]).unwrap();
+        assert_eq!(map.orbit_count(), Ok(3));
+        assert_eq!(
+            map.transfers("YOU", "SAN"),
+            Err(Day6Error::Unreachable {
+                from: "YOU".into(),
+                to: "SAN".into()
+            })
+        );
+    }
+}
@@ -0,0 +1,322 @@
+use std::fmt;
+
+use shared::intcode::{IntcodeError, State, Vm, parse_program};
+use shared::solution::{Answer, Solution, answer};
+
+/// Reasons an amplifier chain can't produce a thruster signal
+#[derive(Debug, PartialEq)]
+pub enum Day7Error {
+    /// an amplifier's program faulted
+    Intcode(IntcodeError),
+    /// an amplifier finished without passing a signal on
+    NoOutput { amplifier: usize },
+    /// every amplifier is waiting on input nobody will send
+    Deadlock,
+    /// there are no phase settings to try
+    NoPhases,
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day7Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day7Error::Intcode(err) => write!(f, "{}", err),
+            Day7Error::NoOutput { amplifier } => {
+                write!(f, "amplifier {} produced no signal", amplifier)
+            }
+            Day7Error::Deadlock => write!(f, "amplifiers are all waiting on input"),
+            Day7Error::NoPhases => write!(f, "no phase settings to try"),
+            Day7Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day7Error {}
+
+impl From<IntcodeError> for Day7Error {
+    fn from(err: IntcodeError) -> Self {
+        Day7Error::Intcode(err)
+    }
+}
+
+/// Phase settings giving the strongest signal
+#[derive(Debug, PartialEq)]
+pub struct Best {
+    pub phases: Vec<i64>,
+    pub signal: i64,
+}
+
+/// Every ordering of `items`, generated with Heap's algorithm
+///
+/// # Arguments
+///
+/// * `items` - values to arrange
+///
+/// # Returns
+///
+/// * `items.len()!` orderings
+///
+pub fn permutations(items: &[i64]) -> Vec<Vec<i64>> {
+    let mut items = items.to_vec();
+    let mut counters = vec![0; items.len()];
+    let mut result = vec![items.clone()];
+    let mut i = 1;
+    while i < items.len() {
+        if counters[i] < i {
+            let j = if i % 2 == 0 { 0 } else { counters[i] };
+            items.swap(j, i);
+            result.push(items.clone());
+            counters[i] += 1;
+            i = 1;
+        } else {
+            counters[i] = 0;
+            i += 1;
+        }
+    }
+    result
+}
+
+/// Boots one amplifier per phase setting
+fn boot(program: &[i64], phases: &[i64]) -> Vec<Vm> {
+    phases
+        .iter()
+        .map(|&phase| {
+            let mut vm = Vm::new(program.to_vec());
+            vm.push_input(phase);
+            vm
+        })
+        .collect()
+}
+
+/// Runs the amplifiers in series, each feeding the next once
+///
+/// # Arguments
+///
+/// * `program` - amplifier controller software
+/// * `phases` - phase setting of each amplifier in order
+///
+/// # Returns
+///
+/// * signal out of the last amplifier
+///
+pub fn amplify(program: &[i64], phases: &[i64]) -> Result<i64, Day7Error> {
+    let mut signal = 0;
+    for (amplifier, mut vm) in boot(program, phases).into_iter().enumerate() {
+        vm.push_input(signal);
+        vm.run()?;
+        signal = *vm
+            .output()
+            .last()
+            .ok_or(Day7Error::NoOutput { amplifier })?;
+    }
+    Ok(signal)
+}
+
+/// Runs the amplifiers in a loop, the last feeding back into the first,
+/// until the last one halts
+///
+/// Each amplifier runs until it blocks on input, then passes whatever it
+/// produced along to the next.
+///
+/// # Arguments
+///
+/// * `program` - amplifier controller software
+/// * `phases` - phase setting of each amplifier in order
+///
+/// # Returns
+///
+/// * last signal out of the final amplifier
+///
+pub fn feedback(program: &[i64], phases: &[i64]) -> Result<i64, Day7Error> {
+    let mut amps = boot(program, phases);
+    let count = amps.len();
+    let last = count.checked_sub(1).ok_or(Day7Error::NoPhases)?;
+    amps[0].push_input(0);
+    let mut signal = None;
+    loop {
+        let mut progressed = false;
+        for i in 0..count {
+            let state = amps[i].run_until_blocked()?;
+            let output = amps[i].take_output();
+            progressed |= !output.is_empty();
+            if i == last {
+                signal = output.last().copied().or(signal);
+                if state == State::Halted {
+                    return signal.ok_or(Day7Error::NoOutput { amplifier: last });
+                }
+            }
+            for value in output {
+                amps[(i + 1) % count].push_input(value);
+            }
+        }
+        if !progressed {
+            return Err(Day7Error::Deadlock);
+        }
+    }
+}
+
+/// Tries every ordering of the phase settings
+///
+/// # Arguments
+///
+/// * `program` - amplifier controller software
+/// * `phases` - phase settings to arrange
+/// * `chain` - how to run the amplifiers, `amplify` or `feedback`
+///
+/// # Returns
+///
+/// * ordering giving the strongest signal, ties going to the first tried
+///
+pub fn best_phases<F>(program: &[i64], phases: &[i64], chain: F) -> Result<Best, Day7Error>
+where
+    F: Fn(&[i64], &[i64]) -> Result<i64, Day7Error>,
+{
+    let mut best: Option<Best> = None;
+    for order in permutations(phases) {
+        let signal = chain(program, &order)?;
+        if best.as_ref().is_none_or(|b| signal > b.signal) {
+            best = Some(Best {
+                phases: order,
+                signal,
+            });
+        }
+    }
+    best.ok_or(Day7Error::NoPhases)
+}
+
+/// Reads a program from the first line of a file
+///
+/// # Arguments
+///
+/// * `filename` - path of file containing the intcode
+///
+pub fn load_program(filename: &str) -> Result<Vec<i64>, Day7Error> {
+    let input = shared::try_ingest_file(filename).map_err(|err| Day7Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })?;
+    Ok(parse_program(input.first().map_or("", |s| s))?)
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * highest signal from amplifiers in series with phases 0 to 4
+///
+pub fn part1(filename: &str) -> Result<i64, Day7Error> {
+    Ok(best_phases(&load_program(filename)?, &[0, 1, 2, 3, 4], amplify)?.signal)
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * highest signal from amplifiers in a feedback loop with phases 5 to 9
+///
+pub fn part2(filename: &str) -> Result<i64, Day7Error> {
+    Ok(best_phases(&load_program(filename)?, &[5, 6, 7, 8, 9], feedback)?.signal)
+}
+
+/// Day 7 as driven by the workspace runner
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "Amplification Circuit"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program(text: &str) -> Vec<i64> {
+        parse_program(text).unwrap()
+    }
+
+    #[test]
+    fn permutations_works() {
+        let mut perms = permutations(&[1, 2, 3]);
+        assert_eq!(perms.len(), 6);
+        perms.sort();
+        perms.dedup();
+        assert_eq!(perms.len(), 6);
+        assert_eq!(permutations(&[]), vec![Vec::<i64>::new()]);
+    }
+
+    #[test]
+    fn amplify_examples() {
+        let cases = [
+            (
+                "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0",
+                vec![4, 3, 2, 1, 0],
+                43210,
+            ),
+            (
+                "3,23,3,24,1002,24,10,24,1002,23,-1,23,101,5,23,23,1,24,23,23,4,23,99,0,0",
+                vec![0, 1, 2, 3, 4],
+                54321,
+            ),
+            (
+                "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,33,7,33,1,33,31,31,\
+                 1,32,31,31,4,31,99,0,0,0",
+                vec![1, 0, 4, 3, 2],
+                65210,
+            ),
+        ];
+        for (text, phases, signal) in cases {
+            assert_eq!(
+                best_phases(&program(text), &[0, 1, 2, 3, 4], amplify),
+                Ok(Best { phases, signal })
+            );
+        }
+    }
+
+    #[test]
+    fn feedback_examples() {
+        let cases = [
+            (
+                "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,\
+                 99,0,0,5",
+                vec![9, 8, 7, 6, 5],
+                139629729,
+            ),
+            (
+                "3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,-5,54,\
+                 1105,1,12,1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,53,1001,56,-1,\
+                 56,1005,56,6,99,0,0,0,0,10",
+                vec![9, 7, 8, 5, 6],
+                18216,
+            ),
+        ];
+        for (text, phases, signal) in cases {
+            assert_eq!(feedback(&program(text), &phases), Ok(signal));
+            assert_eq!(
+                best_phases(&program(text), &[5, 6, 7, 8, 9], feedback),
+                Ok(Best { phases, signal })
+            );
+        }
+    }
+
+    #[test]
+    fn feedback_reports_deadlock() {
+        // reads the phase then waits for a second input forever
+        let p = program("3,9,3,9,3,9,4,9,99,0");
+        assert_eq!(feedback(&p, &[1, 2]), Err(Day7Error::Deadlock));
+        assert_eq!(feedback(&p, &[]), Err(Day7Error::NoPhases));
+    }
+}
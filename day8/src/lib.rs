@@ -0,0 +1,256 @@
+use std::fmt;
+
+use shared::grid::Grid;
+use shared::ocr;
+use shared::solution::{Answer, Solution, answer};
+
+/// Width of the password image in pixels
+pub const WIDTH: usize = 25;
+/// Height of the password image in pixels
+pub const HEIGHT: usize = 6;
+
+const WHITE: u8 = 1;
+const TRANSPARENT: u8 = 2;
+
+/// Reasons an image can't be decoded
+#[derive(Debug, PartialEq)]
+pub enum Day8Error {
+    /// a pixel isn't a digit
+    NotADigit { index: usize, c: char },
+    /// the data doesn't fill a whole number of layers
+    PartialLayer { pixels: usize, layer_size: usize },
+    /// the image has no layers
+    Empty,
+    /// the rendered message isn't made of known letters
+    Unreadable(Grid<bool>),
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day8Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day8Error::NotADigit { index, c } => {
+                write!(f, "pixel {} ({:?}) is not a digit", index, c)
+            }
+            Day8Error::PartialLayer { pixels, layer_size } => {
+                write!(f, "{} pixels don't fill layers of {}", pixels, layer_size)
+            }
+            Day8Error::Empty => write!(f, "image has no layers"),
+            Day8Error::Unreadable(grid) => write!(f, "can't read message:\n{}", grid),
+            Day8Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day8Error {}
+
+/// Image in the Space Image Format, stored as layers of pixels
+#[derive(Debug, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    layers: Vec<Vec<u8>>,
+}
+
+impl Image {
+    /// Constructor for an Image
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - one digit per pixel, layer after layer
+    /// * `width` - pixels per row
+    /// * `height` - rows per layer
+    ///
+    /// # Returns
+    ///
+    /// * image, or an error if the data isn't whole layers of digits
+    ///
+    pub fn new(data: &str, width: usize, height: usize) -> Result<Image, Day8Error> {
+        let pixels = data
+            .trim()
+            .chars()
+            .enumerate()
+            .map(|(index, c)| {
+                c.to_digit(10)
+                    .map(|d| d as u8)
+                    .ok_or(Day8Error::NotADigit { index, c })
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        let layer_size = width * height;
+        if pixels.is_empty() {
+            return Err(Day8Error::Empty);
+        }
+        if layer_size == 0 || !pixels.len().is_multiple_of(layer_size) {
+            return Err(Day8Error::PartialLayer {
+                pixels: pixels.len(),
+                layer_size,
+            });
+        }
+        Ok(Image {
+            width,
+            height,
+            layers: pixels.chunks(layer_size).map(|l| l.to_vec()).collect(),
+        })
+    }
+
+    /// Layers of the image, front first
+    pub fn layers(&self) -> &[Vec<u8>] {
+        &self.layers
+    }
+
+    /// Checks the image wasn't corrupted in transit
+    ///
+    /// # Returns
+    ///
+    /// * number of 1 digits times number of 2 digits on the layer with the
+    ///   fewest 0 digits
+    ///
+    pub fn checksum(&self) -> usize {
+        let count = |layer: &[u8], digit: u8| layer.iter().filter(|&&d| d == digit).count();
+        self.layers
+            .iter()
+            .min_by_key(|layer| count(layer, 0))
+            .map_or(0, |layer| count(layer, 1) * count(layer, 2))
+    }
+
+    /// Stacks the layers, each pixel taking its colour from the frontmost
+    /// layer that isn't transparent there
+    ///
+    /// # Returns
+    ///
+    /// * colour of each pixel, transparent where every layer is
+    ///
+    pub fn composite(&self) -> Grid<u8> {
+        let mut grid = Grid::new(self.width, self.height, TRANSPARENT);
+        for layer in self.layers.iter().rev() {
+            for (i, &pixel) in layer.iter().enumerate() {
+                if pixel != TRANSPARENT {
+                    grid.set(i % self.width, i / self.width, pixel);
+                }
+            }
+        }
+        grid
+    }
+
+    /// Composited image with white pixels lit
+    pub fn render(&self) -> Grid<bool> {
+        self.composite().map(|&p| p == WHITE)
+    }
+}
+
+/// Reads the password image from the first line of a file
+///
+/// # Arguments
+///
+/// * `filename` - path of file containing the image data
+///
+pub fn load_image(filename: &str) -> Result<Image, Day8Error> {
+    let input = shared::try_ingest_file(filename).map_err(|err| Day8Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })?;
+    Image::new(input.first().map_or("", |s| s), WIDTH, HEIGHT)
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * image checksum
+///
+pub fn part1(filename: &str) -> Result<usize, Day8Error> {
+    Ok(load_image(filename)?.checksum())
+}
+
+/// Decodes the rendered message
+///
+/// # Arguments
+///
+/// * `message` - rendered password image
+///
+/// # Returns
+///
+/// * letters of the message, or the grid itself if it can't be read
+///
+pub fn read_message(message: Grid<bool>) -> Result<String, Day8Error> {
+    ocr::read(&message).ok_or(Day8Error::Unreadable(message))
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * message shown by the decoded image
+///
+pub fn part2(filename: &str) -> Result<String, Day8Error> {
+    read_message(load_image(filename)?.render())
+}
+
+/// Day 8 as driven by the workspace runner
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Space Image Format"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksum_works() {
+        let image = Image::new("123456789012", 3, 2).unwrap();
+        assert_eq!(image.layers().len(), 2);
+        assert_eq!(image.checksum(), 1);
+
+        let image = Image::new("001122102201", 3, 2).unwrap();
+        assert_eq!(image.checksum(), 4);
+    }
+
+    #[test]
+    fn composite_works() {
+        let image = Image::new("0222112222120000", 2, 2).unwrap();
+        assert_eq!(
+            image.composite(),
+            Grid::from_cells(2, vec![0, 1, 1, 0]).unwrap()
+        );
+        assert_eq!(image.render().to_string(), " #\n#\n");
+    }
+
+    #[test]
+    fn new_rejects_bad_data() {
+        assert_eq!(
+            Image::new("12a4", 2, 1),
+            Err(Day8Error::NotADigit { index: 2, c: 'a' })
+        );
+        assert_eq!(
+            Image::new("12345", 2, 2),
+            Err(Day8Error::PartialLayer {
+                pixels: 5,
+                layer_size: 4
+            })
+        );
+        assert_eq!(Image::new("", 2, 2), Err(Day8Error::Empty));
+    }
+
+    #[test]
+    fn part2_works() {
+        // a transparent layer over "HI" drawn in white on black
+        assert_eq!(part2("src/test.txt"), Ok(String::from("HI")));
+    }
+}
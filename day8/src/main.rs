@@ -0,0 +1,47 @@
+use std::process::ExitCode;
+
+use clap::Parser;
+use day8::{load_image, read_message};
+use shared::args::DayArgs;
+
+/// Space Image Format decoder for the Mars rover password
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: DayArgs,
+    /// also draw the decoded image
+    #[arg(long)]
+    show: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let image = match load_image(&cli.args.input) {
+        Ok(image) => image,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if cli.args.wants(1) {
+        println!("Part1: {}", image.checksum());
+    }
+    if !cli.args.wants(2) {
+        return ExitCode::SUCCESS;
+    }
+    let message = image.render();
+    if cli.show {
+        print!("{}", message);
+    }
+    match read_message(message) {
+        Ok(text) => {
+            println!("Part2: {}", text);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Part2: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+
+use shared::intcode::{IntcodeError, Vm, parse_program};
+use shared::solution::{Answer, Solution, answer};
+
+/// Input running BOOST in test mode
+pub const TEST_MODE: i64 = 1;
+/// Input running BOOST in sensor boost mode
+pub const SENSOR_BOOST: i64 = 2;
+
+/// Reasons a BOOST run can't produce an answer
+#[derive(Debug, PartialEq)]
+pub enum Day9Error {
+    /// the program faulted
+    Intcode(IntcodeError),
+    /// BOOST reported opcodes that ran incorrectly instead of one answer
+    Malfunction(Vec<i64>),
+    /// the program halted without any output
+    NoOutput,
+    /// the input file couldn't be read
+    Io { filename: String, message: String },
+}
+
+impl fmt::Display for Day9Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Day9Error::Intcode(err) => write!(f, "{}", err),
+            Day9Error::Malfunction(opcodes) => {
+                write!(f, "BOOST reported malfunctioning opcodes {:?}", opcodes)
+            }
+            Day9Error::NoOutput => write!(f, "program produced no output"),
+            Day9Error::Io { filename, message } => write!(f, "{}: {}", filename, message),
+        }
+    }
+}
+
+impl std::error::Error for Day9Error {}
+
+impl From<IntcodeError> for Day9Error {
+    fn from(err: IntcodeError) -> Self {
+        Day9Error::Intcode(err)
+    }
+}
+
+/// Runs a program with the given inputs and returns everything it outputs
+///
+/// # Arguments
+///
+/// * `program` - intcode to run, left untouched
+/// * `input` - values fed to input instructions in order
+///
+/// # Returns
+///
+/// * output values in order, or the fault that stopped the program
+///
+pub fn run(program: &[i64], input: &[i64]) -> Result<Vec<i64>, IntcodeError> {
+    let mut vm = Vm::new(program.to_vec());
+    input.iter().for_each(|&v| vm.push_input(v));
+    vm.run()?;
+    Ok(vm.take_output())
+}
+
+/// Runs the Basic Operation Of System Test
+///
+/// # Arguments
+///
+/// * `program` - BOOST program
+/// * `mode` - `TEST_MODE` or `SENSOR_BOOST`
+///
+/// # Returns
+///
+/// * the single value BOOST outputs when every opcode works
+///
+pub fn boost(program: &[i64], mode: i64) -> Result<i64, Day9Error> {
+    match run(program, &[mode])?.as_slice() {
+        [] => Err(Day9Error::NoOutput),
+        [answer] => Ok(*answer),
+        [opcodes @ .., _] => Err(Day9Error::Malfunction(opcodes.to_vec())),
+    }
+}
+
+/// Reads a program from the first line of a file
+///
+/// # Arguments
+///
+/// * `filename` - path of file containing the intcode
+///
+pub fn load_program(filename: &str) -> Result<Vec<i64>, Day9Error> {
+    let input = shared::try_ingest_file(filename).map_err(|err| Day9Error::Io {
+        filename: String::from(filename),
+        message: err.to_string(),
+    })?;
+    Ok(parse_program(input.first().map_or("", |s| s))?)
+}
+
+/// Performs all parts necessary for part1
+///
+/// # Returns
+///
+/// * BOOST keycode from test mode
+///
+pub fn part1(filename: &str) -> Result<i64, Day9Error> {
+    boost(&load_program(filename)?, TEST_MODE)
+}
+
+/// Performs all parts necessary for part2
+///
+/// # Returns
+///
+/// * coordinates of the distress signal from sensor boost mode
+///
+pub fn part2(filename: &str) -> Result<i64, Day9Error> {
+    boost(&load_program(filename)?, SENSOR_BOOST)
+}
+
+/// Day 9 as driven by the workspace runner
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn title(&self) -> &'static str {
+        "Sensor Boost"
+    }
+
+    fn part1(&self, filename: &str) -> Answer {
+        answer(part1(filename))
+    }
+
+    fn part2(&self, filename: &str) -> Answer {
+        answer(part2(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program(text: &str) -> Vec<i64> {
+        parse_program(text).unwrap()
+    }
+
+    #[test]
+    fn quine_works() {
+        let p = program("109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99");
+        assert_eq!(run(&p, &[]), Ok(p.clone()));
+    }
+
+    #[test]
+    fn large_numbers_work() {
+        let p = program("1102,34915192,34915192,7,4,7,99,0");
+        assert_eq!(run(&p, &[]), Ok(vec![1219070632396864]));
+        let p = program("104,1125899906842624,99");
+        assert_eq!(run(&p, &[]), Ok(vec![1125899906842624]));
+    }
+
+    #[test]
+    fn boost_checks_output() {
+        // echoes its input
+        assert_eq!(boost(&program("3,0,4,0,99"), SENSOR_BOOST), Ok(2));
+        assert_eq!(
+            boost(&program("104,203,104,9,104,0,99"), TEST_MODE),
+            Err(Day9Error::Malfunction(vec![203, 9]))
+        );
+        assert_eq!(boost(&program("99"), TEST_MODE), Err(Day9Error::NoOutput));
+    }
+}
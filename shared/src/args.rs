@@ -0,0 +1,61 @@
+use clap::Args;
+
+/// Input a day's binary reads unless given `--input`, relative to the
+/// day's crate
+pub const DEFAULT_INPUT: &str = "src/input.txt";
+
+/// Flags every day's binary takes, flattened into its own `Cli` with
+/// `#[command(flatten)]`
+#[derive(Args, Debug, PartialEq)]
+pub struct DayArgs {
+    /// file holding the puzzle input
+    #[arg(long, default_value = DEFAULT_INPUT)]
+    pub input: String,
+    /// only print the answer to this part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+}
+
+impl DayArgs {
+    /// Whether a part should be run, every part unless `--part` picked one
+    ///
+    /// # Arguments
+    ///
+    /// * `part` - 1 or 2
+    ///
+    pub fn wants(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: DayArgs,
+    }
+
+    fn parse(argv: &[&str]) -> Result<DayArgs, clap::Error> {
+        Cli::try_parse_from(["day"].iter().chain(argv)).map(|cli| cli.args)
+    }
+
+    #[test]
+    fn defaults_to_crate_input_and_every_part() {
+        let args = parse(&[]).unwrap();
+        assert_eq!(args.input, DEFAULT_INPUT);
+        assert!(args.wants(1) && args.wants(2));
+    }
+
+    #[test]
+    fn part_picks_one() {
+        let args = parse(&["--input", "x.txt", "--part", "2"]).unwrap();
+        assert_eq!(args.input, "x.txt");
+        assert!(!args.wants(1) && args.wants(2));
+        assert!(parse(&["--part", "3"]).is_err());
+    }
+}
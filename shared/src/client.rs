@@ -0,0 +1,399 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use ureq::Agent;
+
+/// Site the puzzles are served from
+pub const BASE_URL: &str = "https://adventofcode.com";
+/// Sent with every request so the site knows who is asking
+pub const USER_AGENT: &str = "github.com/burlap101/aoc2019-rust";
+
+/// Reasons talking to the puzzle site failed
+#[derive(Debug)]
+pub enum ClientError {
+    /// there is no session token to identify the user with
+    NoSession,
+    /// the site answered with an error status
+    Status { status: u16, body: String },
+    /// the site couldn't be reached
+    Transport(String),
+    /// a downloaded input couldn't be saved
+    Io(io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::NoSession => write!(f, "no session token set"),
+            ClientError::Status { status, body } => {
+                write!(f, "site answered {}: {}", status, body.trim())
+            }
+            ClientError::Transport(err) => write!(f, "{}", err),
+            ClientError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<ureq::Error> for ClientError {
+    fn from(err: ureq::Error) -> Self {
+        ClientError::Transport(err.to_string())
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(err: io::Error) -> Self {
+        ClientError::Io(err)
+    }
+}
+
+/// What the site made of a submitted answer
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    /// the answer is right
+    Correct,
+    /// the answer is wrong, perhaps with a hint of `too high` or `too low`
+    Incorrect { hint: Option<String> },
+    /// another answer went in too recently, with how long is left to wait
+    TooSoon { wait: Option<String> },
+    /// the part was already solved, so the answer wasn't checked
+    AlreadySolved,
+    /// the reply didn't match anything known, kept as text
+    Unrecognised(String),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect { hint: Some(hint) } => write!(f, "incorrect, {}", hint),
+            Verdict::Incorrect { hint: None } => write!(f, "incorrect"),
+            Verdict::TooSoon { wait: Some(wait) } => write!(f, "too soon, {} left to wait", wait),
+            Verdict::TooSoon { wait: None } => write!(f, "too soon"),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::Unrecognised(text) => write!(f, "unrecognised reply: {}", text),
+        }
+    }
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page the site answers a submission
+    /// with
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - page returned by the site
+    ///
+    /// # Returns
+    ///
+    /// * verdict, falling back to the page's text if it isn't recognised
+    ///
+    pub fn parse(html: &str) -> Verdict {
+        let article = html
+            .split_once("<article>")
+            .and_then(|(_, rest)| rest.split_once("</article>"))
+            .map_or(html, |(article, _)| article);
+        let text = strip_tags(article);
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("That's not the right answer") {
+            let hint = ["too high", "too low"]
+                .into_iter()
+                .find(|h| text.contains(&format!("your answer is {}", h)))
+                .map(String::from);
+            Verdict::Incorrect { hint }
+        } else if text.contains("You gave an answer too recently") {
+            let wait = text
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| String::from(wait));
+            Verdict::TooSoon { wait }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unrecognised(text)
+        }
+    }
+}
+
+/// Drops markup from html, squeezing the remaining whitespace
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Logged in connection to the puzzle site for one year's calendar
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    year: u16,
+    session: String,
+}
+
+impl Client {
+    /// Constructor for a Client
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - value of the site's `session` cookie
+    /// * `year` - year of the calendar
+    ///
+    /// # Returns
+    ///
+    /// * client talking to the real site, or an error if the session is
+    ///   blank
+    ///
+    pub fn new(session: &str, year: u16) -> Result<Client, ClientError> {
+        let session = session.trim();
+        if session.is_empty() {
+            return Err(ClientError::NoSession);
+        }
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .into();
+        Ok(Client {
+            agent,
+            base_url: String::from(BASE_URL),
+            year,
+            session: String::from(session),
+        })
+    }
+
+    /// Points the client at another server, such as a local test one
+    pub fn with_base_url(mut self, base_url: &str) -> Client {
+        self.base_url = String::from(base_url.trim_end_matches('/'));
+        self
+    }
+
+    /// Address of a page for one day
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day)
+    }
+
+    /// Fetches the puzzle input for a day
+    ///
+    /// # Arguments
+    ///
+    /// * `day` - day of the calendar
+    ///
+    /// # Returns
+    ///
+    /// * input exactly as served, or why it couldn't be fetched
+    ///
+    pub fn input(&self, day: u8) -> Result<String, ClientError> {
+        let mut response = self
+            .agent
+            .get(format!("{}/input", self.day_url(day)))
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .call()?;
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string()?;
+        if status != 200 {
+            return Err(ClientError::Status { status, body });
+        }
+        Ok(body)
+    }
+
+    /// Submits an answer for one part of a day
+    ///
+    /// # Arguments
+    ///
+    /// * `day` - day of the calendar
+    /// * `part` - 1 or 2
+    /// * `answer` - answer as the site expects it
+    ///
+    /// # Returns
+    ///
+    /// * what the site made of the answer, or why it couldn't be sent
+    ///
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, ClientError> {
+        let mut response = self
+            .agent
+            .post(format!("{}/answer", self.day_url(day)))
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])?;
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string()?;
+        if status != 200 {
+            return Err(ClientError::Status { status, body });
+        }
+        Ok(Verdict::parse(&body))
+    }
+}
+
+/// Where a day's input is cached
+pub fn cached_input(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{}.txt", day))
+}
+
+/// Downloads a day's input into a cache directory
+///
+/// # Arguments
+///
+/// * `client` - logged in client
+/// * `day` - day of the calendar
+/// * `dir` - cache directory, created if missing
+///
+/// # Returns
+///
+/// * path of the saved input, or why it couldn't be fetched or saved
+///
+pub fn download(client: &Client, day: u8, dir: &Path) -> Result<PathBuf, ClientError> {
+    let input = client.input(day)?;
+    fs::create_dir_all(dir)?;
+    let path = cached_input(dir, day);
+    fs::write(&path, input)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    use super::*;
+
+    /// Serves one canned response and hands back the request it answered,
+    /// its body last
+    fn serve_once(status: &str, body: &str) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut request = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                request.push(String::from(line));
+            }
+            let length = request
+                .iter()
+                .find_map(|l| {
+                    l.to_ascii_lowercase()
+                        .strip_prefix("content-length: ")?
+                        .parse()
+                        .ok()
+                })
+                .unwrap_or(0);
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push(String::from_utf8(body).unwrap());
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn new_needs_session() {
+        assert!(matches!(
+            Client::new("  ", 2019),
+            Err(ClientError::NoSession)
+        ));
+    }
+
+    #[test]
+    fn download_saves_input() {
+        let (url, server) = serve_once("200 OK", "1,2,3\n");
+        let client = Client::new("abc123", 2019).unwrap().with_base_url(&url);
+        let dir = std::env::temp_dir().join(format!("shared-client-{}", std::process::id()));
+        let path = download(&client, 9, &dir).unwrap();
+        assert_eq!(path, cached_input(&dir, 9));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1,2,3\n");
+        fs::remove_dir_all(&dir).unwrap();
+
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /2019/day/9/input HTTP/1.1");
+        assert!(
+            request
+                .iter()
+                .any(|l| l.eq_ignore_ascii_case("cookie: session=abc123"))
+        );
+    }
+
+    #[test]
+    fn input_reports_status() {
+        let (url, server) = serve_once("400 Bad Request", "Please log in.\n");
+        let client = Client::new("expired", 2019).unwrap().with_base_url(&url);
+        match client.input(1) {
+            Err(ClientError::Status { status, body }) => {
+                assert_eq!(status, 400);
+                assert_eq!(body, "Please log in.\n");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submit_posts_answer() {
+        let page = "<html><main><article><p>That's the right answer! \
+                    You are <em>one gold star</em> closer.</p></article></main></html>";
+        let (url, server) = serve_once("200 OK", page);
+        let client = Client::new("abc123", 2019).unwrap().with_base_url(&url);
+        assert_eq!(client.submit(2, 2, "6979").unwrap(), Verdict::Correct);
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "POST /2019/day/2/answer HTTP/1.1");
+        assert_eq!(request.last().unwrap(), "level=2&answer=6979");
+    }
+
+    #[test]
+    fn verdict_parses_replies() {
+        let article = |text: &str| format!("<main><article><p>{}</p></article></main>", text);
+        assert_eq!(
+            Verdict::parse(&article(
+                "That's not the right answer; your answer is too high.  If you're stuck, \
+                 <a href=\"/2019/day/1\">return</a>."
+            )),
+            Verdict::Incorrect {
+                hint: Some(String::from("too high"))
+            }
+        );
+        assert_eq!(
+            Verdict::parse(&article("That's not the right answer.")),
+            Verdict::Incorrect { hint: None }
+        );
+        let too_soon = Verdict::parse(&article(
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have 4m 12s left to wait.",
+        ));
+        assert_eq!(too_soon.to_string(), "too soon, 4m 12s left to wait");
+        assert_eq!(
+            Verdict::parse(&article(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Verdict::AlreadySolved
+        );
+        assert_eq!(
+            Verdict::parse("<p>Something <b>else</b></p>"),
+            Verdict::Unrecognised(String::from("Something else"))
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::pqueue::MinQueue;
+
+/// Graph stored as adjacency lists
+#[derive(Clone, Debug)]
+pub struct Graph<N> {
+    edges: HashMap<N, Vec<N>>,
+}
+
+impl<N: Eq + Hash + Clone> Default for Graph<N> {
+    fn default() -> Self {
+        Graph {
+            edges: HashMap::new(),
+        }
+    }
+}
+
+impl<N: Eq + Hash + Clone> Graph<N> {
+    /// Constructor for an empty Graph
+    pub fn new() -> Self {
+        Graph::default()
+    }
+
+    /// Adds an edge leading from `from` to `to`
+    pub fn add_edge(&mut self, from: N, to: N) {
+        self.edges.entry(to.clone()).or_default();
+        self.edges.entry(from).or_default().push(to);
+    }
+
+    /// Adds edges in both directions between `a` and `b`
+    pub fn add_undirected_edge(&mut self, a: N, b: N) {
+        self.add_edge(a.clone(), b.clone());
+        self.add_edge(b, a);
+    }
+
+    /// Whether `node` appears at either end of any edge
+    pub fn contains(&self, node: &N) -> bool {
+        self.edges.contains_key(node)
+    }
+
+    /// Nodes reachable from `node` over a single edge
+    pub fn neighbours(&self, node: &N) -> &[N] {
+        self.edges.get(node).map_or(&[], |v| v)
+    }
+
+    /// Every node in the graph, in no particular order
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.edges.keys()
+    }
+
+    /// Number of edges from `start` to every node reachable from it
+    pub fn distances_from(&self, start: &N) -> HashMap<N, usize> {
+        bfs(start.clone(), |n| self.neighbours(n).to_vec())
+    }
+
+    /// Fewest edges from `start` to `goal`, or none if it can't be reached
+    pub fn distance(&self, start: &N, goal: &N) -> Option<usize> {
+        bfs_distance(
+            start.clone(),
+            |n| n == goal,
+            |n| self.neighbours(n).to_vec(),
+        )
+    }
+}
+
+/// Breadth first search over an implicit graph
+///
+/// # Arguments
+///
+/// * `start` - node to search from
+/// * `neighbours` - nodes one step away from a node
+///
+/// # Returns
+///
+/// * fewest steps from `start` to every reachable node
+///
+pub fn bfs<N, I, F>(start: N, mut neighbours: F) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+{
+    let mut seen = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let steps = seen[&node] + 1;
+        for next in neighbours(&node) {
+            if !seen.contains_key(&next) {
+                seen.insert(next.clone(), steps);
+                queue.push_back(next);
+            }
+        }
+    }
+    seen
+}
+
+/// Breadth first search that stops at the first node accepted by `is_goal`
+///
+/// # Arguments
+///
+/// * `start` - node to search from
+/// * `is_goal` - whether a node ends the search
+/// * `neighbours` - nodes one step away from a node
+///
+/// # Returns
+///
+/// * fewest steps to a goal, or none if no goal is reachable
+///
+pub fn bfs_distance<N, I, G, F>(start: N, mut is_goal: G, mut neighbours: F) -> Option<usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+    F: FnMut(&N) -> I,
+{
+    let mut seen = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let steps = seen[&node];
+        if is_goal(&node) {
+            return Some(steps);
+        }
+        for next in neighbours(&node) {
+            if !seen.contains_key(&next) {
+                seen.insert(next.clone(), steps + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra's search over an implicit graph with weighted edges
+///
+/// # Arguments
+///
+/// * `start` - node to search from
+/// * `is_goal` - whether a node ends the search
+/// * `neighbours` - nodes one edge away from a node, with the edge's cost
+///
+/// # Returns
+///
+/// * lowest total cost to a goal, or none if no goal is reachable
+///
+pub fn dijkstra<N, I, G, F>(start: N, mut is_goal: G, mut neighbours: F) -> Option<usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = (N, usize)>,
+    G: FnMut(&N) -> bool,
+    F: FnMut(&N) -> I,
+{
+    let mut best = HashMap::from([(start.clone(), 0)]);
+    let mut queue = MinQueue::new();
+    queue.push(0, start);
+    while let Some((cost, node)) = queue.pop() {
+        if best.get(&node).is_some_and(|&b| b < cost) {
+            // a cheaper way here was already expanded
+            continue;
+        }
+        if is_goal(&node) {
+            return Some(cost);
+        }
+        for (next, step) in neighbours(&node) {
+            let total = cost + step;
+            if best.get(&next).is_none_or(|&b| total < b) {
+                best.insert(next.clone(), total);
+                queue.push(total, next);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_distances_work() {
+        let mut g = Graph::new();
+        g.add_edge("a", "b");
+        g.add_edge("b", "c");
+        g.add_edge("a", "d");
+        g.add_edge("e", "a");
+
+        let d = g.distances_from(&"a");
+        assert_eq!(d.len(), 4);
+        assert_eq!(d[&"c"], 2);
+        assert_eq!(g.distance(&"a", &"c"), Some(2));
+        assert_eq!(g.distance(&"c", &"a"), None);
+        assert!(g.contains(&"c"));
+        assert_eq!(g.neighbours(&"c"), &[] as &[&str]);
+    }
+
+    #[test]
+    fn bfs_on_implicit_graph() {
+        // each number leads to its double and its successor
+        let next = |n: &u32| [n * 2, n + 1].into_iter().filter(|&m| m <= 100);
+        assert_eq!(bfs_distance(1, |&n| n == 100, next), Some(8));
+        assert_eq!(bfs(1, next).len(), 100);
+        assert_eq!(bfs_distance(1, |&n| n == 0, next), None);
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheap_edges() {
+        // a -> c directly costs 10, going through b costs 3
+        let edges = |n: &char| match n {
+            'a' => vec![('c', 10), ('b', 1)],
+            'b' => vec![('c', 2)],
+            _ => vec![],
+        };
+        assert_eq!(dijkstra('a', |&n| n == 'c', edges), Some(3));
+        assert_eq!(dijkstra('a', |&n| n == 'a', edges), Some(0));
+        assert_eq!(dijkstra('c', |&n| n == 'a', edges), None);
+    }
+}
@@ -0,0 +1,251 @@
+use std::fmt;
+
+/// Reasons text can't be read into a grid
+#[derive(Debug, PartialEq)]
+pub enum GridError {
+    /// a row's length differs from the first row's
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// a character has no cell value
+    BadCell { row: usize, col: usize, c: char },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} is {} cells wide, expected {}",
+                row, found, expected
+            ),
+            GridError::BadCell { row, col, c } => {
+                write!(f, "unexpected {:?} at row {} column {}", c, row, col)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/// Rectangle of cells addressed by `(x, y)`, with y growing downwards
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Constructor for a Grid with every cell set to `fill`
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - number of columns
+    /// * `height` - number of rows
+    /// * `fill` - initial value of each cell
+    ///
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Constructor for a Grid from cells listed row by row
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - number of columns
+    /// * `cells` - every cell, top row first
+    ///
+    /// # Returns
+    ///
+    /// * grid, or none if `cells` doesn't fill a whole number of rows
+    ///
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Option<Grid<T>> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            return None;
+        }
+        Some(Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    /// Constructor for a Grid from lines of text, one character per cell
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - rows of the grid, top row first
+    /// * `cell` - value of a character, or none if it isn't allowed
+    ///
+    /// # Returns
+    ///
+    /// * grid, or the first ragged row or unknown character
+    ///
+    pub fn parse<S, F>(lines: &[S], mut cell: F) -> Result<Grid<T>, GridError>
+    where
+        S: AsRef<str>,
+        F: FnMut(char) -> Option<T>,
+    {
+        let width = lines.first().map_or(0, |l| l.as_ref().chars().count());
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            let found = line.as_ref().chars().count();
+            if found != width {
+                return Err(GridError::Ragged {
+                    row,
+                    expected: width,
+                    found,
+                });
+            }
+            for (col, c) in line.as_ref().chars().enumerate() {
+                cells.push(cell(c).ok_or(GridError::BadCell { row, col, c })?);
+            }
+        }
+        Ok(Grid {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    /// Number of columns
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `(x, y)` is inside the grid
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    /// Cell at `(x, y)`, or none outside the grid
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    /// Mutable cell at `(x, y)`, or none outside the grid
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// Replaces the cell at `(x, y)`
+    ///
+    /// # Panics
+    ///
+    /// * if `(x, y)` is outside the grid
+    ///
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        *self.get_mut(x, y).expect("cell outside grid") = value;
+    }
+
+    /// Rows of the grid, top row first
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, c)| ((i % self.width, i / self.width), c))
+    }
+
+    /// Positions of the up to four cells sharing an edge with `(x, y)`
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .map(move |(dx, dy)| (x as i64 + dx, y as i64 + dy))
+            .filter(|&(nx, ny)| self.contains(nx, ny))
+            .map(|(nx, ny)| (nx as usize, ny as usize))
+    }
+
+    /// Grid of the same shape with `f` applied to every cell
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl fmt::Display for Grid<bool> {
+    /// Draws lit cells as `#` and unlit ones as spaces
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            let line: String = row.iter().map(|&c| if c { '#' } else { ' ' }).collect();
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_works() {
+        let grid = Grid::parse(&["#.#", "..#"], |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(2, 1), Some(&true));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.to_string(), "# #\n  #\n");
+        assert_eq!(
+            Grid::parse(&["##", "#"], |_| Some(1)),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::parse(&["#x"], |c| (c == '#').then_some(1)),
+            Err(GridError::BadCell {
+                row: 0,
+                col: 1,
+                c: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn cells_and_neighbours() {
+        let mut grid = Grid::new(3, 2, 0);
+        grid.set(1, 1, 5);
+        assert_eq!(grid.rows().nth(1), Some(&[0, 5, 0][..]));
+        assert_eq!(
+            grid.iter().find(|(_, v)| **v == 5).map(|(p, _)| p),
+            Some((1, 1))
+        );
+        let mut n: Vec<_> = grid.neighbours(0, 0).collect();
+        n.sort();
+        assert_eq!(n, vec![(0, 1), (1, 0)]);
+        assert_eq!(Grid::from_cells(2, vec![1, 2, 3]), None);
+        assert_eq!(grid.map(|&v| v > 0).get(1, 1), Some(&true));
+    }
+}
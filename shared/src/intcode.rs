@@ -0,0 +1,661 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use rayon::prelude::*;
+
+/// Reasons an intcode program can't be loaded or run
+#[derive(Debug, PartialEq)]
+pub enum IntcodeError {
+    /// a value in the program text isn't an integer
+    Parse { index: usize, value: String },
+    /// the instruction at `addr` has an unknown opcode
+    BadOpcode { addr: usize, opcode: i64 },
+    /// the instruction at `addr` has an unknown parameter mode
+    BadMode { addr: usize, mode: i64 },
+    /// an instruction reads or writes a negative or absurdly large address
+    BadAddress { addr: i64 },
+    /// an instruction writes through an immediate mode parameter
+    ImmediateWrite { addr: usize },
+    /// an input instruction ran with nothing queued
+    NoInput { addr: usize },
+    /// a patch targets an address outside the program
+    BadPatch { addr: usize, len: usize },
+}
+
+impl fmt::Display for IntcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IntcodeError::Parse { index, value } => {
+                write!(f, "value {} ({:?}) is not an integer", index, value)
+            }
+            IntcodeError::BadOpcode { addr, opcode } => {
+                write!(f, "unknown opcode {} at address {}", opcode, addr)
+            }
+            IntcodeError::BadMode { addr, mode } => {
+                write!(f, "unknown parameter mode {} at address {}", mode, addr)
+            }
+            IntcodeError::BadAddress { addr } => write!(f, "address {} is out of range", addr),
+            IntcodeError::ImmediateWrite { addr } => {
+                write!(f, "immediate mode write at address {}", addr)
+            }
+            IntcodeError::NoInput { addr } => write!(f, "no input for address {}", addr),
+            IntcodeError::BadPatch { addr, len } => {
+                write!(
+                    f,
+                    "cannot patch address {} of a {} value program",
+                    addr, len
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for IntcodeError {}
+
+/// Parses comma separated intcode
+///
+/// # Arguments
+///
+/// * `text` - program text, surrounding whitespace is ignored
+///
+/// # Returns
+///
+/// * program values, or the first value that isn't an integer
+///
+pub fn parse_program(text: &str) -> Result<Vec<i64>, IntcodeError> {
+    text.trim()
+        .split(',')
+        .enumerate()
+        .map(|(index, s)| {
+            s.trim().parse::<i64>().map_err(|_| IntcodeError::Parse {
+                index,
+                value: String::from(s.trim()),
+            })
+        })
+        .collect()
+}
+
+/// Address and the value written to it before a program runs
+pub type Patch<T = i64> = (usize, T);
+
+/// Writes each patch into program memory, in order
+///
+/// Every address is checked before anything is written, so a bad patch
+/// leaves memory untouched.
+///
+/// # Arguments
+///
+/// * `memory` - program to patch, in whatever integer type it's held in
+/// * `patches` - address and value pairs to write
+///
+/// # Returns
+///
+/// * error naming the first address outside the program
+///
+pub fn apply_patches<T: Copy>(memory: &mut [T], patches: &[Patch<T>]) -> Result<(), IntcodeError> {
+    let len = memory.len();
+    if let Some(&(addr, _)) = patches.iter().find(|(addr, _)| *addr >= len) {
+        return Err(IntcodeError::BadPatch { addr, len });
+    }
+    for &(addr, value) in patches {
+        memory[addr] = value;
+    }
+    Ok(())
+}
+
+/// Why a resumable run stopped
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum State {
+    /// the program reached a halt instruction
+    Halted,
+    /// the program wants input and none is queued
+    AwaitingInput,
+}
+
+/// How an instruction parameter is interpreted
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Mode {
+    /// the parameter is an address
+    Position,
+    /// the parameter is the value itself
+    Immediate,
+    /// the parameter is an offset from the relative base
+    Relative,
+}
+
+/// Highest address a program may write to, keeping a stray write from
+/// allocating gigabytes
+pub const MAX_MEMORY: usize = 1 << 24;
+
+/// Intcode computer with queued input and collected output
+///
+/// Memory grows as the program writes past its end, and reads past the
+/// end see zero.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Vm {
+    memory: Vec<i64>,
+    ip: usize,
+    relative_base: i64,
+    input: VecDeque<i64>,
+    output: Vec<i64>,
+    halted: bool,
+}
+
+impl Vm {
+    /// Constructor for a Vm
+    ///
+    /// # Arguments
+    ///
+    /// * `program` - initial memory
+    ///
+    pub fn new(program: Vec<i64>) -> Vm {
+        Vm {
+            memory: program,
+            ip: 0,
+            relative_base: 0,
+            input: VecDeque::new(),
+            output: Vec::new(),
+            halted: false,
+        }
+    }
+
+    /// Constructor for a Vm from comma separated intcode
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - program text
+    ///
+    pub fn parse(text: &str) -> Result<Vm, IntcodeError> {
+        Ok(Vm::new(parse_program(text)?))
+    }
+
+    /// Current memory contents
+    pub fn memory(&self) -> &[i64] {
+        &self.memory
+    }
+
+    /// Writes each patch into memory before the program runs, see
+    /// `apply_patches`
+    pub fn apply_patches(&mut self, patches: &[Patch]) -> Result<(), IntcodeError> {
+        apply_patches(&mut self.memory, patches)
+    }
+
+    /// Whether the program has reached a halt instruction
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Queues a value for the next input instruction
+    pub fn push_input(&mut self, value: i64) {
+        self.input.push_back(value);
+    }
+
+    /// Queues every character of `text` as its ASCII code
+    pub fn push_ascii(&mut self, text: &str) {
+        self.input.extend(text.bytes().map(i64::from));
+    }
+
+    /// Number of input values queued and not yet read
+    pub fn pending_input(&self) -> usize {
+        self.input.len()
+    }
+
+    /// Values written by output instructions so far
+    pub fn output(&self) -> &[i64] {
+        &self.output
+    }
+
+    /// Removes and returns the collected output
+    pub fn take_output(&mut self) -> Vec<i64> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Runs until the program halts
+    ///
+    /// # Returns
+    ///
+    /// * error if an instruction faults or input runs out
+    ///
+    pub fn run(&mut self) -> Result<(), IntcodeError> {
+        while !self.halted {
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    /// Runs until the program halts or needs input that isn't queued
+    ///
+    /// A program awaiting input picks up from the same input instruction
+    /// on the next call, so values can be pushed in between.
+    ///
+    /// # Returns
+    ///
+    /// * why the run stopped, or the fault that stopped it
+    ///
+    pub fn run_until_blocked(&mut self) -> Result<State, IntcodeError> {
+        while !self.halted {
+            match self.step() {
+                Err(IntcodeError::NoInput { .. }) => return Ok(State::AwaitingInput),
+                result => result?,
+            }
+        }
+        Ok(State::Halted)
+    }
+
+    /// Executes a single instruction
+    ///
+    /// # Returns
+    ///
+    /// * error if the instruction faults, leaving the vm where it was
+    ///
+    pub fn step(&mut self) -> Result<(), IntcodeError> {
+        let addr = self.ip;
+        let instruction = self.read(addr as i64)?;
+        let opcode = instruction % 100;
+        log::trace!(
+            "{}: {} relative base {}",
+            addr,
+            instruction,
+            self.relative_base
+        );
+        let modes = [
+            self.mode(addr, instruction / 100 % 10)?,
+            self.mode(addr, instruction / 1000 % 10)?,
+            self.mode(addr, instruction / 10000 % 10)?,
+        ];
+        match opcode {
+            1 | 2 | 7 | 8 => {
+                let a = self.param(addr, 1, modes[0])?;
+                let b = self.param(addr, 2, modes[1])?;
+                let value = match opcode {
+                    1 => a.wrapping_add(b),
+                    2 => a.wrapping_mul(b),
+                    7 => (a < b) as i64,
+                    _ => (a == b) as i64,
+                };
+                self.write(addr, 3, modes[2], value)?;
+                self.ip = addr + 4;
+            }
+            3 => {
+                if self.input.is_empty() {
+                    return Err(IntcodeError::NoInput { addr });
+                }
+                let dest = self.dest(addr, 1, modes[0])?;
+                self.memory[dest] = self.input.pop_front().unwrap_or_default();
+                self.ip = addr + 2;
+            }
+            4 => {
+                let value = self.param(addr, 1, modes[0])?;
+                self.output.push(value);
+                self.ip = addr + 2;
+            }
+            5 | 6 => {
+                let value = self.param(addr, 1, modes[0])?;
+                let target = self.param(addr, 2, modes[1])?;
+                if (value != 0) == (opcode == 5) {
+                    self.ip = usize::try_from(target)
+                        .map_err(|_| IntcodeError::BadAddress { addr: target })?;
+                } else {
+                    self.ip = addr + 3;
+                }
+            }
+            9 => {
+                let offset = self.param(addr, 1, modes[0])?;
+                self.relative_base = self.relative_base.wrapping_add(offset);
+                self.ip = addr + 2;
+            }
+            99 => self.halted = true,
+            _ => return Err(IntcodeError::BadOpcode { addr, opcode }),
+        }
+        Ok(())
+    }
+
+    fn mode(&self, addr: usize, mode: i64) -> Result<Mode, IntcodeError> {
+        match mode {
+            0 => Ok(Mode::Position),
+            1 => Ok(Mode::Immediate),
+            2 => Ok(Mode::Relative),
+            _ => Err(IntcodeError::BadMode { addr, mode }),
+        }
+    }
+
+    fn read(&self, addr: i64) -> Result<i64, IntcodeError> {
+        let a = usize::try_from(addr).map_err(|_| IntcodeError::BadAddress { addr })?;
+        Ok(self.memory.get(a).copied().unwrap_or(0))
+    }
+
+    /// Value of the `n`th parameter of the instruction at `addr`
+    fn param(&self, addr: usize, n: usize, mode: Mode) -> Result<i64, IntcodeError> {
+        let raw = self.read((addr + n) as i64)?;
+        match mode {
+            Mode::Position => self.read(raw),
+            Mode::Immediate => Ok(raw),
+            Mode::Relative => self.read(self.relative_base.wrapping_add(raw)),
+        }
+    }
+
+    /// Address the `n`th parameter of the instruction at `addr` writes to,
+    /// growing memory to cover it
+    fn dest(&mut self, addr: usize, n: usize, mode: Mode) -> Result<usize, IntcodeError> {
+        let raw = self.read((addr + n) as i64)?;
+        let target = match mode {
+            Mode::Position => raw,
+            Mode::Immediate => return Err(IntcodeError::ImmediateWrite { addr }),
+            Mode::Relative => self.relative_base.wrapping_add(raw),
+        };
+        let dest = usize::try_from(target)
+            .ok()
+            .filter(|&a| a < MAX_MEMORY)
+            .ok_or(IntcodeError::BadAddress { addr: target })?;
+        if dest >= self.memory.len() {
+            self.memory.resize(dest + 1, 0);
+        }
+        Ok(dest)
+    }
+
+    fn write(&mut self, addr: usize, n: usize, mode: Mode, value: i64) -> Result<(), IntcodeError> {
+        let dest = self.dest(addr, n, mode)?;
+        self.memory[dest] = value;
+        Ok(())
+    }
+}
+
+/// Runs a fresh copy of one program for each set of inputs, spread
+/// across threads
+///
+/// Results come back in the order the inputs were given, however the
+/// runs get scheduled.
+///
+/// # Arguments
+///
+/// * `program` - intcode every run starts from
+/// * `inputs` - values queued for each run
+///
+/// # Returns
+///
+/// * output of every run, or the first fault by input order
+///
+pub fn run_batch(program: &[i64], inputs: &[Vec<i64>]) -> Result<Vec<Vec<i64>>, IntcodeError> {
+    inputs
+        .par_iter()
+        .map(|input| {
+            let mut vm = Vm::new(program.to_vec());
+            input.iter().for_each(|&v| vm.push_input(v));
+            vm.run()?;
+            Ok(vm.take_output())
+        })
+        .collect()
+}
+
+/// What every machine did during one turn of a scheduler
+#[derive(Debug, PartialEq)]
+pub struct Round {
+    /// values each machine output, by machine
+    pub outputs: Vec<Vec<i64>>,
+    /// whether no running machine had input queued or output anything
+    pub idle: bool,
+    /// whether every machine has halted
+    pub halted: bool,
+}
+
+/// Several machines taking turns on one thread
+///
+/// Each turn runs a machine until it halts or blocks for input. A machine
+/// blocked with nothing queued can be handed a stand in value, as the
+/// network cards of day 23 expect `-1` when no packet has arrived.
+#[derive(Clone, Debug)]
+pub struct Scheduler {
+    vms: Vec<Vm>,
+    idle_input: Option<i64>,
+}
+
+impl Scheduler {
+    /// Constructor for a Scheduler
+    ///
+    /// # Arguments
+    ///
+    /// * `vms` - machines, numbered by position
+    /// * `idle_input` - value fed to a machine with no input queued, if any
+    ///
+    pub fn new(vms: Vec<Vm>, idle_input: Option<i64>) -> Scheduler {
+        Scheduler { vms, idle_input }
+    }
+
+    /// Number of machines
+    pub fn len(&self) -> usize {
+        self.vms.len()
+    }
+
+    /// Whether there are no machines
+    pub fn is_empty(&self) -> bool {
+        self.vms.is_empty()
+    }
+
+    /// Queues a value for one machine
+    ///
+    /// # Panics
+    ///
+    /// * if there is no machine `index`
+    ///
+    pub fn push_input(&mut self, index: usize, value: i64) {
+        self.vms[index].push_input(value);
+    }
+
+    /// Gives every machine a turn, in order
+    ///
+    /// # Returns
+    ///
+    /// * what each machine output and whether the round was idle, or the
+    ///   first fault
+    ///
+    pub fn round(&mut self) -> Result<Round, IntcodeError> {
+        let mut idle = true;
+        let mut outputs = Vec::with_capacity(self.vms.len());
+        for vm in self.vms.iter_mut() {
+            if vm.is_halted() {
+                outputs.push(Vec::new());
+                continue;
+            }
+            if vm.pending_input() > 0 {
+                idle = false;
+            } else if let Some(value) = self.idle_input {
+                vm.push_input(value);
+            }
+            vm.run_until_blocked()?;
+            let output = vm.take_output();
+            idle &= output.is_empty();
+            outputs.push(output);
+        }
+        Ok(Round {
+            outputs,
+            idle,
+            halted: self.vms.iter().all(Vm::is_halted),
+        })
+    }
+}
+
+/// Decodes output from a program talking through an ASCII adapter
+///
+/// # Arguments
+///
+/// * `values` - output values, one character each
+///
+/// # Returns
+///
+/// * text, or none if a value isn't an ASCII code
+///
+pub fn ascii(values: &[i64]) -> Option<String> {
+    values
+        .iter()
+        .map(|&v| u8::try_from(v).ok().filter(u8::is_ascii).map(char::from))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_with(program: &str, input: &[i64]) -> Vec<i64> {
+        let mut vm = Vm::parse(program).unwrap();
+        input.iter().for_each(|&v| vm.push_input(v));
+        vm.run().unwrap();
+        vm.take_output()
+    }
+
+    #[test]
+    fn parse_program_works() {
+        assert_eq!(parse_program("1,0, -3,99\n"), Ok(vec![1, 0, -3, 99]));
+        assert_eq!(
+            parse_program("1,x,3"),
+            Err(IntcodeError::Parse {
+                index: 1,
+                value: String::from("x")
+            })
+        );
+    }
+
+    #[test]
+    fn apply_patches_writes_in_order() {
+        let mut vm = Vm::parse("1,5,9,4").unwrap();
+        vm.apply_patches(&[(1, 7), (3, 0), (1, 8)]).unwrap();
+        assert_eq!(vm.memory(), &[1, 8, 9, 0]);
+
+        let mut code: Vec<u64> = vec![1, 0, 0, 0, 99];
+        apply_patches(&mut code, &[(1, 4), (2, 4)]).unwrap();
+        assert_eq!(code, vec![1, 4, 4, 0, 99]);
+    }
+
+    #[test]
+    fn apply_patches_rejects_bad_address() {
+        let mut vm = Vm::parse("1,5,9,4").unwrap();
+        assert_eq!(
+            vm.apply_patches(&[(0, 2), (4, 1)]),
+            Err(IntcodeError::BadPatch { addr: 4, len: 4 })
+        );
+        assert_eq!(vm.memory(), &[1, 5, 9, 4]);
+    }
+
+    #[test]
+    fn parameter_modes_work() {
+        let mut vm = Vm::parse("1002,4,3,4,33").unwrap();
+        vm.run().unwrap();
+        assert_eq!(vm.memory(), &[1002, 4, 3, 4, 99]);
+
+        let mut vm = Vm::parse("1101,100,-1,4,0").unwrap();
+        vm.run().unwrap();
+        assert_eq!(vm.memory()[4], 99);
+    }
+
+    #[test]
+    fn input_is_echoed() {
+        assert_eq!(run_with("3,0,4,0,99", &[-42]), vec![-42]);
+    }
+
+    #[test]
+    fn memory_grows_on_write() {
+        let mut vm = Vm::parse("1101,2,3,10,4,10,4,50,99").unwrap();
+        vm.run().unwrap();
+        assert_eq!(vm.memory().len(), 11);
+        assert_eq!(vm.output(), &[5, 0]);
+    }
+
+    #[test]
+    fn relative_mode_works() {
+        // moves the base to 7 and outputs the value 3 past it
+        assert_eq!(run_with("109,7,204,3,99,0,0,0,0,0,42", &[]), vec![42]);
+        // reads input into base + 1 then outputs it
+        assert_eq!(run_with("109,10,203,1,4,11,99", &[13]), vec![13]);
+    }
+
+    #[test]
+    fn run_until_blocked_resumes() {
+        // adds two inputs and outputs the sum, twice
+        let mut vm = Vm::parse("3,20,3,21,1,20,21,22,4,22,1105,1,0,99,0,0,0,0,0,0,0,0,0").unwrap();
+        assert_eq!(vm.run_until_blocked(), Ok(State::AwaitingInput));
+        vm.push_input(2);
+        assert_eq!(vm.run_until_blocked(), Ok(State::AwaitingInput));
+        vm.push_input(3);
+        assert_eq!(vm.run_until_blocked(), Ok(State::AwaitingInput));
+        assert_eq!(vm.take_output(), vec![5]);
+        assert!(!vm.is_halted());
+
+        let mut vm = Vm::parse("104,7,99").unwrap();
+        assert_eq!(vm.run_until_blocked(), Ok(State::Halted));
+        assert_eq!(vm.output(), &[7]);
+    }
+
+    #[test]
+    fn run_batch_keeps_order() {
+        // outputs whether the first input is less than the second
+        let program = parse_program("3,20,3,21,7,20,21,22,4,22,99").unwrap();
+        let inputs: Vec<Vec<i64>> = (0..100).map(|i| vec![i, 50]).collect();
+        let outputs = run_batch(&program, &inputs).unwrap();
+        assert_eq!(outputs.len(), 100);
+        assert!(
+            outputs
+                .iter()
+                .enumerate()
+                .all(|(i, o)| o == &[(i < 50) as i64])
+        );
+        assert_eq!(
+            run_batch(&program, &[vec![1, 2], vec![1]]),
+            Err(IntcodeError::NoInput { addr: 2 })
+        );
+    }
+
+    #[test]
+    fn scheduler_takes_turns() {
+        // echoes each positive input, forever
+        let echo = Vm::parse("3,20,1006,20,0,1007,20,0,21,1005,21,0,4,20,1105,1,0").unwrap();
+        let mut scheduler = Scheduler::new(vec![echo.clone(), echo], Some(-1));
+        assert_eq!(scheduler.len(), 2);
+        scheduler.push_input(1, 7);
+        let round = scheduler.round().unwrap();
+        assert_eq!(round.outputs, vec![vec![], vec![7]]);
+        assert!(!round.idle);
+        let round = scheduler.round().unwrap();
+        assert_eq!(round.outputs, vec![vec![], vec![]]);
+        assert!(round.idle);
+        assert!(!round.halted);
+
+        let mut scheduler = Scheduler::new(vec![Vm::parse("104,1,99").unwrap()], None);
+        let round = scheduler.round().unwrap();
+        assert_eq!(round.outputs, vec![vec![1]]);
+        assert!(round.halted);
+    }
+
+    #[test]
+    fn ascii_round_trips() {
+        let mut vm = Vm::parse("3,0,4,0,3,0,4,0,99").unwrap();
+        vm.push_ascii("a\n");
+        vm.run().unwrap();
+        assert_eq!(ascii(vm.output()), Some(String::from("a\n")));
+        assert_eq!(ascii(&[35, 128]), None);
+        assert_eq!(ascii(&[-1]), None);
+    }
+
+    #[test]
+    fn faults_are_reported() {
+        let mut vm = Vm::parse("3,0,99").unwrap();
+        assert_eq!(vm.run(), Err(IntcodeError::NoInput { addr: 0 }));
+        assert_eq!(
+            Vm::parse("1,0,0,-1,99").unwrap().run(),
+            Err(IntcodeError::BadAddress { addr: -1 })
+        );
+        assert_eq!(
+            Vm::parse("4,-3,99").unwrap().run(),
+            Err(IntcodeError::BadAddress { addr: -3 })
+        );
+        assert_eq!(
+            Vm::parse("11101,0,0,3,99").unwrap().run(),
+            Err(IntcodeError::ImmediateWrite { addr: 0 })
+        );
+        assert_eq!(
+            Vm::parse("42").unwrap().run(),
+            Err(IntcodeError::BadOpcode {
+                addr: 0,
+                opcode: 42
+            })
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
+
+/// Nested copies of something, each level inside the one before
+///
+/// Levels are numbered from 0 at the start, inner levels counting up and
+/// outer ones down, and the stack can grow in either direction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelStack<T> {
+    levels: VecDeque<T>,
+    outermost: i64,
+}
+
+impl<T> LevelStack<T> {
+    /// Constructor for a LevelStack holding only level 0
+    pub fn new(level: T) -> LevelStack<T> {
+        LevelStack {
+            levels: VecDeque::from([level]),
+            outermost: 0,
+        }
+    }
+
+    /// Number of the outermost level held
+    pub fn outermost(&self) -> i64 {
+        self.outermost
+    }
+
+    /// Number of the innermost level held
+    pub fn innermost(&self) -> i64 {
+        self.outermost + self.levels.len() as i64 - 1
+    }
+
+    /// Numbers of every level held, outermost first
+    pub fn depths(&self) -> RangeInclusive<i64> {
+        self.outermost()..=self.innermost()
+    }
+
+    /// Level `depth`, or none if the stack doesn't reach it
+    pub fn get(&self, depth: i64) -> Option<&T> {
+        let index = usize::try_from(depth - self.outermost).ok()?;
+        self.levels.get(index)
+    }
+
+    /// Adds a level outside the outermost one
+    pub fn push_outer(&mut self, level: T) {
+        self.levels.push_front(level);
+        self.outermost -= 1;
+    }
+
+    /// Adds a level inside the innermost one
+    pub fn push_inner(&mut self, level: T) {
+        self.levels.push_back(level);
+    }
+
+    /// Every level with its number, outermost first
+    pub fn iter(&self) -> impl Iterator<Item = (i64, &T)> {
+        self.depths().zip(self.levels.iter())
+    }
+
+    /// Stack of the same depths with `f` applied to every level
+    pub fn map<U, F: FnMut(i64, &T) -> U>(&self, mut f: F) -> LevelStack<U> {
+        LevelStack {
+            levels: self.iter().map(|(depth, level)| f(depth, level)).collect(),
+            outermost: self.outermost,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grows_both_ways() {
+        let mut stack = LevelStack::new('a');
+        stack.push_outer('b');
+        stack.push_inner('c');
+        stack.push_outer('d');
+        assert_eq!(stack.depths(), -2..=1);
+        assert_eq!(stack.get(-2), Some(&'d'));
+        assert_eq!(stack.get(0), Some(&'a'));
+        assert_eq!(stack.get(1), Some(&'c'));
+        assert_eq!(stack.get(2), None);
+        assert_eq!(stack.get(-3), None);
+        let labelled = stack.map(|depth, &c| format!("{}{}", c, depth));
+        assert_eq!(
+            labelled.iter().map(|(_, s)| s.as_str()).collect::<Vec<_>>(),
+            vec!["d-2", "b-1", "a0", "c1"]
+        );
+    }
+}
@@ -1,4 +1,23 @@
+#[cfg(feature = "args")]
+pub mod args;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod graph;
+pub mod grid;
+pub mod intcode;
+pub mod levels;
+pub mod logging;
+pub mod numtheory;
+pub mod ocr;
+pub mod point;
+pub mod pqueue;
+pub mod solution;
+pub mod timing;
+
 use std::fs;
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
@@ -15,13 +34,208 @@ pub fn add(left: u64, right: u64) -> u64 {
 ///* file contents split per line
 ///
 pub fn ingest_file(filename: &str) -> Vec<String> {
-    let bytes = fs::read(filename).unwrap();
-    let s = String::from_utf8(bytes).unwrap();
-    s.lines()
+    try_ingest_file(filename).unwrap()
+}
+
+///Ingests a file for usage, handing back why it can't be read instead of
+///panicking
+///
+///# Arguments
+///
+///* `filename` - path to file
+///
+///# Returns
+///
+///* file contents split per line, or the read error, `InvalidData` if
+///  the file isn't UTF-8
+///
+pub fn try_ingest_file(filename: &str) -> io::Result<Vec<String>> {
+    let s = fs::read_to_string(filename)?;
+    Ok(s.lines()
         .map(|x| x.trim())
         .map(String::from)
         .filter(|x| !x.is_empty())
-        .collect()
+        .collect())
+}
+
+///Ingests a file where whitespace matters, such as a map
+///
+///# Arguments
+///
+///* `filename` - path to file
+///
+///# Returns
+///
+///* file contents split per line, spaces kept and only blank lines at
+///  the end dropped
+///
+pub fn ingest_file_untrimmed(filename: &str) -> Vec<String> {
+    try_ingest_file_untrimmed(filename).unwrap()
+}
+
+///Ingests a file where whitespace matters, handing back why it can't be
+///read instead of panicking
+///
+///# Arguments
+///
+///* `filename` - path to file
+///
+///# Returns
+///
+///* file contents split per line, spaces kept and only blank lines at
+///  the end dropped, or the read error, `InvalidData` if the file isn't
+///  UTF-8
+///
+pub fn try_ingest_file_untrimmed(filename: &str) -> io::Result<Vec<String>> {
+    let s = fs::read_to_string(filename)?;
+    let mut lines: Vec<String> = s.lines().map(String::from).collect();
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+    Ok(lines)
+}
+
+///Whether reporters made with `Progress::auto` draw, off until a runner
+///turns it on
+static PROGRESS_VISIBLE: AtomicBool = AtomicBool::new(false);
+
+///Turns drawing on or off for reporters made with `Progress::auto` from
+///now on
+///
+///# Arguments
+///
+///* `visible` - whether they should draw
+///
+///# Returns
+///
+///* whether they drew before
+///
+pub fn set_progress_visible(visible: bool) -> bool {
+    PROGRESS_VISIBLE.swap(visible, Ordering::Relaxed)
+}
+
+///Whether reporters made with `Progress::auto` draw
+pub fn progress_visible() -> bool {
+    PROGRESS_VISIBLE.load(Ordering::Relaxed)
+}
+
+///Live counter and ETA for a long running loop, drawn on stderr
+///
+///Ticks may come from several threads at once. A hidden reporter counts
+///without drawing anything, which keeps test output clean.
+pub struct Progress {
+    label: String,
+    total: u64,
+    done: AtomicU64,
+    start: Instant,
+    enabled: bool,
+}
+
+impl Progress {
+    ///Constructor for a Progress that draws to stderr
+    ///
+    ///# Arguments
+    ///
+    ///* `label` - name shown before the counter
+    ///* `total` - number of ticks expected
+    ///
+    pub fn new(label: &str, total: u64) -> Progress {
+        Progress {
+            label: String::from(label),
+            total,
+            done: AtomicU64::new(0),
+            start: Instant::now(),
+            enabled: true,
+        }
+    }
+
+    ///Constructor for a Progress that only counts
+    ///
+    ///# Arguments
+    ///
+    ///* `total` - number of ticks expected
+    ///
+    pub fn hidden(total: u64) -> Progress {
+        Progress {
+            enabled: false,
+            ..Progress::new("", total)
+        }
+    }
+
+    ///Constructor for a Progress that draws only if a runner has asked
+    ///for it with `set_progress_visible`
+    ///
+    ///# Arguments
+    ///
+    ///* `label` - name shown before the counter
+    ///* `total` - number of ticks expected
+    ///
+    pub fn auto(label: &str, total: u64) -> Progress {
+        Progress {
+            enabled: progress_visible(),
+            ..Progress::new(label, total)
+        }
+    }
+
+    ///Records one unit of work, redrawing roughly every percent
+    pub fn tick(&self) {
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        let step = (self.total / 100).max(1);
+        if self.enabled && (done.is_multiple_of(step) || done == self.total) {
+            self.draw(done);
+        }
+    }
+
+    ///Records that work has got at least as far as `done`, for work that
+    ///measures how far it's got rather than counting steps
+    pub fn reach(&self, done: u64) {
+        let before = self.done.fetch_max(done, Ordering::Relaxed);
+        if self.enabled && done > before {
+            self.draw(done);
+        }
+    }
+
+    ///Redraws the counter line in place
+    fn draw(&self, done: u64) {
+        eprint!("\r{}\x1b[K", self.line(done, self.start.elapsed()));
+    }
+
+    ///Ends the counter line so later output starts on a fresh one
+    pub fn finish(&self) {
+        if self.enabled && self.done() > 0 {
+            eprintln!();
+        }
+    }
+
+    ///Number of ticks recorded so far
+    pub fn done(&self) -> u64 {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    ///Formats the counter line
+    ///
+    ///# Arguments
+    ///
+    ///* `done` - ticks recorded so far
+    ///* `elapsed` - time since the reporter was created
+    ///
+    ///# Returns
+    ///
+    ///* label, count, percentage and estimated time remaining
+    ///
+    fn line(&self, done: u64, elapsed: Duration) -> String {
+        let percent = done * 100 / self.total.max(1);
+        let remaining = self.total.saturating_sub(done);
+        let eta = elapsed.mul_f64(remaining as f64 / done.max(1) as f64);
+        format!(
+            "{} {}/{} ({}%) eta {:.1}s",
+            self.label,
+            done,
+            self.total,
+            percent,
+            eta.as_secs_f64()
+        )
+    }
 }
 
 #[cfg(test)]
@@ -42,4 +256,60 @@ mod tests {
         assert_eq!(result[1], "some text");
         assert_eq!(result[2], "hooray!");
     }
+
+    #[test]
+    fn try_ingest_file_reports_errors() {
+        assert_eq!(try_ingest_file("src/test.txt").unwrap().len(), 3);
+        let missing = try_ingest_file("src/no_such_file.txt").unwrap_err();
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+        let path = std::env::temp_dir().join(format!("shared-bad-utf8-{}", std::process::id()));
+        fs::write(&path, [0xff, 0xfe, b'\n']).unwrap();
+        let bad = try_ingest_file(&path.to_string_lossy()).unwrap_err();
+        let bad_untrimmed = try_ingest_file_untrimmed(&path.to_string_lossy()).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(bad.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(bad_untrimmed.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn ingest_file_untrimmed_keeps_spaces() {
+        let result = ingest_file_untrimmed("src/test2.txt");
+        assert_eq!(result, vec!["  A ", "", " .#"]);
+        let missing = try_ingest_file_untrimmed("src/no_such_file.txt").unwrap_err();
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn progress_counts_ticks() {
+        let progress = Progress::hidden(10);
+        for _ in 0..4 {
+            progress.tick();
+        }
+        assert_eq!(progress.done(), 4);
+        progress.reach(7);
+        progress.reach(5);
+        assert_eq!(progress.done(), 7);
+    }
+
+    #[test]
+    fn progress_auto_follows_switch() {
+        assert!(!Progress::auto("sweep", 10).enabled);
+        assert!(!set_progress_visible(true));
+        assert!(Progress::auto("sweep", 10).enabled);
+        assert!(progress_visible());
+        assert!(set_progress_visible(false));
+    }
+
+    #[test]
+    fn progress_line_estimates_remaining() {
+        let progress = Progress::new("sweep", 200);
+        assert_eq!(
+            progress.line(50, Duration::from_secs(2)),
+            "sweep 50/200 (25%) eta 6.0s"
+        );
+        assert_eq!(
+            progress.line(200, Duration::from_secs(8)),
+            "sweep 200/200 (100%) eta 0.0s"
+        );
+    }
 }
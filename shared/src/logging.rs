@@ -0,0 +1,88 @@
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+/// Writes every enabled record to stderr, tagged with its level and the
+/// module it came from
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("{}", line(record));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Most detailed level logged for a number of `-v` flags
+///
+/// # Arguments
+///
+/// * `verbosity` - times `-v` was given
+///
+/// # Returns
+///
+/// * warnings only by default, solver diagnostics at one and traces such
+///   as every intcode instruction from two
+///
+pub fn level(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+/// Formats a record as one line
+fn line(record: &Record) -> String {
+    format!("[{} {}] {}", record.level(), record.target(), record.args())
+}
+
+/// Sends the `log` macros used across the workspace to stderr
+///
+/// # Arguments
+///
+/// * `verbosity` - times `-v` was given
+///
+/// # Returns
+///
+/// * error if a logger was already installed
+///
+pub fn init(verbosity: u8) -> Result<(), SetLoggerError> {
+    log::set_logger(&LOGGER)?;
+    log::set_max_level(level(verbosity));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use log::Level;
+
+    use super::*;
+
+    #[test]
+    fn level_rises_with_verbosity() {
+        assert_eq!(level(0), LevelFilter::Warn);
+        assert_eq!(level(1), LevelFilter::Debug);
+        assert_eq!(level(2), LevelFilter::Trace);
+        assert_eq!(level(5), LevelFilter::Trace);
+    }
+
+    #[test]
+    fn line_names_level_and_module() {
+        let line = line(
+            &Record::builder()
+                .level(Level::Debug)
+                .target("day3")
+                .args(format_args!("{} crossings", 2))
+                .build(),
+        );
+        assert_eq!(line, "[DEBUG day3] 2 crossings");
+    }
+}
@@ -0,0 +1,127 @@
+/// Greatest common divisor, always non-negative
+///
+/// # Arguments
+///
+/// * `a` - first number
+/// * `b` - second number
+///
+/// # Returns
+///
+/// * largest number dividing both, or 0 when both are 0
+///
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, always non-negative
+///
+/// # Arguments
+///
+/// * `a` - first number
+/// * `b` - second number
+///
+/// # Returns
+///
+/// * smallest number both divide, or 0 when either is 0
+///
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// Raises a number to a power modulo `modulus`, by repeated squaring
+///
+/// # Arguments
+///
+/// * `base` - number to raise, reduced into range first
+/// * `exp` - power
+/// * `modulus` - positive modulus
+///
+/// # Returns
+///
+/// * `base^exp mod modulus`, in `[0, modulus)`
+///
+pub fn modpow(base: i64, exp: u64, modulus: i64) -> i64 {
+    let m = modulus as i128;
+    let (mut result, mut base, mut exp) = (1 % m, (base as i128).rem_euclid(m), exp);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result as i64
+}
+
+/// Multiplicative inverse modulo `modulus`, by the extended Euclidean
+/// algorithm
+///
+/// # Arguments
+///
+/// * `a` - number to invert
+/// * `modulus` - positive modulus
+///
+/// # Returns
+///
+/// * `x` in `[0, modulus)` with `a * x = 1 mod modulus`, or none if `a`
+///   and `modulus` share a factor
+///
+pub fn modinverse(a: i64, modulus: i64) -> Option<i64> {
+    let (mut r0, mut r1) = (modulus as i128, (a as i128).rem_euclid(modulus as i128));
+    let (mut s0, mut s1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (s0, s1) = (s1, s0 - q * s1);
+    }
+    (r0 == 1).then(|| s0.rem_euclid(modulus as i128) as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_works() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-4, 6), 2);
+        assert_eq!(gcd(0, -5), 5);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(17, 5), 1);
+    }
+
+    #[test]
+    fn lcm_works() {
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-3, 5), 15);
+        assert_eq!(lcm(0, 7), 0);
+        assert_eq!([18, 28, 44].into_iter().fold(1, lcm), 2772);
+    }
+
+    #[test]
+    fn modpow_works() {
+        assert_eq!(modpow(3, 4, 5), 1);
+        assert_eq!(modpow(-2, 3, 7), 6);
+        assert_eq!(modpow(5, 0, 1), 0);
+        // Fermat's little theorem with a modulus past 32 bits
+        let p = 119_315_717_514_047;
+        assert_eq!(modpow(123_456_789, p as u64 - 1, p), 1);
+    }
+
+    #[test]
+    fn modinverse_works() {
+        assert_eq!(modinverse(3, 7), Some(5));
+        assert_eq!(modinverse(-3, 7), Some(2));
+        assert_eq!(modinverse(4, 8), None);
+        let p = 119_315_717_514_047;
+        let x = modinverse(987_654_321, p).unwrap();
+        assert_eq!((987_654_321i128 * x as i128) % p as i128, 1);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::grid::Grid;
+
+/// Rows of each known letter, `#` lit, padded to five columns
+const GLYPHS: [(char, [&str; 6]); 18] = [
+    ('A', [".##..", "#..#.", "#..#.", "####.", "#..#.", "#..#."]),
+    ('B', ["###..", "#..#.", "###..", "#..#.", "#..#.", "###.."]),
+    ('C', [".##..", "#..#.", "#....", "#....", "#..#.", ".##.."]),
+    ('E', ["####.", "#....", "###..", "#....", "#....", "####."]),
+    ('F', ["####.", "#....", "###..", "#....", "#....", "#...."]),
+    ('G', [".##..", "#..#.", "#....", "#.##.", "#..#.", ".###."]),
+    ('H', ["#..#.", "#..#.", "####.", "#..#.", "#..#.", "#..#."]),
+    ('I', [".###.", "..#..", "..#..", "..#..", "..#..", ".###."]),
+    ('J', ["..##.", "...#.", "...#.", "...#.", "#..#.", ".##.."]),
+    ('K', ["#..#.", "#.#..", "##...", "#.#..", "#.#..", "#..#."]),
+    ('L', ["#....", "#....", "#....", "#....", "#....", "####."]),
+    ('O', [".##..", "#..#.", "#..#.", "#..#.", "#..#.", ".##.."]),
+    ('P', ["###..", "#..#.", "#..#.", "###..", "#....", "#...."]),
+    ('R', ["###..", "#..#.", "#..#.", "###..", "#.#..", "#..#."]),
+    ('S', [".###.", "#....", "#....", ".##..", "...#.", "###.."]),
+    ('U', ["#..#.", "#..#.", "#..#.", "#..#.", "#..#.", ".##.."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####.", "...#.", "..#..", ".#...", "#....", "####."]),
+];
+
+/// Height of a letter in cells
+pub const LETTER_HEIGHT: usize = 6;
+/// Columns between the starts of neighbouring letters
+pub const LETTER_WIDTH: usize = 5;
+
+/// Letter drawn in the five columns starting at `start`, if any
+fn letter(grid: &Grid<bool>, start: usize) -> Option<char> {
+    let lit = |x: usize, y: usize| *grid.get(x, y).unwrap_or(&false);
+    GLYPHS
+        .iter()
+        .find(|(_, rows)| {
+            rows.iter().enumerate().all(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .all(|(dx, c)| (c == '#') == lit(start + dx, y))
+            })
+        })
+        .map(|(c, _)| *c)
+}
+
+/// Reads capital letters drawn in the puzzle's six cell high font
+///
+/// Letters sit five columns apart. The grid may have blank columns
+/// before the first letter or after the last.
+///
+/// # Arguments
+///
+/// * `grid` - six rows of lit and unlit cells
+///
+/// # Returns
+///
+/// * letters read left to right, or none if the grid holds anything else
+///
+pub fn read(grid: &Grid<bool>) -> Option<String> {
+    if grid.height() != LETTER_HEIGHT {
+        return None;
+    }
+    let lit_columns: Vec<usize> = (0..grid.width())
+        .filter(|&x| (0..grid.height()).any(|y| grid.get(x, y) == Some(&true)))
+        .collect();
+    let (&first, &last) = (lit_columns.first()?, lit_columns.last()?);
+    // letters such as J leave their first columns blank, so try each
+    // alignment that could put the first lit column inside a letter
+    (first.saturating_sub(LETTER_WIDTH - 1)..=first)
+        .rev()
+        .find_map(|start| {
+            (start..=last)
+                .step_by(LETTER_WIDTH)
+                .map(|x| letter(grid, x))
+                .collect()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Grid<bool> {
+        Grid::parse(rows, |c| Some(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn read_works() {
+        let hi = grid(&[
+            "#..#..###.",
+            "#..#...#..",
+            "####...#..",
+            "#..#...#..",
+            "#..#...#..",
+            "#..#..###.",
+        ]);
+        assert_eq!(read(&hi), Some(String::from("HI")));
+    }
+
+    #[test]
+    fn read_handles_offsets() {
+        let jy = grid(&[
+            "....##.#...#",
+            ".....#.#...#",
+            ".....#..#.#.",
+            ".....#...#..",
+            "..#..#...#..",
+            "...##....#..",
+        ]);
+        assert_eq!(read(&jy), Some(String::from("JY")));
+        assert_eq!(read(&grid(&["#"; 6])), None);
+        assert_eq!(read(&grid(&["....."; 6])), None);
+        assert_eq!(read(&grid(&["##"; 5])), None);
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+/// Point or vector in three dimensions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    /// Constructor for a Point3
+    pub fn new(x: i64, y: i64, z: i64) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    /// Sum of the absolute values of the coordinates
+    pub fn manhattan(&self) -> i64 {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+
+    /// Sign of each coordinate, as -1, 0 or 1
+    pub fn signum(&self) -> Point3 {
+        Point3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    /// Coordinates as an array, x first
+    pub fn axes(&self) -> [i64; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, other: Point3) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, other: Point3) {
+        *self = *self - other;
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<x={}, y={}, z={}>", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_works() {
+        let mut a = Point3::new(1, -2, 3);
+        let b = Point3::new(-4, 5, 0);
+        assert_eq!(a + b, Point3::new(-3, 3, 3));
+        assert_eq!(a - b, Point3::new(5, -7, 3));
+        assert_eq!((a - b).signum(), Point3::new(1, -1, 1));
+        a += b;
+        assert_eq!(a.manhattan(), 9);
+        a -= b;
+        a += b;
+        assert_eq!(a.axes(), [-3, 3, 3]);
+        assert_eq!(a.to_string(), "<x=-3, y=3, z=3>");
+    }
+}
@@ -0,0 +1,95 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Item waiting in a queue, ordered only by its priority
+#[derive(Debug)]
+struct Entry<P, T> {
+    priority: P,
+    item: T,
+}
+
+impl<P: Ord, T> PartialEq for Entry<P, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<P: Ord, T> Eq for Entry<P, T> {}
+
+impl<P: Ord, T> PartialOrd for Entry<P, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: Ord, T> Ord for Entry<P, T> {
+    /// Reversed so the heap's greatest entry has the lowest priority
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Queue handing back the item with the lowest priority first
+///
+/// Items needn't be comparable themselves. Items of equal priority come
+/// out in no particular order.
+#[derive(Debug)]
+pub struct MinQueue<P, T> {
+    heap: BinaryHeap<Entry<P, T>>,
+}
+
+impl<P: Ord, T> Default for MinQueue<P, T> {
+    fn default() -> Self {
+        MinQueue {
+            heap: BinaryHeap::new(),
+        }
+    }
+}
+
+impl<P: Ord, T> MinQueue<P, T> {
+    /// Constructor for an empty MinQueue
+    pub fn new() -> Self {
+        MinQueue::default()
+    }
+
+    /// Adds an item
+    pub fn push(&mut self, priority: P, item: T) {
+        self.heap.push(Entry { priority, item });
+    }
+
+    /// Removes the item with the lowest priority
+    pub fn pop(&mut self) -> Option<(P, T)> {
+        self.heap.pop().map(|e| (e.priority, e.item))
+    }
+
+    /// Number of items waiting
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Whether no items are waiting
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pops_lowest_first() {
+        let mut queue = MinQueue::new();
+        assert!(queue.is_empty());
+        queue.push(5, "five");
+        queue.push(1, "one");
+        queue.push(3, "three");
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.pop(), Some((1, "one")));
+        queue.push(2, "two");
+        assert_eq!(queue.pop(), Some((2, "two")));
+        assert_eq!(queue.pop(), Some((3, "three")));
+        assert_eq!(queue.pop(), Some((5, "five")));
+        assert_eq!(queue.pop(), None);
+    }
+}
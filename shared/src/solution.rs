@@ -0,0 +1,114 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Answer to one part, formatted as the puzzle site expects it, or why
+/// it couldn't be found
+pub type Answer = Result<String, Box<dyn Error>>;
+
+/// Common face every day puts on its puzzle so one runner can drive them
+/// all without knowing their answer or error types
+pub trait Solution: Sync {
+    /// Day of the advent calendar, from 1
+    fn day(&self) -> u8;
+
+    /// Puzzle title
+    fn title(&self) -> &'static str;
+
+    /// Whether a part takes long enough to be worth showing progress for
+    ///
+    /// Runners only draw the progress a long running part reports, so
+    /// quick parts don't flicker.
+    fn long_running(&self, _part: u8) -> bool {
+        false
+    }
+
+    /// Number of parts the puzzle has
+    fn parts(&self) -> u8 {
+        2
+    }
+
+    /// Reads the input in `filename` without solving anything, so reading
+    /// can be timed apart from solving
+    ///
+    /// By default the input is only split into lines, days with costlier
+    /// parsing can do more.
+    fn parse(&self, filename: &str) -> Result<(), Box<dyn Error>> {
+        crate::try_ingest_file(filename)?;
+        Ok(())
+    }
+
+    /// Solves part1 for the input in `filename`
+    fn part1(&self, filename: &str) -> Answer;
+
+    /// Solves part2 for the input in `filename`
+    fn part2(&self, filename: &str) -> Answer {
+        let _ = filename;
+        Err(format!("day {} has no part2", self.day()).into())
+    }
+
+    /// Solves either part
+    ///
+    /// # Arguments
+    ///
+    /// * `part` - 1 or 2
+    /// * `filename` - path of the puzzle input
+    ///
+    /// # Returns
+    ///
+    /// * formatted answer, or why there isn't one
+    ///
+    fn solve(&self, part: u8, filename: &str) -> Answer {
+        match part {
+            1 => self.part1(filename),
+            2 if self.parts() >= 2 => self.part2(filename),
+            _ => Err(format!("day {} has no part{}", self.day(), part).into()),
+        }
+    }
+}
+
+/// Formats the result of a day's own part function as an Answer
+pub fn answer<T: Display, E: Into<Box<dyn Error>>>(result: Result<T, E>) -> Answer {
+    result.map(|a| a.to_string()).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Echo;
+
+    impl Solution for Echo {
+        fn day(&self) -> u8 {
+            25
+        }
+
+        fn title(&self) -> &'static str {
+            "Echo"
+        }
+
+        fn parts(&self) -> u8 {
+            1
+        }
+
+        fn part1(&self, filename: &str) -> Answer {
+            answer(Ok::<_, String>(filename.len()))
+        }
+    }
+
+    #[test]
+    fn solve_dispatches_parts() {
+        assert_eq!(Echo.solve(1, "input").unwrap(), "5");
+        assert_eq!(
+            Echo.solve(2, "input").unwrap_err().to_string(),
+            "day 25 has no part2"
+        );
+        assert_eq!(
+            Echo.solve(3, "input").unwrap_err().to_string(),
+            "day 25 has no part3"
+        );
+        assert_eq!(
+            answer(Err::<u8, _>("bad input")).unwrap_err().to_string(),
+            "bad input"
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Summary of repeated timings of the same work
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub mean: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    /// population standard deviation
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarises some timings
+    ///
+    /// # Returns
+    ///
+    /// * the summary, or none if there are no timings
+    ///
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        let n = samples.len();
+        let min = *samples.iter().min()?;
+        let max = *samples.iter().max()?;
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Some(Stats {
+            samples: n,
+            mean,
+            min,
+            max,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Times some work over and over
+///
+/// The work's result is passed through `black_box` so it can't be
+/// optimised away.
+///
+/// # Arguments
+///
+/// * `warmup` - untimed runs made first, to fill caches
+/// * `samples` - timed runs, at least one
+/// * `work` - work to time
+///
+/// # Returns
+///
+/// * summary of the timed runs
+///
+pub fn measure<T>(warmup: usize, samples: usize, mut work: impl FnMut() -> T) -> Stats {
+    for _ in 0..warmup {
+        black_box(work());
+    }
+    let timings: Vec<Duration> = (0..samples.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(work());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(&timings).expect("at least one sample")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_samples_summarises() {
+        let ms = Duration::from_millis;
+        let stats =
+            Stats::from_samples(&[ms(2), ms(4), ms(4), ms(4), ms(5), ms(5), ms(7), ms(9)]).unwrap();
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.mean, ms(5));
+        assert_eq!(stats.min, ms(2));
+        assert_eq!(stats.max, ms(9));
+        assert_eq!(stats.stddev.as_micros(), 2000);
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn measure_runs_warmup_and_samples() {
+        let mut runs = 0;
+        let stats = measure(3, 5, || runs += 1);
+        assert_eq!(runs, 8);
+        assert_eq!(stats.samples, 5);
+        assert!(stats.min <= stats.mean && stats.mean <= stats.max);
+        assert_eq!(measure(0, 0, || ()).samples, 1);
+    }
+}